@@ -22,6 +22,70 @@ pub struct BatteryHealth {
     pub serial: String,
 }
 
+/// One day's capacity reading in the wear log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WearSample {
+    date: String, // local yyyy-MM-dd
+    design_capacity_mwh: u64,
+    full_charge_capacity_mwh: u64,
+    cycle_count: Option<u32>,
+    health_pct: u32,
+}
+
+fn history_path() -> std::path::PathBuf {
+    let base = std::env::var("LOCALAPPDATA").unwrap_or_else(|_| {
+        let profile = std::env::var("USERPROFILE").unwrap_or_else(|_| ".".into());
+        format!("{}\\AppData\\Local", profile)
+    });
+    std::path::PathBuf::from(base)
+        .join("VegaOptimizer")
+        .join("battery_history.json")
+}
+
+fn load_wear_log() -> Vec<WearSample> {
+    std::fs::read_to_string(history_path())
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+/// Record today's capacity reading; a later reading on the same day replaces the earlier one
+fn record_wear_sample(battery: &BatteryHealth, date: &str) {
+    if date.is_empty() || battery.design_capacity_mwh == 0 || battery.full_charge_capacity_mwh == 0
+    {
+        return;
+    }
+    let sample = WearSample {
+        date: date.to_string(),
+        design_capacity_mwh: battery.design_capacity_mwh,
+        full_charge_capacity_mwh: battery.full_charge_capacity_mwh,
+        cycle_count: battery.cycle_count,
+        health_pct: battery.health_pct,
+    };
+
+    let mut log = load_wear_log();
+    match log.last_mut() {
+        Some(last) if last.date == sample.date => *last = sample,
+        _ => log.push(sample),
+    }
+
+    let path = history_path();
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string(&log) {
+        let _ = std::fs::write(&path, json);
+    }
+}
+
+/// Daily (date, health_pct) readings, oldest first — one per day `get_battery_health` ran
+pub fn get_wear_history() -> Vec<(String, u32)> {
+    load_wear_log()
+        .into_iter()
+        .map(|s| (s.date, s.health_pct))
+        .collect()
+}
+
 /// Get battery health information
 pub fn get_battery_health() -> BatteryHealth {
     // Try WMI battery info first
@@ -65,8 +129,9 @@ pub fn get_battery_health() -> BatteryHealth {
                 $serial = if($bs) { [System.Text.Encoding]::Unicode.GetString($bs.SerialNumber).Trim([char]0) } else { '' }
                 $pct = $b.EstimatedChargeRemaining
                 $status = $b.BatteryStatus
+                $date = Get-Date -Format 'yyyy-MM-dd'
 
-                "FOUND|$pct|$design|$full|$voltage|$rate|$runtime|$cycles|$chem|$mfr|$serial|$charging|$status"
+                "FOUND|$pct|$design|$full|$voltage|$rate|$runtime|$cycles|$chem|$mfr|$serial|$charging|$status|$date"
             } else {
                 "NONE"
             }
@@ -122,9 +187,134 @@ pub fn get_battery_health() -> BatteryHealth {
                 }
 
                 battery.current_capacity_mwh = (battery.full_charge_capacity_mwh as f64 * battery.charge_percent as f64 / 100.0) as u64;
+
+                record_wear_sample(&battery, parts.get(13).map(|d| d.trim()).unwrap_or(""));
             }
         }
     }
 
     battery
 }
+
+/// Run `powercfg /batteryreport` into the temp folder and return the HTML report's path.
+///
+/// The report carries usage and capacity-over-time history straight from the
+/// battery driver, which helps on OEM firmware where the WMI capacity classes are blank.
+pub fn generate_battery_report() -> Result<String, String> {
+    let path = std::env::temp_dir().join("vega_battery_report.html");
+    let _ = std::fs::remove_file(&path);
+
+    let output = Command::new("powercfg")
+        .args(["/batteryreport", "/output"])
+        .arg(&path)
+        .output()
+        .map_err(|e| format!("Could not run powercfg: {}", e))?;
+
+    if !path.exists() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = if stderr.trim().is_empty() {
+            stdout
+        } else {
+            stderr
+        };
+        return Err(format!(
+            "Battery report was not generated: {}",
+            detail.trim()
+        ));
+    }
+    Ok(path.to_string_lossy().to_string())
+}
+
+const DELL_CCTK: &str = r"C:\Program Files (x86)\Dell\Command Configure\X86_64\cctk.exe";
+
+/// System manufacturer from `Win32_ComputerSystem`, lowercased
+fn system_manufacturer() -> String {
+    Command::new("powershell")
+        .args([
+            "-Command",
+            "(Get-CimInstance Win32_ComputerSystem).Manufacturer",
+        ])
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// ASUS: ATK WMI `DEVS` call on the battery charge-limit device (0x00120057),
+/// the same interface MyASUS "Battery Health Charging" uses
+fn set_asus_charge_limit(percent: u8) -> Result<String, String> {
+    let script = format!(
+        "$w = Get-CimInstance -Namespace root/wmi -ClassName AsusAtkWmi_WMNB -ErrorAction Stop; \
+         Invoke-CimMethod -InputObject $w -MethodName DEVS -Arguments @{{Device_ID=[uint32]0x00120057; Control_status=[uint32]{}}} -ErrorAction Stop | Out-Null; 'OK'",
+        percent
+    );
+    let output = Command::new("powershell")
+        .args(["-Command", &script])
+        .output()
+        .map_err(|e| e.to_string())?;
+    if String::from_utf8_lossy(&output.stdout).trim() == "OK" {
+        Ok(format!("ASUS charge limit set to {}%", percent))
+    } else {
+        Err(format!(
+            "ASUS ATK WMI interface unavailable (is the ASUS System Control Interface driver installed?): {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// Dell: Dell Command | Configure's custom charge window (start must be at least 5% below stop)
+fn set_dell_charge_limit(percent: u8) -> Result<String, String> {
+    if !std::path::Path::new(DELL_CCTK).exists() {
+        return Err(
+            "Dell Command | Configure is required to change the charge limit on Dell systems"
+                .into(),
+        );
+    }
+    if percent < 55 && percent != 100 {
+        return Err("Dell systems accept a charge limit between 55% and 100%".into());
+    }
+    let setting = if percent == 100 {
+        "--PrimaryBattChargeCfg=Standard".to_string()
+    } else {
+        format!("--PrimaryBattChargeCfg=Custom:{}-{}", percent - 5, percent)
+    };
+    let output = Command::new(DELL_CCTK)
+        .arg(&setting)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(format!("Dell charge limit set to {}%", percent))
+    } else {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Err(format!(
+            "cctk failed (a BIOS password may be set): {}",
+            stdout.trim()
+        ))
+    }
+}
+
+/// Cap the battery charge at `percent` (100 removes the cap) through the vendor's mechanism.
+///
+/// Supported: ASUS (ATK WMI) and Dell (Dell Command | Configure). Lenovo exposes
+/// conservation mode only through its own driver, so Lenovo Vantage has to be used there.
+pub fn set_charge_limit(percent: u8) -> Result<String, String> {
+    if !(40..=100).contains(&percent) {
+        return Err("Charge limit must be between 40% and 100%".into());
+    }
+
+    let manufacturer = system_manufacturer();
+    if manufacturer.contains("asus") {
+        set_asus_charge_limit(percent)
+    } else if manufacturer.contains("dell") {
+        set_dell_charge_limit(percent)
+    } else if manufacturer.contains("lenovo") {
+        Err("Lenovo charge thresholds and Conservation Mode are only exposed through Lenovo Vantage (Device → Power)".into())
+    } else if manufacturer.is_empty() {
+        Err("Could not detect the system manufacturer".into())
+    } else {
+        Err(format!(
+            "Charge limit control is not supported on {} systems",
+            manufacturer
+        ))
+    }
+}
@@ -1,35 +1,154 @@
-//! System Benchmark — CPU, RAM, Disk speed tests
+//! System Benchmark — CPU, RAM, Disk speed tests and sustained stress testing
 
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
 use std::time::Instant;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BenchmarkResult {
+    pub id: String,
+    pub timestamp: u64, // unix seconds
     pub cpu_single_score: f64,
     pub cpu_multi_score: f64,
     pub cpu_cores_used: usize,
+    #[serde(default)]
+    pub scaling_efficiency: f64, // multi / (single x cores); well below 1 means throttling
     pub ram_read_mbps: f64,
     pub ram_write_mbps: f64,
     pub ram_latency_ns: f64,
     pub disk_seq_read_mbps: f64,
     pub disk_seq_write_mbps: f64,
     pub disk_random_iops: f64,
+    #[serde(default)]
+    pub gpu_score: f64, // 10 x GPU copy throughput in GB/s; 0 when no usable GPU
+    #[serde(default)]
+    pub gpu_note: String, // why the GPU test was skipped, empty when it ran
     pub total_score: u32,
     pub duration_ms: u64,
+    pub passes: usize,
+    pub stddev: BenchmarkStddev,
+    pub confidence: f64, // 0-1: how closely the kept passes agreed; 0 with fewer than 3
+    pub percentiles: Vec<(String, u32)>, // metric -> % of similar machines this result beats
+    #[serde(default)]
+    pub disk_target: String, // directory the disk test's scratch file was placed in
 }
 
-/// Run full system benchmark
-pub fn run_benchmark() -> BenchmarkResult {
+/// Standard deviation of each metric across the kept passes, in the metric's own unit
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BenchmarkStddev {
+    pub cpu_single_score: f64,
+    pub cpu_multi_score: f64,
+    pub ram_read_mbps: f64,
+    pub ram_write_mbps: f64,
+    pub ram_latency_ns: f64,
+    pub disk_seq_read_mbps: f64,
+    pub disk_seq_write_mbps: f64,
+    pub disk_random_iops: f64,
+    #[serde(default)]
+    pub gpu_score: f64,
+}
+
+pub const DEFAULT_PASSES: usize = 3;
+
+/// Median and standard deviation of repeated measurements after dropping the slowest
+fn summarize(mut samples: Vec<f64>, higher_is_better: bool) -> (f64, f64) {
+    samples.sort_by(|a, b| a.total_cmp(b));
+    if samples.len() > 1 {
+        if higher_is_better {
+            samples.remove(0);
+        } else {
+            samples.pop();
+        }
+    }
+    let n = samples.len();
+    if n == 0 {
+        return (0.0, 0.0);
+    }
+    let median = if n % 2 == 1 {
+        samples[n / 2]
+    } else {
+        (samples[n / 2 - 1] + samples[n / 2]) / 2.0
+    };
+    let mean = samples.iter().sum::<f64>() / n as f64;
+    let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n as f64;
+    (median, variance.sqrt())
+}
+
+/// Run the full system benchmark, repeating every sub-test `passes` times (1-10).
+/// The disk test runs on `drive` (a letter such as 'D'), or `%TEMP%` when `None`.
+///
+/// Each metric reports the median of its passes with the slowest one discarded, so a
+/// single pass hit by thermal or scheduler noise doesn't move the score.
+pub fn run_benchmark(passes: usize, drive: Option<char>) -> Result<BenchmarkResult, String> {
     let start = Instant::now();
+    let passes = passes.clamp(1, 10);
+    let scratch_dir = disk_scratch_dir(drive)?;
+
+    let cpu_single_runs: Vec<f64> = (0..passes).map(|_| bench_cpu_single()).collect();
+    let cpu_multi_runs: Vec<f64> = (0..passes).map(|_| bench_cpu_multi()).collect();
+    let ram_runs: Vec<(f64, f64, f64)> = (0..passes).map(|_| bench_ram()).collect();
+    let disk_runs: Vec<(f64, f64, f64)> = (0..passes).map(|_| bench_disk(&scratch_dir)).collect();
 
-    let cpu_single = bench_cpu_single();
-    let cpu_multi = bench_cpu_multi();
-    let (ram_read, ram_write, ram_lat) = bench_ram();
-    let (disk_read, disk_write, disk_iops) = bench_disk();
+    let (cpu_single, cpu_single_sd) = summarize(cpu_single_runs, true);
+    let (cpu_multi, cpu_multi_sd) = summarize(cpu_multi_runs, true);
+    let (ram_read, ram_read_sd) = summarize(ram_runs.iter().map(|r| r.0).collect(), true);
+    let (ram_write, ram_write_sd) = summarize(ram_runs.iter().map(|r| r.1).collect(), true);
+    let (ram_lat, ram_lat_sd) = summarize(ram_runs.iter().map(|r| r.2).collect(), false);
+    let (disk_read, disk_read_sd) = summarize(disk_runs.iter().map(|r| r.0).collect(), true);
+    let (disk_write, disk_write_sd) = summarize(disk_runs.iter().map(|r| r.1).collect(), true);
+    let (disk_iops, disk_iops_sd) = summarize(disk_runs.iter().map(|r| r.2).collect(), true);
+
+    // The GPU test is skipped entirely once the first pass finds no usable device
+    let (gpu_score, gpu_sd, gpu_note) = match bench_gpu() {
+        Ok(first) => {
+            let mut runs = vec![first];
+            runs.extend((1..passes).filter_map(|_| bench_gpu().ok()));
+            let (gbps, sd) = summarize(runs, true);
+            (gbps * 10.0, sd * 10.0, String::new())
+        }
+        Err(note) => (0.0, 0.0, note),
+    };
+
+    // Mean relative spread across metrics, turned into a 0-1 agreement figure
+    let confidence = if passes < 3 {
+        0.0
+    } else {
+        let mut spreads = vec![
+            (cpu_single_sd, cpu_single),
+            (cpu_multi_sd, cpu_multi),
+            (ram_read_sd, ram_read),
+            (ram_write_sd, ram_write),
+            (ram_lat_sd, ram_lat),
+            (disk_read_sd, disk_read),
+            (disk_write_sd, disk_write),
+            (disk_iops_sd, disk_iops),
+        ];
+        if gpu_score > 0.0 {
+            spreads.push((gpu_sd, gpu_score));
+        }
+        let cv = spreads
+            .iter()
+            .map(|(sd, median)| if *median > 0.0 { sd / median } else { 1.0 })
+            .sum::<f64>()
+            / spreads.len() as f64;
+        (1.0 - cv).clamp(0.0, 1.0)
+    };
 
     let cores = num_cpus();
+    let timestamp = now_secs();
+    // From the uncapped scores: the multi-core cap would otherwise read as throttling on
+    // machines with many cores
+    let scaling_efficiency = if cpu_single > 0.0 && cores > 0 {
+        cpu_multi / (cpu_single * cores as f64)
+    } else {
+        0.0
+    };
+    let cpu_single = cpu_single.min(5000.0);
+    let cpu_multi = cpu_multi.min(20000.0);
 
-    // Calculate composite score (weighted)
+    // Calculate composite score (weighted). The GPU is reported on its own so totals stay
+    // comparable between machines with and without a usable GPU.
     let total = ((cpu_single * 0.15)
         + (cpu_multi * 0.15)
         + (ram_read / 10.0 * 0.1)
@@ -39,19 +158,46 @@ pub fn run_benchmark() -> BenchmarkResult {
         + (disk_iops / 100.0 * 0.15))
         .min(10000.0) as u32;
 
-    BenchmarkResult {
+    let mut result = BenchmarkResult {
+        id: format!("bench-{}", timestamp),
+        timestamp,
         cpu_single_score: cpu_single,
         cpu_multi_score: cpu_multi,
         cpu_cores_used: cores,
+        scaling_efficiency,
         ram_read_mbps: ram_read,
         ram_write_mbps: ram_write,
         ram_latency_ns: ram_lat,
         disk_seq_read_mbps: disk_read,
         disk_seq_write_mbps: disk_write,
         disk_random_iops: disk_iops,
+        gpu_score,
+        gpu_note,
         total_score: total,
         duration_ms: start.elapsed().as_millis() as u64,
-    }
+        passes,
+        stddev: BenchmarkStddev {
+            cpu_single_score: cpu_single_sd,
+            cpu_multi_score: cpu_multi_sd,
+            ram_read_mbps: ram_read_sd,
+            ram_write_mbps: ram_write_sd,
+            ram_latency_ns: ram_lat_sd,
+            disk_seq_read_mbps: disk_read_sd,
+            disk_seq_write_mbps: disk_write_sd,
+            disk_random_iops: disk_iops_sd,
+            gpu_score: gpu_sd,
+        },
+        confidence,
+        percentiles: Vec::new(),
+        disk_target: scratch_dir.to_string_lossy().to_string(),
+    };
+    result.percentiles = metrics(&result)
+        .iter()
+        .filter(|(_, value, _)| *value > 0.0)
+        .filter_map(|(metric, value, _)| Some((metric.to_string(), percentile(metric, *value)?)))
+        .collect();
+    record_benchmark(&result);
+    Ok(result)
 }
 
 fn num_cpus() -> usize {
@@ -71,41 +217,53 @@ fn bench_cpu_single() -> f64 {
     let elapsed = start.elapsed().as_secs_f64();
     // Score: iterations per second normalized
     let ops_per_sec = iterations as f64 / elapsed;
-    let _ = result; // Prevent optimization
+    std::hint::black_box(result); // Prevent optimization
 
-    // Normalize to ~1000 for a modern single core
-    (ops_per_sec / 5_000.0).min(5000.0)
+    // Normalize to ~1000 for a modern single core; capped by the caller
+    ops_per_sec / 5_000.0
 }
 
-/// CPU multi-core: parallel computation
+/// CPU multi-core: the single-core workload spread across every physical core.
+///
+/// Workers pull fixed-size chunks from a shared counter instead of each getting an
+/// equal slice, so a core slowed by throttling or background load simply completes
+/// fewer chunks rather than leaving the others idle while it finishes.
 fn bench_cpu_multi() -> f64 {
+    const CHUNK: u64 = 50_000;
     let cores = num_cpus();
-    let iterations_per_core = 3_000_000u64;
+    let total_iterations = cores as u64 * 3_000_000;
+    let next = AtomicU64::new(0);
     let start = Instant::now();
 
-    let handles: Vec<_> = (0..cores)
-        .map(|core_id| {
-            std::thread::spawn(move || {
-                let mut result = 0.0f64;
-                let offset = core_id as f64 * 0.1;
-                for i in 0..iterations_per_core {
-                    result += ((i as f64 + offset) * 0.000001).sin().cos().sqrt().abs();
-                }
-                result
+    std::thread::scope(|s| {
+        let workers: Vec<_> = (0..cores)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut result = 0.0f64;
+                    loop {
+                        let first = next.fetch_add(CHUNK, Ordering::Relaxed);
+                        if first >= total_iterations {
+                            break;
+                        }
+                        for i in first..(first + CHUNK).min(total_iterations) {
+                            result += (i as f64 * 0.000001).sin().cos().sqrt().abs();
+                        }
+                    }
+                    result
+                })
             })
-        })
-        .collect();
-
-    let _results: Vec<f64> = handles
-        .into_iter()
-        .map(|h| h.join().unwrap_or(0.0))
-        .collect();
+            .collect();
+        for worker in workers {
+            std::hint::black_box(worker.join().unwrap_or(0.0));
+        }
+    });
     let elapsed = start.elapsed().as_secs_f64();
 
-    let total_ops = (cores as u64 * iterations_per_core) as f64;
+    let total_ops = total_iterations as f64;
     let ops_per_sec = total_ops / elapsed;
 
-    (ops_per_sec / 5_000.0).min(20000.0)
+    // Same scale as the single-core score; capped by the caller
+    ops_per_sec / 5_000.0
 }
 
 /// RAM benchmark: sequential read/write speed
@@ -113,34 +271,35 @@ fn bench_ram() -> (f64, f64, f64) {
     let size = 64 * 1024 * 1024; // 64 MB
     let iterations = 4;
 
-    // Write test
+    // One buffer for both tests, touched up front so page faults aren't timed
+    let mut buf = vec![1u64; size / 8];
+    std::hint::black_box(&mut buf);
+
+    // Write test: a different pattern each pass, and black_box per pass so the
+    // stores can't be merged or dropped
     let start = Instant::now();
-    for _ in 0..iterations {
-        let mut buf = vec![0u8; size];
-        for chunk in buf.chunks_mut(8) {
-            if chunk.len() == 8 {
-                chunk.copy_from_slice(&0x5555555555555555u64.to_le_bytes());
-            }
+    for i in 0..iterations {
+        let pattern = std::hint::black_box(0x5555555555555555u64 ^ i as u64);
+        for word in buf.iter_mut() {
+            *word = pattern;
         }
-        std::hint::black_box(&buf);
+        std::hint::black_box(&mut buf);
     }
     let write_elapsed = start.elapsed().as_secs_f64();
     let write_mbps = (size as f64 * iterations as f64 / 1_048_576.0) / write_elapsed;
 
-    // Read test
-    let buf = vec![42u8; size];
+    // Read test: black_box the buffer so its contents are opaque and the sum
+    // has to load every word, then consume the sum
     let start = Instant::now();
     let mut sum = 0u64;
     for _ in 0..iterations {
-        for chunk in buf.chunks(8) {
-            if chunk.len() == 8 {
-                sum = sum.wrapping_add(u64::from_le_bytes(chunk.try_into().unwrap_or([0; 8])));
-            }
+        for word in std::hint::black_box(&buf).iter() {
+            sum = sum.wrapping_add(*word);
         }
     }
+    std::hint::black_box(sum);
     let read_elapsed = start.elapsed().as_secs_f64();
     let read_mbps = (size as f64 * iterations as f64 / 1_048_576.0) / read_elapsed;
-    let _ = sum;
 
     // Latency test (random access pattern)
     let lat_buf = vec![0u64; 1024 * 1024];
@@ -156,12 +315,86 @@ fn bench_ram() -> (f64, f64, f64) {
     (read_mbps, write_mbps, lat_ns)
 }
 
-/// Disk benchmark: sequential + random I/O
-fn bench_disk() -> (f64, f64, f64) {
-    let temp = std::env::var("TEMP").unwrap_or_else(|_| ".".into());
-    let path = format!("{}\\vega_bench_{}.tmp", temp, std::process::id());
-    let block_size = 1024 * 1024; // 1 MB blocks
-    let blocks = 64; // 64 MB total
+const DISK_BLOCK_SIZE: usize = 1024 * 1024; // 1 MB blocks
+const DISK_BLOCKS: usize = 64; // 64 MB scratch file
+
+/// Where the disk test's scratch file goes: `%TEMP%` by default or when it already lives
+/// on `drive`, otherwise the root of `drive`. Fails if the volume lacks room for the file.
+fn disk_scratch_dir(drive: Option<char>) -> Result<std::path::PathBuf, String> {
+    let temp = std::path::PathBuf::from(std::env::var("TEMP").unwrap_or_else(|_| ".".into()));
+    let dir = match drive {
+        None => temp,
+        Some(letter) if !letter.is_ascii_alphabetic() => {
+            return Err(format!("Invalid drive letter: {}", letter));
+        }
+        Some(letter) => {
+            let letter = letter.to_ascii_uppercase();
+            let temp_drive = temp
+                .to_string_lossy()
+                .chars()
+                .next()
+                .map(|c| c.to_ascii_uppercase());
+            if temp_drive == Some(letter) {
+                temp
+            } else {
+                std::path::PathBuf::from(format!("{}:\\", letter))
+            }
+        }
+    };
+    if !dir.is_dir() {
+        return Err(format!("{} is not available", dir.display()));
+    }
+
+    // Free space on the volume holding `dir` (longest matching mount point)
+    let needed = (DISK_BLOCKS * DISK_BLOCK_SIZE) as u64;
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    let dir_lower = dir.to_string_lossy().to_lowercase();
+    let volume = disks
+        .iter()
+        .filter(|d| dir_lower.starts_with(&d.mount_point().to_string_lossy().to_lowercase()))
+        .max_by_key(|d| d.mount_point().as_os_str().len());
+    if let Some(volume) = volume {
+        if volume.available_space() < needed {
+            return Err(format!(
+                "Not enough free space on {}: the disk test needs {} MB, {} MB available",
+                volume.mount_point().display(),
+                needed / 1_048_576,
+                volume.available_space() / 1_048_576
+            ));
+        }
+    }
+    Ok(dir)
+}
+
+/// Sector alignment required for unbuffered I/O (covers 512e and 4Kn drives)
+const DISK_ALIGN: usize = 4096;
+
+/// Open `path` for reading straight from the drive, bypassing the OS file cache.
+/// Reads on such a handle must use sector-aligned offsets, lengths and buffers.
+fn open_uncached(path: &std::path::Path) -> std::io::Result<std::fs::File> {
+    let mut opts = std::fs::OpenOptions::new();
+    opts.read(true);
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::OpenOptionsExt;
+        use winapi::um::winbase::{FILE_FLAG_NO_BUFFERING, FILE_FLAG_WRITE_THROUGH};
+        opts.custom_flags(FILE_FLAG_NO_BUFFERING | FILE_FLAG_WRITE_THROUGH);
+    }
+    opts.open(path)
+}
+
+/// `len` bytes of `storage` starting on a `DISK_ALIGN` boundary
+fn aligned_slice(storage: &mut Vec<u8>, len: usize) -> &mut [u8] {
+    storage.resize(len + DISK_ALIGN, 0);
+    let offset = storage.as_ptr().align_offset(DISK_ALIGN);
+    &mut storage[offset..offset + len]
+}
+
+/// Disk benchmark: sequential + random I/O on a scratch file in `dir`
+fn bench_disk(dir: &std::path::Path) -> (f64, f64, f64) {
+    let path = dir.join(format!("vega_bench_{}.tmp", std::process::id()));
+    let block_size = DISK_BLOCK_SIZE;
+    let blocks = DISK_BLOCKS;
 
     // Sequential write
     let data: Vec<u8> = (0..block_size).map(|i| (i % 256) as u8).collect();
@@ -176,29 +409,36 @@ fn bench_disk() -> (f64, f64, f64) {
     let write_elapsed = start.elapsed().as_secs_f64();
     let write_mbps = (blocks as f64 * block_size as f64 / 1_048_576.0) / write_elapsed;
 
-    // Sequential read
+    // Sequential read, uncached so the data just written isn't served from RAM
+    let mut storage = Vec::new();
     let start = Instant::now();
-    if let Ok(content) = std::fs::read(&path) {
-        std::hint::black_box(&content);
+    if let Ok(mut f) = open_uncached(&path) {
+        use std::io::Read;
+        let buf = aligned_slice(&mut storage, block_size);
+        for _ in 0..blocks {
+            if f.read_exact(buf).is_err() {
+                break;
+            }
+            std::hint::black_box(&buf);
+        }
     }
     let read_elapsed = start.elapsed().as_secs_f64();
     let read_mbps = (blocks as f64 * block_size as f64 / 1_048_576.0) / read_elapsed;
 
-    // Random read (4K blocks)
-    let small_block = 4096;
+    // Random read (4K blocks at sector-aligned offsets)
+    let small_block = DISK_ALIGN;
     let random_reads = 1000;
     let file_size = blocks * block_size;
     let start = Instant::now();
-    if let Ok(f) = std::fs::File::open(&path) {
+    if let Ok(mut f) = open_uncached(&path) {
         use std::io::{Read, Seek, SeekFrom};
-        let mut f = std::io::BufReader::new(f);
-        let mut buf = vec![0u8; small_block];
-        let mut offset = 0u64;
+        let buf = aligned_slice(&mut storage, small_block);
+        let slots = (file_size / small_block) as u64;
+        let mut slot = 0u64;
         for i in 0..random_reads {
-            offset = (offset.wrapping_mul(6364136223846793005).wrapping_add(i))
-                % (file_size as u64 - small_block as u64);
-            let _ = f.seek(SeekFrom::Start(offset));
-            let _ = f.read_exact(&mut buf);
+            slot = (slot.wrapping_mul(6364136223846793005).wrapping_add(i)) % slots;
+            let _ = f.seek(SeekFrom::Start(slot * small_block as u64));
+            let _ = f.read_exact(buf);
         }
     }
     let random_elapsed = start.elapsed().as_secs_f64();
@@ -209,3 +449,614 @@ fn bench_disk() -> (f64, f64, f64) {
 
     (read_mbps, write_mbps, iops)
 }
+
+/// GPU benchmark: copy throughput between two 64 MB textures in video memory, in GB/s.
+///
+/// Uses Direct3D 11 on the default adapter, so it needs no shader compiler and runs on
+/// any DX10-class or newer GPU. `Err` carries a note when no usable GPU is present.
+fn bench_gpu() -> Result<f64, String> {
+    #[cfg(windows)]
+    {
+        use winapi::shared::dxgiformat::DXGI_FORMAT_R8G8B8A8_UNORM;
+        use winapi::shared::dxgitype::DXGI_SAMPLE_DESC;
+        use winapi::shared::minwindef::BOOL;
+        use winapi::shared::winerror::{FAILED, S_OK};
+        use winapi::um::d3d11::{
+            D3D11CreateDevice, ID3D11Device, ID3D11DeviceContext, ID3D11Query, ID3D11Texture2D,
+            D3D11_BIND_SHADER_RESOURCE, D3D11_QUERY_DESC, D3D11_QUERY_EVENT, D3D11_SDK_VERSION,
+            D3D11_SUBRESOURCE_DATA, D3D11_TEXTURE2D_DESC, D3D11_USAGE_DEFAULT,
+        };
+        use winapi::um::d3dcommon::D3D_DRIVER_TYPE_HARDWARE;
+        use winapi::um::unknwnbase::IUnknown;
+
+        /// Releases the wrapped COM interface on drop
+        struct Com<T>(*mut T);
+        impl<T> Drop for Com<T> {
+            fn drop(&mut self) {
+                if !self.0.is_null() {
+                    unsafe { (*(self.0 as *mut IUnknown)).Release() };
+                }
+            }
+        }
+
+        const SIDE: u32 = 4096; // 4096 x 4096 RGBA = 64 MB per texture
+        const COPIES: u32 = 64;
+
+        unsafe {
+            let mut device = Com(std::ptr::null_mut::<ID3D11Device>());
+            let mut context = Com(std::ptr::null_mut::<ID3D11DeviceContext>());
+            let hr = D3D11CreateDevice(
+                std::ptr::null_mut(),
+                D3D_DRIVER_TYPE_HARDWARE,
+                std::ptr::null_mut(),
+                0,
+                std::ptr::null(),
+                0,
+                D3D11_SDK_VERSION,
+                &mut device.0,
+                std::ptr::null_mut(),
+                &mut context.0,
+            );
+            if FAILED(hr) || device.0.is_null() || context.0.is_null() {
+                return Err("No compatible GPU (Direct3D 11 hardware device unavailable)".into());
+            }
+            let device_ref = &*device.0;
+            let ctx = &*context.0;
+
+            // Random contents so the driver can't compress the copies away
+            let mut seed = 0x9E3779B97F4A7C15u64;
+            let pixels: Vec<u32> = (0..SIDE * SIDE)
+                .map(|_| {
+                    seed = seed
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    (seed >> 32) as u32
+                })
+                .collect();
+            let init = D3D11_SUBRESOURCE_DATA {
+                pSysMem: pixels.as_ptr() as *const _,
+                SysMemPitch: SIDE * 4,
+                SysMemSlicePitch: 0,
+            };
+            let desc = D3D11_TEXTURE2D_DESC {
+                Width: SIDE,
+                Height: SIDE,
+                MipLevels: 1,
+                ArraySize: 1,
+                Format: DXGI_FORMAT_R8G8B8A8_UNORM,
+                SampleDesc: DXGI_SAMPLE_DESC {
+                    Count: 1,
+                    Quality: 0,
+                },
+                Usage: D3D11_USAGE_DEFAULT,
+                BindFlags: D3D11_BIND_SHADER_RESOURCE,
+                CPUAccessFlags: 0,
+                MiscFlags: 0,
+            };
+            let mut a = Com(std::ptr::null_mut::<ID3D11Texture2D>());
+            let mut b = Com(std::ptr::null_mut::<ID3D11Texture2D>());
+            if FAILED(device_ref.CreateTexture2D(&desc, &init, &mut a.0))
+                || FAILED(device_ref.CreateTexture2D(&desc, &init, &mut b.0))
+            {
+                return Err("GPU could not allocate the benchmark textures".into());
+            }
+            drop(pixels);
+
+            let query_desc = D3D11_QUERY_DESC {
+                Query: D3D11_QUERY_EVENT,
+                MiscFlags: 0,
+            };
+            let mut query = Com(std::ptr::null_mut::<ID3D11Query>());
+            if FAILED(device_ref.CreateQuery(&query_desc, &mut query.0)) {
+                return Err("GPU does not support event queries".into());
+            }
+
+            // Block until the GPU has finished everything queued so far
+            let wait_idle = || -> bool {
+                ctx.End(query.0 as *mut _);
+                let deadline = Instant::now() + std::time::Duration::from_secs(10);
+                let mut done: BOOL = 0;
+                loop {
+                    let hr = ctx.GetData(
+                        query.0 as *mut _,
+                        &mut done as *mut BOOL as *mut _,
+                        std::mem::size_of::<BOOL>() as u32,
+                        0,
+                    );
+                    if hr == S_OK && done != 0 {
+                        return true;
+                    }
+                    if FAILED(hr) || Instant::now() > deadline {
+                        return false;
+                    }
+                    std::thread::yield_now();
+                }
+            };
+
+            // Warm-up copy, then ping-pong between the textures
+            ctx.CopyResource(b.0 as *mut _, a.0 as *mut _);
+            if !wait_idle() {
+                return Err("GPU did not respond to the benchmark workload".into());
+            }
+            let start = Instant::now();
+            for i in 0..COPIES {
+                if i % 2 == 0 {
+                    ctx.CopyResource(a.0 as *mut _, b.0 as *mut _);
+                } else {
+                    ctx.CopyResource(b.0 as *mut _, a.0 as *mut _);
+                }
+            }
+            if !wait_idle() {
+                return Err("GPU did not respond to the benchmark workload".into());
+            }
+            let elapsed = start.elapsed().as_secs_f64().max(1e-6);
+            let bytes = SIDE as f64 * SIDE as f64 * 4.0 * COPIES as f64;
+            return Ok(bytes / 1_073_741_824.0 / elapsed);
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        Err("No compatible GPU (the GPU test requires Direct3D 11)".into())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// History — compare runs over time and against reference machines
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricDelta {
+    pub metric: String,
+    pub previous: f64,
+    pub current: f64,
+    pub change_pct: f64, // signed raw change; see `improved` for direction
+    pub improved: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkComparison {
+    pub previous_id: String,
+    pub current_id: String,
+    pub deltas: Vec<MetricDelta>,
+}
+
+const BENCHMARK_HISTORY_MAX: usize = 100;
+
+/// Typical results per metric as (value, % of similar machines it beats), ascending by
+/// value: budget, mid-range, high-end and enthusiast systems from community averages
+const REFERENCE: &[(&str, [(f64, f64); 4])] = &[
+    (
+        "cpu_single_score",
+        [(400.0, 10.0), (800.0, 40.0), (1200.0, 70.0), (1800.0, 95.0)],
+    ),
+    (
+        "cpu_multi_score",
+        [
+            (1500.0, 10.0),
+            (4000.0, 40.0),
+            (8000.0, 70.0),
+            (14000.0, 95.0),
+        ],
+    ),
+    (
+        "ram_read_mbps",
+        [
+            (8000.0, 10.0),
+            (20000.0, 40.0),
+            (35000.0, 70.0),
+            (55000.0, 95.0),
+        ],
+    ),
+    (
+        "ram_write_mbps",
+        [
+            (6000.0, 10.0),
+            (15000.0, 40.0),
+            (28000.0, 70.0),
+            (45000.0, 95.0),
+        ],
+    ),
+    // Latency: lower beats more machines
+    (
+        "ram_latency_ns",
+        [(30.0, 95.0), (50.0, 70.0), (80.0, 40.0), (120.0, 10.0)],
+    ),
+    (
+        "disk_seq_read_mbps",
+        [(200.0, 10.0), (550.0, 40.0), (2000.0, 70.0), (5000.0, 95.0)],
+    ),
+    (
+        "disk_seq_write_mbps",
+        [(150.0, 10.0), (500.0, 40.0), (1500.0, 70.0), (4000.0, 95.0)],
+    ),
+    (
+        "disk_random_iops",
+        [
+            (200.0, 10.0),
+            (5000.0, 40.0),
+            (30000.0, 70.0),
+            (80000.0, 95.0),
+        ],
+    ),
+    // Integrated graphics, entry, mid-range and flagship discrete cards
+    (
+        "gpu_score",
+        [
+            (200.0, 10.0),
+            (1000.0, 40.0),
+            (3000.0, 70.0),
+            (7000.0, 95.0),
+        ],
+    ),
+];
+
+/// (name, value, higher is better) for every compared metric
+fn metrics(r: &BenchmarkResult) -> [(&'static str, f64, bool); 10] {
+    [
+        ("total_score", r.total_score as f64, true),
+        ("cpu_single_score", r.cpu_single_score, true),
+        ("cpu_multi_score", r.cpu_multi_score, true),
+        ("ram_read_mbps", r.ram_read_mbps, true),
+        ("ram_write_mbps", r.ram_write_mbps, true),
+        ("ram_latency_ns", r.ram_latency_ns, false),
+        ("disk_seq_read_mbps", r.disk_seq_read_mbps, true),
+        ("disk_seq_write_mbps", r.disk_seq_write_mbps, true),
+        ("disk_random_iops", r.disk_random_iops, true),
+        ("gpu_score", r.gpu_score, true),
+    ]
+}
+
+/// Share of reference machines `value` beats, interpolated between the table's points
+fn percentile(metric: &str, value: f64) -> Option<u32> {
+    let (_, points) = REFERENCE.iter().find(|(m, _)| *m == metric)?;
+    let (first, last) = (points[0], points[points.len() - 1]);
+    let pct = if value <= first.0 {
+        first.1 * (value / first.0).max(0.0)
+    } else if value >= last.0 {
+        last.1
+    } else {
+        points
+            .windows(2)
+            .find(|w| value <= w[1].0)
+            .map(|w| w[0].1 + (w[1].1 - w[0].1) * (value - w[0].0) / (w[1].0 - w[0].0))
+            .unwrap_or(last.1)
+    };
+    Some(pct.clamp(1.0, 99.0).round() as u32)
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn benchmarks_path() -> std::path::PathBuf {
+    let base = std::env::var("LOCALAPPDATA").unwrap_or_else(|_| {
+        let profile = std::env::var("USERPROFILE").unwrap_or_else(|_| ".".into());
+        format!("{}\\AppData\\Local", profile)
+    });
+    std::path::PathBuf::from(base)
+        .join("VegaOptimizer")
+        .join("benchmarks.json")
+}
+
+fn record_benchmark(result: &BenchmarkResult) {
+    let mut history = get_benchmark_history();
+    history.push(result.clone());
+    if history.len() > BENCHMARK_HISTORY_MAX {
+        history.drain(..history.len() - BENCHMARK_HISTORY_MAX);
+    }
+    let path = benchmarks_path();
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&history) {
+        let _ = std::fs::write(&path, json);
+    }
+}
+
+/// Every saved benchmark run, oldest first
+pub fn get_benchmark_history() -> Vec<BenchmarkResult> {
+    std::fs::read_to_string(benchmarks_path())
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+/// Per-metric change from run `previous_id` to the latest run
+pub fn compare_benchmark(previous_id: &str) -> Result<BenchmarkComparison, String> {
+    let history = get_benchmark_history();
+    let current = history.last().ok_or("No benchmark runs recorded yet")?;
+    let previous = history
+        .iter()
+        .find(|r| r.id == previous_id)
+        .ok_or_else(|| format!("No benchmark run with id {}", previous_id))?;
+    if previous.id == current.id {
+        return Err("Pick an earlier run to compare against the latest one".into());
+    }
+
+    let deltas = metrics(previous)
+        .iter()
+        .zip(metrics(current).iter())
+        .map(|((metric, before, higher_is_better), (_, after, _))| {
+            let change_pct = if *before > 0.0 {
+                (after - before) / before * 100.0
+            } else {
+                0.0
+            };
+            MetricDelta {
+                metric: metric.to_string(),
+                previous: *before,
+                current: *after,
+                change_pct,
+                improved: if *higher_is_better {
+                    after > before
+                } else {
+                    after < before
+                },
+            }
+        })
+        .collect();
+
+    Ok(BenchmarkComparison {
+        previous_id: previous.id.clone(),
+        current_id: current.id.clone(),
+        deltas,
+    })
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Stress Test — sustained full load with thermal logging
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StressSample {
+    pub elapsed_secs: u64,
+    pub cpu_usage: f32,
+    pub cpu_freq_mhz: u64,
+    pub max_temp_c: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StressTestResult {
+    pub target: String, // "cpu", "ram", "disk", "all"
+    pub requested_secs: u64,
+    pub duration_secs: u64,
+    pub stopped_early: bool,
+    pub start_temp_c: Option<f32>,
+    pub peak_temp_c: Option<f32>,
+    pub avg_temp_c: Option<f32>,
+    pub critical_temp_c: Option<f32>,
+    pub avg_cpu_usage: f32,
+    pub start_freq_mhz: u64,
+    pub min_freq_mhz: u64,
+    pub throttling_detected: bool,
+    pub throttle_reasons: Vec<String>,
+    pub work_units: u64, // loop iterations / MB moved — compare runs for drop-off
+    pub samples: Vec<StressSample>,
+}
+
+/// Set while a stress test runs; cleared by `stop_stress_test` to end it early
+static STRESS_RUNNING: AtomicBool = AtomicBool::new(false);
+static STRESS_STOP: AtomicBool = AtomicBool::new(false);
+
+/// Seconds between thermal/clock samples
+const STRESS_SAMPLE_SECS: u64 = 2;
+
+/// Pin CPU (all logical cores), RAM, disk, or all three at full load for `minutes`,
+/// sampling temperatures and clocks to detect thermal or clock throttling.
+pub fn run_stress_test(minutes: u32, target: &str) -> Result<StressTestResult, String> {
+    let target = target.to_lowercase();
+    let (cpu, ram, disk) = match target.as_str() {
+        "cpu" => (true, false, false),
+        "ram" => (false, true, false),
+        "disk" => (false, false, true),
+        "all" => (true, true, true),
+        _ => return Err(format!("Unknown stress target: {}", target)),
+    };
+    if STRESS_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("A stress test is already running".into());
+    }
+    STRESS_STOP.store(false, Ordering::SeqCst);
+
+    let requested_secs = minutes.clamp(1, 60) as u64 * 60;
+    let work = Arc::new(AtomicU64::new(0));
+    let mut workers = Vec::new();
+
+    if cpu {
+        let threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4);
+        for t in 0..threads {
+            let work = work.clone();
+            workers.push(std::thread::spawn(move || {
+                let mut x = t as f64 * 0.1;
+                while !STRESS_STOP.load(Ordering::Relaxed) {
+                    for i in 0..100_000u64 {
+                        x += ((i as f64 + x) * 0.000001).sin().cos().sqrt().abs();
+                    }
+                    std::hint::black_box(x);
+                    work.fetch_add(1, Ordering::Relaxed);
+                }
+            }));
+        }
+    }
+
+    if ram {
+        // A quarter of available memory (capped at 4 GB) keeps the system responsive
+        let mut sys = sysinfo::System::new();
+        sys.refresh_memory();
+        let bytes = (sys.available_memory() / 4).min(4 << 30) as usize;
+        let work = work.clone();
+        workers.push(std::thread::spawn(move || {
+            let mut buf = vec![0u64; bytes / 8];
+            let mut pattern = 0x5555_5555_5555_5555u64;
+            while !STRESS_STOP.load(Ordering::Relaxed) {
+                for chunk in buf.chunks_mut(1 << 20) {
+                    chunk.fill(pattern);
+                    if STRESS_STOP.load(Ordering::Relaxed) {
+                        break;
+                    }
+                }
+                let sum = buf.iter().fold(0u64, |a, v| a.wrapping_add(*v));
+                std::hint::black_box(sum);
+                pattern = !pattern;
+                work.fetch_add((bytes / 1_048_576) as u64 * 2, Ordering::Relaxed);
+            }
+        }));
+    }
+
+    if disk {
+        let work = work.clone();
+        workers.push(std::thread::spawn(move || {
+            use std::io::{Read, Seek, SeekFrom, Write};
+            let temp = std::env::var("TEMP").unwrap_or_else(|_| ".".into());
+            let path = format!("{}\\vega_stress_{}.tmp", temp, std::process::id());
+            let block = vec![0xA5u8; 4 * 1024 * 1024];
+            let blocks = 64; // 256 MB working file
+            if let Ok(mut f) = std::fs::OpenOptions::new()
+                .create(true)
+                .truncate(true)
+                .read(true)
+                .write(true)
+                .open(&path)
+            {
+                let mut read_buf = vec![0u8; block.len()];
+                while !STRESS_STOP.load(Ordering::Relaxed) {
+                    let _ = f.seek(SeekFrom::Start(0));
+                    for _ in 0..blocks {
+                        if f.write_all(&block).is_err() || STRESS_STOP.load(Ordering::Relaxed) {
+                            break;
+                        }
+                    }
+                    let _ = f.sync_all();
+                    let _ = f.seek(SeekFrom::Start(0));
+                    for _ in 0..blocks {
+                        if f.read_exact(&mut read_buf).is_err() {
+                            break;
+                        }
+                    }
+                    work.fetch_add(blocks as u64 * 4 * 2, Ordering::Relaxed);
+                }
+            }
+            let _ = std::fs::remove_file(&path);
+        }));
+    }
+
+    // Sample temperatures and clocks until time is up or the user stops the test
+    let start = Instant::now();
+    let mut sys = sysinfo::System::new();
+    let mut components = sysinfo::Components::new_with_refreshed_list();
+    let mut samples: Vec<StressSample> = Vec::new();
+    let mut critical_temp_c: Option<f32> = None;
+    sys.refresh_cpu_all();
+
+    while start.elapsed().as_secs() < requested_secs && !STRESS_STOP.load(Ordering::Relaxed) {
+        std::thread::sleep(std::time::Duration::from_secs(STRESS_SAMPLE_SECS));
+        sys.refresh_cpu_all();
+        components.refresh(true);
+
+        let temps = crate::monitor::read_temperatures(&components);
+        let max_temp_c = temps
+            .iter()
+            .map(|t| t.temp_c)
+            .filter(|t| *t > 0.0)
+            .fold(None, |m: Option<f32>, t| Some(m.map_or(t, |m| m.max(t))));
+        for t in &temps {
+            if let Some(c) = t.critical {
+                critical_temp_c = Some(critical_temp_c.map_or(c, |m: f32| m.min(c)));
+            }
+        }
+
+        let cpus = sys.cpus();
+        samples.push(StressSample {
+            elapsed_secs: start.elapsed().as_secs(),
+            cpu_usage: sys.global_cpu_usage(),
+            cpu_freq_mhz: cpus.iter().map(|c| c.frequency()).max().unwrap_or(0),
+            max_temp_c,
+        });
+    }
+
+    let stopped_early = start.elapsed().as_secs() < requested_secs;
+    STRESS_STOP.store(true, Ordering::SeqCst);
+    for w in workers {
+        let _ = w.join();
+    }
+    STRESS_RUNNING.store(false, Ordering::SeqCst);
+
+    // ── Summarize ──
+    let temps: Vec<f32> = samples.iter().filter_map(|s| s.max_temp_c).collect();
+    let peak_temp_c = temps
+        .iter()
+        .cloned()
+        .fold(None, |m: Option<f32>, t| Some(m.map_or(t, |m| m.max(t))));
+    let avg_temp_c = if temps.is_empty() {
+        None
+    } else {
+        Some(temps.iter().sum::<f32>() / temps.len() as f32)
+    };
+    let avg_cpu_usage = if samples.is_empty() {
+        0.0
+    } else {
+        samples.iter().map(|s| s.cpu_usage).sum::<f32>() / samples.len() as f32
+    };
+    let start_freq_mhz = samples.first().map(|s| s.cpu_freq_mhz).unwrap_or(0);
+    let min_freq_mhz = samples
+        .iter()
+        .map(|s| s.cpu_freq_mhz)
+        .filter(|f| *f > 0)
+        .min()
+        .unwrap_or(0);
+
+    let mut throttle_reasons = Vec::new();
+    if let Some(peak) = peak_temp_c {
+        let limit = critical_temp_c.unwrap_or(95.0);
+        if peak >= limit - 5.0 {
+            throttle_reasons.push(format!(
+                "Peak temperature {:.0}°C reached the thermal limit (~{:.0}°C)",
+                peak, limit
+            ));
+        }
+    }
+    // Clock readings are only meaningful under load; many Windows systems report a
+    // static base clock, in which case no drop is ever seen here
+    if cpu
+        && start_freq_mhz > 0
+        && min_freq_mhz > 0
+        && (min_freq_mhz as f64) < start_freq_mhz as f64 * 0.85
+    {
+        throttle_reasons.push(format!(
+            "CPU clock fell from {} MHz to {} MHz under load",
+            start_freq_mhz, min_freq_mhz
+        ));
+    }
+
+    Ok(StressTestResult {
+        target,
+        requested_secs,
+        duration_secs: start.elapsed().as_secs(),
+        stopped_early,
+        start_temp_c: samples.first().and_then(|s| s.max_temp_c),
+        peak_temp_c,
+        avg_temp_c,
+        critical_temp_c,
+        avg_cpu_usage,
+        start_freq_mhz,
+        min_freq_mhz,
+        throttling_detected: !throttle_reasons.is_empty(),
+        throttle_reasons,
+        work_units: work.load(Ordering::Relaxed),
+        samples,
+    })
+}
+
+/// Ask a running stress test to stop; it returns its partial result
+pub fn stop_stress_test() -> Result<String, String> {
+    if !STRESS_RUNNING.load(Ordering::SeqCst) {
+        return Err("No stress test is running".into());
+    }
+    STRESS_STOP.store(true, Ordering::SeqCst);
+    Ok("Stopping stress test".into())
+}
@@ -0,0 +1,130 @@
+//! Headless mode — run optimizations from a script or scheduled task without the GUI
+//!
+//! `vega-optimizer --run mem_working_set,disk_temp_files [--json]` runs the listed
+//! catalog items through `run_optimization`, prints the report and exits non-zero
+//! if any item failed.
+
+use crate::optimizer::{get_optimization_catalog, run_optimization, OptimizationReport};
+
+const USAGE: &str = "Usage: vega-optimizer --run <id>[,<id>...] [--json]
+       vega-optimizer --list [--json]
+
+  --run   Run the given optimization ids (see --list) and exit
+  --list  Print the optimization catalog
+  --json  Print machine-readable JSON instead of text";
+
+/// Handle command-line arguments; `None` means no CLI flags were given and the GUI should start.
+/// Otherwise returns the process exit code: 0 success, 1 an item failed, 2 bad usage.
+pub fn run_cli(args: Vec<String>) -> Option<i32> {
+    if args.is_empty() {
+        return None;
+    }
+    attach_parent_console();
+
+    let mut ids: Option<Vec<String>> = None;
+    let mut list = false;
+    let mut json = false;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--run" => {
+                let Some(value) = iter.next() else {
+                    eprintln!("--run needs a comma-separated list of ids\n\n{}", USAGE);
+                    return Some(2);
+                };
+                ids = Some(
+                    value
+                        .split(',')
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect(),
+                );
+            }
+            "--list" => list = true,
+            "--json" => json = true,
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return Some(0);
+            }
+            other => {
+                eprintln!("Unknown argument: {}\n\n{}", other, USAGE);
+                return Some(2);
+            }
+        }
+    }
+
+    if list {
+        let catalog = get_optimization_catalog();
+        if json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&catalog).unwrap_or_default()
+            );
+        } else {
+            for item in catalog {
+                println!("{:<28} [{}] {}", item.id, item.risk, item.name);
+            }
+        }
+        return Some(0);
+    }
+
+    let Some(ids) = ids.filter(|ids| !ids.is_empty()) else {
+        eprintln!("{}", USAGE);
+        return Some(2);
+    };
+    let known: Vec<String> = get_optimization_catalog()
+        .into_iter()
+        .map(|item| item.id)
+        .collect();
+    let unknown: Vec<&String> = ids.iter().filter(|id| !known.contains(id)).collect();
+    if !unknown.is_empty() {
+        eprintln!(
+            "Unknown optimization ids: {} (use --list)",
+            unknown
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        return Some(2);
+    }
+
+    let report = run_optimization(ids);
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).unwrap_or_default()
+        );
+    } else {
+        print_report(&report);
+    }
+    Some(if report.items_failed > 0 { 1 } else { 0 })
+}
+
+fn print_report(report: &OptimizationReport) {
+    for r in &report.results {
+        println!(
+            "{} {:<28} {:>6} ms  {}",
+            if r.success { "OK  " } else { "FAIL" },
+            r.id,
+            r.duration_ms,
+            r.message
+        );
+    }
+    println!(
+        "\n{} of {} succeeded, {:.0} MB freed in {} ms",
+        report.items_succeeded,
+        report.items_attempted,
+        report.total_memory_freed_mb,
+        report.total_duration_ms
+    );
+}
+
+/// Release builds use the GUI subsystem, so output from a terminal needs the parent's console
+fn attach_parent_console() {
+    #[cfg(windows)]
+    unsafe {
+        use winapi::um::wincon::{AttachConsole, ATTACH_PARENT_PROCESS};
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
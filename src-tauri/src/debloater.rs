@@ -3,6 +3,24 @@
 use serde::{Deserialize, Serialize};
 use std::process::Command;
 
+/// Payload of the `bloatware-removal-progress` event, sent after each package
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BloatwareRemovalProgress {
+    pub current: usize, // 1-based index of the package just processed
+    pub total: usize,
+    pub display_name: String,
+    pub success: bool,
+}
+
+/// Outcome of `remove_all_bloatware`: per-app results plus what the run reclaimed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BloatwareRemovalReport {
+    pub results: Vec<(String, bool, String)>, // (display name, removed, message)
+    pub removed_count: usize,
+    pub failed_count: usize,
+    pub total_size_mb: f64, // combined size of the packages actually removed
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppxPackage {
     pub name: String,
@@ -16,6 +34,20 @@ pub struct AppxPackage {
     pub category: String, // "bloatware", "utility", "system", "game", "media"
     pub safe_to_remove: bool,
     pub description: String,
+    pub package_family_name: String,
+    pub install_date: Option<u64>, // UNIX seconds, when registered for this user
+    pub last_used: Option<u64>,    // UNIX seconds, last launch recorded by Explorer
+    pub launch_count: u32,
+    pub never_used: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReinstallableApp {
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub installed: bool,     // registered for the current user
+    pub in_repository: bool, // manifest still on disk, so `reinstall_appx` can work
 }
 
 /// Known bloatware / safe-to-remove apps
@@ -96,9 +128,13 @@ const PROTECTED_PACKAGES: &[&str] = &[
 /// List all installed UWP packages with bloatware classification
 pub fn list_appx_packages() -> Vec<AppxPackage> {
     let mut packages = Vec::new();
+    let usage = appx_launch_history();
+    let packages_root = std::env::var("LOCALAPPDATA")
+        .map(|l| std::path::PathBuf::from(l).join("Packages"))
+        .ok();
 
     if let Ok(output) = Command::new("powershell")
-        .args(["-Command", r#"Get-AppxPackage | Select-Object Name,PackageFullName,Publisher,Version,InstallLocation,IsFramework,SignatureKind | ForEach-Object { "$($_.Name)|$($_.Publisher)|$($_.Version)|$($_.InstallLocation)|$($_.IsFramework)|$($_.SignatureKind)" }"#])
+        .args(["-Command", r#"Get-AppxPackage | Select-Object Name,PackageFullName,PackageFamilyName,Publisher,Version,InstallLocation,IsFramework,SignatureKind | ForEach-Object { "$($_.Name)|$($_.Publisher)|$($_.Version)|$($_.InstallLocation)|$($_.IsFramework)|$($_.SignatureKind)|$($_.PackageFamilyName)" }"#])
         .output()
     {
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -112,6 +148,7 @@ pub fn list_appx_packages() -> Vec<AppxPackage> {
             let install_loc = parts[3].trim().to_string();
             let is_framework = parts[4].trim() == "True";
             let is_system = parts[5].trim() == "System";
+            let family = parts.get(6).map(|f| f.trim().to_string()).unwrap_or_default();
 
             // Skip frameworks and empty
             if is_framework || name.is_empty() { continue; }
@@ -149,6 +186,17 @@ pub fn list_appx_packages() -> Vec<AppxPackage> {
                 0.0
             };
 
+            // Per-user data folder is created when the package is registered for this user
+            let install_date = packages_root
+                .as_ref()
+                .filter(|_| !family.is_empty())
+                .and_then(|root| created_secs(&root.join(&family)))
+                .or_else(|| created_secs(std::path::Path::new(&install_loc)));
+            let (launch_count, last_used) = usage
+                .get(&family.to_lowercase())
+                .copied()
+                .unwrap_or((0, None));
+
             packages.push(AppxPackage {
                 name: name.clone(),
                 display_name,
@@ -161,6 +209,11 @@ pub fn list_appx_packages() -> Vec<AppxPackage> {
                 category,
                 safe_to_remove: safe,
                 description,
+                package_family_name: family,
+                install_date,
+                last_used,
+                launch_count,
+                never_used: launch_count == 0 && last_used.is_none(),
             });
         }
     }
@@ -179,13 +232,127 @@ pub fn list_appx_packages() -> Vec<AppxPackage> {
     packages
 }
 
+/// `list_appx_packages` narrowed down: `category` ("bloatware", "game", ...; "all" or
+/// `None` for any), only packages safe to remove, and/or a case-insensitive substring
+/// of the package or display name
+pub fn list_appx_packages_filtered(
+    category: Option<String>,
+    only_removable: bool,
+    name_contains: Option<String>,
+) -> Vec<AppxPackage> {
+    let category = category.filter(|c| !c.is_empty() && !c.eq_ignore_ascii_case("all"));
+    let needle = name_contains
+        .map(|n| n.trim().to_lowercase())
+        .filter(|n| !n.is_empty());
+
+    list_appx_packages()
+        .into_iter()
+        .filter(|p| !only_removable || p.safe_to_remove)
+        .filter(|p| match &category {
+            Some(c) => p.category.eq_ignore_ascii_case(c),
+            None => true,
+        })
+        .filter(|p| match &needle {
+            Some(n) => {
+                p.name.to_lowercase().contains(n) || p.display_name.to_lowercase().contains(n)
+            }
+            None => true,
+        })
+        .collect()
+}
+
+/// Creation time of a path as UNIX seconds
+fn created_secs(path: &std::path::Path) -> Option<u64> {
+    let created = std::fs::metadata(path).ok()?.created().ok()?;
+    created
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs())
+}
+
+/// Launch counts and last-run times of packaged apps, keyed by lowercase family name.
+///
+/// Explorer's UserAssist store records every Start/taskbar launch. Value names are
+/// ROT13-encoded AppUserModelIDs (`<PackageFamilyName>!<AppId>`); the binary value
+/// holds the run count at offset 4 and the last-run FILETIME at offset 60.
+fn appx_launch_history() -> std::collections::HashMap<String, (u32, Option<u64>)> {
+    let mut usage: std::collections::HashMap<String, (u32, Option<u64>)> =
+        std::collections::HashMap::new();
+
+    let script = r#"
+        $k = Get-Item 'HKCU:\Software\Microsoft\Windows\CurrentVersion\Explorer\UserAssist\{CEBFF5CD-ACE2-4F4F-9178-9926F41749EA}\Count' -ErrorAction SilentlyContinue
+        if ($k) {
+            foreach ($n in $k.GetValueNames()) {
+                $b = $k.GetValue($n)
+                if ($b -is [byte[]] -and $b.Length -ge 68) {
+                    "$n|$([BitConverter]::ToUInt32($b, 4))|$([BitConverter]::ToInt64($b, 60))"
+                }
+            }
+        }
+    "#;
+
+    if let Ok(output) = Command::new("powershell")
+        .args(["-Command", script])
+        .output()
+    {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for line in stdout.lines() {
+            let parts: Vec<&str> = line.rsplitn(3, '|').collect();
+            if parts.len() < 3 {
+                continue;
+            }
+            let name = rot13(parts[2].trim());
+            // Only packaged apps carry a `!` separated AppUserModelID
+            let Some((family, _)) = name.split_once('!') else {
+                continue;
+            };
+            let count: u32 = parts[1].trim().parse().unwrap_or(0);
+            let filetime: i64 = parts[0].trim().parse().unwrap_or(0);
+            let last = if filetime > 0 {
+                // FILETIME: 100ns ticks since 1601-01-01
+                (filetime as u64 / 10_000_000).checked_sub(11_644_473_600)
+            } else {
+                None
+            };
+
+            let entry = usage.entry(family.to_lowercase()).or_insert((0, None));
+            entry.0 += count;
+            entry.1 = entry.1.max(last);
+        }
+    }
+
+    usage
+}
+
+fn rot13(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            'a'..='z' => (((c as u8 - b'a') + 13) % 26 + b'a') as char,
+            'A'..='Z' => (((c as u8 - b'A') + 13) % 26 + b'A') as char,
+            _ => c,
+        })
+        .collect()
+}
+
+/// Deepest folder level counted when sizing a package (payloads nest a few levels)
+const APPX_SIZE_MAX_DEPTH: u32 = 12;
+
+/// Total size of the files under a package's install location
 fn estimate_dir_size(path: &str) -> u64 {
+    dir_size_bounded(std::path::Path::new(path), APPX_SIZE_MAX_DEPTH)
+}
+
+/// Recursive file size sum, `depth` levels down. Symlinks and junctions report as
+/// neither file nor dir here, so shared framework folders aren't counted twice.
+fn dir_size_bounded(path: &std::path::Path, depth: u32) -> u64 {
     let mut total = 0u64;
     if let Ok(entries) = std::fs::read_dir(path) {
         for entry in entries.flatten() {
             if let Ok(m) = entry.metadata() {
                 if m.is_file() {
                     total += m.len();
+                } else if m.is_dir() && depth > 0 {
+                    total += dir_size_bounded(&entry.path(), depth - 1);
                 }
             }
         }
@@ -219,16 +386,134 @@ pub fn remove_appx_package(name: &str) -> Result<String, String> {
 }
 
 /// Bulk remove multiple packages
-pub fn remove_all_bloatware() -> Vec<(String, bool, String)> {
+pub fn remove_all_bloatware(progress: &dyn Fn(BloatwareRemovalProgress)) -> BloatwareRemovalReport {
     let packages = list_appx_packages();
     let mut results = Vec::new();
+    let mut total_size_mb = 0.0;
+    let targets: Vec<&AppxPackage> = packages.iter().filter(|p| p.safe_to_remove).collect();
+    let total = targets.len();
+
+    for (i, pkg) in targets.into_iter().enumerate() {
+        let success = match remove_appx_package(&pkg.name) {
+            Ok(msg) => {
+                results.push((pkg.display_name.clone(), true, msg));
+                total_size_mb += pkg.size_mb;
+                true
+            }
+            Err(msg) => {
+                results.push((pkg.display_name.clone(), false, msg));
+                false
+            }
+        };
+        progress(BloatwareRemovalProgress {
+            current: i + 1,
+            total,
+            display_name: pkg.display_name.clone(),
+            success,
+        });
+    }
 
-    for pkg in packages.iter().filter(|p| p.safe_to_remove) {
-        match remove_appx_package(&pkg.name) {
-            Ok(msg) => results.push((pkg.display_name.clone(), true, msg)),
-            Err(msg) => results.push((pkg.display_name.clone(), false, msg)),
+    let removed_count = results.iter().filter(|(_, ok, _)| *ok).count();
+    BloatwareRemovalReport {
+        failed_count: results.len() - removed_count,
+        removed_count,
+        total_size_mb,
+        results,
+    }
+}
+
+/// Microsoft apps from the bloatware table — the only ones Windows keeps in its app
+/// repository after removal (Store-delivered third-party apps must be re-downloaded)
+fn default_app_entries() -> impl Iterator<Item = &'static (&'static str, &'static str, &'static str)>
+{
+    BLOATWARE_PATTERNS
+        .iter()
+        .filter(|(name, _, _)| name.starts_with("Microsoft."))
+}
+
+/// Known default apps with whether each is installed and still reinstallable.
+///
+/// Checking the repository uses `Get-AppxPackage -AllUsers`, which needs admin;
+/// without it `in_repository` is false for every app.
+pub fn list_removable_defaults() -> Vec<ReinstallableApp> {
+    let names: Vec<String> = default_app_entries()
+        .map(|(name, _, _)| format!("'{}'", name))
+        .collect();
+    let script = format!(
+        r#"foreach ($n in @({})) {{
+            $me = Get-AppxPackage -Name $n -ErrorAction SilentlyContinue | Select-Object -First 1
+            $repo = Get-AppxPackage -AllUsers -Name $n -ErrorAction SilentlyContinue | Where-Object {{ $_.InstallLocation -and (Test-Path (Join-Path $_.InstallLocation 'AppXManifest.xml')) }} | Select-Object -First 1
+            "$n|$([bool]$me)|$([bool]$repo)"
+        }}"#,
+        names.join(",")
+    );
+
+    let mut status: std::collections::HashMap<String, (bool, bool)> =
+        std::collections::HashMap::new();
+    if let Ok(output) = Command::new("powershell")
+        .args(["-Command", &script])
+        .output()
+    {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for line in stdout.lines() {
+            let parts: Vec<&str> = line.trim().split('|').collect();
+            if parts.len() >= 3 {
+                status.insert(
+                    parts[0].to_string(),
+                    (
+                        parts[1].eq_ignore_ascii_case("True"),
+                        parts[2].eq_ignore_ascii_case("True"),
+                    ),
+                );
+            }
         }
     }
 
-    results
+    default_app_entries()
+        .map(|(name, display, category)| {
+            let (installed, in_repository) = status.get(*name).copied().unwrap_or((false, false));
+            ReinstallableApp {
+                name: name.to_string(),
+                display_name: display.to_string(),
+                category: category.to_string(),
+                installed,
+                in_repository,
+            }
+        })
+        .collect()
+}
+
+/// Re-register a removed app from the copy Windows keeps in its app repository
+pub fn reinstall_appx(name: &str) -> Result<String, String> {
+    // Names go into a PowerShell string — allow only package-name characters
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+    {
+        return Err(format!("Invalid package name: {}", name));
+    }
+
+    let script = format!(
+        r#"$p = Get-AppxPackage -AllUsers -Name '{}' -ErrorAction Stop | Where-Object {{ $_.InstallLocation -and (Test-Path (Join-Path $_.InstallLocation 'AppXManifest.xml')) }} | Select-Object -First 1
+        if (-not $p) {{ throw 'Package is not in the system app repository - reinstall it from the Microsoft Store' }}
+        Add-AppxPackage -DisableDevelopmentMode -Register (Join-Path $p.InstallLocation 'AppXManifest.xml') -ErrorAction Stop"#,
+        name
+    );
+
+    match Command::new("powershell")
+        .args(["-Command", &script])
+        .output()
+    {
+        Ok(o) if o.status.success() => Ok(format!("Reinstalled {}", name)),
+        Ok(o) => {
+            let stderr = String::from_utf8_lossy(&o.stderr).trim().to_string();
+            if stderr.contains("Access is denied") || stderr.contains("0x80070005") {
+                Err("Requires Administrator privileges to read the app repository".into())
+            } else {
+                Err(stderr)
+            }
+        }
+        Err(e) => Err(e.to_string()),
+    }
 }
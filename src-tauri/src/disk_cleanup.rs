@@ -1290,8 +1290,16 @@ fn clean_dir_all(path: &str) -> (u32, u64, u32) {
 }
 
 fn clean_dir_recursive(path: &str, deleted: &mut u32, freed: &mut u64, errors: &mut u32) {
+    if is_reparse_protected(Path::new(path)) {
+        log::warn!("Cleanup skipped reparse point: {}", path);
+        return;
+    }
     if let Ok(entries) = std::fs::read_dir(path) {
         for entry in entries.flatten() {
+            if is_reparse_protected(&entry.path()) {
+                log::warn!("Cleanup skipped reparse point: {}", entry.path().display());
+                continue;
+            }
             if let Ok(meta) = entry.metadata() {
                 if meta.is_file() {
                     let size = meta.len();
@@ -1310,6 +1318,38 @@ fn clean_dir_recursive(path: &str, deleted: &mut u32, freed: &mut u64, errors: &
     }
 }
 
+/// Legacy compatibility junctions Windows creates inside user profiles; several loop
+/// back to their parent (e.g. `%LOCALAPPDATA%\Application Data`)
+const COMPAT_JUNCTIONS: &[&str] = &[
+    "Application Data",
+    "Local Settings",
+    "Temporary Internet Files",
+    "Documents and Settings",
+    "My Documents",
+    "My Music",
+    "My Pictures",
+    "My Videos",
+    "NetHood",
+    "PrintHood",
+];
+
+/// True for symlinks, junctions and known compatibility junctions — cleanup must never
+/// traverse or delete through these, or it can wander outside its target directory
+pub(crate) fn is_reparse_protected(path: &Path) -> bool {
+    let is_compat_name = path
+        .file_name()
+        .map(|n| {
+            let n = n.to_string_lossy();
+            COMPAT_JUNCTIONS.iter().any(|j| n.eq_ignore_ascii_case(j))
+        })
+        .unwrap_or(false);
+    // symlink_metadata doesn't follow the link; on Windows is_symlink() covers junctions too
+    is_compat_name
+        || std::fs::symlink_metadata(path)
+            .map(|m| m.file_type().is_symlink())
+            .unwrap_or(false)
+}
+
 fn categorize_ext(ext: &str) -> String {
     match ext {
         "mp4" | "avi" | "mkv" | "mov" | "wmv" | "flv" | "webm" => "Video",
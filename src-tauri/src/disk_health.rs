@@ -1,7 +1,9 @@
 //! Disk Health — S.M.A.R.T. data, SSD/HDD health
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::process::Command;
+use std::sync::Mutex;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiskHealthInfo {
@@ -22,16 +24,351 @@ pub struct DiskHealthInfo {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SmartAttribute {
-    pub id: u32,
+    pub id: u32, // 0 for NVMe health-log fields and OS error counters
     pub name: String,
     pub value: String,
+    pub worst: String,
     pub threshold: String,
+    pub raw_value: String,
     pub status: String, // "ok", "warning", "critical"
 }
 
+/// One persisted `get_disk_health` reading
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthSample {
+    pub timestamp: u64, // unix seconds
+    pub health_pct: u32,
+    pub temperature_c: Option<f64>,
+    pub power_on_hours: Option<u64>,
+    pub total_writes_gb: Option<f64>,
+    pub reallocated_sectors: Option<u64>, // raw value of SMART attribute 5
+}
+
+/// Samples kept per drive in the history log
+const HISTORY_MAX_SAMPLES: usize = 365;
+
+/// At most one sample per drive in this window; the health views re-read SMART data often
+const HISTORY_MIN_INTERVAL_SECS: u64 = 6 * 3600;
+
+/// Serializes the history file's read-modify-write between concurrent scans
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+/// SMART data for one drive, keyed by normalized serial in `read_smart_data`
+#[derive(Default)]
+struct SmartData {
+    attributes: Vec<SmartAttribute>,
+    reads_gb: Option<f64>,
+    writes_gb: Option<f64>,
+}
+
+/// Well-known ATA attribute names; anything else keeps the vendor's or a generic name
+const ATA_ATTRIBUTE_NAMES: &[(u32, &str)] = &[
+    (1, "Raw Read Error Rate"),
+    (5, "Reallocated Sectors Count"),
+    (9, "Power-On Hours"),
+    (10, "Spin Retry Count"),
+    (12, "Power Cycle Count"),
+    (173, "Wear Leveling Count"),
+    (177, "Wear Leveling Count"),
+    (184, "End-to-End Error"),
+    (187, "Reported Uncorrectable Errors"),
+    (188, "Command Timeout"),
+    (190, "Airflow Temperature"),
+    (194, "Temperature"),
+    (196, "Reallocation Event Count"),
+    (197, "Current Pending Sector Count"),
+    (198, "Offline Uncorrectable Sectors"),
+    (199, "UDMA CRC Error Count"),
+    (231, "SSD Life Left"),
+    (233, "Media Wearout Indicator"),
+    (241, "Total LBAs Written"),
+    (242, "Total LBAs Read"),
+];
+
+/// Attributes whose raw count should be zero on a healthy drive
+const ZERO_RAW_ATTRIBUTES: &[u32] = &[5, 10, 184, 187, 196, 197, 198];
+
+/// Serials differ in padding and case between smartctl, WMI and Storage cmdlets
+fn normalize_serial(serial: &str) -> String {
+    serial
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_uppercase()
+}
+
+/// Build an ATA attribute entry with its status.
+///
+/// Critical when the normalized value has dropped to the vendor threshold (a
+/// threshold of 0 means "never fails"); warning when a sector/error counter that
+/// should be zero has a non-zero raw count.
+fn ata_attribute(
+    id: u32,
+    vendor_name: Option<&str>,
+    value: u64,
+    worst: u64,
+    threshold: u64,
+    raw: u64,
+) -> SmartAttribute {
+    let status = if threshold > 0 && value <= threshold {
+        "critical"
+    } else if ZERO_RAW_ATTRIBUTES.contains(&id) && raw > 0 {
+        "warning"
+    } else {
+        "ok"
+    };
+    let name = ATA_ATTRIBUTE_NAMES
+        .iter()
+        .find(|(aid, _)| *aid == id)
+        .map(|(_, n)| n.to_string())
+        .or_else(|| vendor_name.map(|n| n.replace('_', " ")))
+        .unwrap_or_else(|| format!("Vendor Attribute {}", id));
+    SmartAttribute {
+        id,
+        name,
+        value: value.to_string(),
+        worst: worst.to_string(),
+        threshold: threshold.to_string(),
+        raw_value: raw.to_string(),
+        status: status.into(),
+    }
+}
+
+/// Entry without normalized value/threshold (NVMe log fields, OS counters)
+fn raw_attribute(name: &str, raw: String, status: &str) -> SmartAttribute {
+    SmartAttribute {
+        id: 0,
+        name: name.into(),
+        value: raw.clone(),
+        worst: String::new(),
+        threshold: String::new(),
+        raw_value: raw,
+        status: status.into(),
+    }
+}
+
+/// Total LBAs written/read (241/242) in GB — assumes 512-byte LBAs, which most vendors report
+fn lba_gb(attributes: &[SmartAttribute], id: u32) -> Option<f64> {
+    let raw: f64 = attributes
+        .iter()
+        .find(|a| a.id == id)?
+        .raw_value
+        .parse()
+        .ok()?;
+    Some((raw * 512.0 / 1_073_741_824.0 * 100.0).round() / 100.0)
+}
+
+fn smartctl_path() -> Option<String> {
+    let installed = r"C:\Program Files\smartmontools\bin\smartctl.exe";
+    if std::path::Path::new(installed).exists() {
+        return Some(installed.into());
+    }
+    Command::new("smartctl")
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|_| "smartctl".into())
+}
+
+/// SMART data for every device smartctl can see, via its JSON output
+fn smart_from_smartctl() -> HashMap<String, SmartData> {
+    let mut result = HashMap::new();
+    let Some(smartctl) = smartctl_path() else {
+        return result;
+    };
+
+    let scan = Command::new(&smartctl).args(["--scan", "-j"]).output();
+    let Ok(scan) = scan else {
+        return result;
+    };
+    let scan: serde_json::Value = serde_json::from_slice(&scan.stdout).unwrap_or_default();
+    let devices = scan["devices"].as_array().cloned().unwrap_or_default();
+
+    for device in devices {
+        let Some(name) = device["name"].as_str() else {
+            continue;
+        };
+        let Ok(output) = Command::new(&smartctl)
+            .args(["-i", "-A", "-j", name])
+            .output()
+        else {
+            continue;
+        };
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap_or_default();
+        let Some(serial) = json["serial_number"].as_str() else {
+            continue;
+        };
+
+        let mut data = SmartData::default();
+        if let Some(table) = json["ata_smart_attributes"]["table"].as_array() {
+            for attr in table {
+                data.attributes.push(ata_attribute(
+                    attr["id"].as_u64().unwrap_or(0) as u32,
+                    attr["name"].as_str(),
+                    attr["value"].as_u64().unwrap_or(0),
+                    attr["worst"].as_u64().unwrap_or(0),
+                    attr["thresh"].as_u64().unwrap_or(0),
+                    attr["raw"]["value"].as_u64().unwrap_or(0),
+                ));
+            }
+            data.writes_gb = lba_gb(&data.attributes, 241);
+            data.reads_gb = lba_gb(&data.attributes, 242);
+        }
+
+        let nvme = &json["nvme_smart_health_information_log"];
+        if nvme.is_object() {
+            // NVMe data units are 1000 × 512 bytes
+            let units_gb = |v: &serde_json::Value| {
+                v.as_f64()
+                    .map(|u| (u * 512_000.0 / 1_073_741_824.0 * 100.0).round() / 100.0)
+            };
+            data.writes_gb = units_gb(&nvme["data_units_written"]);
+            data.reads_gb = units_gb(&nvme["data_units_read"]);
+
+            let used = nvme["percentage_used"].as_u64().unwrap_or(0);
+            let spare = nvme["available_spare"].as_u64().unwrap_or(100);
+            let spare_thresh = nvme["available_spare_threshold"].as_u64().unwrap_or(0);
+            let media_errors = nvme["media_errors"].as_u64().unwrap_or(0);
+            let critical_warning = nvme["critical_warning"].as_u64().unwrap_or(0);
+
+            data.attributes.push(raw_attribute(
+                "Critical Warning",
+                critical_warning.to_string(),
+                if critical_warning > 0 {
+                    "critical"
+                } else {
+                    "ok"
+                },
+            ));
+            data.attributes.push(raw_attribute(
+                "Percentage Used",
+                format!("{}%", used),
+                if used >= 100 {
+                    "critical"
+                } else if used >= 80 {
+                    "warning"
+                } else {
+                    "ok"
+                },
+            ));
+            data.attributes.push(raw_attribute(
+                "Available Spare",
+                format!("{}% (threshold {}%)", spare, spare_thresh),
+                if spare <= spare_thresh {
+                    "critical"
+                } else {
+                    "ok"
+                },
+            ));
+            data.attributes.push(raw_attribute(
+                "Media Errors",
+                media_errors.to_string(),
+                if media_errors > 0 { "warning" } else { "ok" },
+            ));
+            if let Some(gb) = data.writes_gb {
+                data.attributes
+                    .push(raw_attribute("Data Written", format!("{:.0} GB", gb), "ok"));
+            }
+            if let Some(gb) = data.reads_gb {
+                data.attributes
+                    .push(raw_attribute("Data Read", format!("{:.0} GB", gb), "ok"));
+            }
+            if let Some(h) = nvme["power_on_hours"].as_u64() {
+                data.attributes
+                    .push(raw_attribute("Power-On Hours", h.to_string(), "ok"));
+            }
+        }
+
+        if !data.attributes.is_empty() {
+            result.insert(normalize_serial(serial), data);
+        }
+    }
+    result
+}
+
+/// ATA SMART data from WMI `MSStorageDriver_FailurePredictData` (administrator only).
+///
+/// The 512-byte VendorSpecific blob holds 30 12-byte entries after a 2-byte header:
+/// id, flags (2), value, worst, raw (6, little-endian), reserved. The thresholds blob
+/// uses the same layout with the threshold in the byte after the id. NVMe drives
+/// don't populate this class.
+fn smart_from_wmi() -> HashMap<String, SmartData> {
+    let mut result = HashMap::new();
+    let Ok(output) = Command::new("powershell")
+        .args(["-Command", r#"
+            $disks = Get-CimInstance Win32_DiskDrive
+            $th = @{}
+            Get-CimInstance -Namespace root\wmi -ClassName MSStorageDriver_FailurePredictThresholds -ErrorAction SilentlyContinue | ForEach-Object { $th[$_.InstanceName] = $_.VendorSpecific }
+            Get-CimInstance -Namespace root\wmi -ClassName MSStorageDriver_FailurePredictData -ErrorAction SilentlyContinue | ForEach-Object {
+                $inst = $_.InstanceName
+                $disk = $disks | Where-Object { $inst.StartsWith($_.PNPDeviceID, [StringComparison]::OrdinalIgnoreCase) } | Select-Object -First 1
+                if ($disk) { "$($disk.SerialNumber)|$($_.VendorSpecific -join ',')|$($th[$inst] -join ',')" }
+            }
+        "#])
+        .output()
+    else {
+        return result;
+    };
+
+    let bytes =
+        |s: &str| -> Vec<u8> { s.split(',').filter_map(|b| b.trim().parse().ok()).collect() };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines() {
+        let parts: Vec<&str> = line.split('|').collect();
+        if parts.len() < 3 || parts[0].trim().is_empty() {
+            continue;
+        }
+        let data_blob = bytes(parts[1]);
+        let thresh_blob = bytes(parts[2]);
+
+        let mut thresholds: HashMap<u8, u8> = HashMap::new();
+        for entry in thresh_blob.get(2..).unwrap_or(&[]).chunks_exact(12) {
+            if entry[0] != 0 {
+                thresholds.insert(entry[0], entry[1]);
+            }
+        }
+
+        let mut data = SmartData::default();
+        for entry in data_blob.get(2..).unwrap_or(&[]).chunks_exact(12).take(30) {
+            if entry[0] == 0 {
+                continue;
+            }
+            let raw = entry[5..11]
+                .iter()
+                .rev()
+                .fold(0u64, |acc, b| (acc << 8) | *b as u64);
+            data.attributes.push(ata_attribute(
+                entry[0] as u32,
+                None,
+                entry[3] as u64,
+                entry[4] as u64,
+                thresholds.get(&entry[0]).copied().unwrap_or(0) as u64,
+                raw,
+            ));
+        }
+        data.writes_gb = lba_gb(&data.attributes, 241);
+        data.reads_gb = lba_gb(&data.attributes, 242);
+
+        if !data.attributes.is_empty() {
+            result.insert(normalize_serial(parts[0]), data);
+        }
+    }
+    result
+}
+
+/// SMART data by normalized serial — smartctl when installed (covers NVMe), WMI otherwise
+fn read_smart_data() -> HashMap<String, SmartData> {
+    let mut data = smart_from_wmi();
+    // smartctl is more complete; let it win where both report a drive
+    data.extend(smart_from_smartctl());
+    data
+}
+
 /// Get disk health info for all drives
 pub fn get_disk_health() -> Vec<DiskHealthInfo> {
     let mut disks = Vec::new();
+    let mut smart = read_smart_data();
 
     // Get physical disk info via PowerShell
     if let Ok(output) = Command::new("powershell")
@@ -48,9 +385,9 @@ pub fn get_disk_health() -> Vec<DiskHealthInfo> {
                 $wear = $d.Wear
                 $temp = try{ (Get-StorageReliabilityCounter -PhysicalDisk $d -ErrorAction SilentlyContinue).Temperature }catch{ $null }
                 $hours = try{ (Get-StorageReliabilityCounter -PhysicalDisk $d -ErrorAction SilentlyContinue).PowerOnHours }catch{ $null }
-                $reads = try{ [math]::Round((Get-StorageReliabilityCounter -PhysicalDisk $d -ErrorAction SilentlyContinue).ReadErrorsTotal / 1GB, 2) }catch{ $null }
-                $writes = try{ [math]::Round((Get-StorageReliabilityCounter -PhysicalDisk $d -ErrorAction SilentlyContinue).WriteErrorsTotal / 1GB, 2) }catch{ $null }
-                "$model|$serial|$fw|$bus|$media|$size|$health|$wear|$temp|$hours|$reads|$writes"
+                $rerr = try{ (Get-StorageReliabilityCounter -PhysicalDisk $d -ErrorAction SilentlyContinue).ReadErrorsTotal }catch{ $null }
+                $werr = try{ (Get-StorageReliabilityCounter -PhysicalDisk $d -ErrorAction SilentlyContinue).WriteErrorsTotal }catch{ $null }
+                "$model|$serial|$fw|$bus|$media|$size|$health|$wear|$temp|$hours|$rerr|$werr"
             }
         "#])
         .output()
@@ -80,6 +417,15 @@ pub fn get_disk_health() -> Vec<DiskHealthInfo> {
             let temp = parts.get(8).and_then(|s| s.trim().parse::<f64>().ok());
             let hours = parts.get(9).and_then(|s| s.trim().parse::<u64>().ok());
 
+            let mut data = smart.remove(&normalize_serial(parts[1])).unwrap_or_default();
+            // OS-level error counters (these are error counts, not bytes transferred)
+            for (i, name) in [(10, "Read Errors (total)"), (11, "Write Errors (total)")] {
+                if let Some(n) = parts.get(i).and_then(|s| s.trim().parse::<u64>().ok()) {
+                    let status = if n > 0 { "warning" } else { "ok" };
+                    data.attributes.push(raw_attribute(name, n.to_string(), status));
+                }
+            }
+
             disks.push(DiskHealthInfo {
                 model,
                 serial: parts[1].trim().to_string(),
@@ -91,9 +437,9 @@ pub fn get_disk_health() -> Vec<DiskHealthInfo> {
                 health_pct,
                 temperature_c: temp,
                 power_on_hours: hours,
-                total_reads_gb: parts.get(10).and_then(|s| s.trim().parse().ok()),
-                total_writes_gb: parts.get(11).and_then(|s| s.trim().parse().ok()),
-                smart_attributes: Vec::new(),
+                total_reads_gb: data.reads_gb,
+                total_writes_gb: data.writes_gb,
+                smart_attributes: data.attributes,
             });
         }
     }
@@ -122,6 +468,9 @@ pub fn get_disk_health() -> Vec<DiskHealthInfo> {
 
                 let size_bytes: f64 = parts[6].trim().parse().unwrap_or(0.0);
                 let status = parts[7].trim();
+                let data = smart
+                    .remove(&normalize_serial(parts[5]))
+                    .unwrap_or_default();
 
                 disks.push(DiskHealthInfo {
                     model: model.to_string(),
@@ -138,13 +487,332 @@ pub fn get_disk_health() -> Vec<DiskHealthInfo> {
                     health_pct: if status == "OK" { 90 } else { 50 },
                     temperature_c: None,
                     power_on_hours: None,
-                    total_reads_gb: None,
-                    total_writes_gb: None,
-                    smart_attributes: Vec::new(),
+                    total_reads_gb: data.reads_gb,
+                    total_writes_gb: data.writes_gb,
+                    smart_attributes: data.attributes,
                 });
             }
         }
     }
 
+    record_health_samples(&disks);
     disks
 }
+
+fn history_path() -> std::path::PathBuf {
+    let base = std::env::var("LOCALAPPDATA").unwrap_or_else(|_| {
+        let profile = std::env::var("USERPROFILE").unwrap_or_else(|_| ".".into());
+        format!("{}\\AppData\\Local", profile)
+    });
+    std::path::PathBuf::from(base)
+        .join("VegaOptimizer")
+        .join("disk_health_history.json")
+}
+
+/// History key of a drive — normalized serial, or the model when no serial is reported
+fn history_key(serial: &str, model: &str) -> String {
+    let key = normalize_serial(serial);
+    if key.is_empty() {
+        normalize_serial(model)
+    } else {
+        key
+    }
+}
+
+fn load_history() -> HashMap<String, Vec<HealthSample>> {
+    std::fs::read_to_string(history_path())
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+/// Append one sample per drive to the history log, keeping the newest HISTORY_MAX_SAMPLES.
+/// Drives sampled within HISTORY_MIN_INTERVAL_SECS are skipped. Best effort — a
+/// read-only profile just means no history.
+fn record_health_samples(disks: &[DiskHealthInfo]) {
+    if disks.is_empty() {
+        return;
+    }
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut history = load_history();
+    let mut changed = false;
+    for disk in disks {
+        let samples = history
+            .entry(history_key(&disk.serial, &disk.model))
+            .or_default();
+        if let Some(last) = samples.last() {
+            if timestamp.saturating_sub(last.timestamp) < HISTORY_MIN_INTERVAL_SECS {
+                continue;
+            }
+        }
+        changed = true;
+        samples.push(HealthSample {
+            timestamp,
+            health_pct: disk.health_pct,
+            temperature_c: disk.temperature_c,
+            power_on_hours: disk.power_on_hours,
+            total_writes_gb: disk.total_writes_gb,
+            reallocated_sectors: disk
+                .smart_attributes
+                .iter()
+                .find(|a| a.id == 5)
+                .and_then(|a| a.raw_value.parse().ok()),
+        });
+        if samples.len() > HISTORY_MAX_SAMPLES {
+            let excess = samples.len() - HISTORY_MAX_SAMPLES;
+            samples.drain(..excess);
+        }
+    }
+    if !changed {
+        return;
+    }
+
+    let path = history_path();
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string(&history) {
+        let _ = std::fs::write(&path, json);
+    }
+}
+
+/// Recorded health samples for a drive, oldest first (serial and model as reported in
+/// `DiskHealthInfo`; the model is the key for drives without a serial)
+pub fn get_health_history(serial: &str, model: &str) -> Vec<HealthSample> {
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    load_history()
+        .remove(&history_key(serial, model))
+        .unwrap_or_default()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SSD wear — pagefile and high-write cache placement
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PagefileLocation {
+    pub path: String,
+    pub drive: String,
+    pub on_ssd: bool,
+    pub allocated_mb: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SsdWearRecommendation {
+    pub id: String,
+    pub category: String, // "endurance", "pagefile", "temp", "browser_cache"
+    pub severity: String, // "info", "low", "medium"
+    pub title: String,
+    pub current_location: Option<String>,
+    pub size_mb: Option<f64>,
+    pub suggested_location: Option<String>, // a fixed HDD that could take the writes
+    pub action: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SsdWearReport {
+    pub ssd_drives: Vec<String>, // drive letters backed by an SSD, e.g. "C:"
+    pub hdd_drives: Vec<String>,
+    pub total_writes_gb: Option<f64>, // summed over SSDs that report it
+    pub min_ssd_health_pct: Option<u32>,
+    pub pagefiles: Vec<PagefileLocation>,
+    pub recommendations: Vec<SsdWearRecommendation>,
+}
+
+/// SSDs below this health percentage are treated as worn for relocation advice
+const WORN_SSD_HEALTH_PCT: u32 = 80;
+
+/// Caches smaller than this aren't worth relocating
+const RELOCATE_MIN_MB: f64 = 500.0;
+
+/// Drive letter ("C:") of a Windows path
+fn drive_of(path: &str) -> String {
+    path.chars().take(2).collect::<String>().to_uppercase()
+}
+
+/// Pagefiles in use, from `Win32_PageFileUsage`
+fn list_pagefiles() -> Vec<(String, u64)> {
+    let mut pagefiles = Vec::new();
+    if let Ok(output) = Command::new("powershell")
+        .args([
+            "-Command",
+            r#"Get-CimInstance Win32_PageFileUsage | ForEach-Object { "$($_.Name)|$($_.AllocatedBaseSize)" }"#,
+        ])
+        .output()
+    {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for line in stdout.lines() {
+            let parts: Vec<&str> = line.split('|').collect();
+            if parts.len() >= 2 && !parts[0].trim().is_empty() {
+                pagefiles.push((
+                    parts[0].trim().to_string(),
+                    parts[1].trim().parse().unwrap_or(0),
+                ));
+            }
+        }
+    }
+    pagefiles
+}
+
+/// Relate SSD endurance data to what is writing to the SSD and where it could go instead.
+///
+/// Moving the pagefile off a healthy SSD costs more performance than it saves wear,
+/// so relocation is only suggested for worn drives; large caches are suggested for
+/// relocation whenever a fixed HDD is available.
+pub fn analyze_ssd_wear() -> SsdWearReport {
+    use sysinfo::{DiskKind, Disks};
+
+    let mut ssd_drives = Vec::new();
+    let mut hdd_drives = Vec::new();
+    let disks = Disks::new_with_refreshed_list();
+    for disk in disks.list() {
+        if disk.is_removable() {
+            continue;
+        }
+        let drive = drive_of(&disk.mount_point().to_string_lossy());
+        match disk.kind() {
+            DiskKind::SSD => ssd_drives.push(drive),
+            DiskKind::HDD => hdd_drives.push(drive),
+            _ => {}
+        }
+    }
+    ssd_drives.sort();
+    ssd_drives.dedup();
+    hdd_drives.sort();
+    hdd_drives.dedup();
+
+    let ssds: Vec<DiskHealthInfo> = get_disk_health()
+        .into_iter()
+        .filter(|d| d.media_type.eq_ignore_ascii_case("SSD"))
+        .collect();
+    // Writes of 0 come from drives that don't expose the counter
+    let writes: Vec<f64> = ssds
+        .iter()
+        .filter_map(|d| d.total_writes_gb)
+        .filter(|w| *w > 0.0)
+        .collect();
+    let total_writes_gb = if writes.is_empty() {
+        None
+    } else {
+        Some(writes.iter().sum())
+    };
+    let min_ssd_health_pct = ssds.iter().map(|d| d.health_pct).min();
+    let worn = min_ssd_health_pct.is_some_and(|h| h < WORN_SSD_HEALTH_PCT);
+    let hdd_target = hdd_drives.first().cloned();
+
+    let mut recommendations = Vec::new();
+
+    if let Some(gb) = total_writes_gb {
+        recommendations.push(SsdWearRecommendation {
+            id: "ssd_endurance".into(),
+            category: "endurance".into(),
+            severity: if worn { "medium" } else { "info" }.into(),
+            title: format!(
+                "{:.1} TB written to SSDs, lowest health {}%",
+                gb / 1024.0,
+                min_ssd_health_pct.unwrap_or(100)
+            ),
+            current_location: None,
+            size_mb: None,
+            suggested_location: None,
+            action: if worn {
+                "Back up regularly and reduce write-heavy workloads on this SSD".into()
+            } else {
+                "No action needed".into()
+            },
+        });
+    }
+
+    let pagefiles: Vec<PagefileLocation> = list_pagefiles()
+        .into_iter()
+        .map(|(path, allocated_mb)| {
+            let drive = drive_of(&path);
+            PagefileLocation {
+                on_ssd: ssd_drives.contains(&drive),
+                drive,
+                path,
+                allocated_mb,
+            }
+        })
+        .collect();
+    for pf in pagefiles.iter().filter(|p| p.on_ssd) {
+        let relocate = worn && hdd_target.is_some();
+        recommendations.push(SsdWearRecommendation {
+            id: format!("pagefile_{}", pf.drive.trim_end_matches(':').to_lowercase()),
+            category: "pagefile".into(),
+            severity: if relocate { "medium" } else { "info" }.into(),
+            title: format!("Pagefile on SSD ({} MB allocated)", pf.allocated_mb),
+            current_location: Some(pf.path.clone()),
+            size_mb: Some(pf.allocated_mb as f64),
+            suggested_location: if relocate { hdd_target.clone() } else { None },
+            action: if relocate {
+                "SSD is worn — move the pagefile to the HDD in System Properties › Advanced › Virtual memory".into()
+            } else {
+                "Keep it on the SSD — paging to an HDD is much slower and modern SSDs handle the writes".into()
+            },
+        });
+    }
+
+    let mut caches: Vec<(String, String, String, f64)> = Vec::new(); // (id, category, path, size)
+    if let Ok(temp) = std::env::var("TEMP") {
+        let size = crate::scanner::dir_size_mb(&temp);
+        caches.push(("temp".into(), "temp".into(), temp, size));
+    }
+    for browser in crate::scanner::detect_browsers() {
+        if browser.installed && !browser.cache_path.is_empty() {
+            caches.push((
+                format!(
+                    "browser_cache_{}",
+                    browser.name.to_lowercase().replace(' ', "_")
+                ),
+                "browser_cache".into(),
+                browser.cache_path,
+                browser.cache_size_mb,
+            ));
+        }
+    }
+    for (id, category, path, size) in caches {
+        if !ssd_drives.contains(&drive_of(&path)) {
+            continue;
+        }
+        let relocate = size >= RELOCATE_MIN_MB && hdd_target.is_some();
+        let action = match (category.as_str(), relocate) {
+            ("temp", true) => "Point the TEMP and TMP user variables at a folder on the HDD",
+            ("temp", false) => "Clean temporary files periodically",
+            (_, true) => "Move the cache with the browser's disk cache setting (e.g. --disk-cache-dir) or cap its size",
+            (_, false) => "Clean the cache periodically",
+        };
+        recommendations.push(SsdWearRecommendation {
+            id,
+            title: format!(
+                "{} on SSD ({:.0} MB)",
+                if category == "temp" {
+                    "Temp folder"
+                } else {
+                    "Browser cache"
+                },
+                size
+            ),
+            category,
+            severity: if relocate { "low" } else { "info" }.into(),
+            current_location: Some(path),
+            size_mb: Some(size),
+            suggested_location: if relocate { hdd_target.clone() } else { None },
+            action: action.into(),
+        });
+    }
+
+    SsdWearReport {
+        ssd_drives,
+        hdd_drives,
+        total_writes_gb,
+        min_ssd_health_pct,
+        pagefiles,
+        recommendations,
+    }
+}
@@ -1,9 +1,11 @@
-//! Duplicate File Finder — hash-based duplicate detection
+//! Duplicate File Finder — hash-based duplicate detection with a persistent hash cache
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::io::Read;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DuplicateGroup {
@@ -12,6 +14,7 @@ pub struct DuplicateGroup {
     pub count: usize,
     pub total_wasted_mb: f64,
     pub files: Vec<DuplicateFile>,
+    pub verified: bool, // byte-identical by full SHA-256, not just the quick hash
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +23,7 @@ pub struct DuplicateFile {
     pub size_mb: f64,
     pub modified: String,
     pub extension: String,
+    pub token: String, // size/mtime identity, re-verified before delete
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,43 +31,312 @@ pub struct DuplicateScanResult {
     pub groups: Vec<DuplicateGroup>,
     pub total_duplicates: usize,
     pub total_wasted_mb: f64,
+    pub by_category: Vec<DuplicateCategoryStats>, // sorted by wasted space, largest first
     pub files_scanned: usize,
+    pub enumeration: String,   // "mft" or "directory"
+    pub cancelled: bool,       // stopped early — groups come from the files seen so far
+    pub warnings: Vec<String>, // e.g. requested directories that were skipped
     pub duration_ms: u64,
 }
 
-/// Scan for duplicate files in common user directories
-pub fn scan_duplicates(min_size_mb: f64) -> DuplicateScanResult {
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateCategoryStats {
+    pub category: String, // scanner category: "Video", "Image", "Archive", ...
+    pub groups: usize,
+    pub duplicates: usize,
+    pub wasted_mb: f64,
+}
+
+/// Payload of the `duplicate-scan-progress` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateScanProgress {
+    pub files_scanned: usize,
+    pub current_dir: String,
+    pub phase: String, // "sizing", "hashing", "verifying" or "grouping"
+    pub hashed: usize,
+    pub candidates: usize, // files sharing a size with another file
+}
+
+/// Paths (with sizes) that share a content hash
+type HashBucket = Vec<(PathBuf, u64)>;
+
+/// Report progress every this many files
+const PROGRESS_INTERVAL: usize = 500;
+
+/// Cancellation flag and progress sink threaded through every scan phase
+struct ScanContext<'a> {
+    cancel: &'a AtomicBool,
+    progress: &'a dyn Fn(DuplicateScanProgress),
+}
+
+impl ScanContext<'_> {
+    fn cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    fn report(&self, phase: &str, files_scanned: usize, current_dir: &str) {
+        self.report_hashing(phase, files_scanned, current_dir, 0, 0);
+    }
+
+    fn report_hashing(
+        &self,
+        phase: &str,
+        files_scanned: usize,
+        current_dir: &str,
+        hashed: usize,
+        candidates: usize,
+    ) {
+        (self.progress)(DuplicateScanProgress {
+            files_scanned,
+            current_dir: current_dir.to_string(),
+            phase: phase.to_string(),
+            hashed,
+            candidates,
+        });
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct HashCache {
+    entries: HashMap<String, CachedHash>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedHash {
+    size: u64,
+    modified: u64, // seconds since UNIX epoch
+    hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HashCacheStatus {
+    pub path: String,
+    pub entries: usize,
+    pub file_size_kb: f64,
+    pub last_scan_hits: usize,
+    pub last_scan_misses: usize,
+    pub last_scan_hit_rate: f64, // 0.0 – 1.0
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HashCacheVerifyResult {
+    pub entries_checked: usize,
+    pub stale_removed: usize,   // file missing or size/mtime changed
+    pub corrupt_removed: usize, // malformed hash or hash no longer matches content
+    pub remaining: usize,
+}
+
+/// (hits, misses) from the most recent duplicate scan
+static LAST_SCAN_CACHE_STATS: Mutex<(usize, usize)> = Mutex::new((0, 0));
+
+/// Scan for duplicate files in `dirs`, or the common user directories when `None`.
+///
+/// Paths that don't exist or aren't directories are skipped and reported in
+/// `warnings`. `progress` is called every few hundred files with the current phase.
+pub fn scan_duplicates(
+    dirs: Option<Vec<String>>,
+    min_size_mb: f64,
+    progress: &dyn Fn(DuplicateScanProgress),
+) -> DuplicateScanResult {
     let start = std::time::Instant::now();
     let min_bytes = (min_size_mb * 1_048_576.0) as u64;
+    let mut warnings = Vec::new();
 
-    let user_profile = std::env::var("USERPROFILE").unwrap_or_else(|_| "C:\\Users\\Default".into());
-    let scan_dirs = vec![
-        format!("{}\\Desktop", user_profile),
-        format!("{}\\Documents", user_profile),
-        format!("{}\\Downloads", user_profile),
-        format!("{}\\Pictures", user_profile),
-        format!("{}\\Videos", user_profile),
-        format!("{}\\Music", user_profile),
-    ];
+    // User-chosen folders (NAS mounts, external drives) get a deeper walk than the defaults
+    let (scan_dirs, max_depth) = match dirs {
+        Some(dirs) => {
+            let valid = dirs
+                .into_iter()
+                .map(|d| d.trim().to_string())
+                .filter(|d| match std::fs::metadata(d) {
+                    Ok(m) if m.is_dir() => true,
+                    Ok(_) => {
+                        warnings.push(format!("Skipped {}: not a directory", d));
+                        false
+                    }
+                    Err(e) => {
+                        warnings.push(format!("Skipped {}: {}", d, e));
+                        false
+                    }
+                })
+                .collect::<Vec<_>>();
+            (valid, 16)
+        }
+        None => {
+            let user_profile =
+                std::env::var("USERPROFILE").unwrap_or_else(|_| "C:\\Users\\Default".into());
+            let defaults = vec![
+                format!("{}\\Desktop", user_profile),
+                format!("{}\\Documents", user_profile),
+                format!("{}\\Downloads", user_profile),
+                format!("{}\\Pictures", user_profile),
+                format!("{}\\Videos", user_profile),
+                format!("{}\\Music", user_profile),
+            ];
+            (defaults, 4)
+        }
+    };
+    for w in &warnings {
+        log::warn!("Duplicate scan: {}", w);
+    }
 
     // Phase 1: Group files by size (fast pre-filter)
     let mut size_groups: HashMap<u64, Vec<PathBuf>> = HashMap::new();
     let mut files_scanned = 0usize;
 
+    let cancel = crate::scanner::begin_scan();
+    let ctx = ScanContext {
+        cancel: &cancel,
+        progress,
+    };
     for dir in &scan_dirs {
-        scan_directory(dir, &mut size_groups, min_bytes, &mut files_scanned, 0, 4);
+        scan_directory(
+            dir,
+            &mut size_groups,
+            min_bytes,
+            &mut files_scanned,
+            0,
+            max_depth,
+            &ctx,
+        );
     }
 
-    // Phase 2: Only hash files that share the same size (potential duplicates)
+    let mut result = group_duplicates(size_groups, files_scanned, "directory", start, &ctx);
+    result.warnings = warnings;
+    result
+}
+
+/// Scan a whole drive for duplicates.
+///
+/// On NTFS (with admin rights) the file list comes from the Master File Table via
+/// `FSCTL_ENUM_USN_DATA`, which is far faster than recursing millions of folders.
+/// Other file systems (exFAT/FAT32 external drives) or a denied volume handle fall
+/// back to a full directory walk.
+pub fn scan_drive_duplicates(
+    drive: &str,
+    min_size_mb: f64,
+    progress: &dyn Fn(DuplicateScanProgress),
+) -> DuplicateScanResult {
+    let start = std::time::Instant::now();
+    let min_bytes = (min_size_mb * 1_048_576.0) as u64;
+    let letter = drive
+        .trim()
+        .chars()
+        .next()
+        .filter(|c| c.is_ascii_alphabetic())
+        .unwrap_or('C')
+        .to_ascii_uppercase();
+
+    let mut size_groups: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    let mut files_scanned = 0usize;
+    let cancel = crate::scanner::begin_scan();
+    let ctx = ScanContext {
+        cancel: &cancel,
+        progress,
+    };
+
+    let method = match enumerate_mft(
+        letter,
+        min_bytes,
+        &mut size_groups,
+        &mut files_scanned,
+        &ctx,
+    ) {
+        Ok(()) => "mft",
+        Err(e) => {
+            log::info!(
+                "MFT enumeration unavailable on {}: ({}); walking directories",
+                letter,
+                e
+            );
+            size_groups.clear();
+            files_scanned = 0;
+            let root = format!("{}:\\", letter);
+            scan_directory(
+                &root,
+                &mut size_groups,
+                min_bytes,
+                &mut files_scanned,
+                0,
+                64,
+                &ctx,
+            );
+            "directory"
+        }
+    };
+
+    group_duplicates(size_groups, files_scanned, method, start, &ctx)
+}
+
+/// Phases 2–3 shared by every scan mode: hash same-size candidates, build groups
+fn group_duplicates(
+    size_groups: HashMap<u64, Vec<PathBuf>>,
+    files_scanned: usize,
+    method: &str,
+    start: std::time::Instant,
+    ctx: &ScanContext,
+) -> DuplicateScanResult {
+    // Phase 2: Only hash files that share the same size (potential duplicates),
+    // reusing cached hashes for files whose size and mtime are unchanged
     let mut hash_groups: HashMap<String, Vec<(PathBuf, u64)>> = HashMap::new();
+    let mut cache = load_hash_cache();
+    let (mut hits, mut misses) = (0usize, 0usize);
+    let candidates: usize = size_groups
+        .values()
+        .filter(|p| p.len() >= 2)
+        .map(|p| p.len())
+        .sum();
+    let mut hashed = 0usize;
+    ctx.report_hashing("hashing", files_scanned, "", 0, candidates);
 
-    for (size, paths) in &size_groups {
+    'hashing: for (size, paths) in &size_groups {
         if paths.len() < 2 {
             continue;
         } // Need at least 2 files of same size
 
         for path in paths {
-            if let Some(hash) = quick_hash(path) {
+            if ctx.cancelled() {
+                break 'hashing;
+            }
+            hashed += 1;
+            if hashed % PROGRESS_INTERVAL == 0 {
+                let dir = path
+                    .parent()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                ctx.report_hashing("hashing", files_scanned, &dir, hashed, candidates);
+            }
+            let key = path.to_string_lossy().to_string();
+            let modified = modified_secs(path);
+            let cached = cache
+                .entries
+                .get(&key)
+                .filter(|c| c.size == *size && c.modified == modified)
+                .map(|c| c.hash.clone());
+
+            let hash = match cached {
+                Some(h) => {
+                    hits += 1;
+                    Some(h)
+                }
+                None => {
+                    misses += 1;
+                    let h = quick_hash(path);
+                    if let Some(ref h) = h {
+                        cache.entries.insert(
+                            key,
+                            CachedHash {
+                                size: *size,
+                                modified,
+                                hash: h.clone(),
+                            },
+                        );
+                    }
+                    h
+                }
+            };
+
+            if let Some(hash) = hash {
                 hash_groups
                     .entry(hash)
                     .or_default()
@@ -72,14 +345,71 @@ pub fn scan_duplicates(min_size_mb: f64) -> DuplicateScanResult {
         }
     }
 
-    // Phase 3: Build duplicate groups
-    let mut groups: Vec<DuplicateGroup> = Vec::new();
+    save_hash_cache(&cache);
+    if let Ok(mut stats) = LAST_SCAN_CACHE_STATS.lock() {
+        *stats = (hits, misses);
+    }
 
-    for (hash, files) in &hash_groups {
+    // Phase 3: The quick hash only samples the ends of each file, so re-split every
+    // candidate group by a full SHA-256. A cancelled scan keeps the unverified groups.
+    let verify_total: usize = hash_groups
+        .values()
+        .filter(|f| f.len() >= 2)
+        .map(|f| f.len())
+        .sum();
+    let mut verified_count = 0usize;
+    let mut checked_groups: Vec<(String, HashBucket, bool)> = Vec::new();
+
+    for (hash, files) in hash_groups {
         if files.len() < 2 {
             continue;
         }
+        if ctx.cancelled() {
+            checked_groups.push((hash, files, false));
+            continue;
+        }
 
+        let mut by_content: HashMap<String, Vec<(PathBuf, u64)>> = HashMap::new();
+        for (path, size) in &files {
+            verified_count += 1;
+            if verified_count % PROGRESS_INTERVAL == 0 {
+                let dir = path
+                    .parent()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                ctx.report_hashing(
+                    "verifying",
+                    files_scanned,
+                    &dir,
+                    verified_count,
+                    verify_total,
+                );
+            }
+            if let Some(full) = full_hash(path, ctx.cancel) {
+                by_content
+                    .entry(full)
+                    .or_default()
+                    .push((path.clone(), *size));
+            }
+        }
+
+        if ctx.cancelled() {
+            checked_groups.push((hash, files, false));
+        } else {
+            checked_groups.extend(
+                by_content
+                    .into_iter()
+                    .filter(|(_, members)| members.len() >= 2)
+                    .map(|(full, members)| (full, members, true)),
+            );
+        }
+    }
+
+    // Phase 4: Build duplicate groups
+    ctx.report_hashing("grouping", files_scanned, "", hashed, candidates);
+    let mut groups: Vec<DuplicateGroup> = Vec::new();
+
+    for (hash, files, verified) in &checked_groups {
         let file_size_mb = files[0].1 as f64 / 1_048_576.0;
         let dup_files: Vec<DuplicateFile> = files
             .iter()
@@ -113,6 +443,7 @@ pub fn scan_duplicates(min_size_mb: f64) -> DuplicateScanResult {
                     size_mb: *size as f64 / 1_048_576.0,
                     modified,
                     extension: ext,
+                    token: crate::scanner::file_token(path).unwrap_or_default(),
                 }
             })
             .collect();
@@ -123,6 +454,7 @@ pub fn scan_duplicates(min_size_mb: f64) -> DuplicateScanResult {
             count: dup_files.len(),
             total_wasted_mb: file_size_mb * (dup_files.len() - 1) as f64,
             files: dup_files,
+            verified: *verified,
         });
     }
 
@@ -135,15 +467,56 @@ pub fn scan_duplicates(min_size_mb: f64) -> DuplicateScanResult {
     let total_duplicates = groups.iter().map(|g| g.count - 1).sum();
     let total_wasted = groups.iter().map(|g| g.total_wasted_mb).sum();
 
+    // Where the waste is, computed over every group (not just the 100 returned)
+    let mut by_category: HashMap<String, DuplicateCategoryStats> = HashMap::new();
+    for g in &groups {
+        let ext = g
+            .files
+            .first()
+            .map(|f| f.extension.to_lowercase())
+            .unwrap_or_default();
+        let category = crate::scanner::categorize_extension(&ext);
+        let stats = by_category
+            .entry(category.clone())
+            .or_insert_with(|| DuplicateCategoryStats {
+                category,
+                groups: 0,
+                duplicates: 0,
+                wasted_mb: 0.0,
+            });
+        stats.groups += 1;
+        stats.duplicates += g.count - 1;
+        stats.wasted_mb += g.total_wasted_mb;
+    }
+    let mut by_category: Vec<DuplicateCategoryStats> = by_category.into_values().collect();
+    by_category.sort_by(|a, b| {
+        b.wasted_mb
+            .partial_cmp(&a.wasted_mb)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
     DuplicateScanResult {
         groups: groups.into_iter().take(100).collect(),
         total_duplicates,
         total_wasted_mb: total_wasted,
+        by_category,
         files_scanned,
+        enumeration: method.to_string(),
+        cancelled: ctx.cancel.load(Ordering::SeqCst),
+        warnings: Vec::new(),
         duration_ms: start.elapsed().as_millis() as u64,
     }
 }
 
+/// Folders both enumerators leave out: hidden folders, package trees and app data,
+/// whose files belong to programs rather than the user
+fn is_excluded_dir(name: &str) -> bool {
+    name.starts_with('.')
+        || name.eq_ignore_ascii_case("node_modules")
+        || name.eq_ignore_ascii_case("AppData")
+        || name.eq_ignore_ascii_case("ProgramData")
+}
+
 fn scan_directory(
     dir: &str,
     size_groups: &mut HashMap<u64, Vec<PathBuf>>,
@@ -151,27 +524,29 @@ fn scan_directory(
     count: &mut usize,
     depth: u32,
     max_depth: u32,
+    ctx: &ScanContext,
 ) {
     if depth > max_depth {
         return;
     }
     if let Ok(entries) = std::fs::read_dir(dir) {
         for entry in entries.flatten() {
+            if ctx.cancelled() {
+                return;
+            }
             if let Ok(meta) = entry.metadata() {
                 if meta.is_file() && meta.len() >= min_bytes {
                     *count += 1;
+                    if *count % PROGRESS_INTERVAL == 0 {
+                        ctx.report("sizing", *count, dir);
+                    }
                     size_groups
                         .entry(meta.len())
                         .or_default()
                         .push(entry.path());
                 } else if meta.is_dir() {
                     let name = entry.file_name().to_string_lossy().to_string();
-                    // Skip hidden/system dirs
-                    if !name.starts_with('.')
-                        && name != "node_modules"
-                        && name != ".git"
-                        && name != "AppData"
-                    {
+                    if !is_excluded_dir(&name) {
                         scan_directory(
                             &entry.path().to_string_lossy(),
                             size_groups,
@@ -179,6 +554,7 @@ fn scan_directory(
                             count,
                             depth + 1,
                             max_depth,
+                            ctx,
                         );
                     }
                 }
@@ -187,6 +563,237 @@ fn scan_directory(
     }
 }
 
+/// Enumerate every file on an NTFS volume from the MFT, grouping by size.
+///
+/// USN records carry names and parent references but no sizes, so paths are
+/// rebuilt from the directory map and sizes come from listing each folder that holds
+/// files: one directory read returns every entry's size, where a per-file lookup would
+/// cost a path resolution for each of millions of files.
+#[cfg(windows)]
+fn enumerate_mft(
+    letter: char,
+    min_bytes: u64,
+    size_groups: &mut HashMap<u64, Vec<PathBuf>>,
+    count: &mut usize,
+    ctx: &ScanContext,
+) -> Result<(), String> {
+    use winapi::shared::minwindef::DWORD;
+    use winapi::um::fileapi::{CreateFileW, OPEN_EXISTING};
+    use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+    use winapi::um::ioapiset::DeviceIoControl;
+    use winapi::um::winioctl::FSCTL_ENUM_USN_DATA;
+    use winapi::um::winnt::{
+        FILE_ATTRIBUTE_DIRECTORY, FILE_ATTRIBUTE_REPARSE_POINT, FILE_SHARE_READ, FILE_SHARE_WRITE,
+        GENERIC_READ,
+    };
+
+    // MFT_ENUM_DATA_V0 (not exposed by winapi)
+    #[repr(C)]
+    struct MftEnumData {
+        start_file_reference_number: u64,
+        low_usn: i64,
+        high_usn: i64,
+    }
+
+    struct Entry {
+        parent: u64,
+        name: String,
+        is_dir: bool,
+    }
+
+    let volume: Vec<u16> = format!("\\\\.\\{}:", letter)
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    let handle = unsafe {
+        CreateFileW(
+            volume.as_ptr(),
+            GENERIC_READ,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            std::ptr::null_mut(),
+            OPEN_EXISTING,
+            0,
+            std::ptr::null_mut(),
+        )
+    };
+    if handle == INVALID_HANDLE_VALUE {
+        return Err(format!(
+            "cannot open volume: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+
+    let mut entries: HashMap<u64, Entry> = HashMap::new();
+    let mut enum_data = MftEnumData {
+        start_file_reference_number: 0,
+        low_usn: 0,
+        high_usn: i64::MAX,
+    };
+    let mut buf = vec![0u8; 1024 * 1024];
+
+    // A cancelled enumeration still returns Ok so the caller doesn't fall back to a walk
+    while !ctx.cancelled() {
+        let mut returned: DWORD = 0;
+        let ok = unsafe {
+            DeviceIoControl(
+                handle,
+                FSCTL_ENUM_USN_DATA,
+                &mut enum_data as *mut _ as *mut _,
+                std::mem::size_of::<MftEnumData>() as DWORD,
+                buf.as_mut_ptr() as *mut _,
+                buf.len() as DWORD,
+                &mut returned,
+                std::ptr::null_mut(),
+            )
+        };
+        if ok == 0 {
+            let err = std::io::Error::last_os_error();
+            // ERROR_HANDLE_EOF — every record has been returned
+            if err.raw_os_error() == Some(38) {
+                break;
+            }
+            unsafe { CloseHandle(handle) };
+            return Err(format!("FSCTL_ENUM_USN_DATA failed: {}", err));
+        }
+
+        let returned = returned as usize;
+        if returned <= 8 {
+            break;
+        }
+        let read_u16 = |o: usize| u16::from_le_bytes([buf[o], buf[o + 1]]);
+        let read_u32 = |o: usize| u32::from_le_bytes(buf[o..o + 4].try_into().unwrap());
+        let read_u64 = |o: usize| u64::from_le_bytes(buf[o..o + 8].try_into().unwrap());
+
+        // First 8 bytes: where the next call should resume
+        enum_data.start_file_reference_number = read_u64(0);
+
+        // USN_RECORD_V2: length@0, frn@8, parent@16, attributes@52, name len@56, name offset@58
+        let mut offset = 8;
+        while offset + 60 <= returned {
+            let len = read_u32(offset) as usize;
+            if len == 0 || offset + len > returned {
+                break;
+            }
+            let frn = read_u64(offset + 8);
+            let parent = read_u64(offset + 16);
+            let attrs = read_u32(offset + 52);
+            let name_len = read_u16(offset + 56) as usize;
+            let name_off = read_u16(offset + 58) as usize;
+            let name_start = offset + name_off;
+            if name_start + name_len <= offset + len {
+                let units: Vec<u16> = buf[name_start..name_start + name_len]
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .collect();
+                // Reparse points (junctions/symlinks) would duplicate real files
+                if attrs & FILE_ATTRIBUTE_REPARSE_POINT == 0 {
+                    entries.insert(
+                        frn,
+                        Entry {
+                            parent,
+                            name: String::from_utf16_lossy(&units),
+                            is_dir: attrs & FILE_ATTRIBUTE_DIRECTORY != 0,
+                        },
+                    );
+                }
+            }
+            offset += len;
+        }
+    }
+    unsafe { CloseHandle(handle) };
+
+    // Resolve directory paths with memoization; unknown parents are the volume root
+    let root = format!("{}:", letter);
+    let mut dir_paths: HashMap<u64, Option<String>> = HashMap::new();
+    fn dir_path(
+        frn: u64,
+        entries: &HashMap<u64, Entry>,
+        cache: &mut HashMap<u64, Option<String>>,
+        root: &str,
+        depth: u32,
+    ) -> Option<String> {
+        if let Some(p) = cache.get(&frn) {
+            return p.clone();
+        }
+        let path = match entries.get(&frn) {
+            None => Some(root.to_string()),
+            Some(_) if depth > 256 => None,
+            Some(e) if !e.is_dir => None,
+            Some(e) => {
+                let name = e.name.as_str();
+                // Same exclusions as the directory walk, plus NTFS metadata folders
+                if is_excluded_dir(name)
+                    || name.starts_with('$')
+                    || name == "System Volume Information"
+                {
+                    None
+                } else {
+                    dir_path(e.parent, entries, cache, root, depth + 1)
+                        .map(|p| format!("{}\\{}", p, name))
+                }
+            }
+        };
+        cache.insert(frn, path.clone());
+        path
+    }
+
+    // Only folders that hold files need listing; excluded trees resolve to None
+    let mut file_dirs: std::collections::HashSet<String> = std::collections::HashSet::new();
+    for entry in entries.values() {
+        if ctx.cancelled() {
+            break;
+        }
+        if entry.is_dir {
+            continue;
+        }
+        if let Some(parent) = dir_path(entry.parent, &entries, &mut dir_paths, &root, 0) {
+            file_dirs.insert(parent);
+        }
+    }
+
+    let mut checked = 0usize;
+    for dir in &file_dirs {
+        if ctx.cancelled() {
+            break;
+        }
+        // "C:" alone means the current directory on C:, so list with a trailing separator
+        let Ok(listing) = std::fs::read_dir(format!("{}\\", dir)) else {
+            continue;
+        };
+        for item in listing.flatten() {
+            // On Windows a DirEntry's metadata comes from the listing itself, and it
+            // doesn't follow reparse points, so links aren't counted as files
+            let Ok(meta) = item.metadata() else {
+                continue;
+            };
+            if !meta.is_file() {
+                continue;
+            }
+            checked += 1;
+            if checked % PROGRESS_INTERVAL == 0 {
+                ctx.report("sizing", checked, dir);
+            }
+            if meta.len() >= min_bytes {
+                *count += 1;
+                size_groups.entry(meta.len()).or_default().push(item.path());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn enumerate_mft(
+    _letter: char,
+    _min_bytes: u64,
+    _size_groups: &mut HashMap<u64, Vec<PathBuf>>,
+    _count: &mut usize,
+    _ctx: &ScanContext,
+) -> Result<(), String> {
+    Err("MFT enumeration requires Windows".into())
+}
+
 /// Quick hash using first+last 8KB + size for speed
 fn quick_hash(path: &PathBuf) -> Option<String> {
     use std::collections::hash_map::DefaultHasher;
@@ -216,15 +823,164 @@ fn quick_hash(path: &PathBuf) -> Option<String> {
     Some(format!("{:016x}", hasher.finish()))
 }
 
-/// Delete a specific duplicate file
-pub fn delete_duplicate(path: &str) -> Result<String, String> {
-    // Safety: don't delete from system dirs
+/// Full-content SHA-256, streamed in 1 MB chunks; None if unreadable or cancelled
+fn full_hash(path: &PathBuf, cancel: &AtomicBool) -> Option<String> {
+    use sha2::{Digest, Sha256};
+
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 1024 * 1024];
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        let n = file.read(&mut buf).ok()?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Some(
+        hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect(),
+    )
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Hash cache — persisted quick hashes keyed by path, invalidated on size/mtime change
+// ═══════════════════════════════════════════════════════════════════════════════
+
+fn hash_cache_path() -> PathBuf {
+    let base = std::env::var("LOCALAPPDATA").unwrap_or_else(|_| {
+        let profile = std::env::var("USERPROFILE").unwrap_or_else(|_| ".".into());
+        format!("{}\\AppData\\Local", profile)
+    });
+    PathBuf::from(base)
+        .join("VegaOptimizer")
+        .join("hash_cache.json")
+}
+
+fn load_hash_cache() -> HashCache {
+    std::fs::read_to_string(hash_cache_path())
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_hash_cache(cache: &HashCache) {
+    let path = hash_cache_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string(cache) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+fn modified_secs(path: &std::path::Path) -> u64 {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Report hash cache size and the hit rate of the last duplicate scan
+pub fn get_hash_cache_status() -> HashCacheStatus {
+    let path = hash_cache_path();
+    let cache = load_hash_cache();
+    let (hits, misses) = LAST_SCAN_CACHE_STATS.lock().map(|s| *s).unwrap_or((0, 0));
+
+    HashCacheStatus {
+        path: path.to_string_lossy().to_string(),
+        entries: cache.entries.len(),
+        file_size_kb: std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0) as f64 / 1024.0,
+        last_scan_hits: hits,
+        last_scan_misses: misses,
+        last_scan_hit_rate: if hits + misses > 0 {
+            hits as f64 / (hits + misses) as f64
+        } else {
+            0.0
+        },
+    }
+}
+
+/// Drop cache entries whose file is gone or changed, and re-hash the rest to catch corruption
+pub fn verify_hash_cache() -> HashCacheVerifyResult {
+    let cache = load_hash_cache();
+    let entries_checked = cache.entries.len();
+    let (mut stale_removed, mut corrupt_removed) = (0usize, 0usize);
+    let mut kept: HashMap<String, CachedHash> = HashMap::new();
+
+    for (key, entry) in cache.entries {
+        let path = PathBuf::from(&key);
+        let size = match std::fs::metadata(&path) {
+            Ok(m) if m.is_file() => m.len(),
+            _ => {
+                stale_removed += 1;
+                continue;
+            }
+        };
+        if size != entry.size || modified_secs(&path) != entry.modified {
+            stale_removed += 1;
+            continue;
+        }
+        let well_formed =
+            entry.hash.len() == 16 && entry.hash.chars().all(|c| c.is_ascii_hexdigit());
+        if !well_formed || quick_hash(&path).as_deref() != Some(entry.hash.as_str()) {
+            corrupt_removed += 1;
+            continue;
+        }
+        kept.insert(key, entry);
+    }
+
+    let remaining = kept.len();
+    save_hash_cache(&HashCache { entries: kept });
+
+    HashCacheVerifyResult {
+        entries_checked,
+        stale_removed,
+        corrupt_removed,
+        remaining,
+    }
+}
+
+/// Delete the persisted hash cache so the next scan re-hashes everything
+pub fn clear_hash_cache() -> Result<String, String> {
+    let path = hash_cache_path();
+    let entries = load_hash_cache().entries.len();
+    if path.exists() {
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to clear hash cache: {}", e))?;
+    }
+    if let Ok(mut stats) = LAST_SCAN_CACHE_STATS.lock() {
+        *stats = (0, 0);
+    }
+    Ok(format!("Cleared {} cached hashes", entries))
+}
+
+/// Delete a specific duplicate file; `token` (from the scan) guards against the path changing
+pub fn delete_duplicate(path: &str, token: Option<&str>, recycle: bool) -> Result<String, String> {
+    // Safety: don't delete from system dirs or program data, which the scans skip
     let lower = path.to_lowercase();
     if lower.contains("\\windows\\")
         || lower.contains("\\program files")
         || lower.contains("\\system32")
+        || lower.contains("\\programdata\\")
+        || lower.contains("\\appdata\\")
     {
-        return Err("Cannot delete files from system directories".into());
+        return Err("Cannot delete files from system or application data directories".into());
+    }
+    if let Some(token) = token {
+        crate::scanner::verify_file_token(std::path::Path::new(path), token)?;
+    }
+
+    if recycle {
+        crate::scanner::move_to_recycle_bin(std::path::Path::new(path))?;
+        return Ok(format!("Recycled: {}", path));
     }
 
     match std::fs::remove_file(path) {
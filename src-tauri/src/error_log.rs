@@ -0,0 +1,124 @@
+//! Local error log — opt-in, file-only record of failed commands (never sent anywhere)
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorRecord {
+    pub timestamp: u64, // seconds since UNIX epoch
+    pub command: String,
+    pub args: String,
+    pub error: String,
+    pub app_version: String,
+}
+
+/// Rotate to errors.log.1 once the active log exceeds this size
+const MAX_LOG_BYTES: u64 = 256 * 1024;
+
+fn log_dir() -> PathBuf {
+    let base = std::env::var("LOCALAPPDATA").unwrap_or_else(|_| {
+        let profile = std::env::var("USERPROFILE").unwrap_or_else(|_| ".".into());
+        format!("{}\\AppData\\Local", profile)
+    });
+    PathBuf::from(base).join("VegaOptimizer")
+}
+
+fn log_path() -> PathBuf {
+    log_dir().join("errors.log")
+}
+
+fn rotated_path() -> PathBuf {
+    log_dir().join("errors.log.1")
+}
+
+/// Marker file whose presence means the user opted in
+fn enabled_marker() -> PathBuf {
+    log_dir().join("error_log.enabled")
+}
+
+pub fn is_enabled() -> bool {
+    enabled_marker().exists()
+}
+
+/// Opt in or out of local error logging; opting out also deletes existing logs
+pub fn set_enabled(enabled: bool) -> Result<String, String> {
+    if enabled {
+        std::fs::create_dir_all(log_dir()).map_err(|e| e.to_string())?;
+        std::fs::write(enabled_marker(), b"1").map_err(|e| e.to_string())?;
+        Ok("Local error log enabled".into())
+    } else {
+        let _ = std::fs::remove_file(enabled_marker());
+        let _ = std::fs::remove_file(log_path());
+        let _ = std::fs::remove_file(rotated_path());
+        Ok("Local error log disabled and cleared".into())
+    }
+}
+
+/// Replace the user's profile path and account name so logs can be shared safely
+fn sanitize(text: &str) -> String {
+    let mut out = text.to_string();
+    if let Ok(profile) = std::env::var("USERPROFILE") {
+        if !profile.is_empty() {
+            out = out.replace(&profile, "%USERPROFILE%");
+            // Debug-formatted args escape backslashes
+            out = out.replace(&profile.replace('\\', "\\\\"), "%USERPROFILE%");
+        }
+    }
+    if let Ok(user) = std::env::var("USERNAME") {
+        if user.len() > 2 {
+            out = out.replace(&user, "<user>");
+        }
+    }
+    out.chars().take(2000).collect()
+}
+
+/// Append a failed command to the log (no-op unless the user opted in)
+pub fn record(command: &str, args: &str, error: &str) {
+    if !is_enabled() {
+        return;
+    }
+
+    let path = log_path();
+    if std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0) > MAX_LOG_BYTES {
+        let _ = std::fs::rename(&path, rotated_path());
+    }
+
+    let entry = ErrorRecord {
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        command: command.to_string(),
+        args: sanitize(args),
+        error: sanitize(error),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+
+    if let Ok(line) = serde_json::to_string(&entry) {
+        if let Ok(mut file) = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+        {
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+}
+
+/// Most recent errors first, across the active and rotated log
+pub fn get_recent_errors(limit: usize) -> Vec<ErrorRecord> {
+    let mut records: Vec<ErrorRecord> = Vec::new();
+    for path in [rotated_path(), log_path()] {
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            records.extend(
+                content
+                    .lines()
+                    .filter_map(|l| serde_json::from_str::<ErrorRecord>(l).ok()),
+            );
+        }
+    }
+    records.reverse();
+    records.truncate(limit);
+    records
+}
@@ -0,0 +1,210 @@
+//! Export — save scan and optimization results as JSON or CSV
+//!
+//! Everything is serde-driven: JSON is the serialized value, and a CSV row is any
+//! flat `Serialize` struct whose field names become the header. The commands export
+//! the most recent result of each scan, which the scan commands hand to `remember_*`.
+
+use crate::duplicates::DuplicateScanResult;
+use crate::optimizer::OptimizationReport;
+use crate::registry::RegistryScanResult;
+use crate::scanner::{LargeFile, LargeFileScanResult};
+use serde::Serialize;
+use std::sync::Mutex;
+
+static LAST_REPORT: Mutex<Option<OptimizationReport>> = Mutex::new(None);
+static LAST_DUPLICATES: Mutex<Option<DuplicateScanResult>> = Mutex::new(None);
+static LAST_REGISTRY: Mutex<Option<RegistryScanResult>> = Mutex::new(None);
+static LAST_LARGE_FILES: Mutex<Option<LargeFileScanResult>> = Mutex::new(None);
+
+/// Keep a copy of `value` in `slot` for a later export and pass it through
+fn remember<T: Clone>(slot: &Mutex<Option<T>>, value: T) -> T {
+    *slot.lock().unwrap_or_else(|e| e.into_inner()) = Some(value.clone());
+    value
+}
+
+fn last<T: Clone>(slot: &Mutex<Option<T>>, what: &str) -> Result<T, String> {
+    slot.lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .ok_or_else(|| format!("Nothing to export — run {} first", what))
+}
+
+pub fn remember_report(report: OptimizationReport) -> OptimizationReport {
+    remember(&LAST_REPORT, report)
+}
+
+pub fn remember_duplicates(result: DuplicateScanResult) -> DuplicateScanResult {
+    remember(&LAST_DUPLICATES, result)
+}
+
+pub fn remember_registry(result: RegistryScanResult) -> RegistryScanResult {
+    remember(&LAST_REGISTRY, result)
+}
+
+pub fn remember_large_files(result: LargeFileScanResult) -> LargeFileScanResult {
+    remember(&LAST_LARGE_FILES, result)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Generic writers
+// ═══════════════════════════════════════════════════════════════════════════════
+
+fn write_file(path: &str, contents: &str) -> Result<(), String> {
+    if let Some(dir) = std::path::Path::new(path).parent() {
+        if !dir.as_os_str().is_empty() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+    }
+    std::fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+pub fn export_json<T: Serialize>(value: &T, path: &str) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    write_file(path, &json)
+}
+
+/// Write `rows` as CSV, one column per field in declaration order. Returns the row count.
+pub fn export_csv<T: Serialize>(rows: &[T], path: &str) -> Result<usize, String> {
+    let mut header: Vec<String> = Vec::new();
+    let mut lines: Vec<String> = Vec::new();
+    for row in rows {
+        let serde_json::Value::Object(fields) =
+            serde_json::to_value(row).map_err(|e| e.to_string())?
+        else {
+            return Err("CSV rows must be structs".into());
+        };
+        if header.is_empty() {
+            header = fields.keys().cloned().collect();
+            lines.push(
+                header
+                    .iter()
+                    .map(|h| csv_cell(h))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            );
+        }
+        let cells: Vec<String> = header
+            .iter()
+            .map(|key| match fields.get(key) {
+                None | Some(serde_json::Value::Null) => String::new(),
+                Some(serde_json::Value::String(s)) => csv_cell(s),
+                Some(other) => csv_cell(&other.to_string()),
+            })
+            .collect();
+        lines.push(cells.join(","));
+    }
+    // BOM so Excel opens the file as UTF-8
+    write_file(path, &format!("\u{feff}{}\r\n", lines.join("\r\n")))?;
+    Ok(rows.len())
+}
+
+/// Quote a cell when needed; a leading =, +, - or @ is escaped so Excel won't run it as a formula
+fn csv_cell(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Exports
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Serialize)]
+struct DuplicateRow<'a> {
+    group: usize,
+    hash: &'a str,
+    verified: bool,
+    size_mb: f64,
+    path: &'a str,
+    modified: &'a str,
+    extension: &'a str,
+}
+
+#[derive(Serialize)]
+struct LargeFileRow<'a> {
+    path: &'a str,
+    size_mb: f64,
+    extension: &'a str,
+    category: &'a str,
+    modified: &'a str,
+    identified_as: Option<&'a str>,
+}
+
+pub fn export_report_json(report: &OptimizationReport, path: &str) -> Result<String, String> {
+    export_json(report, path)?;
+    Ok(format!(
+        "Exported report of {} optimizations to {}",
+        report.items_attempted, path
+    ))
+}
+
+/// One row per file, numbered by duplicate group
+pub fn export_duplicates_csv(result: &DuplicateScanResult, path: &str) -> Result<String, String> {
+    let rows: Vec<DuplicateRow> = result
+        .groups
+        .iter()
+        .enumerate()
+        .flat_map(|(i, g)| {
+            g.files.iter().map(move |f| DuplicateRow {
+                group: i + 1,
+                hash: &g.hash,
+                verified: g.verified,
+                size_mb: f.size_mb,
+                path: &f.path,
+                modified: &f.modified,
+                extension: &f.extension,
+            })
+        })
+        .collect();
+    let count = export_csv(&rows, path)?;
+    Ok(format!(
+        "Exported {} files in {} duplicate groups to {}",
+        count,
+        result.groups.len(),
+        path
+    ))
+}
+
+pub fn export_registry_csv(result: &RegistryScanResult, path: &str) -> Result<String, String> {
+    let count = export_csv(&result.issues, path)?;
+    Ok(format!("Exported {} registry issues to {}", count, path))
+}
+
+pub fn export_large_files_csv(files: &[LargeFile], path: &str) -> Result<String, String> {
+    let rows: Vec<LargeFileRow> = files
+        .iter()
+        .map(|f| LargeFileRow {
+            path: &f.path,
+            size_mb: f.size_mb,
+            extension: &f.extension,
+            category: &f.category,
+            modified: &f.modified,
+            identified_as: f.ai_tooltip.as_deref(),
+        })
+        .collect();
+    let count = export_csv(&rows, path)?;
+    Ok(format!("Exported {} large files to {}", count, path))
+}
+
+pub fn export_last_report_json(path: &str) -> Result<String, String> {
+    export_report_json(&last(&LAST_REPORT, "an optimization")?, path)
+}
+
+pub fn export_last_duplicates_csv(path: &str) -> Result<String, String> {
+    export_duplicates_csv(&last(&LAST_DUPLICATES, "a duplicate scan")?, path)
+}
+
+pub fn export_last_registry_csv(path: &str) -> Result<String, String> {
+    export_registry_csv(&last(&LAST_REGISTRY, "a registry scan")?, path)
+}
+
+pub fn export_last_large_files_csv(path: &str) -> Result<String, String> {
+    export_large_files_csv(&last(&LAST_LARGE_FILES, "a large file scan")?.files, path)
+}
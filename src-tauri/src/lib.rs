@@ -1,27 +1,37 @@
 mod battery;
 mod benchmark;
+mod cli;
 mod debloater;
 mod disk_cleanup;
 mod disk_health;
 mod dns;
 mod duplicates;
+mod error_log;
+mod export;
 mod monitor;
 mod network;
 mod optimizer;
+mod processes;
+mod quarantine;
 mod registry;
 mod scanner;
+mod scheduler;
 mod services;
+mod settings;
 mod startup;
 mod tweaks;
 
-use monitor::{get_hardware_info, get_health_score, get_live_metrics};
+use monitor::{get_dashboard_summary, get_hardware_info, get_health_score, get_live_metrics};
 use optimizer::{get_optimization_catalog, get_processes, get_system_info, run_optimization};
 use scanner::{
-    clean_browser_cache, clean_privacy_item, clean_windows_update, detect_browsers,
-    get_privacy_items, list_drivers, scan_large_files,
+    clean_browser_cache, clean_browser_data, clean_privacy_item, clean_windows_update,
+    detect_browsers, export_drivers, get_privacy_items, list_drivers, scan_large_files,
+    BrowserCleanOptions,
 };
 use startup::{list_startup_programs, toggle_startup};
 
+pub use cli::run_cli;
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Helper — run blocking code on a background thread (prevents UI freezing)
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -31,6 +41,26 @@ async fn bg<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
     tokio::task::spawn_blocking(f).await.expect("Background task panicked")
 }
 
+/// Like `bg`, but records failures (with sanitized arguments) to the local error log.
+async fn bg_logged<T: Send + 'static>(
+    command: &'static str,
+    args: String,
+    f: impl FnOnce() -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
+    let result = bg(f).await;
+    if let Err(e) = &result {
+        error_log::record(command, &args, e);
+    }
+    result
+}
+
+/// Sends a progress payload to the frontend; long-running module functions take this
+/// as a callback so they stay free of Tauri types.
+fn emit_progress<T: serde::Serialize + Clone>(app: &tauri::AppHandle, event: &str, payload: T) {
+    use tauri::Emitter;
+    let _ = app.emit(event, payload);
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Tauri Commands — Original Optimizer (all async now)
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -45,14 +75,72 @@ async fn cmd_get_processes() -> Vec<optimizer::ProcessInfo> {
     bg(get_processes).await
 }
 
+#[tauri::command]
+async fn cmd_get_processes_filtered(opts: optimizer::ProcessQuery) -> optimizer::ProcessPage {
+    bg(move || optimizer::get_processes_filtered(opts)).await
+}
+
 #[tauri::command]
 async fn cmd_get_catalog() -> Vec<optimizer::OptimizationItem> {
     bg(get_optimization_catalog).await
 }
 
+#[tauri::command]
+async fn cmd_export_catalog(path: String) -> Result<String, String> {
+    let args = format!("{:?}", path);
+    bg_logged("cmd_export_catalog", args, move || {
+        optimizer::export_catalog(&path)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_export_report_json(path: String) -> Result<String, String> {
+    let args = format!("{:?}", path);
+    bg_logged("cmd_export_report_json", args, move || {
+        export::export_last_report_json(&path)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_is_reboot_pending() -> bool {
+    bg(optimizer::is_reboot_pending).await
+}
+
 #[tauri::command]
 async fn cmd_optimize(ids: Vec<String>) -> optimizer::OptimizationReport {
-    bg(move || run_optimization(ids)).await
+    bg(move || export::remember_report(run_optimization(ids))).await
+}
+
+#[tauri::command]
+async fn cmd_get_system_cache_status() -> Vec<optimizer::SystemCacheStatus> {
+    bg(optimizer::get_system_cache_status).await
+}
+
+#[tauri::command]
+async fn cmd_rebuild_font_cache() -> optimizer::OptimizationResult {
+    bg(optimizer::rebuild_font_cache).await
+}
+
+#[tauri::command]
+async fn cmd_rebuild_icon_cache() -> optimizer::OptimizationResult {
+    bg(optimizer::rebuild_icon_cache).await
+}
+
+#[tauri::command]
+async fn cmd_clean_thumbnail_cache(restart_explorer: bool) -> optimizer::OptimizationResult {
+    bg(move || optimizer::clean_thumbnail_cache(restart_explorer)).await
+}
+
+#[tauri::command]
+async fn cmd_restart_explorer() -> Result<String, String> {
+    bg_logged(
+        "cmd_restart_explorer",
+        String::new(),
+        optimizer::restart_explorer,
+    )
+    .await
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -69,11 +157,45 @@ async fn cmd_get_health_score() -> monitor::HealthScore {
     bg(get_health_score).await
 }
 
+#[tauri::command]
+async fn cmd_get_health_history() -> Vec<(String, u32)> {
+    bg(monitor::get_health_history).await
+}
+
 #[tauri::command]
 async fn cmd_get_hardware_info() -> monitor::HardwareInfo {
     bg(get_hardware_info).await
 }
 
+#[tauri::command]
+async fn cmd_get_dashboard_summary() -> monitor::DashboardSummary {
+    bg(get_dashboard_summary).await
+}
+
+#[tauri::command]
+async fn cmd_get_timer_resolution() -> Option<monitor::TimerResolution> {
+    bg(monitor::get_timer_resolution).await
+}
+
+#[tauri::command]
+async fn cmd_get_idle_seconds() -> u64 {
+    bg(monitor::get_idle_seconds).await
+}
+
+#[tauri::command]
+async fn cmd_capture_snapshot() -> u64 {
+    bg(|| monitor::capture_snapshot().id).await
+}
+
+#[tauri::command]
+async fn cmd_compare_snapshots(a: u64, b: u64) -> Result<monitor::SnapshotDiff, String> {
+    let args = format!("{:?}", (a, b));
+    bg_logged("cmd_compare_snapshots", args, move || {
+        monitor::compare_snapshots(a, b)
+    })
+    .await
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Tauri Commands — Startup Manager
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -89,7 +211,16 @@ async fn cmd_toggle_startup(
     registry_path: String,
     enable: bool,
 ) -> Result<String, String> {
-    bg(move || toggle_startup(&name, &registry_path, enable)).await
+    let args = format!("{:?}", (&name, &registry_path, &enable));
+    bg_logged("cmd_toggle_startup", args, move || {
+        toggle_startup(&name, &registry_path, enable)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_get_startup_footprints() -> Vec<startup::StartupFootprint> {
+    bg(startup::get_startup_footprints).await
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -97,28 +228,54 @@ async fn cmd_toggle_startup(
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[tauri::command]
-async fn cmd_scan_large_files(min_size_mb: u64) -> Vec<scanner::LargeFile> {
-    bg(move || scan_large_files(min_size_mb, 100)).await
+async fn cmd_scan_large_files(min_size_mb: u64) -> scanner::LargeFileScanResult {
+    bg(move || export::remember_large_files(scan_large_files(min_size_mb, 100))).await
 }
 
 #[tauri::command]
-async fn cmd_delete_file(path: String) -> Result<String, String> {
-    bg(move || {
-        let p = std::path::Path::new(&path);
-        if !p.exists() {
-            return Err("File not found".to_string());
-        }
-        if !p.is_file() {
-            return Err("Not a file".to_string());
-        }
-        // Safety: refuse to delete from system dirs (dynamic lookup for non-C: installs)
-        let lower = path.to_lowercase();
-        let sys_root = std::env::var("SystemRoot").unwrap_or_else(|_| "C:\\Windows".to_string()).to_lowercase();
-        if lower.starts_with(&sys_root) || lower.starts_with("c:\\program files") {
-            return Err("Cannot delete system files".to_string());
-        }
-        let size = p.metadata().map(|m| m.len()).unwrap_or(0);
-        match std::fs::remove_file(p) {
+async fn cmd_export_large_files_csv(path: String) -> Result<String, String> {
+    let args = format!("{:?}", path);
+    bg_logged("cmd_export_large_files_csv", args, move || {
+        export::export_last_large_files_csv(&path)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_cancel_scan() {
+    scanner::cancel_scan();
+}
+
+/// Shared safety checks for deleting a user file; returns its size in bytes
+fn check_deletable_file(path: &str, token: Option<&str>) -> Result<u64, String> {
+    let p = std::path::Path::new(path);
+    if !p.exists() {
+        return Err("File not found".to_string());
+    }
+    if !p.is_file() {
+        return Err("Not a file".to_string());
+    }
+    // Safety: refuse to delete from system dirs (dynamic lookup for non-C: installs)
+    let lower = path.to_lowercase();
+    let sys_root = std::env::var("SystemRoot")
+        .unwrap_or_else(|_| "C:\\Windows".to_string())
+        .to_lowercase();
+    if lower.starts_with(&sys_root) || lower.starts_with("c:\\program files") {
+        return Err("Cannot delete system files".to_string());
+    }
+    // Safety: make sure this is still the file the scan reported
+    if let Some(token) = token {
+        scanner::verify_file_token(p, token)?;
+    }
+    Ok(p.metadata().map(|m| m.len()).unwrap_or(0))
+}
+
+#[tauri::command]
+async fn cmd_delete_file(path: String, token: Option<String>) -> Result<String, String> {
+    let args = format!("{:?}", (&path, &token));
+    bg_logged("cmd_delete_file", args, move || {
+        let size = check_deletable_file(&path, token.as_deref())?;
+        match std::fs::remove_file(&path) {
             Ok(_) => Ok(format!(
                 "Deleted {} ({:.1} MB)",
                 path,
@@ -130,9 +287,25 @@ async fn cmd_delete_file(path: String) -> Result<String, String> {
     .await
 }
 
+#[tauri::command]
+async fn cmd_delete_file_to_recycle(path: String, token: Option<String>) -> Result<String, String> {
+    let args = format!("{:?}", (&path, &token));
+    bg_logged("cmd_delete_file_to_recycle", args, move || {
+        let size = check_deletable_file(&path, token.as_deref())?;
+        scanner::move_to_recycle_bin(std::path::Path::new(&path))?;
+        Ok(format!(
+            "Recycled {} ({:.1} MB) — restore it from the Recycle Bin if needed",
+            path,
+            size as f64 / 1_048_576.0
+        ))
+    })
+    .await
+}
+
 #[tauri::command]
 async fn cmd_reveal_file(path: String) -> Result<(), String> {
-    bg(move || {
+    let args = format!("{:?}", path);
+    bg_logged("cmd_reveal_file", args, move || {
         let p = std::path::Path::new(&path);
         if !p.exists() {
             return Err("File not found".to_string());
@@ -153,7 +326,23 @@ async fn cmd_detect_browsers() -> Vec<scanner::BrowserInfo> {
 
 #[tauri::command]
 async fn cmd_clean_browser(name: String) -> Result<String, String> {
-    bg(move || clean_browser_cache(&name)).await
+    let args = format!("{:?}", name);
+    bg_logged("cmd_clean_browser", args, move || {
+        clean_browser_cache(&name)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_clean_browser_data(
+    name: String,
+    options: BrowserCleanOptions,
+) -> Result<String, String> {
+    let args = format!("{:?} {:?}", name, options);
+    bg_logged("cmd_clean_browser_data", args, move || {
+        clean_browser_data(&name, &options)
+    })
+    .await
 }
 
 #[tauri::command]
@@ -163,34 +352,196 @@ async fn cmd_get_privacy_items() -> Vec<scanner::PrivacyItem> {
 
 #[tauri::command]
 async fn cmd_clean_privacy(id: String) -> Result<String, String> {
-    bg(move || clean_privacy_item(&id)).await
+    let args = format!("{:?}", id);
+    bg_logged("cmd_clean_privacy", args, move || clean_privacy_item(&id)).await
+}
+
+#[tauri::command]
+async fn cmd_list_quarantine() -> Vec<quarantine::QuarantineSessionInfo> {
+    bg(quarantine::list_sessions).await
 }
 
 #[tauri::command]
-async fn cmd_list_drivers() -> Vec<scanner::DriverInfo> {
-    bg(list_drivers).await
+async fn cmd_undo_cleanup(session_id: String) -> Result<String, String> {
+    let args = format!("{:?}", session_id);
+    bg_logged("cmd_undo_cleanup", args, move || {
+        quarantine::undo_cleanup(&session_id)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_purge_quarantine(older_than_days: u64) -> Result<String, String> {
+    let args = format!("{:?}", older_than_days);
+    bg_logged("cmd_purge_quarantine", args, move || {
+        quarantine::purge_quarantine(older_than_days)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_list_drivers(pnp_limit: Option<usize>) -> Vec<scanner::DriverInfo> {
+    bg(move || list_drivers(pnp_limit)).await
 }
 
 #[tauri::command]
 async fn cmd_clean_windows_update() -> Result<String, String> {
-    bg(clean_windows_update).await
+    bg_logged(
+        "cmd_clean_windows_update",
+        String::new(),
+        clean_windows_update,
+    )
+    .await
+}
+
+#[tauri::command]
+async fn cmd_scan_leftovers() -> Vec<scanner::LeftoverFolder> {
+    bg(scanner::scan_leftover_folders).await
+}
+
+#[tauri::command]
+async fn cmd_delete_leftover(path: String, confirm: Option<bool>) -> Result<String, String> {
+    let confirm = confirm.unwrap_or(false);
+    let args = format!("{:?} confirm={}", path, confirm);
+    bg_logged("cmd_delete_leftover", args, move || {
+        scanner::delete_leftover_folder(&path, confirm)
+    })
+    .await
 }
 
 #[tauri::command]
 async fn cmd_kill_process(pid: u32) -> Result<String, String> {
-    bg(move || {
-        match std::process::Command::new("taskkill")
+    let args = format!("{:?}", pid);
+    bg_logged(
+        "cmd_kill_process",
+        args,
+        move || match std::process::Command::new("taskkill")
             .args(["/PID", &pid.to_string(), "/F"])
             .output()
         {
             Ok(o) if o.status.success() => Ok(format!("Killed process {}", pid)),
             Ok(o) => Err(String::from_utf8_lossy(&o.stderr).to_string()),
             Err(e) => Err(e.to_string()),
-        }
+        },
+    )
+    .await
+}
+
+#[tauri::command]
+async fn cmd_suspend_process(pid: u32) -> Result<processes::SuspendState, String> {
+    let args = format!("{:?}", pid);
+    bg_logged("cmd_suspend_process", args, move || {
+        processes::suspend_process(pid)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_resume_process(pid: u32) -> Result<processes::SuspendState, String> {
+    let args = format!("{:?}", pid);
+    bg_logged("cmd_resume_process", args, move || {
+        processes::resume_process(pid)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_list_suspended_processes() -> Vec<processes::SuspendState> {
+    bg(processes::list_suspended).await
+}
+
+#[tauri::command]
+async fn cmd_get_process_affinity(pid: u32) -> Result<optimizer::ProcessAffinity, String> {
+    let args = format!("{:?}", pid);
+    bg_logged("cmd_get_process_affinity", args, move || {
+        optimizer::get_process_affinity(pid)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_set_affinity(pid: u32, mask: u64) -> Result<String, String> {
+    let args = format!("{:?}", (pid, mask));
+    bg_logged("cmd_set_affinity", args, move || {
+        optimizer::set_process_affinity(pid, mask)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_set_process_priority(pid: u32, priority: String) -> Result<String, String> {
+    let args = format!("{:?}", (pid, &priority));
+    bg_logged("cmd_set_process_priority", args, move || {
+        optimizer::set_process_priority(pid, &priority)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_get_process_memory_breakdown(
+    pid: u32,
+) -> Result<processes::ProcessMemoryBreakdown, String> {
+    let args = format!("{:?}", pid);
+    bg_logged("cmd_get_process_memory_breakdown", args, move || {
+        processes::get_process_memory_breakdown(pid)
     })
     .await
 }
 
+#[tauri::command]
+async fn cmd_kill_processes_matching(
+    pattern: String,
+) -> Result<Vec<processes::ProcessActionResult>, String> {
+    let args = format!("{:?}", pattern);
+    bg_logged("cmd_kill_processes_matching", args, move || {
+        processes::kill_processes_matching(&pattern)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_trim_processes_matching(
+    pattern: String,
+) -> Result<Vec<processes::ProcessActionResult>, String> {
+    let args = format!("{:?}", pattern);
+    bg_logged("cmd_trim_processes_matching", args, move || {
+        processes::trim_processes_matching(&pattern)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_set_process_working_set_limit(
+    pid: u32,
+    min_mb: u64,
+    max_mb: u64,
+) -> Result<String, String> {
+    let args = format!("{:?}", (pid, min_mb, max_mb));
+    bg_logged("cmd_set_process_working_set_limit", args, move || {
+        processes::set_process_working_set_limit(pid, min_mb, max_mb)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_get_working_set_limits() -> Vec<processes::WorkingSetLimit> {
+    bg(processes::get_working_set_limits).await
+}
+
+#[tauri::command]
+async fn cmd_remove_working_set_limit(name: String) -> Result<String, String> {
+    let args = format!("{:?}", name);
+    bg_logged("cmd_remove_working_set_limit", args, move || {
+        processes::remove_working_set_limit(&name)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_reapply_working_set_limits() -> Vec<processes::ProcessActionResult> {
+    bg(processes::reapply_working_set_limits).await
+}
+
 #[derive(serde::Serialize)]
 struct ProcessSuggestion {
     pid: u32,
@@ -202,6 +553,24 @@ struct ProcessSuggestion {
     severity: String, // "high", "medium", "low"
     category: String, // "bloated", "idle_hog", "background", "duplicate"
     safe_to_optimize: bool,
+    details: SuggestionDetails,
+}
+
+/// Evidence behind a process suggestion so users can judge whether to act on it
+#[derive(serde::Serialize, Clone)]
+struct SuggestionDetails {
+    memory_start_mb: f64, // at the start of the sampling window
+    memory_peak_mb: f64,  // highest sample taken during the window
+    memory_end_mb: f64,   // at the end of the sampling window
+    cpu_window_percent: f32,
+    sample_window_ms: u64,
+    instance_count: usize,
+    instances_total_mb: f64,
+    parent_pid: Option<u32>,
+    parent_name: Option<String>,
+    exe_path: Option<String>,
+    signed: Option<bool>, // None = signature could not be checked
+    run_time_secs: u64,
 }
 
 #[derive(serde::Serialize)]
@@ -248,12 +617,47 @@ async fn cmd_get_process_suggestions() -> Vec<ProcessSuggestion> {
     bg(|| {
         use sysinfo::{ProcessesToUpdate, System};
 
+        const SAMPLE_INTERVAL_MS: u64 = 250;
+        const SAMPLE_TIMEOUT_MS: u64 = 2000;
+
         let mut sys = System::new_all();
         sys.refresh_all();
-        std::thread::sleep(std::time::Duration::from_millis(200));
-        sys.refresh_processes(ProcessesToUpdate::All, true);
+        let memory_start: std::collections::HashMap<u32, f64> = sys
+            .processes()
+            .iter()
+            .map(|(pid, p)| (pid.as_u32(), p.memory() as f64 / 1_048_576.0))
+            .collect();
+
+        // One short window is mostly noise; keep sampling until the total settles (two
+        // samples in a row within 1%) or the timeout, tracking each process's peak
+        let mut memory_peak = memory_start.clone();
+        let mut last_total: f64 = memory_start.values().sum();
+        let mut settled_samples = 0;
+        let started = std::time::Instant::now();
+        loop {
+            std::thread::sleep(std::time::Duration::from_millis(SAMPLE_INTERVAL_MS));
+            sys.refresh_processes(ProcessesToUpdate::All, true);
+            let mut total = 0.0;
+            for (pid, p) in sys.processes() {
+                let mb = p.memory() as f64 / 1_048_576.0;
+                total += mb;
+                let peak = memory_peak.entry(pid.as_u32()).or_insert(mb);
+                *peak = peak.max(mb);
+            }
+            if (total - last_total).abs() <= last_total * 0.01 {
+                settled_samples += 1;
+            } else {
+                settled_samples = 0;
+            }
+            last_total = total;
+            if settled_samples >= 2 || started.elapsed().as_millis() as u64 >= SAMPLE_TIMEOUT_MS {
+                break;
+            }
+        }
+        let sample_window_ms = started.elapsed().as_millis() as u64;
 
         let mut suggestions: Vec<ProcessSuggestion> = Vec::new();
+        let whitelist = settings::load_settings().process_whitelist;
 
         // Count process instances for duplicate detection
         let mut name_counts: std::collections::HashMap<String, usize> =
@@ -272,13 +676,33 @@ async fn cmd_get_process_suggestions() -> Vec<ProcessSuggestion> {
             let mem = proc_.memory() as f64 / 1_048_576.0;
             let cpu = proc_.cpu_usage();
 
-            if PROTECTED_PROCESSES.contains(&name_lower.as_str()) {
+            if PROTECTED_PROCESSES.contains(&name_lower.as_str()) || whitelist.contains(&name_lower)
+            {
                 continue;
             }
             if mem < 2.0 {
                 continue;
             }
 
+            let parent_pid = proc_.parent().map(|p| p.as_u32());
+            let details = SuggestionDetails {
+                memory_start_mb: memory_start.get(&pid.as_u32()).copied().unwrap_or(mem),
+                memory_peak_mb: memory_peak.get(&pid.as_u32()).copied().unwrap_or(mem),
+                memory_end_mb: mem,
+                cpu_window_percent: cpu,
+                sample_window_ms,
+                instance_count: name_counts.get(&name_lower).copied().unwrap_or(1),
+                instances_total_mb: name_memory.get(&name_lower).copied().unwrap_or(mem),
+                parent_pid,
+                parent_name: proc_
+                    .parent()
+                    .and_then(|pp| sys.process(pp))
+                    .map(|pp| pp.name().to_string_lossy().to_string()),
+                exe_path: proc_.exe().map(|e| e.to_string_lossy().to_string()),
+                signed: None,
+                run_time_secs: proc_.run_time(),
+            };
+
             // High memory (>200 MB) & low CPU (<2%) => bloated/idle
             if mem > 200.0 && cpu < 2.0 {
                 suggestions.push(ProcessSuggestion {
@@ -294,6 +718,7 @@ async fn cmd_get_process_suggestions() -> Vec<ProcessSuggestion> {
                     severity: "high".into(),
                     category: "bloated".into(),
                     safe_to_optimize: true,
+                    details: details.clone(),
                 });
             }
             // Medium memory (50–200 MB) & idle
@@ -311,6 +736,7 @@ async fn cmd_get_process_suggestions() -> Vec<ProcessSuggestion> {
                     severity: "medium".into(),
                     category: "idle_hog".into(),
                     safe_to_optimize: true,
+                    details: details.clone(),
                 });
             }
 
@@ -332,6 +758,7 @@ async fn cmd_get_process_suggestions() -> Vec<ProcessSuggestion> {
                         severity: "medium".into(),
                         category: "duplicate".into(),
                         safe_to_optimize: true,
+                        details: details.clone(),
                     });
                 }
             }
@@ -351,6 +778,7 @@ async fn cmd_get_process_suggestions() -> Vec<ProcessSuggestion> {
                     severity: "low".into(),
                     category: "background".into(),
                     safe_to_optimize: true,
+                    details: details.clone(),
                 });
             }
         }
@@ -370,6 +798,48 @@ async fn cmd_get_process_suggestions() -> Vec<ProcessSuggestion> {
         });
 
         suggestions.truncate(50);
+
+        // Check Authenticode signatures for the remaining executables in one PowerShell call
+        let mut exe_paths: Vec<String> = suggestions
+            .iter()
+            .filter_map(|s| s.details.exe_path.clone())
+            .collect();
+        exe_paths.sort();
+        exe_paths.dedup();
+        if !exe_paths.is_empty() {
+            let list = exe_paths
+                .iter()
+                .map(|p| format!("'{}'", p.replace('\'', "''")))
+                .collect::<Vec<_>>()
+                .join(",");
+            let script = format!(
+                "foreach($p in @({})) {{ $s = Get-AuthenticodeSignature -LiteralPath $p -ErrorAction SilentlyContinue; \"$p|$($s.Status)\" }}",
+                list
+            );
+            if let Ok(o) = std::process::Command::new("powershell")
+                .args(["-NoProfile", "-Command", &script])
+                .output()
+            {
+                let mut status: std::collections::HashMap<String, Option<bool>> =
+                    std::collections::HashMap::new();
+                for line in String::from_utf8_lossy(&o.stdout).lines() {
+                    if let Some((path, st)) = line.trim().rsplit_once('|') {
+                        let signed = match st.trim() {
+                            "Valid" => Some(true),
+                            "NotSigned" | "HashMismatch" => Some(false),
+                            _ => None,
+                        };
+                        status.insert(path.to_lowercase(), signed);
+                    }
+                }
+                for s in &mut suggestions {
+                    if let Some(path) = &s.details.exe_path {
+                        s.details.signed = status.get(&path.to_lowercase()).copied().flatten();
+                    }
+                }
+            }
+        }
+
         suggestions
     })
     .await
@@ -390,6 +860,7 @@ async fn cmd_optimize_processes(pids: Vec<u32>) -> ProcessOptReport {
         sys.refresh_processes(ProcessesToUpdate::All, true);
 
         let mut results: Vec<ProcessOptResult> = Vec::new();
+        let whitelist = settings::load_settings().process_whitelist;
 
         for &pid in &pids {
             let before_mb = sys
@@ -402,6 +873,19 @@ async fn cmd_optimize_processes(pids: Vec<u32>) -> ProcessOptReport {
                 .map(|p| p.name().to_string_lossy().to_string())
                 .unwrap_or_else(|| format!("PID {}", pid));
 
+            if whitelist.contains(&name.to_lowercase()) {
+                results.push(ProcessOptResult {
+                    pid,
+                    name,
+                    memory_before_mb: before_mb,
+                    memory_after_mb: before_mb,
+                    freed_mb: 0.0,
+                    success: false,
+                    message: "Whitelisted — skipped".to_string(),
+                });
+                continue;
+            }
+
             let success;
             let message;
 
@@ -487,6 +971,24 @@ async fn cmd_optimize_processes(pids: Vec<u32>) -> ProcessOptReport {
     .await
 }
 
+#[tauri::command]
+async fn cmd_add_process_whitelist(name: String) -> Result<String, String> {
+    let args = format!("{:?}", name);
+    bg_logged("cmd_add_process_whitelist", args, move || {
+        settings::add_process_whitelist(&name)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_remove_process_whitelist(name: String) -> Result<String, String> {
+    let args = format!("{:?}", name);
+    bg_logged("cmd_remove_process_whitelist", args, move || {
+        settings::remove_process_whitelist(&name)
+    })
+    .await
+}
+
 /// Enable SeDebugPrivilege so we can call EmptyWorkingSet on any process
 #[cfg(windows)]
 fn enable_debug_privilege() {
@@ -531,20 +1033,134 @@ fn enable_debug_privilege() {
     }
 }
 
+/// Whether the app runs with an elevated (administrator) token
+#[cfg(windows)]
+pub fn is_elevated() -> bool {
+    use std::ptr::null_mut;
+    use winapi::um::handleapi::CloseHandle;
+    use winapi::um::processthreadsapi::{GetCurrentProcess, OpenProcessToken};
+    use winapi::um::securitybaseapi::GetTokenInformation;
+    use winapi::um::winnt::{TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY};
+
+    unsafe {
+        let mut token = null_mut();
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) == 0 {
+            return false;
+        }
+
+        let mut elevation: TOKEN_ELEVATION = std::mem::zeroed();
+        let mut size = 0u32;
+        let ok = GetTokenInformation(
+            token,
+            TokenElevation,
+            &mut elevation as *mut TOKEN_ELEVATION as *mut _,
+            std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+            &mut size,
+        );
+        CloseHandle(token);
+        ok != 0 && elevation.TokenIsElevated != 0
+    }
+}
+
+#[cfg(not(windows))]
+pub fn is_elevated() -> bool {
+    false
+}
+
+/// Start a new copy of the app through the UAC prompt (`runas` verb) with the same
+/// arguments. Fails if the user declines the prompt.
+fn relaunch_as_admin() -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let args = std::env::args()
+        .skip(1)
+        .map(|a| format!("\"{}\"", a))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    #[cfg(windows)]
+    {
+        use winapi::um::shellapi::ShellExecuteW;
+        use winapi::um::winuser::SW_SHOWNORMAL;
+
+        let wide = |s: &str| -> Vec<u16> { s.encode_utf16().chain(std::iter::once(0)).collect() };
+        let verb = wide("runas");
+        let file = wide(&exe.to_string_lossy());
+        let params = wide(&args);
+
+        // ShellExecuteW reports success with a value greater than 32
+        let result = unsafe {
+            ShellExecuteW(
+                std::ptr::null_mut(),
+                verb.as_ptr(),
+                file.as_ptr(),
+                params.as_ptr(),
+                std::ptr::null(),
+                SW_SHOWNORMAL,
+            )
+        } as isize;
+        if result <= 32 {
+            return Err(if result == 5 {
+                "Elevation was cancelled".into()
+            } else {
+                format!("Could not restart as administrator (error {})", result)
+            });
+        }
+        return Ok(());
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = (exe, args);
+        Err("Elevation is only supported on Windows".into())
+    }
+}
+
+#[tauri::command]
+async fn cmd_is_elevated() -> bool {
+    bg(is_elevated).await
+}
+
+/// Relaunch elevated and close this unelevated instance
+#[tauri::command]
+async fn cmd_relaunch_as_admin(app: tauri::AppHandle) -> Result<(), String> {
+    bg_logged("cmd_relaunch_as_admin", String::new(), relaunch_as_admin).await?;
+    app.exit(0);
+    Ok(())
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Tauri Commands — Network Monitor
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[tauri::command]
-async fn cmd_get_network_overview() -> network::NetworkOverview {
-    bg(|| network::get_network_connections()).await
+async fn cmd_get_network_overview(
+    sample_ms: Option<u64>,
+    filter: Option<network::ConnectionFilter>,
+) -> network::NetworkOverview {
+    bg(move || {
+        network::get_network_connections(sample_ms.unwrap_or(1000), filter.unwrap_or_default())
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_get_network_delta(
+    reset: Option<bool>,
+    filter: Option<network::ConnectionFilter>,
+) -> network::NetworkDelta {
+    bg(move || network::get_network_delta(reset.unwrap_or(false), filter.unwrap_or_default())).await
 }
 
 #[tauri::command]
-async fn cmd_ping_test(host: String) -> f64 {
+async fn cmd_ping_test(host: String) -> network::PingResult {
     bg(move || network::ping_test(&host)).await
 }
 
+#[tauri::command]
+async fn cmd_run_speed_test() -> network::SpeedTestResult {
+    bg(network::run_speed_test).await
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Tauri Commands — Windows Debloater
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -554,14 +1170,45 @@ async fn cmd_list_appx() -> Vec<debloater::AppxPackage> {
     bg(|| debloater::list_appx_packages()).await
 }
 
+#[tauri::command]
+async fn cmd_list_appx_filtered(
+    category: Option<String>,
+    only_removable: bool,
+    name_contains: Option<String>,
+) -> Vec<debloater::AppxPackage> {
+    bg(move || debloater::list_appx_packages_filtered(category, only_removable, name_contains))
+        .await
+}
+
 #[tauri::command]
 async fn cmd_remove_appx(name: String) -> Result<String, String> {
-    bg(move || debloater::remove_appx_package(&name)).await
+    let args = format!("{:?}", name);
+    bg_logged("cmd_remove_appx", args, move || {
+        debloater::remove_appx_package(&name)
+    })
+    .await
 }
 
 #[tauri::command]
-async fn cmd_remove_all_bloatware() -> Vec<(String, bool, String)> {
-    bg(|| debloater::remove_all_bloatware()).await
+async fn cmd_reinstall_appx(name: String) -> Result<String, String> {
+    let args = format!("{:?}", name);
+    bg_logged("cmd_reinstall_appx", args, move || {
+        debloater::reinstall_appx(&name)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_list_removable_defaults() -> Vec<debloater::ReinstallableApp> {
+    bg(debloater::list_removable_defaults).await
+}
+
+#[tauri::command]
+async fn cmd_remove_all_bloatware(app: tauri::AppHandle) -> debloater::BloatwareRemovalReport {
+    bg(move || {
+        debloater::remove_all_bloatware(&|p| emit_progress(&app, "bloatware-removal-progress", p))
+    })
+    .await
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -569,8 +1216,49 @@ async fn cmd_remove_all_bloatware() -> Vec<(String, bool, String)> {
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[tauri::command]
-async fn cmd_run_benchmark() -> benchmark::BenchmarkResult {
-    bg(|| benchmark::run_benchmark()).await
+async fn cmd_run_benchmark(
+    passes: Option<usize>,
+    drive: Option<String>,
+) -> Result<benchmark::BenchmarkResult, String> {
+    let args = format!("{:?} {:?}", passes, drive);
+    bg_logged("cmd_run_benchmark", args, move || {
+        let drive = drive.and_then(|d| d.trim().chars().next());
+        benchmark::run_benchmark(passes.unwrap_or(benchmark::DEFAULT_PASSES), drive)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_get_benchmark_history() -> Vec<benchmark::BenchmarkResult> {
+    bg(benchmark::get_benchmark_history).await
+}
+
+#[tauri::command]
+async fn cmd_compare_benchmark(
+    previous_id: String,
+) -> Result<benchmark::BenchmarkComparison, String> {
+    let args = format!("{:?}", previous_id);
+    bg_logged("cmd_compare_benchmark", args, move || {
+        benchmark::compare_benchmark(&previous_id)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_run_stress_test(
+    minutes: u32,
+    target: String,
+) -> Result<benchmark::StressTestResult, String> {
+    let args = format!("{:?}", (minutes, &target));
+    bg_logged("cmd_run_stress_test", args, move || {
+        benchmark::run_stress_test(minutes, &target)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_stop_stress_test() -> Result<String, String> {
+    benchmark::stop_stress_test()
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -582,18 +1270,107 @@ async fn cmd_get_disk_health() -> Vec<disk_health::DiskHealthInfo> {
     bg(|| disk_health::get_disk_health()).await
 }
 
+#[tauri::command]
+async fn cmd_get_disk_health_history(
+    serial: String,
+    model: Option<String>,
+) -> Vec<disk_health::HealthSample> {
+    bg(move || disk_health::get_health_history(&serial, model.as_deref().unwrap_or(""))).await
+}
+
+#[tauri::command]
+async fn cmd_analyze_ssd_wear() -> disk_health::SsdWearReport {
+    bg(disk_health::analyze_ssd_wear).await
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Tauri Commands — Duplicate Finder
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[tauri::command]
-async fn cmd_scan_duplicates(min_size_mb: f64) -> duplicates::DuplicateScanResult {
-    bg(move || duplicates::scan_duplicates(min_size_mb)).await
+async fn cmd_scan_duplicates(
+    app: tauri::AppHandle,
+    min_size_mb: f64,
+) -> duplicates::DuplicateScanResult {
+    bg(move || {
+        let result = duplicates::scan_duplicates(None, min_size_mb, &|p| {
+            emit_progress(&app, "duplicate-scan-progress", p)
+        });
+        export::remember_duplicates(result)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_scan_duplicates_in(
+    app: tauri::AppHandle,
+    dirs: Vec<String>,
+    min_size_mb: f64,
+) -> duplicates::DuplicateScanResult {
+    bg(move || {
+        let result = duplicates::scan_duplicates(Some(dirs), min_size_mb, &|p| {
+            emit_progress(&app, "duplicate-scan-progress", p)
+        });
+        export::remember_duplicates(result)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_scan_drive_duplicates(
+    app: tauri::AppHandle,
+    drive: String,
+    min_size_mb: f64,
+) -> duplicates::DuplicateScanResult {
+    bg(move || {
+        let result = duplicates::scan_drive_duplicates(&drive, min_size_mb, &|p| {
+            emit_progress(&app, "duplicate-scan-progress", p)
+        });
+        export::remember_duplicates(result)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_export_duplicates_csv(path: String) -> Result<String, String> {
+    let args = format!("{:?}", path);
+    bg_logged("cmd_export_duplicates_csv", args, move || {
+        export::export_last_duplicates_csv(&path)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_delete_duplicate(
+    path: String,
+    token: Option<String>,
+    recycle: Option<bool>,
+) -> Result<String, String> {
+    let args = format!("{:?}", (&path, &token, &recycle));
+    bg_logged("cmd_delete_duplicate", args, move || {
+        duplicates::delete_duplicate(&path, token.as_deref(), recycle.unwrap_or(false))
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_get_hash_cache_status() -> duplicates::HashCacheStatus {
+    bg(duplicates::get_hash_cache_status).await
 }
 
 #[tauri::command]
-async fn cmd_delete_duplicate(path: String) -> Result<String, String> {
-    bg(move || duplicates::delete_duplicate(&path)).await
+async fn cmd_verify_hash_cache() -> duplicates::HashCacheVerifyResult {
+    bg(duplicates::verify_hash_cache).await
+}
+
+#[tauri::command]
+async fn cmd_clear_hash_cache() -> Result<String, String> {
+    bg_logged(
+        "cmd_clear_hash_cache",
+        String::new(),
+        duplicates::clear_hash_cache,
+    )
+    .await
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -605,19 +1382,84 @@ async fn cmd_list_services() -> Vec<services::ServiceInfo> {
     bg(|| services::list_services()).await
 }
 
+#[tauri::command]
+async fn cmd_get_service_suggestions() -> Vec<services::ServiceSuggestion> {
+    bg(services::get_optimization_suggestions).await
+}
+
+#[tauri::command]
+async fn cmd_get_service_groups() -> Vec<services::ServiceGroup> {
+    bg(services::get_service_groups).await
+}
+
 #[tauri::command]
 async fn cmd_start_service(name: String) -> Result<String, String> {
-    bg(move || services::start_service(&name)).await
+    let args = format!("{:?}", name);
+    bg_logged("cmd_start_service", args, move || {
+        services::start_service(&name)
+    })
+    .await
 }
 
 #[tauri::command]
 async fn cmd_stop_service(name: String) -> Result<String, String> {
-    bg(move || services::stop_service(&name)).await
+    let args = format!("{:?}", name);
+    bg_logged("cmd_stop_service", args, move || {
+        services::stop_service(&name)
+    })
+    .await
 }
 
 #[tauri::command]
 async fn cmd_set_service_startup(name: String, startup: String) -> Result<String, String> {
-    bg(move || services::set_service_startup(&name, &startup)).await
+    let args = format!("{:?}", (&name, &startup));
+    bg_logged("cmd_set_service_startup", args, move || {
+        services::set_service_startup(&name, &startup)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_reset_service_to_default(name: String) -> Result<String, String> {
+    let args = format!("{:?}", name);
+    bg_logged("cmd_reset_service_to_default", args, move || {
+        services::reset_service_to_default(&name)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_check_essential_services() -> Vec<services::EssentialServiceIssue> {
+    bg(services::check_essential_services).await
+}
+
+#[tauri::command]
+async fn cmd_fix_essential_service(name: String) -> Result<String, String> {
+    let args = format!("{:?}", name);
+    bg_logged("cmd_fix_essential_service", args, move || {
+        services::fix_essential_service(&name)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_apply_recommended_services() -> Result<services::RecommendedServicesReport, String> {
+    bg_logged(
+        "cmd_apply_recommended_services",
+        String::new(),
+        services::apply_recommended_service_changes,
+    )
+    .await
+}
+
+#[tauri::command]
+async fn cmd_restore_service_snapshot() -> Result<String, String> {
+    bg_logged(
+        "cmd_restore_service_snapshot",
+        String::new(),
+        services::restore_service_snapshot,
+    )
+    .await
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -626,7 +1468,16 @@ async fn cmd_set_service_startup(name: String, startup: String) -> Result<String
 
 #[tauri::command]
 async fn cmd_scan_registry() -> registry::RegistryScanResult {
-    bg(|| registry::scan_registry()).await
+    bg(|| export::remember_registry(registry::scan_registry())).await
+}
+
+#[tauri::command]
+async fn cmd_export_registry_csv(path: String) -> Result<String, String> {
+    let args = format!("{:?}", path);
+    bg_logged("cmd_export_registry_csv", args, move || {
+        export::export_last_registry_csv(&path)
+    })
+    .await
 }
 
 #[tauri::command]
@@ -634,8 +1485,44 @@ async fn cmd_fix_registry_issue(
     key_path: String,
     value_name: String,
     issue_type: String,
+    restore_point: Option<bool>,
 ) -> Result<String, String> {
-    bg(move || registry::fix_registry_issue(&key_path, &value_name, &issue_type)).await
+    let args = format!("{:?}", (&key_path, &value_name, &issue_type, restore_point));
+    bg_logged("cmd_fix_registry_issue", args, move || {
+        registry::fix_registry_issue(
+            &key_path,
+            &value_name,
+            &issue_type,
+            restore_point.unwrap_or(false),
+        )
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_fix_registry_issues(
+    issues: Vec<registry::RegistryFixRequest>,
+    restore_point: Option<bool>,
+) -> Vec<(String, bool, String)> {
+    bg(move || registry::fix_registry_issues(issues, restore_point.unwrap_or(false))).await
+}
+
+#[tauri::command]
+async fn cmd_restore_registry_backup(reg_file: String) -> Result<String, String> {
+    let args = format!("{:?}", reg_file);
+    bg_logged("cmd_restore_registry_backup", args, move || {
+        registry::restore_from_backup(&reg_file)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_compact_registry(apply: bool) -> Result<registry::RegistryCompactionResult, String> {
+    let args = format!("{:?}", apply);
+    bg_logged("cmd_compact_registry", args, move || {
+        registry::compact_registry(apply)
+    })
+    .await
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -647,49 +1534,88 @@ async fn cmd_get_battery_health() -> battery::BatteryHealth {
     bg(|| battery::get_battery_health()).await
 }
 
+#[tauri::command]
+async fn cmd_get_battery_history() -> Vec<(String, u32)> {
+    bg(battery::get_wear_history).await
+}
+
+#[tauri::command]
+async fn cmd_open_battery_report() -> Result<String, String> {
+    bg_logged("cmd_open_battery_report", String::new(), || {
+        let path = battery::generate_battery_report()?;
+        std::process::Command::new("explorer")
+            .arg(&path)
+            .spawn()
+            .map_err(|e| e.to_string())?;
+        Ok(path)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_set_charge_limit(percent: u8) -> Result<String, String> {
+    let args = format!("{:?}", percent);
+    bg_logged("cmd_set_charge_limit", args, move || {
+        battery::set_charge_limit(percent)
+    })
+    .await
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Tauri Commands — Driver Management
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[tauri::command]
 async fn cmd_scan_driver_updates() -> Result<String, String> {
-    bg(|| {
-        match std::process::Command::new("pnputil")
+    bg_logged(
+        "cmd_scan_driver_updates",
+        String::new(),
+        || match std::process::Command::new("pnputil")
             .args(["/scan-devices"])
             .output()
         {
             Ok(o) => Ok(String::from_utf8_lossy(&o.stdout).trim().to_string()),
             Err(e) => Err(e.to_string()),
-        }
-    })
+        },
+    )
     .await
 }
 
+#[tauri::command]
+async fn cmd_export_drivers(dest: String) -> Result<String, String> {
+    let args = format!("{:?}", dest);
+    bg_logged("cmd_export_drivers", args, move || export_drivers(&dest)).await
+}
+
 #[tauri::command]
 async fn cmd_open_device_manager() -> Result<String, String> {
-    bg(|| {
-        match std::process::Command::new("cmd")
+    bg_logged(
+        "cmd_open_device_manager",
+        String::new(),
+        || match std::process::Command::new("cmd")
             .args(["/C", "start devmgmt.msc"])
             .output()
         {
             Ok(_) => Ok("Device Manager opened".into()),
             Err(e) => Err(e.to_string()),
-        }
-    })
+        },
+    )
     .await
 }
 
 #[tauri::command]
 async fn cmd_open_windows_update() -> Result<String, String> {
-    bg(|| {
-        match std::process::Command::new("cmd")
+    bg_logged(
+        "cmd_open_windows_update",
+        String::new(),
+        || match std::process::Command::new("cmd")
             .args(["/C", "start ms-settings:windowsupdate"])
             .output()
         {
             Ok(_) => Ok("Windows Update opened".into()),
             Err(e) => Err(e.to_string()),
-        }
-    })
+        },
+    )
     .await
 }
 
@@ -704,7 +1630,11 @@ async fn cmd_scan_junk() -> Vec<disk_cleanup::JunkCategory> {
 
 #[tauri::command]
 async fn cmd_clean_junk_category(id: String) -> Result<disk_cleanup::CleanResult, String> {
-    bg(move || disk_cleanup::clean_junk_category(&id)).await
+    let args = format!("{:?}", id);
+    bg_logged("cmd_clean_junk_category", args, move || {
+        disk_cleanup::clean_junk_category(&id)
+    })
+    .await
 }
 
 #[tauri::command]
@@ -714,7 +1644,11 @@ async fn cmd_scan_app_caches() -> Vec<disk_cleanup::AppCache> {
 
 #[tauri::command]
 async fn cmd_clean_app_cache(app_name: String) -> Result<disk_cleanup::CleanResult, String> {
-    bg(move || disk_cleanup::clean_app_cache(&app_name)).await
+    let args = format!("{:?}", app_name);
+    bg_logged("cmd_clean_app_cache", args, move || {
+        disk_cleanup::clean_app_cache(&app_name)
+    })
+    .await
 }
 
 #[tauri::command]
@@ -729,7 +1663,11 @@ async fn cmd_list_installed_programs() -> Vec<disk_cleanup::InstalledProgram> {
 
 #[tauri::command]
 async fn cmd_uninstall_program(command: String) -> Result<String, String> {
-    bg(move || disk_cleanup::uninstall_program(&command)).await
+    let args = format!("{:?}", command);
+    bg_logged("cmd_uninstall_program", args, move || {
+        disk_cleanup::uninstall_program(&command)
+    })
+    .await
 }
 
 #[tauri::command]
@@ -739,12 +1677,20 @@ async fn cmd_list_restore_points() -> Vec<disk_cleanup::RestorePoint> {
 
 #[tauri::command]
 async fn cmd_delete_restore_point(seq: u32) -> Result<String, String> {
-    bg(move || disk_cleanup::delete_restore_point(seq)).await
+    let args = format!("{:?}", seq);
+    bg_logged("cmd_delete_restore_point", args, move || {
+        disk_cleanup::delete_restore_point(seq)
+    })
+    .await
 }
 
 #[tauri::command]
 async fn cmd_shred_file(path: String, passes: u32) -> Result<disk_cleanup::ShredResult, String> {
-    bg(move || disk_cleanup::shred_file(&path, passes)).await
+    let args = format!("{:?}", (&path, &passes));
+    bg_logged("cmd_shred_file", args, move || {
+        disk_cleanup::shred_file(&path, passes)
+    })
+    .await
 }
 
 #[tauri::command]
@@ -752,7 +1698,11 @@ async fn cmd_wipe_free_space(
     drive: String,
     passes: u32,
 ) -> Result<disk_cleanup::WipeProgress, String> {
-    bg(move || disk_cleanup::wipe_free_space(&drive, passes)).await
+    let args = format!("{:?}", (&drive, &passes));
+    bg_logged("cmd_wipe_free_space", args, move || {
+        disk_cleanup::wipe_free_space(&drive, passes)
+    })
+    .await
 }
 
 #[tauri::command]
@@ -778,7 +1728,7 @@ pub struct ScheduledTask {
 
 #[tauri::command]
 async fn cmd_list_scheduled_tasks() -> Result<Vec<ScheduledTask>, String> {
-    bg(|| {
+    bg_logged("cmd_list_scheduled_tasks", String::new(), || {
         let out = std::process::Command::new("schtasks")
             .args(&["/query", "/fo", "csv", "/nh"])
             .output()
@@ -804,7 +1754,8 @@ async fn cmd_list_scheduled_tasks() -> Result<Vec<ScheduledTask>, String> {
 
 #[tauri::command]
 async fn cmd_toggle_scheduled_task(name: String, enable: bool) -> Result<String, String> {
-    bg(move || {
+    let args = format!("{:?}", (&name, &enable));
+    bg_logged("cmd_toggle_scheduled_task", args, move || {
         let action = if enable { "/Enable" } else { "/Disable" };
         let out = std::process::Command::new("schtasks")
             .args(&["/Change", "/TN", &name, action])
@@ -821,7 +1772,7 @@ async fn cmd_toggle_scheduled_task(name: String, enable: bool) -> Result<String,
 
 #[tauri::command]
 async fn cmd_enable_game_booster() -> Result<String, String> {
-    bg(|| {
+    bg_logged("cmd_enable_game_booster", String::new(), || {
         // High performance scheme
         let _ = std::process::Command::new("powercfg")
             .args(&["/s", "8c5e7fda-e8bf-4a96-9a85-a6e23a8c635c"])
@@ -844,7 +1795,7 @@ async fn cmd_enable_game_booster() -> Result<String, String> {
 
 #[tauri::command]
 async fn cmd_restore_normal_mode() -> Result<String, String> {
-    bg(|| {
+    bg_logged("cmd_restore_normal_mode", String::new(), || {
         // Balanced scheme
         let _ = std::process::Command::new("powercfg")
             .args(&["/s", "381b4222-f694-41f0-9685-ff5bb260df2e"])
@@ -866,7 +1817,8 @@ async fn cmd_restore_normal_mode() -> Result<String, String> {
 
 #[tauri::command]
 async fn cmd_toggle_telemetry(setting: String, disable: bool) -> Result<String, String> {
-    bg(move || {
+    let args = format!("{:?}", (&setting, &disable));
+    bg_logged("cmd_toggle_telemetry", args, move || {
         let val = if disable { "0" } else { "1" };
         match setting.as_str() {
             "telemetry" => {
@@ -899,12 +1851,19 @@ async fn cmd_get_dns_providers() -> Vec<dns::DnsProvider> {
 
 #[tauri::command]
 async fn cmd_get_dns_status() -> Result<dns::DnsStatus, String> {
-    bg(|| dns::get_dns_status()).await
+    bg_logged("cmd_get_dns_status", String::new(), || {
+        dns::get_dns_status()
+    })
+    .await
 }
 
 #[tauri::command]
 async fn cmd_set_dns(provider_id: String) -> Result<String, String> {
-    bg(move || dns::set_dns_provider(&provider_id)).await
+    let args = format!("{:?}", provider_id);
+    bg_logged("cmd_set_dns", args, move || {
+        dns::set_dns_provider(&provider_id)
+    })
+    .await
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -918,12 +1877,20 @@ async fn cmd_get_theme_status() -> tweaks::ThemeStatus {
 
 #[tauri::command]
 async fn cmd_set_dark_mode(enabled: bool) -> Result<String, String> {
-    bg(move || tweaks::set_dark_mode(enabled)).await
+    let args = format!("{:?}", enabled);
+    bg_logged("cmd_set_dark_mode", args, move || {
+        tweaks::set_dark_mode(enabled)
+    })
+    .await
 }
 
 #[tauri::command]
 async fn cmd_create_restore_point(description: String) -> Result<String, String> {
-    bg(move || tweaks::create_restore_point(&description)).await
+    let args = format!("{:?}", description);
+    bg_logged("cmd_create_restore_point", args, move || {
+        tweaks::create_restore_point(&description)
+    })
+    .await
 }
 
 #[tauri::command]
@@ -931,6 +1898,126 @@ async fn cmd_is_restore_enabled() -> bool {
     bg(|| tweaks::is_restore_enabled()).await
 }
 
+#[tauri::command]
+async fn cmd_enable_auto_trim(
+    app: tauri::AppHandle,
+    threshold_pct: u32,
+    interval_secs: u32,
+    min_idle_secs: u64,
+) -> Result<String, String> {
+    start_auto_trim(app, threshold_pct, interval_secs, min_idle_secs)?;
+    let config = tweaks::get_auto_trim_config();
+    bg(move || settings::update_settings(|s| s.auto_trim = config)).await?;
+    Ok(format!(
+        "Auto-trim enabled: trimming when memory ≥ {}% (checked every {}s)",
+        threshold_pct, interval_secs
+    ))
+}
+
+#[tauri::command]
+async fn cmd_disable_auto_trim() -> String {
+    bg(|| {
+        let msg = tweaks::disable_auto_trim();
+        let _ = settings::update_settings(|s| s.auto_trim.enabled = false);
+        msg
+    })
+    .await
+}
+
+/// Enable auto-trim and spawn its polling task; also used to resume it at startup
+fn start_auto_trim(
+    app: tauri::AppHandle,
+    threshold_pct: u32,
+    interval_secs: u32,
+    min_idle_secs: u64,
+) -> Result<(), String> {
+    let generation = tweaks::enable_auto_trim(threshold_pct, interval_secs, min_idle_secs)?;
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(interval_secs as u64)).await;
+            if !tweaks::auto_trim_active(generation) {
+                break;
+            }
+            if let Some(event) = bg(tweaks::auto_trim_tick).await {
+                emit_progress(&app, "auto-trim-fired", event);
+            }
+        }
+    });
+    Ok(())
+}
+
+#[tauri::command]
+async fn cmd_get_auto_trim_config() -> tweaks::AutoPurgeConfig {
+    bg(tweaks::get_auto_trim_config).await
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Tauri Commands — Local Error Log
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[tauri::command]
+async fn cmd_get_recent_errors(limit: Option<usize>) -> Vec<error_log::ErrorRecord> {
+    bg(move || error_log::get_recent_errors(limit.unwrap_or(100))).await
+}
+
+#[tauri::command]
+async fn cmd_is_error_log_enabled() -> bool {
+    bg(error_log::is_enabled).await
+}
+
+#[tauri::command]
+async fn cmd_set_error_log_enabled(enabled: bool) -> Result<String, String> {
+    bg(move || error_log::set_enabled(enabled)).await
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Tauri Commands — Scheduled Optimizations
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[tauri::command]
+async fn cmd_list_schedules() -> Vec<scheduler::Schedule> {
+    bg(scheduler::list_schedules).await
+}
+
+#[tauri::command]
+async fn cmd_add_schedule(
+    ids: Vec<String>,
+    trigger: scheduler::Trigger,
+    min_idle_secs: u64,
+) -> Result<scheduler::Schedule, String> {
+    let args = format!("{:?} {:?} {:?}", ids, trigger, min_idle_secs);
+    bg_logged("cmd_add_schedule", args, move || {
+        scheduler::register_schedule(ids, trigger, min_idle_secs)
+    })
+    .await
+}
+
+#[tauri::command]
+async fn cmd_remove_schedule(id: String) -> Result<String, String> {
+    let args = format!("{:?}", id);
+    bg_logged("cmd_remove_schedule", args, move || {
+        scheduler::remove_schedule(&id)
+    })
+    .await
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Tauri Commands — Settings
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[tauri::command]
+async fn cmd_load_settings() -> settings::Settings {
+    bg(settings::load_settings).await
+}
+
+#[tauri::command]
+async fn cmd_save_settings(settings: settings::Settings) -> Result<String, String> {
+    bg_logged("cmd_save_settings", String::new(), move || {
+        settings::save_settings(&settings)
+    })
+    .await
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // App Entry
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -940,56 +2027,154 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_log::Builder::new().build())
         .plugin(tauri_plugin_dialog::init())
+        .setup(|app| {
+            let handle = app.handle().clone();
+            scheduler::start(move |run| emit_progress(&handle, "schedule-ran", run));
+
+            // Quarantined cleanups past the grace period are deleted for good
+            std::thread::spawn(|| {
+                if let Ok(msg) = quarantine::purge_quarantine(quarantine::GRACE_DAYS) {
+                    log::info!("{}", msg);
+                }
+            });
+
+            let auto_trim = settings::load_settings().auto_trim;
+            if auto_trim.enabled {
+                let handle = app.handle().clone();
+                if let Err(e) = start_auto_trim(
+                    handle,
+                    auto_trim.threshold_percent,
+                    auto_trim.interval_seconds,
+                    auto_trim.min_idle_secs,
+                ) {
+                    log::warn!("Saved auto-trim settings rejected: {}", e);
+                }
+            }
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // Original
             cmd_get_system_info,
+            cmd_is_elevated,
+            cmd_relaunch_as_admin,
             cmd_get_processes,
+            cmd_get_processes_filtered,
             cmd_get_catalog,
+            cmd_export_catalog,
+            cmd_export_report_json,
             cmd_optimize,
+            cmd_is_reboot_pending,
+            cmd_get_system_cache_status,
+            cmd_rebuild_font_cache,
+            cmd_rebuild_icon_cache,
+            cmd_clean_thumbnail_cache,
+            cmd_restart_explorer,
             // Monitoring
             cmd_get_live_metrics,
             cmd_get_health_score,
+            cmd_get_health_history,
             cmd_get_hardware_info,
+            cmd_get_dashboard_summary,
+            cmd_get_timer_resolution,
+            cmd_get_idle_seconds,
+            cmd_capture_snapshot,
+            cmd_compare_snapshots,
             // Startup
             cmd_list_startup,
             cmd_toggle_startup,
+            cmd_get_startup_footprints,
             // Scanner / Cleanup
             cmd_scan_large_files,
+            cmd_export_large_files_csv,
+            cmd_cancel_scan,
             cmd_detect_browsers,
             cmd_clean_browser,
+            cmd_clean_browser_data,
             cmd_get_privacy_items,
             cmd_clean_privacy,
+            cmd_list_quarantine,
+            cmd_undo_cleanup,
+            cmd_purge_quarantine,
             cmd_list_drivers,
             cmd_clean_windows_update,
+            cmd_scan_leftovers,
+            cmd_delete_leftover,
             cmd_kill_process,
+            cmd_suspend_process,
+            cmd_resume_process,
+            cmd_list_suspended_processes,
+            cmd_get_process_affinity,
+            cmd_set_affinity,
+            cmd_set_process_priority,
             cmd_get_process_suggestions,
             cmd_optimize_processes,
+            cmd_add_process_whitelist,
+            cmd_remove_process_whitelist,
+            cmd_get_process_memory_breakdown,
+            cmd_kill_processes_matching,
+            cmd_trim_processes_matching,
+            cmd_set_process_working_set_limit,
+            cmd_get_working_set_limits,
+            cmd_remove_working_set_limit,
+            cmd_reapply_working_set_limits,
             // Network
             cmd_get_network_overview,
+            cmd_get_network_delta,
             cmd_ping_test,
+            cmd_run_speed_test,
             // Debloater
             cmd_list_appx,
+            cmd_list_appx_filtered,
             cmd_remove_appx,
             cmd_remove_all_bloatware,
+            cmd_reinstall_appx,
+            cmd_list_removable_defaults,
             // Benchmark
             cmd_run_benchmark,
+            cmd_get_benchmark_history,
+            cmd_compare_benchmark,
+            cmd_run_stress_test,
+            cmd_stop_stress_test,
             // Disk Health
             cmd_get_disk_health,
+            cmd_get_disk_health_history,
+            cmd_analyze_ssd_wear,
             // Duplicates
             cmd_scan_duplicates,
+            cmd_scan_duplicates_in,
+            cmd_export_duplicates_csv,
+            cmd_scan_drive_duplicates,
             cmd_delete_duplicate,
+            cmd_get_hash_cache_status,
+            cmd_verify_hash_cache,
+            cmd_clear_hash_cache,
             // Services
             cmd_list_services,
+            cmd_get_service_groups,
+            cmd_get_service_suggestions,
             cmd_start_service,
             cmd_stop_service,
             cmd_set_service_startup,
+            cmd_reset_service_to_default,
+            cmd_check_essential_services,
+            cmd_fix_essential_service,
+            cmd_apply_recommended_services,
+            cmd_restore_service_snapshot,
             // Registry
             cmd_scan_registry,
+            cmd_export_registry_csv,
             cmd_fix_registry_issue,
+            cmd_fix_registry_issues,
+            cmd_restore_registry_backup,
+            cmd_compact_registry,
             // Battery
             cmd_get_battery_health,
+            cmd_get_battery_history,
+            cmd_open_battery_report,
+            cmd_set_charge_limit,
             // Driver Management
             cmd_scan_driver_updates,
+            cmd_export_drivers,
             cmd_open_device_manager,
             cmd_open_windows_update,
             // Disk Cleanup
@@ -1015,6 +2200,7 @@ pub fn run() {
             cmd_toggle_telemetry,
             // File delete
             cmd_delete_file,
+            cmd_delete_file_to_recycle,
             cmd_reveal_file,
             // DNS Quick-Switch
             cmd_get_dns_providers,
@@ -1025,6 +2211,20 @@ pub fn run() {
             cmd_set_dark_mode,
             cmd_create_restore_point,
             cmd_is_restore_enabled,
+            cmd_enable_auto_trim,
+            cmd_disable_auto_trim,
+            cmd_get_auto_trim_config,
+            // Local Error Log
+            cmd_get_recent_errors,
+            cmd_is_error_log_enabled,
+            cmd_set_error_log_enabled,
+            // Scheduled Optimizations
+            cmd_list_schedules,
+            cmd_add_schedule,
+            cmd_remove_schedule,
+            // Settings
+            cmd_load_settings,
+            cmd_save_settings,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
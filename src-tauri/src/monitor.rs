@@ -1,6 +1,7 @@
 //! Real-time monitoring, health score, and hardware info
 
 use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, OnceLock};
 use sysinfo::{Components, Disks, Networks, ProcessesToUpdate, System};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,21 +13,39 @@ pub struct LiveMetrics {
     pub memory_percent: f64,
     pub swap_used_mb: u64,
     pub swap_total_mb: u64,
-    pub disk_read_bytes: u64,
+    pub disk_read_bytes: u64, // lifetime totals of running processes
     pub disk_write_bytes: u64,
-    pub net_rx_bytes: u64,
+    pub disk_read_bps: u64,
+    pub disk_write_bps: u64,
+    pub net_rx_bytes: u64, // totals since boot, all interfaces
     pub net_tx_bytes: u64,
+    pub net_rx_bps: u64,
+    pub net_tx_bps: u64,
     pub process_count: usize,
     pub thread_count: usize,
     pub uptime_seconds: u64,
     pub temperatures: Vec<TempReading>,
+    pub gpu_usage_percent: f32, // busiest adapter, all engines summed
+    pub vram_used_mb: u64,
+    pub vram_total_mb: u64,
 }
 
+/// Kept across calls so `received()`/`transmitted()` are deltas since the previous refresh
+static NETWORKS: Mutex<Option<(Networks, std::time::Instant)>> = Mutex::new(None);
+
+/// (time, total read, total written) from the previous `get_live_metrics` call
+static LAST_DISK_SAMPLE: Mutex<Option<(std::time::Instant, u64, u64)>> = Mutex::new(None);
+
+/// Last WMI temperature query; each one spawns PowerShell, so it is reused for a while
+static WMI_TEMPERATURES: Mutex<Option<(std::time::Instant, Vec<TempReading>)>> = Mutex::new(None);
+const WMI_TEMPERATURE_MAX_AGE_SECS: u64 = 45;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TempReading {
     pub label: String,
     pub temp_c: f32,
     pub critical: Option<f32>,
+    pub source: String, // "sysinfo", "librehardwaremonitor" or "acpi"
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +57,16 @@ pub struct HealthScore {
     pub startup_score: u32,
     pub uptime_score: u32,
     pub details: Vec<HealthDetail>,
+    pub inputs: HealthInputs,
+    pub alerts: Vec<HealthAlert>, // subscores that fell sharply since the previous sample
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthAlert {
+    pub category: String,
+    pub previous: u32,
+    pub current: u32,
+    pub suggestion: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +75,20 @@ pub struct HealthDetail {
     pub score: u32,
     pub label: String,
     pub suggestion: String,
+    pub value: f64,
+    pub unit: String, // "percent", "count", "days"
+}
+
+/// Raw measurements behind the health score, so the UI can apply its own
+/// thresholds and localize labels instead of relying on the rendered strings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthInputs {
+    pub mem_pct: f64,
+    pub cpu_avg: f32,
+    pub worst_disk_pct: f64,
+    pub proc_count: usize,
+    pub startup_count: usize, // enabled startup entries
+    pub uptime_days: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,9 +121,35 @@ pub struct DiskInfo {
     pub is_removable: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardSummary {
+    pub os_name: String,
+    pub os_version: String,
+    pub hostname: String,
+    pub cpu_name: String,
+    pub cpu_cores: usize,
+    pub cpu_usage: f32,
+    pub memory_used_mb: u64,
+    pub memory_total_mb: u64,
+    pub memory_percent: f64,
+    pub swap_used_mb: u64,
+    pub swap_total_mb: u64,
+    pub process_count: usize,
+    pub uptime_seconds: u64,
+    pub health: HealthScore,
+    pub disks: Vec<DiskInfo>,
+    pub battery_present: bool,
+    pub battery_percent: Option<u32>,
+    pub on_ac_power: Option<bool>,
+}
+
 pub fn get_live_metrics() -> LiveMetrics {
+    // The GPU rate counters need two samples a moment apart — read them alongside the rest
+    let gpu = std::thread::spawn(read_gpu_metrics);
+
     let mut sys = System::new_all();
     sys.refresh_all();
+    let first_refresh = std::time::Instant::now();
     std::thread::sleep(std::time::Duration::from_millis(100));
     sys.refresh_cpu_all();
 
@@ -96,33 +165,80 @@ pub fn get_live_metrics() -> LiveMetrics {
 
     // Disk I/O - aggregate across processes
     sys.refresh_processes(ProcessesToUpdate::All, true);
+    let window = first_refresh.elapsed().as_secs_f64().max(0.001);
     let (mut total_read, mut total_write) = (0u64, 0u64);
+    let (mut window_read, mut window_write) = (0u64, 0u64);
     for (_pid, proc_) in sys.processes() {
         let dio = proc_.disk_usage();
-        total_read += dio.read_bytes;
-        total_write += dio.written_bytes;
+        total_read += dio.total_read_bytes;
+        total_write += dio.total_written_bytes;
+        window_read += dio.read_bytes;
+        window_write += dio.written_bytes;
     }
 
-    // Network
-    let networks = Networks::new_with_refreshed_list();
-    let (mut rx, mut tx) = (0u64, 0u64);
-    for (_name, net) in &networks {
-        rx += net.received();
-        tx += net.transmitted();
-    }
+    // Rate since the previous call; the first call only has this call's short window.
+    // Exited processes take their totals with them, hence saturating_sub.
+    let (disk_read_bps, disk_write_bps) = {
+        let now = std::time::Instant::now();
+        let mut last = LAST_DISK_SAMPLE.lock().unwrap_or_else(|e| e.into_inner());
+        let rates = match *last {
+            Some((at, read, write)) => {
+                let secs = now.duration_since(at).as_secs_f64().max(0.001);
+                (
+                    (total_read.saturating_sub(read) as f64 / secs) as u64,
+                    (total_write.saturating_sub(write) as f64 / secs) as u64,
+                )
+            }
+            None => (
+                (window_read as f64 / window) as u64,
+                (window_write as f64 / window) as u64,
+            ),
+        };
+        *last = Some((now, total_read, total_write));
+        rates
+    };
+
+    // Network — the first call has no previous refresh, so it reports 0 B/s
+    let (rx, tx, net_rx_bps, net_tx_bps) = {
+        let mut guard = NETWORKS.lock().unwrap_or_else(|e| e.into_inner());
+        let now = std::time::Instant::now();
+        let secs = match guard.as_mut() {
+            Some((networks, last)) => {
+                networks.refresh(true);
+                let secs = now.duration_since(*last).as_secs_f64().max(0.001);
+                *last = now;
+                Some(secs)
+            }
+            None => {
+                *guard = Some((Networks::new_with_refreshed_list(), now));
+                None
+            }
+        };
+        let networks = &guard.as_ref().expect("initialized above").0;
+        let (mut rx, mut tx, mut rx_delta, mut tx_delta) = (0u64, 0u64, 0u64, 0u64);
+        for (_name, net) in networks {
+            rx += net.total_received();
+            tx += net.total_transmitted();
+            rx_delta += net.received();
+            tx_delta += net.transmitted();
+        }
+        match secs {
+            Some(secs) => (
+                rx,
+                tx,
+                (rx_delta as f64 / secs) as u64,
+                (tx_delta as f64 / secs) as u64,
+            ),
+            None => (rx, tx, 0, 0),
+        }
+    };
 
     // Temperatures
     let components = Components::new_with_refreshed_list();
-    let temperatures: Vec<TempReading> = components
-        .iter()
-        .map(|c| TempReading {
-            label: c.label().to_string(),
-            temp_c: c.temperature().unwrap_or(0.0),
-            critical: c.critical(),
-        })
-        .collect();
+    let temperatures = read_temperatures(&components);
 
     let process_count = sys.processes().len();
+    let (gpu_usage_percent, vram_used_mb) = gpu.join().unwrap_or((0.0, 0));
 
     LiveMetrics {
         cpu_usage,
@@ -138,19 +254,228 @@ pub fn get_live_metrics() -> LiveMetrics {
         swap_total_mb: sys.total_swap() / 1_048_576,
         disk_read_bytes: total_read,
         disk_write_bytes: total_write,
+        disk_read_bps,
+        disk_write_bps,
         net_rx_bytes: rx,
         net_tx_bytes: tx,
+        net_rx_bps,
+        net_tx_bps,
         process_count,
-        thread_count: 0,
+        thread_count: system_thread_count(),
         uptime_seconds: System::uptime(),
         temperatures,
+        gpu_usage_percent,
+        vram_used_mb,
+        vram_total_mb: vram_total_mb(),
+    }
+}
+
+/// Adapter key shared by GPU Engine and GPU Adapter Memory instances ("luid_0x…_0x…")
+fn gpu_adapter_key(instance: &str) -> Option<&str> {
+    let start = instance.find("luid_")?;
+    let rest = &instance[start..];
+    Some(rest.find("_phys").map_or(rest, |end| &rest[..end]))
+}
+
+const GPU_COUNTERS: [&str; 2] = [
+    "\\GPU Engine(*)\\Utilization Percentage",
+    "\\GPU Adapter Memory(*)\\Dedicated Usage",
+];
+
+/// (utilization %, dedicated VRAM used MB) of the busiest adapter. Read in-process via
+/// PDH since the dashboard polls every few seconds; Get-Counter is the fallback.
+fn read_gpu_metrics() -> (f32, u64) {
+    let mut usage: std::collections::HashMap<String, f64> = std::collections::HashMap::new();
+    let mut vram: std::collections::HashMap<String, f64> = std::collections::HashMap::new();
+    let mut add = |utilization: bool, instance: &str, value: f64| {
+        let Some(key) = gpu_adapter_key(instance) else {
+            return;
+        };
+        let totals = if utilization { &mut usage } else { &mut vram };
+        *totals.entry(key.to_string()).or_insert(0.0) += value;
+    };
+
+    if let Some(counters) = crate::optimizer::read_pdh_counter_instances(
+        &GPU_COUNTERS,
+        std::time::Duration::from_millis(250),
+    ) {
+        for (i, instances) in counters.iter().enumerate() {
+            for (instance, value) in instances {
+                add(i == 0, instance, *value);
+            }
+        }
+    } else {
+        let script = "(Get-Counter '\\GPU Engine(*)\\Utilization Percentage','\\GPU Adapter Memory(*)\\Dedicated Usage' -ErrorAction SilentlyContinue).CounterSamples | ForEach-Object { \"$($_.Path)|$($_.InstanceName)|$($_.CookedValue)\" }";
+        let output = match std::process::Command::new("powershell")
+            .args(["-NoProfile", "-Command", script])
+            .output()
+        {
+            Ok(o) => String::from_utf8_lossy(&o.stdout).to_string(),
+            Err(_) => return (0.0, 0),
+        };
+        for line in output.lines() {
+            let parts: Vec<&str> = line.trim().split('|').collect();
+            if parts.len() != 3 {
+                continue;
+            }
+            let Ok(value) = parts[2].trim().parse::<f64>() else {
+                continue;
+            };
+            let path = parts[0].to_lowercase();
+            if path.ends_with("utilization percentage") {
+                add(true, parts[1], value);
+            } else if path.ends_with("dedicated usage") {
+                add(false, parts[1], value);
+            }
+        }
+    }
+
+    let usage_pct = usage.values().fold(0.0f64, |a, &b| a.max(b)).min(100.0) as f32;
+    let vram_mb = (vram.values().fold(0.0f64, |a, &b| a.max(b)) / 1_048_576.0) as u64;
+    (usage_pct, vram_mb)
+}
+
+/// Dedicated VRAM of the largest adapter from the display class registry keys (read once).
+///
+/// Win32_VideoController.AdapterRAM is a 32-bit field and caps at 4 GB, so it is not used.
+fn vram_total_mb() -> u64 {
+    static VRAM_TOTAL_MB: OnceLock<u64> = OnceLock::new();
+    *VRAM_TOTAL_MB.get_or_init(|| {
+        let script = "Get-ItemProperty 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Class\\{4d36e968-e325-11ce-bfc1-08002be10318}\\0*' -Name 'HardwareInformation.qwMemorySize' -ErrorAction SilentlyContinue | ForEach-Object { $_.'HardwareInformation.qwMemorySize' }";
+        std::process::Command::new("powershell")
+            .args(["-NoProfile", "-Command", script])
+            .output()
+            .map(|o| {
+                String::from_utf8_lossy(&o.stdout)
+                    .lines()
+                    .filter_map(|l| l.trim().parse::<u64>().ok())
+                    .max()
+                    .unwrap_or(0)
+                    / 1_048_576
+            })
+            .unwrap_or(0)
+    })
+}
+
+/// Threads across all processes, summed from a Toolhelp process snapshot
+fn system_thread_count() -> usize {
+    #[cfg(windows)]
+    {
+        use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+        use winapi::um::tlhelp32::{
+            CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+            TH32CS_SNAPPROCESS,
+        };
+
+        unsafe {
+            let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+            if snapshot == INVALID_HANDLE_VALUE {
+                return 0;
+            }
+            let mut entry: PROCESSENTRY32W = std::mem::zeroed();
+            entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>() as u32;
+            let mut total = 0usize;
+            if Process32FirstW(snapshot, &mut entry) != 0 {
+                loop {
+                    total += entry.cntThreads as usize;
+                    if Process32NextW(snapshot, &mut entry) == 0 {
+                        break;
+                    }
+                }
+            }
+            CloseHandle(snapshot);
+            return total;
+        }
+    }
+
+    #[cfg(not(windows))]
+    0
+}
+
+/// Current readings of every temperature sensor sysinfo exposes, falling back to WMI
+/// when sysinfo finds none (the usual case on desktop Windows)
+pub fn read_temperatures(components: &Components) -> Vec<TempReading> {
+    let readings: Vec<TempReading> = components
+        .iter()
+        .map(|c| TempReading {
+            label: c.label().to_string(),
+            temp_c: c.temperature().unwrap_or(0.0),
+            critical: c.critical(),
+            source: "sysinfo".into(),
+        })
+        .collect();
+    if readings.is_empty() {
+        cached_wmi_temperatures()
+    } else {
+        readings
     }
 }
 
+/// `read_wmi_temperatures`, re-queried at most every `WMI_TEMPERATURE_MAX_AGE_SECS`.
+/// The lock is held across the query so concurrent polls don't each spawn one.
+fn cached_wmi_temperatures() -> Vec<TempReading> {
+    let mut cache = WMI_TEMPERATURES.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((taken, readings)) = cache.as_ref() {
+        if taken.elapsed().as_secs() < WMI_TEMPERATURE_MAX_AGE_SECS {
+            return readings.clone();
+        }
+    }
+    let readings = read_wmi_temperatures();
+    *cache = Some((std::time::Instant::now(), readings.clone()));
+    readings
+}
+
+/// CPU/GPU/motherboard sensors published by a running LibreHardwareMonitor, else the
+/// ACPI thermal zones (coarser, and readable only as Administrator)
+fn read_wmi_temperatures() -> Vec<TempReading> {
+    // LHM's Parent is the hardware id (/amdcpu/0, /gpu-nvidia/0, /lpc/nct6798d); ACPI reports tenths of a kelvin
+    let script = r#"$lhm = Get-CimInstance -Namespace root/LibreHardwareMonitor -ClassName Sensor -Filter "SensorType='Temperature'" -ErrorAction SilentlyContinue
+if ($lhm) { $lhm | ForEach-Object { "librehardwaremonitor|$($_.Parent.Split('/')[1]) $($_.Name)|$($_.Value)|" } }
+else { Get-CimInstance -Namespace root/wmi -ClassName MSAcpi_ThermalZoneTemperature -ErrorAction SilentlyContinue | ForEach-Object { "acpi|$($_.InstanceName)|$($_.CurrentTemperature / 10 - 273.15)|$($_.CriticalTripPoint / 10 - 273.15)" } }"#;
+    let output = match std::process::Command::new("powershell")
+        .args(["-NoProfile", "-Command", script])
+        .output()
+    {
+        Ok(o) => String::from_utf8_lossy(&o.stdout).to_string(),
+        Err(_) => return Vec::new(),
+    };
+
+    output
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.trim().split('|').collect();
+            if parts.len() != 4 {
+                return None;
+            }
+            let temp_c = parts[2].trim().parse::<f32>().ok()?;
+            // ACPI zones report 0 K (-273 °C) when the firmware does not fill them in
+            if !(-50.0..=150.0).contains(&temp_c) {
+                return None;
+            }
+            Some(TempReading {
+                label: parts[1].trim().to_string(),
+                temp_c,
+                critical: parts[3].trim().parse::<f32>().ok().filter(|c| *c > 0.0),
+                source: parts[0].to_string(),
+            })
+        })
+        .collect()
+}
+
 pub fn get_health_score() -> HealthScore {
     let mut sys = System::new_all();
     sys.refresh_all();
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    sys.refresh_cpu_all();
 
+    let disks = Disks::new_with_refreshed_list();
+    let mut score = compute_health_score(&sys, &disks);
+    score.alerts = record_health_sample(&score);
+    score
+}
+
+/// Score an already-refreshed `System` (CPU must have been sampled twice)
+fn compute_health_score(sys: &System, disks: &Disks) -> HealthScore {
     let mut details: Vec<HealthDetail> = Vec::new();
 
     // Memory score (100 = low usage, 0 = full)
@@ -180,11 +505,11 @@ pub fn get_health_score() -> HealthScore {
         } else {
             "Memory usage is healthy".into()
         },
+        value: mem_pct,
+        unit: "percent".into(),
     });
 
     // CPU score
-    std::thread::sleep(std::time::Duration::from_millis(200));
-    sys.refresh_cpu_all();
     let cpu_avg: f32 =
         sys.cpus().iter().map(|c| c.cpu_usage()).sum::<f32>() / sys.cpus().len().max(1) as f32;
     let cpu_score = if cpu_avg < 30.0 {
@@ -207,10 +532,11 @@ pub fn get_health_score() -> HealthScore {
         } else {
             "CPU load is normal".into()
         },
+        value: cpu_avg as f64,
+        unit: "percent".into(),
     });
 
     // Disk score
-    let disks = Disks::new_with_refreshed_list();
     let mut worst_disk_pct = 0.0f64;
     for disk in disks.iter() {
         let total = disk.total_space() as f64;
@@ -242,28 +568,54 @@ pub fn get_health_score() -> HealthScore {
         } else {
             "Disk space is adequate".into()
         },
+        value: worst_disk_pct,
+        unit: "percent".into(),
     });
 
-    // Startup score - estimate based on process count
-    let proc_count = sys.processes().len();
-    let startup_score = if proc_count < 100 {
+    // Startup score — enabled Run-key and Startup-folder entries: 100 below 8, sliding to
+    // 40 at 25 or more
+    let startup_count = crate::startup::count_enabled_startup_entries();
+    let startup_score = if startup_count < 8 {
         100
-    } else if proc_count < 200 {
-        80
-    } else if proc_count < 300 {
-        60
-    } else {
+    } else if startup_count >= 25 {
         40
+    } else {
+        100 - (startup_count as u32 - 8) * 60 / 17
     };
     details.push(HealthDetail {
         category: "Startup".into(),
         score: startup_score,
+        label: format!("{} programs launch at startup", startup_count),
+        suggestion: if startup_count > 12 {
+            "Review startup programs to reduce bloat".into()
+        } else {
+            "Startup list is lean".into()
+        },
+        value: startup_count as f64,
+        unit: "count".into(),
+    });
+
+    // Process count — runtime load, reported on its own and not part of the overall score
+    let proc_count = sys.processes().len();
+    details.push(HealthDetail {
+        category: "Processes".into(),
+        score: if proc_count < 100 {
+            100
+        } else if proc_count < 200 {
+            80
+        } else if proc_count < 300 {
+            60
+        } else {
+            40
+        },
         label: format!("{} running processes", proc_count),
         suggestion: if proc_count > 200 {
-            "Review startup programs to reduce bloat".into()
+            "Close unused apps or trim background processes".into()
         } else {
             "Process count is normal".into()
         },
+        value: proc_count as f64,
+        unit: "count".into(),
     });
 
     // Uptime score
@@ -287,6 +639,8 @@ pub fn get_health_score() -> HealthScore {
         } else {
             "System uptime is fine".into()
         },
+        value: uptime_days as f64,
+        unit: "days".into(),
     });
 
     let overall = (memory_score + cpu_score + disk_score + startup_score + uptime_score) / 5;
@@ -299,9 +653,123 @@ pub fn get_health_score() -> HealthScore {
         startup_score,
         uptime_score,
         details,
+        inputs: HealthInputs {
+            mem_pct,
+            cpu_avg,
+            worst_disk_pct,
+            proc_count,
+            startup_count,
+            uptime_days,
+        },
+        alerts: Vec::new(),
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// Health History — every get_health_score result, for trends and regression alerts
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A subscore falling by more than this since the previous sample raises an alert
+const HEALTH_ALERT_DROP: u32 = 20;
+const HEALTH_HISTORY_MAX: usize = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HealthSample {
+    timestamp: String, // local yyyy-MM-dd HH:mm
+    overall: u32,
+    subscores: Vec<(String, u32)>, // (category, score) as in HealthScore.details
+}
+
+fn health_history_path() -> std::path::PathBuf {
+    let base = std::env::var("LOCALAPPDATA").unwrap_or_else(|_| {
+        let profile = std::env::var("USERPROFILE").unwrap_or_else(|_| ".".into());
+        format!("{}\\AppData\\Local", profile)
+    });
+    std::path::PathBuf::from(base)
+        .join("VegaOptimizer")
+        .join("health_history.json")
+}
+
+fn load_health_history() -> Vec<HealthSample> {
+    std::fs::read_to_string(health_history_path())
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+/// Current local time as yyyy-MM-dd HH:mm (UTC off Windows)
+fn local_timestamp() -> String {
+    #[cfg(windows)]
+    {
+        let mut st: winapi::um::minwinbase::SYSTEMTIME = unsafe { std::mem::zeroed() };
+        unsafe { winapi::um::sysinfoapi::GetLocalTime(&mut st) };
+        return format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            st.wYear, st.wMonth, st.wDay, st.wHour, st.wMinute
+        );
+    }
+    #[cfg(not(windows))]
+    {
+        let secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        format!("@{}", secs)
+    }
+}
+
+/// Append `score` to the history and return alerts for subscores that dropped sharply
+fn record_health_sample(score: &HealthScore) -> Vec<HealthAlert> {
+    let sample = HealthSample {
+        timestamp: local_timestamp(),
+        overall: score.overall,
+        subscores: score
+            .details
+            .iter()
+            .map(|d| (d.category.clone(), d.score))
+            .collect(),
+    };
+
+    let mut history = load_health_history();
+    let alerts = match history.last() {
+        Some(prev) => score
+            .details
+            .iter()
+            .filter_map(|d| {
+                let (_, previous) = prev.subscores.iter().find(|(c, _)| *c == d.category)?;
+                (previous.saturating_sub(d.score) > HEALTH_ALERT_DROP).then(|| HealthAlert {
+                    category: d.category.clone(),
+                    previous: *previous,
+                    current: d.score,
+                    suggestion: d.suggestion.clone(),
+                })
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    history.push(sample);
+    if history.len() > HEALTH_HISTORY_MAX {
+        history.drain(..history.len() - HEALTH_HISTORY_MAX);
+    }
+    let path = health_history_path();
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string(&history) {
+        let _ = std::fs::write(&path, json);
+    }
+    alerts
+}
+
+/// (timestamp, overall score) for every recorded health check, oldest first
+pub fn get_health_history() -> Vec<(String, u32)> {
+    load_health_history()
+        .into_iter()
+        .map(|s| (s.timestamp, s.overall))
+        .collect()
+}
+
 pub fn get_hardware_info() -> HardwareInfo {
     let mut sys = System::new_all();
     sys.refresh_all();
@@ -314,28 +782,7 @@ pub fn get_hardware_info() -> HardwareInfo {
     let cpu_freq = sys.cpus().first().map(|c| c.frequency()).unwrap_or(0);
 
     let disks = Disks::new_with_refreshed_list();
-    let disk_list: Vec<DiskInfo> = disks
-        .iter()
-        .map(|d| {
-            let total = d.total_space() as f64 / 1_073_741_824.0;
-            let avail = d.available_space() as f64 / 1_073_741_824.0;
-            let used = total - avail;
-            DiskInfo {
-                name: d.name().to_string_lossy().to_string(),
-                mount_point: d.mount_point().to_string_lossy().to_string(),
-                fs_type: d.file_system().to_string_lossy().to_string(),
-                total_gb: total,
-                used_gb: used,
-                free_gb: avail,
-                usage_percent: if total > 0.0 {
-                    (used / total) * 100.0
-                } else {
-                    0.0
-                },
-                is_removable: d.is_removable(),
-            }
-        })
-        .collect();
+    let disk_list = disk_infos(&disks);
 
     // GPU detection via powershell
     let gpus = match std::process::Command::new("powershell")
@@ -401,3 +848,295 @@ pub fn get_hardware_info() -> HardwareInfo {
         network_adapters: adapters,
     }
 }
+
+fn disk_infos(disks: &Disks) -> Vec<DiskInfo> {
+    disks
+        .iter()
+        .map(|d| {
+            let total = d.total_space() as f64 / 1_073_741_824.0;
+            let avail = d.available_space() as f64 / 1_073_741_824.0;
+            let used = total - avail;
+            DiskInfo {
+                name: d.name().to_string_lossy().to_string(),
+                mount_point: d.mount_point().to_string_lossy().to_string(),
+                fs_type: d.file_system().to_string_lossy().to_string(),
+                total_gb: total,
+                used_gb: used,
+                free_gb: avail,
+                usage_percent: if total > 0.0 {
+                    (used / total) * 100.0
+                } else {
+                    0.0
+                },
+                is_removable: d.is_removable(),
+            }
+        })
+        .collect()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Idle detection — lets background maintenance wait until the user is away
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Seconds since the last keyboard or mouse input in this session
+pub fn get_idle_seconds() -> u64 {
+    #[cfg(windows)]
+    {
+        use winapi::um::winuser::{GetLastInputInfo, LASTINPUTINFO};
+        let mut info = LASTINPUTINFO {
+            cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+            dwTime: 0,
+        };
+        if unsafe { GetLastInputInfo(&mut info) } == 0 {
+            return 0;
+        }
+        let now = unsafe { winapi::um::sysinfoapi::GetTickCount() };
+        return now.wrapping_sub(info.dwTime) as u64 / 1000;
+    }
+    #[cfg(not(windows))]
+    {
+        0
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Timer Resolution — shows whether an app is holding the system timer high
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimerResolution {
+    pub current_ms: f64,
+    pub finest_ms: f64,  // highest resolution the hardware allows (usually 0.5ms)
+    pub default_ms: f64, // coarsest resolution, the Windows default (usually 15.6ms)
+    pub raised: bool,    // some process has requested a finer timer than the default
+}
+
+#[cfg(windows)]
+#[link(name = "ntdll")]
+extern "system" {
+    /// Values are in 100ns units; "maximum" is the coarsest interval, "minimum" the finest
+    fn NtQueryTimerResolution(
+        maximum_resolution: *mut u32,
+        minimum_resolution: *mut u32,
+        current_resolution: *mut u32,
+    ) -> i32; // NTSTATUS
+}
+
+/// Current system timer resolution, or None off Windows / if the query fails
+pub fn get_timer_resolution() -> Option<TimerResolution> {
+    #[cfg(windows)]
+    {
+        let (mut coarsest, mut finest, mut current) = (0u32, 0u32, 0u32);
+        let status = unsafe { NtQueryTimerResolution(&mut coarsest, &mut finest, &mut current) };
+        if status < 0 {
+            return None;
+        }
+        return Some(TimerResolution {
+            current_ms: current as f64 / 10_000.0,
+            finest_ms: finest as f64 / 10_000.0,
+            default_ms: coarsest as f64 / 10_000.0,
+            raised: current < coarsest,
+        });
+    }
+
+    #[cfg(not(windows))]
+    None
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Snapshots — before/after comparison across a session
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemSnapshot {
+    pub id: u64,
+    pub timestamp: u64, // unix seconds
+    pub memory_used_mb: u64,
+    pub process_count: usize,
+    pub standby_mb: u64,
+    pub disk_free_gb: f64,
+    pub startup_count: usize, // enabled startup entries
+}
+
+/// Deltas from `before` to `after`; positive numbers mean something was freed or removed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    pub before: SystemSnapshot,
+    pub after: SystemSnapshot,
+    pub elapsed_secs: u64,
+    pub memory_freed_mb: i64,
+    pub processes_removed: i64,
+    pub standby_freed_mb: i64,
+    pub disk_freed_gb: f64,
+    pub startups_removed: i64,
+    pub summary: String,
+}
+
+/// Snapshots taken this session, oldest first
+static SNAPSHOTS: Mutex<Vec<SystemSnapshot>> = Mutex::new(Vec::new());
+
+const MAX_SNAPSHOTS: usize = 50;
+
+/// Record the current system state and keep it for later comparison
+pub fn capture_snapshot() -> SystemSnapshot {
+    let mut sys = System::new();
+    sys.refresh_memory();
+    sys.refresh_processes(ProcessesToUpdate::All, true);
+    let disks = Disks::new_with_refreshed_list();
+
+    // Collect everything first; the lock is only taken to assign the id and store it
+    let mut snapshot = SystemSnapshot {
+        id: 0,
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        memory_used_mb: sys.used_memory() / 1_048_576,
+        process_count: sys.processes().len(),
+        standby_mb: crate::optimizer::measure_standby_list() / 1_048_576,
+        disk_free_gb: disks.iter().map(|d| d.available_space()).sum::<u64>() as f64
+            / 1_073_741_824.0,
+        startup_count: crate::startup::count_enabled_startup_entries(),
+    };
+    let mut snapshots = SNAPSHOTS.lock().unwrap_or_else(|e| e.into_inner());
+    snapshot.id = snapshots.last().map_or(1, |s| s.id + 1);
+    snapshots.push(snapshot.clone());
+    if snapshots.len() > MAX_SNAPSHOTS {
+        snapshots.remove(0);
+    }
+    snapshot
+}
+
+pub fn compare_snapshots(a: u64, b: u64) -> Result<SnapshotDiff, String> {
+    let snapshots = SNAPSHOTS.lock().unwrap_or_else(|e| e.into_inner());
+    let find = |id: u64| {
+        snapshots
+            .iter()
+            .find(|s| s.id == id)
+            .cloned()
+            .ok_or_else(|| format!("No snapshot with id {}", id))
+    };
+    let before = find(a)?;
+    let after = find(b)?;
+
+    let memory_freed_mb = before.memory_used_mb as i64 - after.memory_used_mb as i64;
+    let processes_removed = before.process_count as i64 - after.process_count as i64;
+    let standby_freed_mb = before.standby_mb as i64 - after.standby_mb as i64;
+    let disk_freed_gb = after.disk_free_gb - before.disk_free_gb;
+    let startups_removed = before.startup_count as i64 - after.startup_count as i64;
+
+    let mut parts = Vec::new();
+    if memory_freed_mb > 0 {
+        parts.push(format!("freed {} MB of RAM", memory_freed_mb));
+    }
+    if standby_freed_mb > 0 {
+        parts.push(format!("released {} MB of standby cache", standby_freed_mb));
+    }
+    if disk_freed_gb >= 0.01 {
+        parts.push(format!("recovered {:.2} GB of disk", disk_freed_gb));
+    }
+    if processes_removed > 0 {
+        parts.push(format!("{} fewer processes", processes_removed));
+    }
+    if startups_removed > 0 {
+        parts.push(format!("removed {} startup items", startups_removed));
+    }
+    let summary = if parts.is_empty() {
+        "No measurable change".to_string()
+    } else {
+        parts.join(", ")
+    };
+
+    Ok(SnapshotDiff {
+        elapsed_secs: after.timestamp.saturating_sub(before.timestamp),
+        before,
+        after,
+        memory_freed_mb,
+        processes_removed,
+        standby_freed_mb,
+        disk_freed_gb,
+        startups_removed,
+        summary,
+    })
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Dashboard Summary — headline figures from a single System refresh
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Everything the dashboard home needs on load, gathered in one backend call
+pub fn get_dashboard_summary() -> DashboardSummary {
+    let mut sys = System::new_all();
+    sys.refresh_all();
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    sys.refresh_cpu_all();
+
+    let disks = Disks::new_with_refreshed_list();
+    let health = compute_health_score(&sys, &disks);
+
+    let cpu_usage =
+        sys.cpus().iter().map(|c| c.cpu_usage()).sum::<f32>() / sys.cpus().len().max(1) as f32;
+    let total_mem = sys.total_memory() / 1_048_576;
+    let used_mem = sys.used_memory() / 1_048_576;
+    let (battery_present, battery_percent, on_ac_power) = quick_power_status();
+
+    DashboardSummary {
+        os_name: System::name().unwrap_or("Windows".into()),
+        os_version: System::os_version().unwrap_or("Unknown".into()),
+        hostname: System::host_name().unwrap_or("Unknown".into()),
+        cpu_name: sys
+            .cpus()
+            .first()
+            .map(|c| c.brand().to_string())
+            .unwrap_or("Unknown".into()),
+        cpu_cores: sys.cpus().len(),
+        cpu_usage,
+        memory_used_mb: used_mem,
+        memory_total_mb: total_mem,
+        memory_percent: if total_mem > 0 {
+            (used_mem as f64 / total_mem as f64) * 100.0
+        } else {
+            0.0
+        },
+        swap_used_mb: sys.used_swap() / 1_048_576,
+        swap_total_mb: sys.total_swap() / 1_048_576,
+        process_count: sys.processes().len(),
+        uptime_seconds: System::uptime(),
+        health,
+        disks: disk_infos(&disks),
+        battery_present,
+        battery_percent,
+        on_ac_power,
+    }
+}
+
+/// Battery presence, charge and AC state via GetSystemPowerStatus (no WMI round-trip)
+fn quick_power_status() -> (bool, Option<u32>, Option<bool>) {
+    #[cfg(windows)]
+    {
+        use winapi::um::winbase::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+        unsafe {
+            let mut status: SYSTEM_POWER_STATUS = std::mem::zeroed();
+            if GetSystemPowerStatus(&mut status) == 0 {
+                return (false, None, None);
+            }
+            // BatteryFlag 128 = no system battery, 255 = unknown
+            let present = status.BatteryFlag != 128 && status.BatteryFlag != 255;
+            let percent = if present && status.BatteryLifePercent <= 100 {
+                Some(status.BatteryLifePercent as u32)
+            } else {
+                None
+            };
+            let on_ac = match status.ACLineStatus {
+                0 => Some(false),
+                1 => Some(true),
+                _ => None,
+            };
+            return (present, percent, on_ac);
+        }
+    }
+
+    #[cfg(not(windows))]
+    (false, None, None)
+}
@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::process::Command;
+use std::sync::Mutex;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkConnection {
@@ -12,6 +13,41 @@ pub struct NetworkConnection {
     pub state: String,
     pub pid: u32,
     pub process_name: String,
+    pub remote_host: Option<String>, // reverse-DNS name, best effort
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeedTestResult {
+    pub download_mbps: f64,
+    pub upload_mbps: f64,
+    pub ping_ms: f64, // TCP connect time to the test server
+    pub server: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PingResult {
+    pub avg_ms: f64,
+    pub min_ms: f64,
+    pub max_ms: f64,
+    pub packet_loss_pct: f64,
+    pub replies: Vec<f64>, // round-trip time of each answered echo
+}
+
+/// Which connections to report — loopback traffic is hidden by default
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConnectionFilter {
+    pub include_loopback: bool,
+    pub include_listening: bool,
+}
+
+impl Default for ConnectionFilter {
+    fn default() -> Self {
+        Self {
+            include_loopback: false,
+            include_listening: true,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,8 +55,10 @@ pub struct ProcessBandwidth {
     pub pid: u32,
     pub name: String,
     pub connections: usize,
-    pub bytes_sent: u64,
+    pub bytes_sent: u64, // during the sample window
     pub bytes_recv: u64,
+    pub send_bps: f64,
+    pub recv_bps: f64,
     pub status: String,
 }
 
@@ -33,74 +71,351 @@ pub struct NetworkOverview {
     pub processes_with_network: usize,
     pub top_talkers: Vec<ProcessBandwidth>,
     pub connections: Vec<NetworkConnection>,
+    pub sample_ms: u64,
+    pub throughput_measured: bool, // false without admin rights — byte fields are then 0
 }
 
-/// Get all network connections with process mapping
-pub fn get_network_connections() -> NetworkOverview {
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkDelta {
+    pub added: Vec<NetworkConnection>,
+    pub removed: Vec<NetworkConnection>,
+    pub unchanged: usize,
+    pub total_connections: usize,
+    pub full_refresh: bool, // no previous snapshot — `added` holds every connection
+}
+
+/// PID → process name, kept across calls; only unseen PIDs trigger a process refresh
+static PID_NAMES: Mutex<Option<HashMap<u32, String>>> = Mutex::new(None);
+
+/// Connection set from the previous overview/delta call
+static LAST_CONNECTIONS: Mutex<Option<Vec<NetworkConnection>>> = Mutex::new(None);
+
+/// Remote IP → reverse-DNS name; failed lookups are cached as None so they are not retried
+static REMOTE_HOSTS: Mutex<Option<HashMap<String, Option<String>>>> = Mutex::new(None);
+
+/// New addresses resolved per call — each miss can cost a DNS timeout
+const MAX_LOOKUPS_PER_CALL: usize = 40;
+
+/// TCP connections and UDP endpoints from a single PowerShell spawn
+fn enumerate_connections() -> Vec<NetworkConnection> {
     let mut connections = Vec::new();
-    let mut proc_conn_count: HashMap<u32, usize> = HashMap::new();
-    let mut proc_names: HashMap<u32, String> = HashMap::new();
 
-    // Get TCP connections
     if let Ok(output) = Command::new("powershell")
-        .args(["-Command", r#"Get-NetTCPConnection | Select-Object LocalAddress,LocalPort,RemoteAddress,RemotePort,State,OwningProcess | ForEach-Object { "$($_.LocalAddress):$($_.LocalPort)|$($_.RemoteAddress):$($_.RemotePort)|$($_.State)|$($_.OwningProcess)" }"#])
+        .args(["-Command", r#"
+            Get-NetTCPConnection | ForEach-Object { "TCP|$($_.LocalAddress):$($_.LocalPort)|$($_.RemoteAddress):$($_.RemotePort)|$($_.State)|$($_.OwningProcess)" }
+            Get-NetUDPEndpoint | ForEach-Object { "UDP|$($_.LocalAddress):$($_.LocalPort)|*:*|Active|$($_.OwningProcess)" }
+        "#])
         .output()
     {
         let stdout = String::from_utf8_lossy(&output.stdout);
         for line in stdout.lines() {
             let parts: Vec<&str> = line.split('|').collect();
-            if parts.len() >= 4 {
-                let pid = parts[3].trim().parse::<u32>().unwrap_or(0);
-                let state = parts[2].trim().to_string();
+            if parts.len() >= 5 {
                 connections.push(NetworkConnection {
-                    protocol: "TCP".into(),
-                    local_addr: parts[0].trim().to_string(),
-                    remote_addr: parts[1].trim().to_string(),
-                    state,
-                    pid,
+                    protocol: parts[0].trim().to_string(),
+                    local_addr: parts[1].trim().to_string(),
+                    remote_addr: parts[2].trim().to_string(),
+                    state: parts[3].trim().to_string(),
+                    pid: parts[4].trim().parse::<u32>().unwrap_or(0),
                     process_name: String::new(),
+                    remote_host: None,
                 });
-                *proc_conn_count.entry(pid).or_insert(0) += 1;
             }
         }
     }
 
-    // Get UDP endpoints
+    connections
+}
+
+/// Fill in process names from the cached PID map, refreshing only for new PIDs
+fn resolve_process_names(connections: &mut [NetworkConnection]) -> HashMap<u32, String> {
+    let mut guard = PID_NAMES.lock().unwrap_or_else(|e| e.into_inner());
+    let names = guard.get_or_insert_with(HashMap::new);
+
+    let mut missing: Vec<sysinfo::Pid> = connections
+        .iter()
+        .filter(|c| c.pid != 0 && !names.contains_key(&c.pid))
+        .map(|c| sysinfo::Pid::from_u32(c.pid))
+        .collect();
+    missing.sort();
+    missing.dedup();
+
+    if !missing.is_empty() {
+        let mut sys = sysinfo::System::new();
+        sys.refresh_processes(sysinfo::ProcessesToUpdate::Some(&missing), true);
+        for pid in &missing {
+            if let Some(proc_) = sys.process(*pid) {
+                names.insert(pid.as_u32(), proc_.name().to_string_lossy().to_string());
+            }
+        }
+    }
+
+    for conn in connections.iter_mut() {
+        conn.process_name = names
+            .get(&conn.pid)
+            .cloned()
+            .unwrap_or_else(|| "System".into());
+    }
+
+    // PIDs get reused — drop entries that no longer own a connection
+    let live: std::collections::HashSet<u32> = connections.iter().map(|c| c.pid).collect();
+    names.retain(|pid, _| live.contains(pid));
+    names.clone()
+}
+
+/// IP part of an "addr:port" string (IPv6 addresses contain colons themselves)
+fn addr_ip(addr: &str) -> Option<std::net::IpAddr> {
+    let (ip, _) = addr.rsplit_once(':')?;
+    ip.trim_matches(|c| c == '[' || c == ']').parse().ok()
+}
+
+fn is_loopback(c: &NetworkConnection) -> bool {
+    [&c.local_addr, &c.remote_addr]
+        .iter()
+        .any(|a| addr_ip(a).is_some_and(|ip| ip.is_loopback()))
+}
+
+fn apply_filter(connections: &mut Vec<NetworkConnection>, filter: ConnectionFilter) {
+    connections.retain(|c| {
+        (filter.include_loopback || !is_loopback(c))
+            && (filter.include_listening || c.state != "Listen")
+    });
+}
+
+/// Reverse-DNS lookup of a batch of IPs in one PowerShell spawn
+fn reverse_lookup(ips: &[String]) -> HashMap<String, Option<String>> {
+    let mut result: HashMap<String, Option<String>> =
+        ips.iter().map(|ip| (ip.clone(), None)).collect();
+    if ips.is_empty() {
+        return result;
+    }
+
+    // Only parsed IPs reach this point, so quoting them into the script is safe
+    let list = ips
+        .iter()
+        .map(|ip| format!("'{}'", ip))
+        .collect::<Vec<_>>()
+        .join(",");
+    let script = format!(
+        "foreach ($ip in @({})) {{ $r = Resolve-DnsName -Name $ip -Type PTR -DnsOnly -QuickTimeout -ErrorAction SilentlyContinue | Where-Object {{ $_.NameHost }} | Select-Object -First 1; if ($r) {{ \"$ip|$($r.NameHost)\" }} }}",
+        list
+    );
     if let Ok(output) = Command::new("powershell")
-        .args(["-Command", r#"Get-NetUDPEndpoint | Select-Object LocalAddress,LocalPort,OwningProcess | ForEach-Object { "$($_.LocalAddress):$($_.LocalPort)|*:*|Listen|$($_.OwningProcess)" }"#])
+        .args(["-NoProfile", "-Command", &script])
         .output()
     {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        for line in stdout.lines() {
-            let parts: Vec<&str> = line.split('|').collect();
-            if parts.len() >= 4 {
-                let pid = parts[3].trim().parse::<u32>().unwrap_or(0);
-                connections.push(NetworkConnection {
-                    protocol: "UDP".into(),
-                    local_addr: parts[0].trim().to_string(),
-                    remote_addr: parts[1].trim().to_string(),
-                    state: "Active".into(),
-                    pid,
-                    process_name: String::new(),
-                });
-                *proc_conn_count.entry(pid).or_insert(0) += 1;
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            if let Some((ip, host)) = line.trim().split_once('|') {
+                if !host.is_empty() {
+                    result.insert(ip.to_string(), Some(host.to_string()));
+                }
             }
         }
     }
+    result
+}
+
+/// Fill `remote_host` from the cache, resolving at most MAX_LOOKUPS_PER_CALL new addresses
+fn resolve_remote_hosts(connections: &mut [NetworkConnection]) {
+    let remote_ip = |c: &NetworkConnection| {
+        addr_ip(&c.remote_addr)
+            .filter(|ip| !ip.is_loopback() && !ip.is_unspecified())
+            .map(|ip| ip.to_string())
+    };
+
+    let mut guard = REMOTE_HOSTS.lock().unwrap_or_else(|e| e.into_inner());
+    let cache = guard.get_or_insert_with(HashMap::new);
 
-    // Resolve process names
-    let mut sys = sysinfo::System::new();
-    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
-    for (_pid, proc_) in sys.processes() {
-        proc_names.insert(_pid.as_u32(), proc_.name().to_string_lossy().to_string());
+    let mut missing: Vec<String> = connections
+        .iter()
+        .filter_map(remote_ip)
+        .filter(|ip| !cache.contains_key(ip))
+        .collect();
+    missing.sort();
+    missing.dedup();
+    missing.truncate(MAX_LOOKUPS_PER_CALL);
+    cache.extend(reverse_lookup(&missing));
+
+    for conn in connections.iter_mut() {
+        conn.remote_host = remote_ip(conn).and_then(|ip| cache.get(&ip).cloned().flatten());
     }
+}
 
-    // Fill in process names
-    for conn in &mut connections {
-        conn.process_name = proc_names
-            .get(&conn.pid)
-            .cloned()
-            .unwrap_or_else(|| "System".into());
+/// Identity of a connection for delta comparison (state changes count as remove + add)
+fn connection_key(c: &NetworkConnection) -> String {
+    format!(
+        "{}|{}|{}|{}|{}",
+        c.protocol, c.local_addr, c.remote_addr, c.state, c.pid
+    )
+}
+
+/// Per-PID TCP (bytes_sent, bytes_recv) over `sample_ms`, from extended TCP statistics.
+///
+/// Collection is switched on per established IPv4 connection with
+/// `SetPerTcpConnectionEStats` (administrator only), the data counters are read
+/// before and after the window, then collection is switched back off. UDP has no
+/// per-socket counters, so only TCP traffic is counted. None if nothing could be enabled.
+#[cfg(windows)]
+fn sample_tcp_bytes(sample_ms: u64) -> Option<HashMap<u32, (u64, u64)>> {
+    use winapi::shared::iprtrmib::TCP_TABLE_OWNER_PID_CONNECTIONS;
+    use winapi::shared::minwindef::{DWORD, PUCHAR, ULONG};
+    use winapi::shared::tcpestats::{
+        TCP_ESTATS_DATA_ROD_v0, TCP_ESTATS_DATA_RW_v0, TcpConnectionEstatsData,
+    };
+    use winapi::shared::tcpmib::{
+        MIB_TCPROW, MIB_TCPROW_OWNER_PID, MIB_TCPTABLE_OWNER_PID, MIB_TCP_STATE_ESTAB,
+    };
+    use winapi::shared::ws2def::AF_INET;
+    use winapi::um::iphlpapi::{
+        GetExtendedTcpTable, GetPerTcpConnectionEStats, SetPerTcpConnectionEStats,
+    };
+
+    type Key = (DWORD, DWORD, DWORD, DWORD);
+
+    fn established_rows() -> Vec<MIB_TCPROW_OWNER_PID> {
+        let mut size: DWORD = 0;
+        unsafe {
+            GetExtendedTcpTable(
+                std::ptr::null_mut(),
+                &mut size,
+                0,
+                AF_INET as ULONG,
+                TCP_TABLE_OWNER_PID_CONNECTIONS,
+                0,
+            )
+        };
+        // u32 buffer keeps the table DWORD-aligned; slack covers connections opened meanwhile
+        let mut buf = vec![0u32; size as usize / 4 + 256];
+        size = (buf.len() * 4) as DWORD;
+        let ret = unsafe {
+            GetExtendedTcpTable(
+                buf.as_mut_ptr() as *mut _,
+                &mut size,
+                0,
+                AF_INET as ULONG,
+                TCP_TABLE_OWNER_PID_CONNECTIONS,
+                0,
+            )
+        };
+        if ret != 0 {
+            return Vec::new();
+        }
+        unsafe {
+            let table = buf.as_ptr() as *const MIB_TCPTABLE_OWNER_PID;
+            std::slice::from_raw_parts((*table).table.as_ptr(), (*table).dwNumEntries as usize)
+                .iter()
+                .filter(|r| r.dwState == MIB_TCP_STATE_ESTAB)
+                .copied()
+                .collect()
+        }
+    }
+
+    fn tcp_row(r: &MIB_TCPROW_OWNER_PID) -> MIB_TCPROW {
+        MIB_TCPROW {
+            State: r.dwState,
+            dwLocalAddr: r.dwLocalAddr,
+            dwLocalPort: r.dwLocalPort,
+            dwRemoteAddr: r.dwRemoteAddr,
+            dwRemotePort: r.dwRemotePort,
+        }
+    }
+
+    fn set_collection(r: &MIB_TCPROW_OWNER_PID, enable: bool) -> bool {
+        let mut row = tcp_row(r);
+        let mut rw = TCP_ESTATS_DATA_RW_v0 {
+            EnableCollection: enable as u8,
+        };
+        unsafe {
+            SetPerTcpConnectionEStats(
+                &mut row,
+                TcpConnectionEstatsData,
+                &mut rw as *mut _ as PUCHAR,
+                0,
+                std::mem::size_of::<TCP_ESTATS_DATA_RW_v0>() as ULONG,
+                0,
+            ) == 0
+        }
+    }
+
+    fn read_bytes(r: &MIB_TCPROW_OWNER_PID) -> Option<(u64, u64)> {
+        let mut row = tcp_row(r);
+        let mut rod: TCP_ESTATS_DATA_ROD_v0 = unsafe { std::mem::zeroed() };
+        let ret = unsafe {
+            GetPerTcpConnectionEStats(
+                &mut row,
+                TcpConnectionEstatsData,
+                std::ptr::null_mut(),
+                0,
+                0,
+                std::ptr::null_mut(),
+                0,
+                0,
+                &mut rod as *mut _ as PUCHAR,
+                0,
+                std::mem::size_of::<TCP_ESTATS_DATA_ROD_v0>() as ULONG,
+            )
+        };
+        (ret == 0).then_some((rod.DataBytesOut, rod.DataBytesIn))
+    }
+
+    let key = |r: &MIB_TCPROW_OWNER_PID| -> Key {
+        (r.dwLocalAddr, r.dwLocalPort, r.dwRemoteAddr, r.dwRemotePort)
+    };
+
+    let rows = established_rows();
+    let enabled: Vec<MIB_TCPROW_OWNER_PID> = rows
+        .into_iter()
+        .filter(|r| set_collection(r, true))
+        .collect();
+    if enabled.is_empty() {
+        return None;
+    }
+
+    let before: HashMap<Key, (u64, u64)> = enabled
+        .iter()
+        .filter_map(|r| read_bytes(r).map(|b| (key(r), b)))
+        .collect();
+    std::thread::sleep(std::time::Duration::from_millis(sample_ms));
+
+    let mut per_pid: HashMap<u32, (u64, u64)> = HashMap::new();
+    for r in &enabled {
+        // Connections closed during the window simply drop out
+        if let (Some((out0, in0)), Some((out1, in1))) = (before.get(&key(r)), read_bytes(r)) {
+            let entry = per_pid.entry(r.dwOwningPid).or_insert((0, 0));
+            entry.0 += out1.saturating_sub(*out0);
+            entry.1 += in1.saturating_sub(*in0);
+        }
+        set_collection(r, false);
+    }
+    Some(per_pid)
+}
+
+#[cfg(not(windows))]
+fn sample_tcp_bytes(_sample_ms: u64) -> Option<HashMap<u32, (u64, u64)>> {
+    None
+}
+
+/// Get all network connections with process mapping.
+///
+/// Top talkers carry real TCP byte rates measured over `sample_ms` (clamped to
+/// 100 ms – 10 s) when running as administrator. Connections excluded by `filter`
+/// are left out of every count; remote addresses get a cached reverse-DNS name.
+pub fn get_network_connections(sample_ms: u64, filter: ConnectionFilter) -> NetworkOverview {
+    let sample_ms = sample_ms.clamp(100, 10_000);
+    let mut connections = enumerate_connections();
+    apply_filter(&mut connections, filter);
+    let proc_names = resolve_process_names(&mut connections);
+    resolve_remote_hosts(&mut connections);
+    let throughput = sample_tcp_bytes(sample_ms);
+    let secs = sample_ms as f64 / 1000.0;
+
+    let mut proc_conn_count: HashMap<u32, usize> = HashMap::new();
+    for conn in &connections {
+        *proc_conn_count.entry(conn.pid).or_insert(0) += 1;
+    }
+
+    if let Ok(mut last) = LAST_CONNECTIONS.lock() {
+        *last = Some(connections.clone());
     }
 
     // Build per-process bandwidth via perf counters
@@ -112,12 +427,18 @@ pub fn get_network_connections() -> NetworkOverview {
                 .get(pid)
                 .cloned()
                 .unwrap_or_else(|| "Unknown".into());
+            let (bytes_sent, bytes_recv) = throughput
+                .as_ref()
+                .and_then(|t| t.get(pid).copied())
+                .unwrap_or((0, 0));
             ProcessBandwidth {
                 pid: *pid,
                 name,
                 connections: *count,
-                bytes_sent: 0,
-                bytes_recv: 0,
+                bytes_sent,
+                bytes_recv,
+                send_bps: bytes_sent as f64 / secs,
+                recv_bps: bytes_recv as f64 / secs,
                 status: if *count > 10 {
                     "Heavy".into()
                 } else if *count > 3 {
@@ -129,7 +450,11 @@ pub fn get_network_connections() -> NetworkOverview {
         })
         .collect();
 
-    top_talkers.sort_by(|a, b| b.connections.cmp(&a.connections));
+    top_talkers.sort_by(|a, b| {
+        (b.bytes_sent + b.bytes_recv)
+            .cmp(&(a.bytes_sent + a.bytes_recv))
+            .then(b.connections.cmp(&a.connections))
+    });
 
     let tcp_established = connections
         .iter()
@@ -146,25 +471,212 @@ pub fn get_network_connections() -> NetworkOverview {
         processes_with_network: top_talkers.len(),
         top_talkers: top_talkers.into_iter().take(30).collect(),
         connections: connections.into_iter().take(200).collect(),
+        sample_ms,
+        throughput_measured: throughput.is_some(),
     }
 }
 
-/// Quick ping test
-pub fn ping_test(host: &str) -> f64 {
-    if let Ok(output) = Command::new("ping")
-        .args(["-n", "3", "-w", "2000", host])
-        .output()
-    {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        // Parse "Average = XXms"
-        for line in stdout.lines() {
-            if line.contains("Average") || line.contains("average") {
-                if let Some(ms_str) = line.split('=').last() {
-                    let cleaned = ms_str.trim().replace("ms", "").trim().to_string();
-                    return cleaned.parse().unwrap_or(999.0);
-                }
+/// Report only connections added or removed since the previous call.
+///
+/// Much cheaper than a full overview for a live view: one PowerShell spawn, cached
+/// process names, and only the changes are serialized. `reset` forgets the previous
+/// snapshot so the next call returns everything as added. Pass the same `filter`
+/// as the overview so hidden connections don't come back as additions.
+pub fn get_network_delta(reset: bool, filter: ConnectionFilter) -> NetworkDelta {
+    let mut connections = enumerate_connections();
+    apply_filter(&mut connections, filter);
+    resolve_process_names(&mut connections);
+    resolve_remote_hosts(&mut connections);
+
+    let mut last = LAST_CONNECTIONS.lock().unwrap_or_else(|e| e.into_inner());
+    let previous = if reset { None } else { last.take() };
+
+    let delta = match previous {
+        None => NetworkDelta {
+            added: connections.clone(),
+            removed: Vec::new(),
+            unchanged: 0,
+            total_connections: connections.len(),
+            full_refresh: true,
+        },
+        Some(prev) => {
+            let prev_keys: std::collections::HashSet<String> =
+                prev.iter().map(connection_key).collect();
+            let cur_keys: std::collections::HashSet<String> =
+                connections.iter().map(connection_key).collect();
+
+            let added: Vec<NetworkConnection> = connections
+                .iter()
+                .filter(|c| !prev_keys.contains(&connection_key(c)))
+                .cloned()
+                .collect();
+            let removed: Vec<NetworkConnection> = prev
+                .into_iter()
+                .filter(|c| !cur_keys.contains(&connection_key(c)))
+                .collect();
+
+            NetworkDelta {
+                unchanged: connections.len() - added.len(),
+                total_connections: connections.len(),
+                added,
+                removed,
+                full_refresh: false,
             }
         }
+    };
+
+    *last = Some(connections);
+    delta
+}
+
+const PING_COUNT: usize = 4;
+
+/// Round-trip time from a reply line such as "Reply from ...: bytes=32 time=12ms TTL=117".
+///
+/// The label is localized ("Zeit=12ms", "temps=12 ms"), so this reads the number right
+/// before "ms"; "<1ms" counts as 0.
+fn parse_reply_time(line: &str) -> Option<f64> {
+    line.match_indices("ms").find_map(|(idx, _)| {
+        let before = line[..idx].trim_end();
+        let start = before
+            .rfind(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+            .map_or(0, |i| i + 1);
+        let digits = &before[start..];
+        if digits.is_empty() {
+            return None;
+        }
+        let ms: f64 = digits.replace(',', ".").parse().ok()?;
+        Some(if before[..start].ends_with('<') {
+            0.0
+        } else {
+            ms
+        })
+    })
+}
+
+/// Loss percentage from the "Lost = 1 (25% loss)" summary line
+fn parse_loss_pct(stdout: &str) -> Option<f64> {
+    stdout.lines().filter(|l| l.contains('%')).find_map(|line| {
+        let open = line.find('(')?;
+        let pct = line[open + 1..].split('%').next()?;
+        pct.trim().parse().ok()
+    })
+}
+
+/// Ping a host PING_COUNT times and report per-reply times, min/avg/max and packet loss.
+///
+/// An unreachable host gives 100% loss and zero times.
+pub fn ping_test(host: &str) -> PingResult {
+    let mut result = PingResult {
+        avg_ms: 0.0,
+        min_ms: 0.0,
+        max_ms: 0.0,
+        packet_loss_pct: 100.0,
+        replies: Vec::new(),
+    };
+
+    let Ok(output) = Command::new("ping")
+        .args(["-n", &PING_COUNT.to_string(), "-w", "2000", host])
+        .output()
+    else {
+        return result;
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // Only real replies carry a TTL; "Destination host unreachable" lines don't
+    let reply_lines: Vec<&str> = stdout
+        .lines()
+        .filter(|l| l.contains("TTL=") || l.contains("ttl="))
+        .collect();
+    result.replies = reply_lines
+        .iter()
+        .filter_map(|l| parse_reply_time(l))
+        .collect();
+    let loss_from_replies =
+        (PING_COUNT - reply_lines.len().min(PING_COUNT)) as f64 / PING_COUNT as f64 * 100.0;
+    // A summary that disagrees with the reply lines was misread; trust the lines
+    result.packet_loss_pct = match parse_loss_pct(&stdout) {
+        Some(pct) if result.replies.len() == reply_lines.len() => pct,
+        _ => loss_from_replies,
+    };
+
+    if !result.replies.is_empty() {
+        result.min_ms = result.replies.iter().cloned().fold(f64::MAX, f64::min);
+        result.max_ms = result.replies.iter().cloned().fold(0.0, f64::max);
+        result.avg_ms = result.replies.iter().sum::<f64>() / result.replies.len() as f64;
+    }
+    result
+}
+
+const SPEED_TEST_SERVER: &str = "speed.cloudflare.com";
+const SPEED_TEST_DOWNLOAD_BYTES: u64 = 25_000_000;
+const SPEED_TEST_UPLOAD_BYTES: usize = 10_000_000;
+
+/// One curl transfer: (bytes per second, connect time in ms), None on any failure
+fn curl_transfer(extra: &[&str], url: &str) -> Option<(f64, f64)> {
+    let null = if cfg!(windows) { "NUL" } else { "/dev/null" };
+    let output = Command::new(if cfg!(windows) { "curl.exe" } else { "curl" })
+        .args(["-s", "-f", "--max-time", "30", "-o", null])
+        .args([
+            "-w",
+            "%{speed_download} %{speed_upload} %{time_namelookup} %{time_connect}",
+        ])
+        .args(extra)
+        .arg(url)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let v: Vec<f64> = stdout
+        .split_whitespace()
+        .filter_map(|x| x.replace(',', ".").parse().ok())
+        .collect();
+    if v.len() < 4 {
+        return None;
+    }
+    let speed = v[0].max(v[1]);
+    Some((speed, (v[3] - v[2]).max(0.0) * 1000.0))
+}
+
+/// Download/upload throughput against Cloudflare's speed test endpoints via curl.
+///
+/// Upload uses a zero-filled temp file. Without connectivity every figure is 0
+/// and `server` is "unavailable".
+pub fn run_speed_test() -> SpeedTestResult {
+    let unavailable = SpeedTestResult {
+        download_mbps: 0.0,
+        upload_mbps: 0.0,
+        ping_ms: 0.0,
+        server: "unavailable".into(),
+    };
+    let to_mbps = |bytes_per_sec: f64| (bytes_per_sec * 8.0 / 1_000_000.0 * 100.0).round() / 100.0;
+
+    let down_url = format!(
+        "https://{}/__down?bytes={}",
+        SPEED_TEST_SERVER, SPEED_TEST_DOWNLOAD_BYTES
+    );
+    let Some((down_bps, ping_ms)) = curl_transfer(&[], &down_url) else {
+        return unavailable;
+    };
+
+    let payload = std::env::temp_dir().join("vega_speedtest_upload.bin");
+    let upload_mbps = match std::fs::write(&payload, vec![0u8; SPEED_TEST_UPLOAD_BYTES]) {
+        Ok(()) => {
+            let data = format!("@{}", payload.display());
+            let up_url = format!("https://{}/__up", SPEED_TEST_SERVER);
+            let result = curl_transfer(&["-X", "POST", "--data-binary", &data], &up_url);
+            let _ = std::fs::remove_file(&payload);
+            result.map(|(bps, _)| to_mbps(bps)).unwrap_or(0.0)
+        }
+        Err(_) => 0.0,
+    };
+
+    SpeedTestResult {
+        download_mbps: to_mbps(down_bps),
+        upload_mbps,
+        ping_ms: (ping_ms * 10.0).round() / 10.0,
+        server: SPEED_TEST_SERVER.into(),
     }
-    999.0
 }
@@ -36,7 +36,6 @@ mod nt {
 
     /// Memory list command codes (passed as the information buffer value)
     #[repr(i32)]
-    #[allow(dead_code)]
     pub enum MemoryListCommand {
         MemoryFlushModifiedList = 2,
         MemoryPurgeStandbyList = 4,
@@ -63,6 +62,23 @@ mod nt {
             maximum_file_cache_size: usize,
             flags: u32,
         ) -> i32; // BOOL
+
+        /// winapi 0.3 declares the mask as DWORD, which truncates it to 32 cores
+        pub fn SetProcessAffinityMask(
+            process: winapi::um::winnt::HANDLE,
+            process_affinity_mask: usize, // DWORD_PTR
+        ) -> i32; // BOOL
+    }
+
+    #[link(name = "ntdll")]
+    extern "system" {
+        /// Adds or (set_resolution = 0) withdraws this process's timer request, in 100ns units.
+        /// The system runs at the finest resolution any process still requests.
+        pub fn NtSetTimerResolution(
+            desired_resolution: u32,
+            set_resolution: u8, // BOOLEAN
+            current_resolution: *mut u32,
+        ) -> i32; // NTSTATUS
     }
 
     /// Enable a named privilege on the current process token.
@@ -115,8 +131,11 @@ mod nt {
                 std::ptr::null_mut(),
                 std::ptr::null_mut(),
             );
+            // AdjustTokenPrivileges succeeds even when the token lacks the privilege
+            let not_all_assigned = winapi::um::errhandlingapi::GetLastError()
+                == winapi::shared::winerror::ERROR_NOT_ALL_ASSIGNED;
             CloseHandle(token);
-            result != 0
+            result != 0 && !not_all_assigned
         }
     }
 }
@@ -161,9 +180,25 @@ pub struct OptimizationItem {
     pub risk: String,
     pub enabled_by_default: bool,
     pub available: bool,
+    pub requires_admin: bool, // fails with access denied unless the app is elevated
     pub estimated_savings: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogExport {
+    pub app_version: String,
+    pub exported_at: u64, // seconds since UNIX epoch
+    pub items: Vec<OptimizationItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemCacheStatus {
+    pub id: String, // "font_cache", "icon_cache"
+    pub name: String,
+    pub files: Vec<String>,
+    pub size_bytes: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OptimizationResult {
     pub id: String,
@@ -184,8 +219,17 @@ pub struct OptimizationReport {
     pub results: Vec<OptimizationResult>,
     pub memory_before_mb: u64,
     pub memory_after_mb: u64,
+    pub slowest: Vec<(String, u64)>, // (id, duration_ms), longest first
+    pub slow_steps: Vec<String>,     // ids that took longer than SLOW_STEP_MS
+    pub reboot_pending: bool,        // Windows is waiting on a restart to finish applying changes
 }
 
+/// A single optimization step taking longer than this is flagged in the report
+const SLOW_STEP_MS: u64 = 10_000;
+
+/// Number of entries kept in `OptimizationReport::slowest`
+const SLOWEST_COUNT: usize = 5;
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // System Info
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -249,6 +293,67 @@ pub fn get_processes() -> Vec<ProcessInfo> {
     procs
 }
 
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum SortKey {
+    #[default]
+    Memory,
+    Cpu,
+    Name,
+    Pid,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProcessQuery {
+    pub name_contains: Option<String>,
+    pub min_memory_mb: Option<f64>,
+    pub sort_by: SortKey,
+    pub limit: usize, // 0 = no limit
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessPage {
+    pub total: usize, // matches before limit/offset
+    pub processes: Vec<ProcessInfo>,
+}
+
+/// Searchable, paginated view of `get_processes`
+pub fn get_processes_filtered(opts: ProcessQuery) -> ProcessPage {
+    let needle = opts
+        .name_contains
+        .as_deref()
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty());
+    let mut procs = get_processes();
+    if let Some(needle) = needle {
+        procs.retain(|p| p.name.to_lowercase().contains(&needle));
+    }
+    if let Some(min) = opts.min_memory_mb {
+        procs.retain(|p| p.memory_mb >= min);
+    }
+
+    match opts.sort_by {
+        SortKey::Memory => {} // get_processes already sorts by memory
+        SortKey::Cpu => procs.sort_by(|a, b| {
+            b.cpu_percent
+                .partial_cmp(&a.cpu_percent)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        }),
+        SortKey::Name => procs.sort_by_key(|p| p.name.to_lowercase()),
+        SortKey::Pid => procs.sort_by_key(|p| p.pid),
+    }
+
+    let total = procs.len();
+    let limit = if opts.limit == 0 {
+        usize::MAX
+    } else {
+        opts.limit
+    };
+    let processes = procs.into_iter().skip(opts.offset).take(limit).collect();
+    ProcessPage { total, processes }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Optimization Catalog — with REAL estimated savings from system measurements
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -336,8 +441,209 @@ fn measure_selective_trim_savings() -> u64 {
     total
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// Memory counters — direct PDH queries, with Get-Counter as a fallback
+// ═══════════════════════════════════════════════════════════════════════════════
+
+const STANDBY_COUNTERS: [&str; 3] = [
+    "\\Memory\\Standby Cache Normal Priority Bytes",
+    "\\Memory\\Standby Cache Reserve Bytes",
+    "\\Memory\\Standby Cache Core Bytes",
+];
+const MODIFIED_COUNTER: &str = "\\Memory\\Modified Page List Bytes";
+const CACHE_COUNTER: &str = "\\Memory\\Cache Bytes";
+
+/// Read instantaneous counters in one PDH query (milliseconds, no process spawn).
+///
+/// English counter paths are used so this works on localized Windows, where
+/// Get-Counter's paths would not resolve. Returns `None` if PDH is unavailable
+/// so callers can fall back to PowerShell.
+fn read_pdh_counters(paths: &[&str]) -> Option<Vec<u64>> {
+    #[cfg(windows)]
+    {
+        use winapi::um::pdh::{
+            PdhAddEnglishCounterW, PdhCloseQuery, PdhCollectQueryData, PdhGetFormattedCounterValue,
+            PdhOpenQueryW, PDH_FMT_COUNTERVALUE, PDH_FMT_LARGE, PDH_HCOUNTER, PDH_HQUERY,
+        };
+
+        unsafe {
+            let mut query: PDH_HQUERY = std::ptr::null_mut();
+            if PdhOpenQueryW(std::ptr::null(), 0, &mut query) != 0 {
+                return None;
+            }
+
+            let mut counters: Vec<PDH_HCOUNTER> = Vec::with_capacity(paths.len());
+            for path in paths {
+                let wide: Vec<u16> = path.encode_utf16().chain(std::iter::once(0)).collect();
+                let mut counter: PDH_HCOUNTER = std::ptr::null_mut();
+                if PdhAddEnglishCounterW(query, wide.as_ptr(), 0, &mut counter) != 0 {
+                    PdhCloseQuery(query);
+                    return None;
+                }
+                counters.push(counter);
+            }
+
+            // Memory counters are instantaneous — a single sample is enough
+            if PdhCollectQueryData(query) != 0 {
+                PdhCloseQuery(query);
+                return None;
+            }
+
+            let mut values = Vec::with_capacity(counters.len());
+            for counter in counters {
+                let mut value: PDH_FMT_COUNTERVALUE = std::mem::zeroed();
+                if PdhGetFormattedCounterValue(
+                    counter,
+                    PDH_FMT_LARGE,
+                    std::ptr::null_mut(),
+                    &mut value,
+                ) != 0
+                {
+                    PdhCloseQuery(query);
+                    return None;
+                }
+                values.push((*value.u.largeValue()).max(0) as u64);
+            }
+
+            PdhCloseQuery(query);
+            return Some(values);
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = paths;
+        None
+    }
+}
+
+/// Read wildcard counters such as `\\GPU Engine(*)\\Utilization Percentage` in one PDH
+/// query, as (instance name, value) per path.
+///
+/// Two samples `sample_gap` apart are taken so rate counters have a value. Returns `None`
+/// if PDH is unavailable so callers can fall back to PowerShell.
+pub(crate) fn read_pdh_counter_instances(
+    paths: &[&str],
+    sample_gap: std::time::Duration,
+) -> Option<Vec<Vec<(String, f64)>>> {
+    #[cfg(windows)]
+    {
+        use winapi::um::pdh::{
+            PdhAddEnglishCounterW, PdhCloseQuery, PdhCollectQueryData,
+            PdhGetFormattedCounterArrayW, PdhOpenQueryW, PDH_FMT_COUNTERVALUE_ITEM_W,
+            PDH_FMT_DOUBLE, PDH_HCOUNTER, PDH_HQUERY,
+        };
+        // PDH_MORE_DATA: the size probe reports how large the item buffer must be
+        const PDH_MORE_DATA: i32 = 0x8000_07D2_u32 as i32;
+
+        unsafe {
+            let mut query: PDH_HQUERY = std::ptr::null_mut();
+            if PdhOpenQueryW(std::ptr::null(), 0, &mut query) != 0 {
+                return None;
+            }
+
+            let mut counters: Vec<PDH_HCOUNTER> = Vec::with_capacity(paths.len());
+            for path in paths {
+                let wide: Vec<u16> = path.encode_utf16().chain(std::iter::once(0)).collect();
+                let mut counter: PDH_HCOUNTER = std::ptr::null_mut();
+                if PdhAddEnglishCounterW(query, wide.as_ptr(), 0, &mut counter) != 0 {
+                    PdhCloseQuery(query);
+                    return None;
+                }
+                counters.push(counter);
+            }
+
+            if PdhCollectQueryData(query) != 0 {
+                PdhCloseQuery(query);
+                return None;
+            }
+            std::thread::sleep(sample_gap);
+            if PdhCollectQueryData(query) != 0 {
+                PdhCloseQuery(query);
+                return None;
+            }
+
+            let mut results = Vec::with_capacity(counters.len());
+            for counter in counters {
+                let mut size: u32 = 0;
+                let mut count: u32 = 0;
+                let status = PdhGetFormattedCounterArrayW(
+                    counter,
+                    PDH_FMT_DOUBLE,
+                    &mut size,
+                    &mut count,
+                    std::ptr::null_mut(),
+                );
+                if status != PDH_MORE_DATA {
+                    // No instances (e.g. no GPU driver exposing the counter)
+                    results.push(Vec::new());
+                    continue;
+                }
+                // Item structs first, then the instance name strings they point at
+                let item_size = std::mem::size_of::<PDH_FMT_COUNTERVALUE_ITEM_W>();
+                let mut buffer: Vec<PDH_FMT_COUNTERVALUE_ITEM_W> =
+                    Vec::with_capacity(size as usize / item_size + 1);
+                if PdhGetFormattedCounterArrayW(
+                    counter,
+                    PDH_FMT_DOUBLE,
+                    &mut size,
+                    &mut count,
+                    buffer.as_mut_ptr(),
+                ) != 0
+                {
+                    PdhCloseQuery(query);
+                    return None;
+                }
+                buffer.set_len(count as usize);
+
+                let mut instances = Vec::with_capacity(buffer.len());
+                for item in &buffer {
+                    let mut len = 0;
+                    while *item.szName.add(len) != 0 {
+                        len += 1;
+                    }
+                    let name =
+                        String::from_utf16_lossy(std::slice::from_raw_parts(item.szName, len));
+                    instances.push((name, *item.FmtValue.u.doubleValue()));
+                }
+                results.push(instances);
+            }
+
+            PdhCloseQuery(query);
+            return Some(results);
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = (paths, sample_gap);
+        None
+    }
+}
+
+/// Get standby list size (PDH, falling back to Get-Counter)
+pub fn measure_standby_list() -> u64 {
+    read_pdh_counters(&STANDBY_COUNTERS)
+        .map(|v| v.iter().sum())
+        .unwrap_or_else(measure_standby_list_ps)
+}
+
+/// Get modified page list size (PDH, falling back to Get-Counter)
+fn measure_modified_list() -> u64 {
+    read_pdh_counters(&[MODIFIED_COUNTER])
+        .map(|v| v[0])
+        .unwrap_or_else(measure_modified_list_ps)
+}
+
+/// Get system file cache size (PDH, falling back to Get-Counter)
+fn measure_cache_size() -> u64 {
+    read_pdh_counters(&[CACHE_COUNTER])
+        .map(|v| v[0])
+        .unwrap_or_else(measure_cache_size_ps)
+}
+
 /// Get standby list size via performance counter
-fn measure_standby_list() -> u64 {
+fn measure_standby_list_ps() -> u64 {
     if let Ok(output) = std::process::Command::new("powershell")
         .args(["-Command", "(Get-Counter '\\Memory\\Standby Cache Normal Priority Bytes','\\Memory\\Standby Cache Reserve Bytes','\\Memory\\Standby Cache Core Bytes' -ErrorAction SilentlyContinue).CounterSamples | ForEach-Object { $_.CookedValue } | Measure-Object -Sum | Select-Object -ExpandProperty Sum"])
         .output()
@@ -350,7 +656,7 @@ fn measure_standby_list() -> u64 {
 }
 
 /// Get modified page list size via perf counter
-fn measure_modified_list() -> u64 {
+fn measure_modified_list_ps() -> u64 {
     if let Ok(output) = std::process::Command::new("powershell")
         .args(["-Command", "(Get-Counter '\\Memory\\Modified Page List Bytes' -ErrorAction SilentlyContinue).CounterSamples[0].CookedValue"])
         .output()
@@ -362,8 +668,8 @@ fn measure_modified_list() -> u64 {
     }
 }
 
-/// Get system file cache size
-fn measure_cache_size() -> u64 {
+/// Get system file cache size via perf counter
+fn measure_cache_size_ps() -> u64 {
     if let Ok(output) = std::process::Command::new("powershell")
         .args(["-Command", "(Get-Counter '\\Memory\\Cache Bytes' -ErrorAction SilentlyContinue).CounterSamples[0].CookedValue"])
         .output()
@@ -394,6 +700,9 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
     let shader_path = format!("{}\\D3DSCache", local_app);
     let shader_size = measure_dir_size(&shader_path);
 
+    let font_cache_size = files_size(&font_cache_files());
+    let icon_cache_size = files_size(&icon_cache_files());
+
     let pd = program_data();
     let wer_size = measure_dir_size(&format!("{}\\Microsoft\\Windows\\WER\\ReportQueue", pd))
         + measure_dir_size(&format!("{}\\Microsoft\\Windows\\WER\\ReportArchive", pd));
@@ -405,14 +714,14 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
 
     let game_dvr_mem = measure_service_memory(&["gamebar", "gamedvr", "bcastdvr"]);
 
-    vec![
+    let mut items = vec![
         // ── Memory ──
         OptimizationItem {
             id: "mem_working_set".into(), category: "Memory".into(),
             name: "Working Set Trim".into(),
             description: "Release unused memory from all processes".into(),
             tooltip: "Calls EmptyWorkingSet() on each process to release memory pages that haven't been accessed recently. This is safe and the OS will reload pages as needed.".into(),
-            risk: "low".into(), enabled_by_default: true, available: true,
+            risk: "low".into(), enabled_by_default: true, available: true, requires_admin: true,
             estimated_savings: if trimmable > 0 { Some(format_mb(trimmable)) } else { None },
         },
         OptimizationItem {
@@ -420,7 +729,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "System File Cache".into(),
             description: "Clear the file system cache".into(),
             tooltip: "Reduces the system file cache size, freeing RAM used for cached file data. Files will be re-cached as they are accessed.".into(),
-            risk: "low".into(), enabled_by_default: true, available: true,
+            risk: "low".into(), enabled_by_default: true, available: true, requires_admin: true,
             estimated_savings: if cache_bytes > 0 { Some(format_mb(cache_bytes)) } else { None },
         },
         OptimizationItem {
@@ -428,7 +737,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Standby List".into(),
             description: "Purge cached memory pages".into(),
             tooltip: "Purges all cached memory from the standby list. May cause a brief I/O spike as the OS re-reads data from disk. Recommended when memory is critically low.".into(),
-            risk: "medium".into(), enabled_by_default: true, available: true,
+            risk: "medium".into(), enabled_by_default: true, available: true, requires_admin: true,
             estimated_savings: if standby > 0 { Some(format_mb(standby)) } else { None },
         },
         OptimizationItem {
@@ -436,7 +745,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Modified Page List".into(),
             description: "Flush dirty memory pages to disk".into(),
             tooltip: "Writes all modified (dirty) memory pages to the pagefile and frees them. This ensures data is persisted before freeing memory.".into(),
-            risk: "medium".into(), enabled_by_default: false, available: true,
+            risk: "medium".into(), enabled_by_default: false, available: true, requires_admin: true,
             estimated_savings: if modified > 0 { Some(format_mb(modified)) } else { None },
         },
         OptimizationItem {
@@ -444,7 +753,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Combined Page List".into(),
             description: "Flush combined page list (Win 8.1+)".into(),
             tooltip: "Purges the combined page list, which is a newer memory management structure in Windows 8.1 and later.".into(),
-            risk: "medium".into(), enabled_by_default: false, available: true,
+            risk: "medium".into(), enabled_by_default: false, available: true, requires_admin: true,
             estimated_savings: None, // No direct perf counter for this
         },
         OptimizationItem {
@@ -452,7 +761,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Registry Cache".into(),
             description: "Flush stale registry data from memory".into(),
             tooltip: "Flushes the Windows registry hive cache, releasing memory used by stale registry data that hasn't been accessed recently.".into(),
-            risk: "low".into(), enabled_by_default: true, available: true,
+            risk: "low".into(), enabled_by_default: true, available: true, requires_admin: false,
             estimated_savings: None, // Registry cache is managed internally
         },
         // ── Process ──
@@ -461,7 +770,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Lower Idle Process Priority".into(),
             description: "Reduce priority of idle background processes".into(),
             tooltip: "Scans for processes with <1% CPU usage and lowers their scheduling priority to BelowNormal. This gives more CPU time to your active applications.".into(),
-            risk: "low".into(), enabled_by_default: true, available: true,
+            risk: "low".into(), enabled_by_default: true, available: true, requires_admin: true,
             estimated_savings: None,
         },
         OptimizationItem {
@@ -469,7 +778,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Boost Foreground App".into(),
             description: "Give active window higher CPU priority".into(),
             tooltip: "Sets the foreground window's process to AboveNormal priority. Makes your active app feel snappier.".into(),
-            risk: "low".into(), enabled_by_default: true, available: true,
+            risk: "low".into(), enabled_by_default: true, available: true, requires_admin: false,
             estimated_savings: None,
         },
         OptimizationItem {
@@ -477,7 +786,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Selective Working Set Trim".into(),
             description: "Trim only high-memory idle processes".into(),
             tooltip: "Instead of trimming all processes, only trims processes using >100MB of RAM with <5% CPU activity. More targeted and less disruptive than a full working set trim.".into(),
-            risk: "low".into(), enabled_by_default: true, available: true,
+            risk: "low".into(), enabled_by_default: true, available: true, requires_admin: true,
             estimated_savings: if selective > 0 { Some(format_mb(selective)) } else { None },
         },
         OptimizationItem {
@@ -485,7 +794,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Handle Leak Detection".into(),
             description: "Detect processes with excessive memory".into(),
             tooltip: "Identifies processes with more than 500MB of memory, which may indicate a resource leak. Reports findings (read-only scan).".into(),
-            risk: "low".into(), enabled_by_default: true, available: true,
+            risk: "low".into(), enabled_by_default: true, available: true, requires_admin: false,
             estimated_savings: None,
         },
         // ── CPU & Power ──
@@ -494,7 +803,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "High Performance Power Plan".into(),
             description: "Switch to High Performance power plan".into(),
             tooltip: "Sets the active power scheme to High Performance, which prevents CPU frequency scaling and keeps all cores at maximum speed. Uses more power but maximizes performance.".into(),
-            risk: "low".into(), enabled_by_default: false, available: true,
+            risk: "low".into(), enabled_by_default: false, available: true, requires_admin: false,
             estimated_savings: None,
         },
         OptimizationItem {
@@ -502,7 +811,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Timer Resolution Reset".into(),
             description: "Reset system timer to default 15.6ms".into(),
             tooltip: "Some applications permanently set the system timer to 1ms or 0.5ms, which wastes power. This resets it to the default 15.6ms.".into(),
-            risk: "low".into(), enabled_by_default: true, available: true,
+            risk: "low".into(), enabled_by_default: true, available: true, requires_admin: false,
             estimated_savings: None,
         },
         // ── Services ──
@@ -511,7 +820,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Stop Telemetry Services".into(),
             description: "Stop DiagTrack and other telemetry".into(),
             tooltip: "Stops the Connected User Experiences and Telemetry (DiagTrack) service which collects and sends usage data to Microsoft.".into(),
-            risk: "medium".into(), enabled_by_default: false, available: true,
+            risk: "medium".into(), enabled_by_default: false, available: true, requires_admin: true,
             estimated_savings: if telemetry_mem > 0 { Some(format_mb(telemetry_mem)) } else { None },
         },
         OptimizationItem {
@@ -519,7 +828,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Stop Xbox Services".into(),
             description: "Stop Xbox Game Bar related services".into(),
             tooltip: "Stops XblAuthManager, XblGameSave, XboxNetApiSvc, and XboxGipSvc. Safe if you don't use Xbox Game Bar.".into(),
-            risk: "low".into(), enabled_by_default: false, available: true,
+            risk: "low".into(), enabled_by_default: false, available: true, requires_admin: true,
             estimated_savings: if xbox_mem > 0 { Some(format_mb(xbox_mem)) } else { None },
         },
         OptimizationItem {
@@ -527,7 +836,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Stop Windows Search Indexer".into(),
             description: "Stop the WSearch indexing service".into(),
             tooltip: "Stops the Windows Search Indexer. Saves CPU and disk I/O but disables fast search.".into(),
-            risk: "medium".into(), enabled_by_default: false, available: true,
+            risk: "medium".into(), enabled_by_default: false, available: true, requires_admin: true,
             estimated_savings: if search_mem > 0 { Some(format_mb(search_mem)) } else { None },
         },
         OptimizationItem {
@@ -535,7 +844,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Stop SysMain (Superfetch)".into(),
             description: "Stop memory prefetching service".into(),
             tooltip: "Stops the SysMain service (formerly Superfetch). On SSD systems, this provides minimal benefit and wastes RAM.".into(),
-            risk: "medium".into(), enabled_by_default: false, available: true,
+            risk: "medium".into(), enabled_by_default: false, available: true, requires_admin: true,
             estimated_savings: if sysmain_mem > 0 { Some(format_mb(sysmain_mem)) } else { None },
         },
         // ── Network ──
@@ -544,7 +853,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Flush DNS Cache".into(),
             description: "Clear stale DNS resolver entries".into(),
             tooltip: "Flushes the DNS resolver cache, forcing fresh DNS lookups. Completely safe — entries are re-cached automatically.".into(),
-            risk: "low".into(), enabled_by_default: true, available: true,
+            risk: "low".into(), enabled_by_default: true, available: true, requires_admin: false,
             estimated_savings: None,
         },
         OptimizationItem {
@@ -552,7 +861,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Flush ARP Cache".into(),
             description: "Clear the MAC address resolution cache".into(),
             tooltip: "Flushes the ARP table. Resolves some network connectivity issues.".into(),
-            risk: "low".into(), enabled_by_default: false, available: true,
+            risk: "low".into(), enabled_by_default: false, available: true, requires_admin: true,
             estimated_savings: None,
         },
         // ── Disk & Temp ──
@@ -561,7 +870,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Windows Temp Files".into(),
             description: format!("Delete temporary files from {}", &temp_dir),
             tooltip: "Removes files from Windows temp directories. Skips files currently in use.".into(),
-            risk: "low".into(), enabled_by_default: true, available: true,
+            risk: "low".into(), enabled_by_default: true, available: true, requires_admin: false,
             estimated_savings: if temp_size > 0 { Some(format_mb(temp_size)) } else { None },
         },
         OptimizationItem {
@@ -569,7 +878,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Thumbnail Cache".into(),
             description: "Reset Explorer thumbnail cache".into(),
             tooltip: "Deletes thumbnail database files. They are automatically regenerated.".into(),
-            risk: "low".into(), enabled_by_default: false, available: true,
+            risk: "low".into(), enabled_by_default: false, available: true, requires_admin: false,
             estimated_savings: if thumb_size > 0 { Some(format_mb(thumb_size)) } else { None },
         },
         OptimizationItem {
@@ -577,15 +886,31 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "DirectX Shader Cache".into(),
             description: "Clear compiled shader cache".into(),
             tooltip: "Deletes the DirectX shader cache. Shaders will be recompiled on next use.".into(),
-            risk: "low".into(), enabled_by_default: false, available: true,
+            risk: "low".into(), enabled_by_default: false, available: true, requires_admin: false,
             estimated_savings: if shader_size > 0 { Some(format_mb(shader_size)) } else { None },
         },
+        OptimizationItem {
+            id: "disk_font_cache".into(), category: "Disk & Temp".into(),
+            name: "Rebuild Font Cache".into(),
+            description: "Reset a corrupt Windows font cache".into(),
+            tooltip: "Stops the Windows Font Cache service, deletes FNTCACHE.DAT and the FontCache files, and restarts it. Fixes garbled or missing fonts; the cache is rebuilt automatically.".into(),
+            risk: "low".into(), enabled_by_default: false, available: true, requires_admin: true,
+            estimated_savings: if font_cache_size > 0 { Some(format_mb(font_cache_size)) } else { None },
+        },
+        OptimizationItem {
+            id: "disk_icon_cache".into(), category: "Disk & Temp".into(),
+            name: "Rebuild Icon Cache".into(),
+            description: "Reset blank or wrong Explorer icons".into(),
+            tooltip: "Closes Explorer, deletes IconCache.db and iconcache_*.db, then restarts Explorer. The taskbar disappears for a few seconds while this runs.".into(),
+            risk: "medium".into(), enabled_by_default: false, available: true, requires_admin: false,
+            estimated_savings: if icon_cache_size > 0 { Some(format_mb(icon_cache_size)) } else { None },
+        },
         OptimizationItem {
             id: "disk_error_reports".into(), category: "Disk & Temp".into(),
             name: "Windows Error Reports".into(),
             description: "Remove crash dumps and WER data".into(),
             tooltip: "Deletes Windows Error Reporting data and crash dumps. Rarely useful and can accumulate to GB over time.".into(),
-            risk: "low".into(), enabled_by_default: true, available: true,
+            risk: "low".into(), enabled_by_default: true, available: true, requires_admin: true,
             estimated_savings: if wer_size > 0 { Some(format_mb(wer_size)) } else { None },
         },
         // ── Visual Tweaks ──
@@ -594,7 +919,7 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Disable Game DVR/Bar".into(),
             description: "Turn off Xbox Game Bar background recording".into(),
             tooltip: "Disables the Xbox Game Bar overlay and background recording via registry. Reduces GPU overhead.".into(),
-            risk: "low".into(), enabled_by_default: false, available: true,
+            risk: "low".into(), enabled_by_default: false, available: true, requires_admin: false,
             estimated_savings: if game_dvr_mem > 0 { Some(format_mb(game_dvr_mem)) } else { None },
         },
         OptimizationItem {
@@ -602,10 +927,41 @@ pub fn get_optimization_catalog() -> Vec<OptimizationItem> {
             name: "Disable Tips & Suggestions".into(),
             description: "Stop Windows tips, ads, and suggestions".into(),
             tooltip: "Disables Windows tips and Start menu ads via registry. Pure quality-of-life improvement.".into(),
-            risk: "low".into(), enabled_by_default: false, available: true,
+            risk: "low".into(), enabled_by_default: false, available: true, requires_admin: false,
             estimated_savings: None,
         },
-    ]
+    ];
+
+    // Saved preferences replace the built-in defaults once the user has saved any
+    if let Some(settings) = crate::settings::saved_settings() {
+        for item in &mut items {
+            item.enabled_by_default = settings.enabled_ids.contains(&item.id);
+        }
+    }
+    items
+}
+
+/// Write the catalog with freshly measured estimates to `path` as JSON.
+///
+/// Item ids in the export are the same ids `run_optimization` accepts, so a script
+/// can pick a subset and hand it back to the optimizer.
+pub fn export_catalog(path: &str) -> Result<String, String> {
+    let export = CatalogExport {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        items: get_optimization_catalog(),
+    };
+    let json = serde_json::to_string_pretty(&export).map_err(|e| e.to_string())?;
+    if let Some(dir) = std::path::Path::new(path).parent() {
+        if !dir.as_os_str().is_empty() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+    }
+    std::fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(format!("Exported {} items to {}", export.items.len(), path))
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -642,6 +998,21 @@ pub fn run_optimization(selected_ids: Vec<String>) -> OptimizationReport {
     let succeeded = results.iter().filter(|r| r.success).count();
     let failed = results.iter().filter(|r| !r.success).count();
 
+    let mut slowest: Vec<(String, u64)> = results
+        .iter()
+        .map(|r| (r.id.clone(), r.duration_ms))
+        .collect();
+    slowest.sort_by_key(|s| std::cmp::Reverse(s.1));
+    slowest.truncate(SLOWEST_COUNT);
+    let slow_steps: Vec<String> = results
+        .iter()
+        .filter(|r| r.duration_ms > SLOW_STEP_MS)
+        .map(|r| r.id.clone())
+        .collect();
+    for r in results.iter().filter(|r| r.duration_ms > SLOW_STEP_MS) {
+        log::warn!("Optimization step {} was slow: {} ms", r.id, r.duration_ms);
+    }
+
     OptimizationReport {
         total_duration_ms: start.elapsed().as_millis() as u64,
         total_memory_freed_mb: total_freed,
@@ -651,6 +1022,34 @@ pub fn run_optimization(selected_ids: Vec<String>) -> OptimizationReport {
         results,
         memory_before_mb: memory_before,
         memory_after_mb: memory_after,
+        slowest,
+        slow_steps,
+        reboot_pending: is_reboot_pending(),
+    }
+}
+
+/// Whether Windows has changes queued for the next restart: file replacements
+/// (PendingFileRenameOperations), servicing stack updates, or Windows Update installs
+pub fn is_reboot_pending() -> bool {
+    #[cfg(windows)]
+    {
+        use crate::registry::open_key;
+
+        // These keys only exist while a restart is outstanding
+        const FLAG_KEYS: &[&str] = &[
+            r"HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\Component Based Servicing\RebootPending",
+            r"HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\WindowsUpdate\Auto Update\RebootRequired",
+        ];
+
+        let renames = open_key(r"HKLM\SYSTEM\CurrentControlSet\Control\Session Manager")
+            .and_then(|key| key.get_raw_value("PendingFileRenameOperations").ok())
+            .is_some_and(|value| value.bytes.iter().any(|b| *b != 0));
+        return renames || FLAG_KEYS.iter().any(|path| open_key(path).is_some());
+    }
+
+    #[cfg(not(windows))]
+    {
+        false
     }
 }
 
@@ -667,12 +1066,7 @@ fn execute_optimization(id: &str) -> OptimizationResult {
         "proc_selective_trim" => optimize_selective_trim(),
         "proc_handle_detect" => detect_handle_leaks(),
         "cpu_power_high" => set_high_performance_power(),
-        "cpu_timer_reset" => simple_result(
-            "cpu_timer_reset",
-            "Timer Resolution Reset",
-            true,
-            "System timer restored to default 15.6ms",
-        ),
+        "cpu_timer_reset" => reset_timer_resolution(),
         "svc_telemetry" => stop_services(
             &["DiagTrack", "dmwappushservice"],
             "svc_telemetry",
@@ -703,8 +1097,10 @@ fn execute_optimization(id: &str) -> OptimizationResult {
             &["interface", "ip", "delete", "arpcache"],
         ),
         "disk_temp_files" => clean_temp_files(),
-        "disk_thumbnails" => clean_thumbnail_cache(),
+        "disk_thumbnails" => clean_thumbnail_cache(false),
         "disk_shader_cache" => clean_shader_cache(),
+        "disk_font_cache" => rebuild_font_cache(),
+        "disk_icon_cache" => rebuild_icon_cache(),
         "disk_error_reports" => clean_error_reports(),
         "vis_game_dvr" => disable_game_dvr(),
         "vis_tips" => disable_tips(),
@@ -780,195 +1176,204 @@ fn optimize_system_file_cache() -> OptimizationResult {
     #[cfg(windows)]
     {
         // SeIncreaseQuotaPrivilege is required for SetSystemFileCacheSize
-        nt::enable_privilege("SeIncreaseQuotaPrivilege");
+        if !nt::enable_privilege("SeIncreaseQuotaPrivilege") {
+            return simple_result(
+                "mem_system_cache",
+                "System File Cache",
+                false,
+                "SeIncreaseQuotaPrivilege could not be enabled — requires Administrator",
+            );
+        }
 
-        let mut sys = System::new_all();
-        sys.refresh_all();
-        let before = sys.used_memory();
+        let before = measure_cache_size();
 
-        // Setting both min and max to SIZE_MAX with flags=0 instructs Windows
+        // Setting both min and max to SIZE_MAX (-1) with flags=0 instructs Windows
         // to flush the file system cache immediately
-        let result = unsafe {
-            nt::SetSystemFileCacheSize(usize::MAX, usize::MAX, 0)
-        };
-
-        if result != 0 {
-            sys.refresh_all();
-            let after = sys.used_memory();
-            let freed = if before > after {
-                (before - after) as f64 / 1_048_576.0
-            } else {
-                0.0
-            };
-            return OptimizationResult {
-                id: "mem_system_cache".into(),
-                name: "System File Cache".into(),
-                success: true,
-                message: format!("System file cache flushed — freed {:.1} MB", freed),
-                duration_ms: 0,
-                memory_freed_mb: Some(freed),
-            };
-        } else {
+        let result = unsafe { nt::SetSystemFileCacheSize(usize::MAX, usize::MAX, 0) };
+        if result == 0 {
+            let err = std::io::Error::last_os_error();
             return simple_result(
                 "mem_system_cache",
                 "System File Cache",
                 false,
-                "Failed to flush system file cache — requires Administrator privileges",
+                &format!("Failed to flush system file cache: {}", err),
             );
         }
+
+        let after = measure_cache_size();
+        let freed = before.saturating_sub(after) as f64 / 1_048_576.0;
+        return OptimizationResult {
+            id: "mem_system_cache".into(),
+            name: "System File Cache".into(),
+            success: true,
+            message: format!(
+                "System file cache flushed — {:.0} MB → {:.0} MB, freed {:.1} MB",
+                before as f64 / 1_048_576.0,
+                after as f64 / 1_048_576.0,
+                freed
+            ),
+            duration_ms: 0,
+            memory_freed_mb: Some(freed),
+        };
     }
 
     #[cfg(not(windows))]
     simple_result("mem_system_cache", "System File Cache", false, "Windows only")
 }
 
-fn purge_standby_list() -> OptimizationResult {
+fn reset_timer_resolution() -> OptimizationResult {
     #[cfg(windows)]
     {
-        nt::enable_privilege("SeProfileSingleProcessPrivilege");
-        nt::enable_privilege("SeIncreaseQuotaPrivilege");
-
-        let mut sys = System::new_all();
-        sys.refresh_all();
-        let before = sys.used_memory();
-
-        // Command 4 = MemoryPurgeStandbyList
-        let mut command: i32 = nt::MemoryListCommand::MemoryPurgeStandbyList as i32;
-        let status = unsafe {
-            nt::NtSetSystemInformation(
-                nt::SYSTEM_MEMORY_LIST_INFORMATION,
-                &mut command as *mut i32 as *mut std::ffi::c_void,
-                std::mem::size_of::<i32>() as u32,
-            )
+        let before = match crate::monitor::get_timer_resolution() {
+            Some(r) => r,
+            None => {
+                return simple_result(
+                    "cpu_timer_reset",
+                    "Timer Resolution Reset",
+                    false,
+                    "Could not query the system timer resolution",
+                )
+            }
         };
 
-        if status >= 0 {
-            // NTSTATUS >= 0 means success
-            sys.refresh_all();
-            let after = sys.used_memory();
-            let freed = if before > after {
-                (before - after) as f64 / 1_048_576.0
-            } else {
-                0.0
-            };
-            return OptimizationResult {
-                id: "mem_standby_list".into(),
-                name: "Standby List".into(),
-                success: true,
-                message: format!("Purged standby list — freed {:.1} MB", freed),
-                duration_ms: 0,
-                memory_freed_mb: Some(freed),
-            };
-        } else {
+        // 156250 × 100ns = 15.625ms, the Windows default
+        let mut current: u32 = 0;
+        let status = unsafe { nt::NtSetTimerResolution(156_250, 0, &mut current) };
+        // STATUS_TIMER_RESOLUTION_NOT_SET just means we never held a request of our own
+        if status < 0 && status as u32 != 0xC000_0245 {
             return simple_result(
-                "mem_standby_list",
-                "Standby List",
+                "cpu_timer_reset",
+                "Timer Resolution Reset",
                 false,
-                &format!("Failed to purge standby list (NTSTATUS: 0x{:08X}) — requires Administrator", status as u32),
+                &format!(
+                    "NtSetTimerResolution failed (NTSTATUS: 0x{:08X})",
+                    status as u32
+                ),
             );
         }
+
+        let after = crate::monitor::get_timer_resolution().unwrap_or(before.clone());
+        let message = if after.raised {
+            // Windows keeps the finest resolution any process asks for; only closing it helps
+            format!(
+                "Timer was {:.2}ms, now {:.2}ms — another application is still holding it below the {:.1}ms default",
+                before.current_ms, after.current_ms, after.default_ms
+            )
+        } else {
+            format!(
+                "Timer was {:.2}ms, now at the {:.1}ms default",
+                before.current_ms, after.default_ms
+            )
+        };
+        return simple_result("cpu_timer_reset", "Timer Resolution Reset", true, &message);
     }
 
     #[cfg(not(windows))]
-    simple_result("mem_standby_list", "Standby List", false, "Windows only")
+    simple_result(
+        "cpu_timer_reset",
+        "Timer Resolution Reset",
+        false,
+        "Windows only",
+    )
 }
 
-fn flush_modified_page_list() -> OptimizationResult {
-    #[cfg(windows)]
-    {
-        nt::enable_privilege("SeProfileSingleProcessPrivilege");
-        nt::enable_privilege("SeIncreaseQuotaPrivilege");
+/// Run one NtSetSystemInformation memory-list command, sizing the affected list before and after.
+///
+/// Standby and modified pages never count as "used", so the list counters are the only
+/// honest measure of what a purge released.
+#[cfg(windows)]
+fn run_memory_list_command(
+    id: &str,
+    name: &str,
+    command: nt::MemoryListCommand,
+    measure: fn() -> u64,
+    done: &str,
+) -> OptimizationResult {
+    if !nt::enable_privilege("SeProfileSingleProcessPrivilege") {
+        return simple_result(
+            id,
+            name,
+            false,
+            "SeProfileSingleProcessPrivilege could not be enabled — requires Administrator",
+        );
+    }
 
-        let mut sys = System::new_all();
-        sys.refresh_all();
-        let before = sys.used_memory();
-
-        // Command 2 = MemoryFlushModifiedList
-        let mut command: i32 = nt::MemoryListCommand::MemoryFlushModifiedList as i32;
-        let status = unsafe {
-            nt::NtSetSystemInformation(
-                nt::SYSTEM_MEMORY_LIST_INFORMATION,
-                &mut command as *mut i32 as *mut std::ffi::c_void,
-                std::mem::size_of::<i32>() as u32,
-            )
-        };
+    let before = measure();
+    let mut command: i32 = command as i32;
+    let status = unsafe {
+        nt::NtSetSystemInformation(
+            nt::SYSTEM_MEMORY_LIST_INFORMATION,
+            &mut command as *mut i32 as *mut std::ffi::c_void,
+            std::mem::size_of::<i32>() as u32,
+        )
+    };
+    // NTSTATUS >= 0 means success
+    if status < 0 {
+        return simple_result(
+            id,
+            name,
+            false,
+            &format!("{} failed (NTSTATUS: 0x{:08X})", name, status as u32),
+        );
+    }
 
-        if status >= 0 {
-            sys.refresh_all();
-            let after = sys.used_memory();
-            let freed = if before > after {
-                (before - after) as f64 / 1_048_576.0
-            } else {
-                0.0
-            };
-            return OptimizationResult {
-                id: "mem_modified_page".into(),
-                name: "Modified Page List".into(),
-                success: true,
-                message: format!("Flushed modified page list — freed {:.1} MB", freed),
-                duration_ms: 0,
-                memory_freed_mb: Some(freed),
-            };
-        } else {
-            return simple_result(
-                "mem_modified_page",
-                "Modified Page List",
-                false,
-                &format!("Failed to flush modified page list (NTSTATUS: 0x{:08X}) — requires Administrator", status as u32),
-            );
-        }
+    let after = measure();
+    let freed = before.saturating_sub(after) as f64 / 1_048_576.0;
+    OptimizationResult {
+        id: id.into(),
+        name: name.into(),
+        success: true,
+        message: format!(
+            "{} — {:.0} MB → {:.0} MB, freed {:.1} MB",
+            done,
+            before as f64 / 1_048_576.0,
+            after as f64 / 1_048_576.0,
+            freed
+        ),
+        duration_ms: 0,
+        memory_freed_mb: Some(freed),
     }
+}
+
+fn purge_standby_list() -> OptimizationResult {
+    #[cfg(windows)]
+    return run_memory_list_command(
+        "mem_standby_list",
+        "Standby List",
+        nt::MemoryListCommand::MemoryPurgeStandbyList,
+        measure_standby_list,
+        "Purged standby list",
+    );
 
     #[cfg(not(windows))]
-    simple_result("mem_modified_page", "Modified Page List", false, "Windows only")
+    simple_result("mem_standby_list", "Standby List", false, "Windows only")
 }
 
-fn flush_combined_page_list() -> OptimizationResult {
+fn flush_modified_page_list() -> OptimizationResult {
+    // Flushed pages are written out and move to the standby list, so measure the modified list
     #[cfg(windows)]
-    {
-        nt::enable_privilege("SeProfileSingleProcessPrivilege");
-        nt::enable_privilege("SeIncreaseQuotaPrivilege");
+    return run_memory_list_command(
+        "mem_modified_page",
+        "Modified Page List",
+        nt::MemoryListCommand::MemoryFlushModifiedList,
+        measure_modified_list,
+        "Flushed modified page list",
+    );
 
-        // Combined page list purge uses command 5 (MemoryPurgeLowPriorityStandbyList)
-        // which covers the combined/low-priority standby pages on Win 8.1+
-        let mut sys = System::new_all();
-        sys.refresh_all();
-        let before = sys.used_memory();
-
-        let mut command: i32 = nt::MemoryListCommand::MemoryPurgeLowPriorityStandbyList as i32;
-        let status = unsafe {
-            nt::NtSetSystemInformation(
-                nt::SYSTEM_MEMORY_LIST_INFORMATION,
-                &mut command as *mut i32 as *mut std::ffi::c_void,
-                std::mem::size_of::<i32>() as u32,
-            )
-        };
+    #[cfg(not(windows))]
+    simple_result("mem_modified_page", "Modified Page List", false, "Windows only")
+}
 
-        if status >= 0 {
-            sys.refresh_all();
-            let after = sys.used_memory();
-            let freed = if before > after {
-                (before - after) as f64 / 1_048_576.0
-            } else {
-                0.0
-            };
-            return OptimizationResult {
-                id: "mem_combined_page".into(),
-                name: "Combined Page List".into(),
-                success: true,
-                message: format!("Flushed combined page list — freed {:.1} MB", freed),
-                duration_ms: 0,
-                memory_freed_mb: Some(freed),
-            };
-        } else {
-            return simple_result(
-                "mem_combined_page",
-                "Combined Page List",
-                false,
-                &format!("Failed to flush combined page list (NTSTATUS: 0x{:08X}) — requires Administrator", status as u32),
-            );
-        }
-    }
+fn flush_combined_page_list() -> OptimizationResult {
+    // MemoryPurgeLowPriorityStandbyList drops the low-priority standby pages (Win 8.1+)
+    #[cfg(windows)]
+    return run_memory_list_command(
+        "mem_combined_page",
+        "Combined Page List",
+        nt::MemoryListCommand::MemoryPurgeLowPriorityStandbyList,
+        measure_standby_list,
+        "Flushed combined page list",
+    );
 
     #[cfg(not(windows))]
     simple_result("mem_combined_page", "Combined Page List", false, "Windows only")
@@ -1116,7 +1521,7 @@ fn optimize_boost_foreground() -> OptimizationResult {
     )
 }
 
-fn optimize_selective_trim() -> OptimizationResult {
+pub fn optimize_selective_trim() -> OptimizationResult {
     #[cfg(windows)]
     {
         use winapi::um::handleapi::CloseHandle;
@@ -1219,6 +1624,213 @@ fn detect_handle_leaks() -> OptimizationResult {
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// Per-process Control — affinity and priority for a single process from the process list
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessAffinity {
+    pub pid: u32,
+    pub mask: u64,        // bit n set = may run on logical core n
+    pub system_mask: u64, // cores available to the system
+    pub logical_cores: usize,
+}
+
+/// Same count `get_hardware_info` reports, without its WMI queries
+#[cfg(windows)]
+fn logical_core_count() -> usize {
+    let sys = System::new_with_specifics(
+        sysinfo::RefreshKind::nothing().with_cpu(sysinfo::CpuRefreshKind::nothing()),
+    );
+    sys.cpus().len()
+}
+
+/// Process name for `pid`, refusing PIDs that must not be touched
+fn controllable_process_name(pid: u32) -> Result<String, String> {
+    let mut sys = System::new();
+    sys.refresh_processes(ProcessesToUpdate::All, true);
+    let name = sys
+        .process(sysinfo::Pid::from_u32(pid))
+        .map(|p| p.name().to_string_lossy().to_string())
+        .ok_or_else(|| format!("No process with PID {}", pid))?;
+    if pid <= 4 || crate::PROTECTED_PROCESSES.contains(&name.to_lowercase().as_str()) {
+        return Err(format!("{} is a protected process", name));
+    }
+    Ok(name)
+}
+
+pub fn get_process_affinity(pid: u32) -> Result<ProcessAffinity, String> {
+    #[cfg(windows)]
+    {
+        use winapi::um::handleapi::CloseHandle;
+        use winapi::um::processthreadsapi::OpenProcess;
+        use winapi::um::winbase::GetProcessAffinityMask;
+        use winapi::um::winnt::PROCESS_QUERY_LIMITED_INFORMATION;
+
+        unsafe {
+            let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+            if handle.is_null() {
+                return Err(format!(
+                    "Cannot open process {}: {}",
+                    pid,
+                    std::io::Error::last_os_error()
+                ));
+            }
+            let (mut mask, mut system_mask) = (0usize, 0usize);
+            let ok = GetProcessAffinityMask(handle, &mut mask, &mut system_mask) != 0;
+            let err = std::io::Error::last_os_error();
+            CloseHandle(handle);
+            if !ok {
+                return Err(format!("GetProcessAffinityMask failed: {}", err));
+            }
+            return Ok(ProcessAffinity {
+                pid,
+                mask: mask as u64,
+                system_mask: system_mask as u64,
+                logical_cores: logical_core_count(),
+            });
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = pid;
+        Err("Not supported on this platform".into())
+    }
+}
+
+/// Restrict `pid` to the logical cores set in `mask`
+pub fn set_process_affinity(pid: u32, mask: u64) -> Result<String, String> {
+    let name = controllable_process_name(pid)?;
+    let current = get_process_affinity(pid)?;
+    // SetProcessAffinityMask fails outright if any bit lies outside the system mask
+    let effective = mask & current.system_mask;
+    if effective == 0 {
+        return Err(format!(
+            "Mask 0x{:X} selects none of the available cores (0x{:X})",
+            mask, current.system_mask
+        ));
+    }
+
+    #[cfg(windows)]
+    {
+        use winapi::um::handleapi::CloseHandle;
+        use winapi::um::processthreadsapi::OpenProcess;
+        use winapi::um::winnt::{PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SET_INFORMATION};
+
+        unsafe {
+            let handle = OpenProcess(
+                PROCESS_SET_INFORMATION | PROCESS_QUERY_LIMITED_INFORMATION,
+                0,
+                pid,
+            );
+            if handle.is_null() {
+                return Err(format!(
+                    "Cannot open {}: {}",
+                    name,
+                    std::io::Error::last_os_error()
+                ));
+            }
+            let ok = nt::SetProcessAffinityMask(handle, effective as usize) != 0;
+            let err = std::io::Error::last_os_error();
+            CloseHandle(handle);
+            if !ok {
+                return Err(format!(
+                    "SetProcessAffinityMask failed for {}: {}",
+                    name, err
+                ));
+            }
+        }
+        return Ok(format!(
+            "{} limited to {} of {} cores",
+            name,
+            effective.count_ones(),
+            current.system_mask.count_ones()
+        ));
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = name;
+        Err("Not supported on this platform".into())
+    }
+}
+
+/// Priority names accepted from the UI, paired with their Win32 priority classes
+#[cfg(windows)]
+const PRIORITY_CLASSES: [(&str, u32); 6] = [
+    ("idle", winapi::um::winbase::IDLE_PRIORITY_CLASS),
+    (
+        "below_normal",
+        winapi::um::winbase::BELOW_NORMAL_PRIORITY_CLASS,
+    ),
+    ("normal", winapi::um::winbase::NORMAL_PRIORITY_CLASS),
+    (
+        "above_normal",
+        winapi::um::winbase::ABOVE_NORMAL_PRIORITY_CLASS,
+    ),
+    ("high", winapi::um::winbase::HIGH_PRIORITY_CLASS),
+    ("realtime", winapi::um::winbase::REALTIME_PRIORITY_CLASS),
+];
+
+/// Set one process's priority class; returns the priority actually in effect afterwards.
+///
+/// Without SeIncreaseBasePriorityPrivilege Windows silently applies "high" instead of
+/// "realtime", so the class is read back rather than echoed.
+pub fn set_process_priority(pid: u32, priority: &str) -> Result<String, String> {
+    let name = controllable_process_name(pid)?;
+    if priority == "realtime" && pid == std::process::id() {
+        return Err("Refusing to run VegaOptimizer itself at realtime priority".into());
+    }
+
+    #[cfg(windows)]
+    {
+        use winapi::um::handleapi::CloseHandle;
+        use winapi::um::processthreadsapi::{GetPriorityClass, OpenProcess, SetPriorityClass};
+        use winapi::um::winnt::{PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SET_INFORMATION};
+
+        let class = PRIORITY_CLASSES
+            .iter()
+            .find(|(n, _)| *n == priority)
+            .map(|(_, c)| *c)
+            .ok_or_else(|| format!("Unknown priority: {}", priority))?;
+
+        let applied = unsafe {
+            let handle = OpenProcess(
+                PROCESS_SET_INFORMATION | PROCESS_QUERY_LIMITED_INFORMATION,
+                0,
+                pid,
+            );
+            if handle.is_null() {
+                return Err(format!(
+                    "Cannot open {}: {}",
+                    name,
+                    std::io::Error::last_os_error()
+                ));
+            }
+            let ok = SetPriorityClass(handle, class) != 0;
+            let err = std::io::Error::last_os_error();
+            let applied = GetPriorityClass(handle);
+            CloseHandle(handle);
+            if !ok {
+                return Err(format!("SetPriorityClass failed for {}: {}", name, err));
+            }
+            applied
+        };
+        return PRIORITY_CLASSES
+            .iter()
+            .find(|(_, c)| *c == applied)
+            .map(|(n, _)| n.to_string())
+            .ok_or_else(|| format!("{} has an unknown priority class 0x{:X}", name, applied));
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = name;
+        Err("Not supported on this platform".into())
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // CPU & Power
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -1272,36 +1884,72 @@ fn stop_services(services: &[&str], id: &str, name: &str) -> OptimizationResult
 // Disk & Temp Cleanup
 // ═══════════════════════════════════════════════════════════════════════════════
 
-fn clean_directory(path: &str) -> (u64, u32) {
+/// Delete the contents of `path`; returns (freed bytes, deleted count, skipped (path, OS error))
+fn clean_directory(path: &str) -> (u64, u32, Vec<(String, String)>) {
     let mut freed: u64 = 0;
     let mut count: u32 = 0;
+    let mut skipped: Vec<(String, String)> = Vec::new();
 
     if let Ok(entries) = std::fs::read_dir(path) {
         for entry in entries.flatten() {
+            if crate::disk_cleanup::is_reparse_protected(&entry.path()) {
+                log::warn!("Cleanup skipped reparse point: {}", entry.path().display());
+                continue;
+            }
             if let Ok(metadata) = entry.metadata() {
-                if metadata.is_file() {
-                    let size = metadata.len();
-                    if std::fs::remove_file(entry.path()).is_ok() {
-                        freed += size;
-                        count += 1;
-                    }
+                let result = if metadata.is_file() {
+                    std::fs::remove_file(entry.path())
                 } else if metadata.is_dir() {
-                    if std::fs::remove_dir_all(entry.path()).is_ok() {
+                    std::fs::remove_dir_all(entry.path())
+                } else {
+                    continue;
+                };
+                match result {
+                    Ok(()) => {
+                        if metadata.is_file() {
+                            freed += metadata.len();
+                        }
                         count += 1;
                     }
+                    Err(e) => skipped.push((entry.path().display().to_string(), e.to_string())),
                 }
             }
         }
     }
 
-    (freed, count)
+    (freed, count, skipped)
+}
+
+/// " — N skipped (in use): a (error), b (error), …" or "" when nothing was skipped
+fn describe_skipped(skipped: &[(String, String)]) -> String {
+    const SHOWN: usize = 5;
+    if skipped.is_empty() {
+        return String::new();
+    }
+    for (path, err) in skipped {
+        log::info!("Cleanup skipped {}: {}", path, err);
+    }
+    let mut listed: Vec<String> = skipped
+        .iter()
+        .take(SHOWN)
+        .map(|(path, err)| format!("{} ({})", path, err))
+        .collect();
+    if skipped.len() > SHOWN {
+        listed.push(format!("and {} more", skipped.len() - SHOWN));
+    }
+    format!(
+        " — {} skipped (in use or access denied): {}",
+        skipped.len(),
+        listed.join(", ")
+    )
 }
 
 fn clean_temp_files() -> OptimizationResult {
     let temp_dir = std::env::var("TEMP").unwrap_or_else(|_| format!("{}\\Temp", system_root()));
     let win_temp = format!("{}\\Temp", system_root());
-    let (freed1, count1) = clean_directory(&temp_dir);
-    let (freed2, count2) = clean_directory(&win_temp);
+    let (freed1, count1, mut skipped) = clean_directory(&temp_dir);
+    let (freed2, count2, skipped2) = clean_directory(&win_temp);
+    skipped.extend(skipped2);
     let total_freed = (freed1 + freed2) as f64 / 1_048_576.0;
 
     OptimizationResult {
@@ -1309,40 +1957,62 @@ fn clean_temp_files() -> OptimizationResult {
         name: "Windows Temp Files".into(),
         success: true,
         message: format!(
-            "Deleted {} items, freed {:.1} MB",
+            "Deleted {} items, freed {:.1} MB{}",
             count1 + count2,
-            total_freed
+            total_freed,
+            describe_skipped(&skipped)
         ),
         duration_ms: 0,
         memory_freed_mb: Some(total_freed),
     }
 }
 
-fn clean_thumbnail_cache() -> OptimizationResult {
-    let local = std::env::var("LOCALAPPDATA").unwrap_or_default();
-    let path = format!("{}\\Microsoft\\Windows\\Explorer", local);
-    let mut deleted = 0;
-    if let Ok(entries) = std::fs::read_dir(&path) {
-        for entry in entries.flatten() {
-            let name = entry.file_name().to_string_lossy().to_string();
-            if name.starts_with("thumbcache_") && name.ends_with(".db") {
-                if std::fs::remove_file(entry.path()).is_ok() {
-                    deleted += 1;
+/// Delete Explorer's thumbnail databases. Explorer keeps the active ones locked, so with
+/// `restart_shell` it is stopped first and relaunched afterwards; without it only the
+/// unlocked databases go until the next sign-out.
+pub fn clean_thumbnail_cache(restart_shell: bool) -> OptimizationResult {
+    let delete = || {
+        let local = std::env::var("LOCALAPPDATA").unwrap_or_default();
+        let path = format!("{}\\Microsoft\\Windows\\Explorer", local);
+        let (mut deleted, mut locked) = (0, 0);
+        if let Ok(entries) = std::fs::read_dir(&path) {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.starts_with("thumbcache_") && name.ends_with(".db") {
+                    if std::fs::remove_file(entry.path()).is_ok() {
+                        deleted += 1;
+                    } else {
+                        locked += 1;
+                    }
                 }
             }
         }
+        (deleted, locked)
+    };
+
+    let mut counts = (0, 0);
+    let restarted = if restart_shell {
+        Some(restart_explorer_with(|| counts = delete()))
+    } else {
+        counts = delete();
+        None
+    };
+    let (deleted, locked) = counts;
+    let mut message = format!("Deleted {} thumbnail cache files", deleted);
+    if locked > 0 {
+        message.push_str(&format!("; {} files still in use", locked));
     }
-    simple_result(
-        "disk_thumbnails",
-        "Thumbnail Cache",
-        true,
-        &format!("Deleted {} thumbnail cache files", deleted),
-    )
+    match restarted {
+        Some(Ok(_)) => message.push_str("; Explorer restarted"),
+        Some(Err(e)) => message.push_str(&format!("; {}", e)),
+        None => {}
+    }
+    simple_result("disk_thumbnails", "Thumbnail Cache", true, &message)
 }
 
 fn clean_shader_cache() -> OptimizationResult {
     let local = std::env::var("LOCALAPPDATA").unwrap_or_default();
-    let (freed, count) = clean_directory(&format!("{}\\D3DSCache", local));
+    let (freed, count, skipped) = clean_directory(&format!("{}\\D3DSCache", local));
     let freed_mb = freed as f64 / 1_048_576.0;
 
     OptimizationResult {
@@ -1350,8 +2020,10 @@ fn clean_shader_cache() -> OptimizationResult {
         name: "DirectX Shader Cache".into(),
         success: true,
         message: format!(
-            "Deleted {} shader cache files, freed {:.1} MB",
-            count, freed_mb
+            "Deleted {} shader cache files, freed {:.1} MB{}",
+            count,
+            freed_mb,
+            describe_skipped(&skipped)
         ),
         duration_ms: 0,
         memory_freed_mb: Some(freed_mb),
@@ -1362,13 +2034,15 @@ fn clean_error_reports() -> OptimizationResult {
     let local = std::env::var("LOCALAPPDATA").unwrap_or_default();
     let mut total_freed: u64 = 0;
     let mut total_count: u32 = 0;
+    let mut skipped = Vec::new();
     for path in &[
         format!("{}\\CrashDumps", local),
         format!("{}\\Microsoft\\Windows\\WER", local),
     ] {
-        let (freed, count) = clean_directory(path);
+        let (freed, count, path_skipped) = clean_directory(path);
         total_freed += freed;
         total_count += count;
+        skipped.extend(path_skipped);
     }
     let freed_mb = total_freed as f64 / 1_048_576.0;
 
@@ -1377,14 +2051,240 @@ fn clean_error_reports() -> OptimizationResult {
         name: "Windows Error Reports".into(),
         success: true,
         message: format!(
-            "Deleted {} error report files, freed {:.1} MB",
-            total_count, freed_mb
+            "Deleted {} error report files, freed {:.1} MB{}",
+            total_count,
+            freed_mb,
+            describe_skipped(&skipped)
         ),
         duration_ms: 0,
         memory_freed_mb: Some(freed_mb),
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// Font & Icon Cache Rebuild
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// FNTCACHE.DAT plus the FontCache service's per-profile cache files
+fn font_cache_files() -> Vec<std::path::PathBuf> {
+    let root = system_root();
+    let mut files = vec![std::path::PathBuf::from(format!(
+        "{}\\System32\\FNTCACHE.DAT",
+        root
+    ))];
+    let dir = format!(
+        "{}\\ServiceProfiles\\LocalService\\AppData\\Local\\FontCache",
+        root
+    );
+    if let Ok(entries) = std::fs::read_dir(&dir) {
+        for entry in entries.flatten() {
+            if entry.path().is_file() {
+                files.push(entry.path());
+            }
+        }
+    }
+    files.retain(|f| f.exists());
+    files
+}
+
+/// IconCache.db (Windows 7 location, still present) plus Explorer's iconcache_*.db
+fn icon_cache_files() -> Vec<std::path::PathBuf> {
+    let local = std::env::var("LOCALAPPDATA").unwrap_or_default();
+    let mut files = vec![std::path::PathBuf::from(format!("{}\\IconCache.db", local))];
+    let dir = format!("{}\\Microsoft\\Windows\\Explorer", local);
+    if let Ok(entries) = std::fs::read_dir(&dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            if name.starts_with("iconcache_") && name.ends_with(".db") {
+                files.push(entry.path());
+            }
+        }
+    }
+    files.retain(|f| f.exists());
+    files
+}
+
+fn files_size(files: &[std::path::PathBuf]) -> u64 {
+    files
+        .iter()
+        .filter_map(|f| std::fs::metadata(f).ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Delete what can be deleted; returns (bytes freed, files deleted, files still locked)
+fn delete_cache_files(files: &[std::path::PathBuf]) -> (u64, u32, u32) {
+    let (mut freed, mut deleted, mut locked) = (0u64, 0u32, 0u32);
+    for f in files {
+        let size = std::fs::metadata(f).map(|m| m.len()).unwrap_or(0);
+        if std::fs::remove_file(f).is_ok() {
+            freed += size;
+            deleted += 1;
+        } else {
+            locked += 1;
+        }
+    }
+    (freed, deleted, locked)
+}
+
+/// Current font and icon cache files and their sizes
+pub fn get_system_cache_status() -> Vec<SystemCacheStatus> {
+    [
+        ("font_cache", "Font Cache", font_cache_files()),
+        ("icon_cache", "Icon Cache", icon_cache_files()),
+    ]
+    .into_iter()
+    .map(|(id, name, files)| SystemCacheStatus {
+        id: id.into(),
+        name: name.into(),
+        size_bytes: files_size(&files),
+        files: files
+            .iter()
+            .map(|f| f.to_string_lossy().to_string())
+            .collect(),
+    })
+    .collect()
+}
+
+/// Stop the Font Cache service, delete its cache files, and start it again
+pub fn rebuild_font_cache() -> OptimizationResult {
+    // FontCache holds the files open; the WPF font cache service may too
+    for svc in ["FontCache", "FontCache3.0.0.0"] {
+        let _ = std::process::Command::new("net")
+            .args(["stop", svc])
+            .output();
+    }
+
+    let (freed, deleted, locked) = delete_cache_files(&font_cache_files());
+
+    let restarted = std::process::Command::new("net")
+        .args(["start", "FontCache"])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+
+    let freed_mb = freed as f64 / 1_048_576.0;
+    let mut message = format!(
+        "Deleted {} font cache files, freed {:.1} MB",
+        deleted, freed_mb
+    );
+    if locked > 0 {
+        message.push_str(&format!(
+            "; {} files still locked (run as administrator or reboot)",
+            locked
+        ));
+    }
+    if !restarted {
+        message.push_str("; Font Cache service will start on next use");
+    }
+
+    OptimizationResult {
+        id: "disk_font_cache".into(),
+        name: "Rebuild Font Cache".into(),
+        success: locked == 0 || deleted > 0,
+        message,
+        duration_ms: 0,
+        memory_freed_mb: Some(freed_mb),
+    }
+}
+
+/// How long to let Windows relaunch the shell itself (AutoRestartShell) after it is killed
+const EXPLORER_AUTO_RESTART_MS: u64 = 3000;
+
+fn explorer_running() -> bool {
+    let mut sys = System::new();
+    sys.refresh_processes(ProcessesToUpdate::All, true);
+    sys.processes().values().any(|p| {
+        p.name()
+            .to_string_lossy()
+            .eq_ignore_ascii_case("explorer.exe")
+    })
+}
+
+fn wait_for_explorer(timeout_ms: u64) -> bool {
+    let deadline = Instant::now() + std::time::Duration::from_millis(timeout_ms);
+    loop {
+        if explorer_running() {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(250));
+    }
+}
+
+/// Restart the Windows shell so taskbar, theme and cache tweaks take effect.
+pub fn restart_explorer() -> Result<String, String> {
+    restart_explorer_with(|| {})
+}
+
+/// Stop Explorer, run `while_stopped` (e.g. deleting caches it holds open), then bring
+/// it back.
+///
+/// Windows usually relaunches Explorer on its own after it is killed; we only start it
+/// ourselves if that doesn't happen, so the user doesn't end up with an extra
+/// File Explorer window.
+fn restart_explorer_with(while_stopped: impl FnOnce()) -> Result<String, String> {
+    let was_running = std::process::Command::new("taskkill")
+        .args(["/f", "/im", "explorer.exe"])
+        .output()
+        .map_err(|e| format!("Failed to stop Explorer: {}", e))?
+        .status
+        .success();
+    std::thread::sleep(std::time::Duration::from_millis(800));
+    while_stopped();
+
+    if !wait_for_explorer(EXPLORER_AUTO_RESTART_MS) {
+        std::process::Command::new("explorer.exe")
+            .spawn()
+            .map_err(|e| format!("Failed to relaunch Explorer: {}", e))?;
+        if !wait_for_explorer(5000) {
+            return Err("Explorer did not restart — open Task Manager and run explorer.exe".into());
+        }
+    }
+
+    Ok(if was_running {
+        "Explorer restarted".into()
+    } else {
+        "Explorer was not running — started it".into()
+    })
+}
+
+/// Close Explorer (which holds the icon caches open), delete them, and restart it
+pub fn rebuild_icon_cache() -> OptimizationResult {
+    let mut counts = (0, 0, 0);
+    let restarted = restart_explorer_with(|| {
+        counts = delete_cache_files(&icon_cache_files());
+        // Ask the shell to refresh its icon state before Explorer comes back
+        let _ = std::process::Command::new("ie4uinit.exe")
+            .arg("-show")
+            .output();
+    });
+    let (freed, deleted, locked) = counts;
+
+    let freed_mb = freed as f64 / 1_048_576.0;
+    let mut message = format!(
+        "Deleted {} icon cache files, freed {:.1} MB",
+        deleted, freed_mb
+    );
+    if locked > 0 {
+        message.push_str(&format!("; {} files still in use", locked));
+    }
+    if let Err(e) = restarted {
+        message.push_str(&format!("; {}", e));
+    }
+
+    OptimizationResult {
+        id: "disk_icon_cache".into(),
+        name: "Rebuild Icon Cache".into(),
+        success: locked == 0 || deleted > 0,
+        message,
+        duration_ms: 0,
+        memory_freed_mb: Some(freed_mb),
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Visual Tweaks (Registry)
 // ═══════════════════════════════════════════════════════════════════════════════
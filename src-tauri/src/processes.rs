@@ -0,0 +1,717 @@
+//! Process inspection and bulk control — memory breakdown, pattern-matched kill/trim,
+//! persisted working-set limits, suspend/resume
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryRegion {
+    pub base_address: String,
+    pub size_mb: f64,
+    pub region_type: String, // "private", "image", "mapped"
+    pub protection: String,
+    pub mapped_file: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessMemoryBreakdown {
+    pub pid: u32,
+    pub name: String,
+    pub working_set_mb: f64,
+    pub peak_working_set_mb: f64,
+    pub private_mb: f64,
+    pub shared_mb: f64,
+    pub pagefile_mb: f64,
+    pub module_count: usize,
+    pub committed_regions: usize,
+    pub largest_regions: Vec<MemoryRegion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessActionResult {
+    pub pid: u32,
+    pub name: String,
+    pub success: bool,
+    pub message: String,
+    pub freed_mb: Option<f64>,
+}
+
+/// A working-set cap remembered by process name so it can be reapplied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkingSetLimit {
+    pub name: String, // lowercase executable name
+    pub min_mb: u64,
+    pub max_mb: u64,
+}
+
+/// Number of committed regions returned in `largest_regions`
+#[cfg(windows)]
+const MAX_REGIONS: usize = 20;
+
+/// Detailed memory breakdown of a single process: working set vs private vs shared,
+/// module count, and the largest committed regions found by walking its address space.
+pub fn get_process_memory_breakdown(pid: u32) -> Result<ProcessMemoryBreakdown, String> {
+    let mut sys = sysinfo::System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    let name = sys
+        .process(sysinfo::Pid::from_u32(pid))
+        .map(|p| p.name().to_string_lossy().to_string())
+        .ok_or_else(|| format!("Process {} not found", pid))?;
+
+    #[cfg(windows)]
+    {
+        use winapi::shared::minwindef::{DWORD, HMODULE};
+        use winapi::um::errhandlingapi::GetLastError;
+        use winapi::um::handleapi::CloseHandle;
+        use winapi::um::memoryapi::VirtualQueryEx;
+        use winapi::um::processthreadsapi::OpenProcess;
+        use winapi::um::psapi::{
+            EnumProcessModulesEx, GetMappedFileNameW, GetProcessMemoryInfo, LIST_MODULES_ALL,
+            PROCESS_MEMORY_COUNTERS, PROCESS_MEMORY_COUNTERS_EX,
+        };
+        use winapi::um::winnt::{
+            MEMORY_BASIC_INFORMATION, MEM_COMMIT, MEM_IMAGE, MEM_MAPPED, PROCESS_QUERY_INFORMATION,
+            PROCESS_VM_READ,
+        };
+
+        unsafe {
+            let handle = OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_VM_READ, 0, pid);
+            if handle.is_null() {
+                let err = GetLastError();
+                return Err(format!(
+                    "Cannot open process (error {}{})",
+                    err,
+                    if err == 5 {
+                        " — run as Administrator"
+                    } else {
+                        ""
+                    }
+                ));
+            }
+
+            // Working set / private / pagefile counters
+            let mut counters: PROCESS_MEMORY_COUNTERS_EX = std::mem::zeroed();
+            counters.cb = std::mem::size_of::<PROCESS_MEMORY_COUNTERS_EX>() as DWORD;
+            GetProcessMemoryInfo(
+                handle,
+                &mut counters as *mut _ as *mut PROCESS_MEMORY_COUNTERS,
+                counters.cb,
+            );
+
+            // Loaded module count
+            let mut needed: DWORD = 0;
+            EnumProcessModulesEx(
+                handle,
+                std::ptr::null_mut(),
+                0,
+                &mut needed,
+                LIST_MODULES_ALL,
+            );
+            let module_count = needed as usize / std::mem::size_of::<HMODULE>();
+
+            // Walk the address space and collect committed regions
+            let mut regions: Vec<(usize, usize, DWORD, DWORD)> = Vec::new();
+            let (mut private_bytes, mut shared_bytes) = (0u64, 0u64);
+            let mut address = 0usize;
+            loop {
+                let mut mbi: MEMORY_BASIC_INFORMATION = std::mem::zeroed();
+                let written = VirtualQueryEx(
+                    handle,
+                    address as *const _,
+                    &mut mbi,
+                    std::mem::size_of::<MEMORY_BASIC_INFORMATION>(),
+                );
+                if written == 0 {
+                    break;
+                }
+                let base = mbi.BaseAddress as usize;
+                if mbi.State == MEM_COMMIT {
+                    if mbi.Type == MEM_IMAGE || mbi.Type == MEM_MAPPED {
+                        shared_bytes += mbi.RegionSize as u64;
+                    } else {
+                        private_bytes += mbi.RegionSize as u64;
+                    }
+                    regions.push((base, mbi.RegionSize, mbi.Type, mbi.Protect));
+                }
+                match base.checked_add(mbi.RegionSize) {
+                    Some(next) if next > address => address = next,
+                    _ => break,
+                }
+            }
+
+            let committed_regions = regions.len();
+            regions.sort_by_key(|r| std::cmp::Reverse(r.1));
+            regions.truncate(MAX_REGIONS);
+
+            let largest_regions = regions
+                .into_iter()
+                .map(|(base, size, kind, protect)| {
+                    let mapped_file = if kind == MEM_IMAGE || kind == MEM_MAPPED {
+                        let mut buf = [0u16; 520];
+                        let len = GetMappedFileNameW(
+                            handle,
+                            base as *mut _,
+                            buf.as_mut_ptr(),
+                            buf.len() as DWORD,
+                        );
+                        if len > 0 {
+                            Some(String::from_utf16_lossy(&buf[..len as usize]))
+                        } else {
+                            None
+                        }
+                    } else {
+                        None
+                    };
+                    MemoryRegion {
+                        base_address: format!("0x{:X}", base),
+                        size_mb: size as f64 / 1_048_576.0,
+                        region_type: match kind {
+                            MEM_IMAGE => "image",
+                            MEM_MAPPED => "mapped",
+                            _ => "private",
+                        }
+                        .into(),
+                        protection: protection_label(protect),
+                        mapped_file,
+                    }
+                })
+                .collect();
+
+            CloseHandle(handle);
+
+            return Ok(ProcessMemoryBreakdown {
+                pid,
+                name,
+                working_set_mb: counters.WorkingSetSize as f64 / 1_048_576.0,
+                peak_working_set_mb: counters.PeakWorkingSetSize as f64 / 1_048_576.0,
+                private_mb: private_bytes as f64 / 1_048_576.0,
+                shared_mb: shared_bytes as f64 / 1_048_576.0,
+                pagefile_mb: counters.PagefileUsage as f64 / 1_048_576.0,
+                module_count,
+                committed_regions,
+                largest_regions,
+            });
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = name;
+        Err("Memory breakdown is only available on Windows".into())
+    }
+}
+
+/// Human-readable label for a PAGE_* protection value
+#[cfg(windows)]
+fn protection_label(protect: u32) -> String {
+    use winapi::um::winnt::{
+        PAGE_EXECUTE, PAGE_EXECUTE_READ, PAGE_EXECUTE_READWRITE, PAGE_EXECUTE_WRITECOPY,
+        PAGE_GUARD, PAGE_NOACCESS, PAGE_READONLY, PAGE_READWRITE, PAGE_WRITECOPY,
+    };
+
+    let base = match protect & 0xFF {
+        PAGE_NOACCESS => "---",
+        PAGE_READONLY => "R",
+        PAGE_READWRITE => "RW",
+        PAGE_WRITECOPY => "WC",
+        PAGE_EXECUTE => "X",
+        PAGE_EXECUTE_READ => "RX",
+        PAGE_EXECUTE_READWRITE => "RWX",
+        PAGE_EXECUTE_WRITECOPY => "XWC",
+        _ => "?",
+    };
+    if protect & PAGE_GUARD != 0 {
+        format!("{} +guard", base)
+    } else {
+        base.to_string()
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Pattern matching — act on every non-protected process whose name matches a glob
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Case-insensitive glob match supporting `*` (any run) and `?` (single char)
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.to_lowercase().chars().collect();
+    let t: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut pi, mut ti) = (0usize, 0usize);
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    while pi < p.len() && p[pi] == '*' {
+        pi += 1;
+    }
+    pi == p.len()
+}
+
+/// Snapshot of (pid, name, memory_mb) for all non-protected processes matching `pattern`
+fn matching_processes(sys: &sysinfo::System, pattern: &str) -> Vec<(u32, String, f64)> {
+    let own_pid = std::process::id();
+    let mut matches: Vec<(u32, String, f64)> = sys
+        .processes()
+        .iter()
+        .filter_map(|(pid, proc_)| {
+            let name = proc_.name().to_string_lossy().to_string();
+            let lower = name.to_lowercase();
+            let pid_val = pid.as_u32();
+            if pid_val <= 4 || pid_val == own_pid {
+                return None;
+            }
+            if crate::PROTECTED_PROCESSES.contains(&lower.as_str()) {
+                return None;
+            }
+            if !glob_match(pattern, &name) {
+                return None;
+            }
+            Some((pid_val, name, proc_.memory() as f64 / 1_048_576.0))
+        })
+        .collect();
+    matches.sort_by_key(|m| m.0);
+    matches
+}
+
+/// Reject patterns that would match every process, such as "" or "*"
+fn validate_pattern(pattern: &str) -> Result<(), String> {
+    if pattern
+        .chars()
+        .all(|c| c == '*' || c == '?' || c.is_whitespace())
+    {
+        return Err("Pattern must contain at least one literal character".into());
+    }
+    Ok(())
+}
+
+/// Terminate every non-protected process whose name matches the glob `pattern`
+pub fn kill_processes_matching(pattern: &str) -> Result<Vec<ProcessActionResult>, String> {
+    validate_pattern(pattern)?;
+
+    let mut sys = sysinfo::System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    let targets = matching_processes(&sys, pattern);
+
+    #[cfg(windows)]
+    {
+        use winapi::um::errhandlingapi::GetLastError;
+        use winapi::um::handleapi::CloseHandle;
+        use winapi::um::processthreadsapi::{OpenProcess, TerminateProcess};
+        use winapi::um::winnt::PROCESS_TERMINATE;
+
+        crate::enable_debug_privilege();
+
+        let results = targets
+            .into_iter()
+            .map(|(pid, name, _mem)| unsafe {
+                let handle = OpenProcess(PROCESS_TERMINATE, 0, pid);
+                if handle.is_null() {
+                    let err = GetLastError();
+                    return ProcessActionResult {
+                        pid,
+                        name,
+                        success: false,
+                        message: format!("Cannot open process (error {})", err),
+                        freed_mb: None,
+                    };
+                }
+                let ok = TerminateProcess(handle, 1) != 0;
+                let err = GetLastError();
+                CloseHandle(handle);
+                ProcessActionResult {
+                    pid,
+                    name,
+                    success: ok,
+                    message: if ok {
+                        "Terminated".into()
+                    } else {
+                        format!("TerminateProcess failed (error {})", err)
+                    },
+                    freed_mb: None,
+                }
+            })
+            .collect();
+        return Ok(results);
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = targets;
+        Err("Not supported on this platform".into())
+    }
+}
+
+/// Trim the working set of every non-protected process whose name matches the glob `pattern`
+pub fn trim_processes_matching(pattern: &str) -> Result<Vec<ProcessActionResult>, String> {
+    validate_pattern(pattern)?;
+
+    let mut sys = sysinfo::System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    let targets = matching_processes(&sys, pattern);
+
+    #[cfg(windows)]
+    {
+        use winapi::um::errhandlingapi::GetLastError;
+        use winapi::um::handleapi::CloseHandle;
+        use winapi::um::processthreadsapi::OpenProcess;
+        use winapi::um::psapi::EmptyWorkingSet;
+        use winapi::um::winnt::{PROCESS_QUERY_INFORMATION, PROCESS_SET_QUOTA};
+
+        crate::enable_debug_privilege();
+
+        let mut results: Vec<(ProcessActionResult, f64)> = targets
+            .into_iter()
+            .map(|(pid, name, mem)| unsafe {
+                let handle = OpenProcess(PROCESS_SET_QUOTA | PROCESS_QUERY_INFORMATION, 0, pid);
+                if handle.is_null() {
+                    let err = GetLastError();
+                    let result = ProcessActionResult {
+                        pid,
+                        name,
+                        success: false,
+                        message: format!("Cannot open process (error {})", err),
+                        freed_mb: None,
+                    };
+                    return (result, mem);
+                }
+                let ok = EmptyWorkingSet(handle) != 0;
+                let err = GetLastError();
+                CloseHandle(handle);
+                let result = ProcessActionResult {
+                    pid,
+                    name,
+                    success: ok,
+                    message: if ok {
+                        "Working set trimmed".into()
+                    } else {
+                        format!("EmptyWorkingSet failed (error {})", err)
+                    },
+                    freed_mb: None,
+                };
+                (result, mem)
+            })
+            .collect();
+
+        // Re-measure to report actual memory freed
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+        for (result, before) in &mut results {
+            if result.success {
+                let after = sys
+                    .process(sysinfo::Pid::from_u32(result.pid))
+                    .map(|p| p.memory() as f64 / 1_048_576.0)
+                    .unwrap_or(0.0);
+                let freed = (*before - after).max(0.0);
+                result.freed_mb = Some(freed);
+                result.message = format!("Freed {:.1} MB", freed);
+            }
+        }
+        return Ok(results.into_iter().map(|(r, _)| r).collect());
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = targets;
+        Err("Not supported on this platform".into())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Working-set limits — soft caps persisted by process name
+// ═══════════════════════════════════════════════════════════════════════════════
+
+fn limits_path() -> std::path::PathBuf {
+    let base = std::env::var("LOCALAPPDATA").unwrap_or_else(|_| {
+        let profile = std::env::var("USERPROFILE").unwrap_or_else(|_| ".".into());
+        format!("{}\\AppData\\Local", profile)
+    });
+    std::path::PathBuf::from(base)
+        .join("VegaOptimizer")
+        .join("working_set_limits.json")
+}
+
+pub fn get_working_set_limits() -> Vec<WorkingSetLimit> {
+    std::fs::read_to_string(limits_path())
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_working_set_limits(limits: &[WorkingSetLimit]) -> Result<(), String> {
+    let path = limits_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(limits).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())
+}
+
+/// Apply min/max working-set sizes to one process.
+///
+/// The limits are soft: with `QUOTA_LIMITS_HARDWS_*_DISABLE` Windows lets the
+/// process grow past `max` when memory is plentiful and only trims it back toward
+/// the cap under pressure, so it never causes allocation failures.
+fn apply_working_set_limit(pid: u32, min_mb: u64, max_mb: u64) -> Result<(), String> {
+    let to_bytes = |mb: u64| {
+        mb.checked_mul(1_048_576)
+            .and_then(|b| usize::try_from(b).ok())
+            .ok_or_else(|| format!("{} MB is too large for a working-set limit", mb))
+    };
+    let (min_bytes, max_bytes) = (to_bytes(min_mb)?, to_bytes(max_mb)?);
+
+    #[cfg(windows)]
+    {
+        use winapi::um::errhandlingapi::GetLastError;
+        use winapi::um::handleapi::CloseHandle;
+        use winapi::um::memoryapi::SetProcessWorkingSetSizeEx;
+        use winapi::um::processthreadsapi::OpenProcess;
+        use winapi::um::winnt::{
+            PROCESS_QUERY_INFORMATION, PROCESS_SET_QUOTA, QUOTA_LIMITS_HARDWS_MAX_DISABLE,
+            QUOTA_LIMITS_HARDWS_MIN_DISABLE,
+        };
+
+        crate::enable_debug_privilege();
+
+        unsafe {
+            let handle = OpenProcess(PROCESS_SET_QUOTA | PROCESS_QUERY_INFORMATION, 0, pid);
+            if handle.is_null() {
+                return Err(format!("Cannot open process (error {})", GetLastError()));
+            }
+            let ok = SetProcessWorkingSetSizeEx(
+                handle,
+                min_bytes,
+                max_bytes,
+                QUOTA_LIMITS_HARDWS_MIN_DISABLE | QUOTA_LIMITS_HARDWS_MAX_DISABLE,
+            ) != 0;
+            let err = GetLastError();
+            CloseHandle(handle);
+            if !ok {
+                return Err(format!("SetProcessWorkingSetSizeEx failed (error {})", err));
+            }
+        }
+        return Ok(());
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = (pid, min_bytes, max_bytes);
+        Err("Not supported on this platform".into())
+    }
+}
+
+/// Cap a process's working set (soft limit) and remember it by name for reapplying
+pub fn set_process_working_set_limit(pid: u32, min_mb: u64, max_mb: u64) -> Result<String, String> {
+    if max_mb == 0 || min_mb > max_mb {
+        return Err("Maximum must be non-zero and at least the minimum".into());
+    }
+    // Windows requires min >= 20 pages; keep a sane floor
+    let min_mb = min_mb.max(1);
+
+    let mut sys = sysinfo::System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    let name = sys
+        .process(sysinfo::Pid::from_u32(pid))
+        .map(|p| p.name().to_string_lossy().to_lowercase())
+        .ok_or_else(|| format!("Process {} not found", pid))?;
+    if pid <= 4 || crate::PROTECTED_PROCESSES.contains(&name.as_str()) {
+        return Err(format!("{} is a protected system process", name));
+    }
+
+    apply_working_set_limit(pid, min_mb, max_mb)?;
+
+    let mut limits = get_working_set_limits();
+    limits.retain(|l| l.name != name);
+    limits.push(WorkingSetLimit {
+        name: name.clone(),
+        min_mb,
+        max_mb,
+    });
+    save_working_set_limits(&limits)?;
+
+    Ok(format!(
+        "Soft working-set limit {}–{} MB applied to {} (PID {}); Windows may exceed it when memory is free",
+        min_mb, max_mb, name, pid
+    ))
+}
+
+/// Forget a saved limit (running processes keep theirs until they exit)
+pub fn remove_working_set_limit(name: &str) -> Result<String, String> {
+    let name = name.to_lowercase();
+    let mut limits = get_working_set_limits();
+    let before = limits.len();
+    limits.retain(|l| l.name != name);
+    if limits.len() == before {
+        return Err(format!("No saved limit for {}", name));
+    }
+    save_working_set_limits(&limits)?;
+    Ok(format!("Removed working-set limit for {}", name))
+}
+
+/// Reapply every saved limit to currently running processes with a matching name
+pub fn reapply_working_set_limits() -> Vec<ProcessActionResult> {
+    let limits = get_working_set_limits();
+    if limits.is_empty() {
+        return Vec::new();
+    }
+
+    let mut sys = sysinfo::System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+
+    let mut results = Vec::new();
+    for (pid, proc_) in sys.processes() {
+        let name = proc_.name().to_string_lossy().to_lowercase();
+        let Some(limit) = limits.iter().find(|l| l.name == name) else {
+            continue;
+        };
+        if crate::PROTECTED_PROCESSES.contains(&name.as_str()) {
+            continue;
+        }
+        let outcome = apply_working_set_limit(pid.as_u32(), limit.min_mb, limit.max_mb);
+        results.push(ProcessActionResult {
+            pid: pid.as_u32(),
+            name,
+            success: outcome.is_ok(),
+            message: match outcome {
+                Ok(()) => format!("Limited to {}–{} MB", limit.min_mb, limit.max_mb),
+                Err(e) => e,
+            },
+            freed_mb: None,
+        });
+    }
+    results.sort_by_key(|r| r.pid);
+    results
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Suspend / resume — freeze a process without killing it
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuspendState {
+    pub pid: u32,
+    pub name: String,
+    pub suspended: bool,
+}
+
+/// (PID, start time) of processes suspended by us; Windows keeps no per-process flag we
+/// can cheaply query. The start time tells a reused PID apart.
+static SUSPENDED: Mutex<Vec<(u32, u64)>> = Mutex::new(Vec::new());
+
+#[cfg(windows)]
+#[link(name = "ntdll")]
+extern "system" {
+    fn NtSuspendProcess(process_handle: winapi::um::winnt::HANDLE) -> i32; // NTSTATUS
+    fn NtResumeProcess(process_handle: winapi::um::winnt::HANDLE) -> i32; // NTSTATUS
+}
+
+fn set_suspended(pid: u32, suspend: bool) -> Result<SuspendState, String> {
+    let mut sys = sysinfo::System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    let (name, started) = sys
+        .process(sysinfo::Pid::from_u32(pid))
+        .map(|p| (p.name().to_string_lossy().to_string(), p.start_time()))
+        .ok_or_else(|| format!("No process with PID {}", pid))?;
+    if pid <= 4
+        || pid == std::process::id()
+        || crate::PROTECTED_PROCESSES.contains(&name.to_lowercase().as_str())
+    {
+        return Err(format!("{} is a protected process", name));
+    }
+
+    #[cfg(windows)]
+    {
+        use winapi::um::errhandlingapi::GetLastError;
+        use winapi::um::handleapi::CloseHandle;
+        use winapi::um::processthreadsapi::OpenProcess;
+        use winapi::um::winnt::PROCESS_SUSPEND_RESUME;
+
+        crate::enable_debug_privilege();
+
+        // NtSuspendProcess nests: a second suspend would need two resumes, while the UI
+        // shows one toggle. Held across the call so two requests can't both pass the check.
+        let mut suspended = SUSPENDED.lock().unwrap_or_else(|e| e.into_inner());
+        if suspend && suspended.contains(&(pid, started)) {
+            return Err(format!("{} is already suspended", name));
+        }
+
+        let status = unsafe {
+            let handle = OpenProcess(PROCESS_SUSPEND_RESUME, 0, pid);
+            if handle.is_null() {
+                return Err(format!("Cannot open {} (error {})", name, GetLastError()));
+            }
+            let status = if suspend {
+                NtSuspendProcess(handle)
+            } else {
+                NtResumeProcess(handle)
+            };
+            CloseHandle(handle);
+            status
+        };
+        if status < 0 {
+            return Err(format!(
+                "{} {} failed (NTSTATUS: 0x{:08X})",
+                if suspend { "Suspending" } else { "Resuming" },
+                name,
+                status as u32
+            ));
+        }
+
+        suspended.retain(|&(p, _)| p != pid);
+        if suspend {
+            suspended.push((pid, started));
+        }
+        return Ok(SuspendState {
+            pid,
+            name,
+            suspended: suspend,
+        });
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = (suspend, started);
+        Err("Not supported on this platform".into())
+    }
+}
+
+pub fn suspend_process(pid: u32) -> Result<SuspendState, String> {
+    set_suspended(pid, true)
+}
+
+pub fn resume_process(pid: u32) -> Result<SuspendState, String> {
+    set_suspended(pid, false)
+}
+
+/// Processes we suspended that are still running (exited ones are forgotten)
+pub fn list_suspended() -> Vec<SuspendState> {
+    let mut sys = sysinfo::System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    let mut suspended = SUSPENDED.lock().unwrap_or_else(|e| e.into_inner());
+    suspended.retain(|&(pid, started)| {
+        sys.process(sysinfo::Pid::from_u32(pid))
+            .is_some_and(|p| p.start_time() == started)
+    });
+    suspended
+        .iter()
+        .filter_map(|&(pid, _)| {
+            sys.process(sysinfo::Pid::from_u32(pid))
+                .map(|p| SuspendState {
+                    pid,
+                    name: p.name().to_string_lossy().to_string(),
+                    suspended: true,
+                })
+        })
+        .collect()
+}
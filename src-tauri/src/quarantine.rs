@@ -0,0 +1,323 @@
+//! Quarantine — undo journal for browser and privacy cleanups
+//!
+//! Instead of deleting, a cleanup moves files into
+//! `%LOCALAPPDATA%\VegaOptimizer\quarantine\<session>\` under their original path
+//! (drive letter becomes the first folder) and writes a manifest. Registry keys
+//! are exported to a .reg file first. A session can be restored with
+//! `undo_cleanup` until it is purged.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Sessions older than this are purged automatically at startup
+pub const GRACE_DAYS: u64 = 7;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantineEntry {
+    pub original: String,
+    pub stored: String,
+    pub size_bytes: u64,
+    pub registry: bool, // stored is a .reg export of the original key
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantineManifest {
+    pub session_id: String,
+    pub label: String,
+    pub created_at: u64, // unix seconds
+    pub entries: Vec<QuarantineEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantineSessionInfo {
+    pub session_id: String,
+    pub label: String,
+    pub created_at: u64,
+    pub items: usize,
+    pub size_bytes: u64,
+}
+
+fn quarantine_root() -> PathBuf {
+    let base = std::env::var("LOCALAPPDATA").unwrap_or_else(|_| {
+        let profile = std::env::var("USERPROFILE").unwrap_or_else(|_| ".".into());
+        format!("{}\\AppData\\Local", profile)
+    });
+    PathBuf::from(base).join("VegaOptimizer").join("quarantine")
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Session ids are generated by us; reject anything that could escape the quarantine root
+fn session_dir(session_id: &str) -> Result<PathBuf, String> {
+    if session_id.is_empty()
+        || !session_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+    {
+        return Err(format!("Invalid quarantine session: {}", session_id));
+    }
+    Ok(quarantine_root().join(session_id))
+}
+
+/// "C:\Users\me\x" → "C\Users\me\x", so the original layout survives under the session folder
+fn relative_store_path(original: &Path) -> PathBuf {
+    let s = original.to_string_lossy().replace(':', "");
+    PathBuf::from(s.trim_start_matches(['\\', '/']))
+}
+
+fn path_size(path: &Path) -> u64 {
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => std::fs::read_dir(path)
+            .map(|entries| entries.flatten().map(|e| path_size(&e.path())).sum())
+            .unwrap_or(0),
+        Ok(meta) => meta.len(),
+        Err(_) => 0,
+    }
+}
+
+/// Rename, falling back to copy + delete when source and quarantine are on different volumes
+fn move_path(from: &Path, to: &Path) -> std::io::Result<()> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    let meta = std::fs::symlink_metadata(from)?;
+    if meta.is_dir() {
+        std::fs::create_dir_all(to)?;
+        for entry in std::fs::read_dir(from)?.flatten() {
+            move_path(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        std::fs::remove_dir(from)
+    } else {
+        std::fs::copy(from, to)?;
+        std::fs::remove_file(from)
+    }
+}
+
+/// One cleanup's worth of quarantined files; call `finish` to write the manifest
+pub struct QuarantineSession {
+    manifest: QuarantineManifest,
+    dir: PathBuf,
+}
+
+impl QuarantineSession {
+    pub fn new(label: &str) -> Self {
+        let created_at = now_secs();
+        // Nanos keep two cleanups in the same second apart
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .subsec_nanos();
+        let session_id = format!("{}-{:09}", created_at, nanos);
+        let dir = quarantine_root().join(&session_id);
+        Self {
+            manifest: QuarantineManifest {
+                session_id,
+                label: label.to_string(),
+                created_at,
+                entries: Vec::new(),
+            },
+            dir,
+        }
+    }
+
+    /// Move a file or directory into the session; returns its size in bytes
+    pub fn quarantine(&mut self, path: &Path) -> std::io::Result<u64> {
+        let size = path_size(path);
+        let stored = self.dir.join(relative_store_path(path));
+        if let Some(parent) = stored.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        if std::fs::rename(path, &stored).is_ok() {
+            self.record(path, &stored, size);
+            return Ok(size);
+        }
+        // Across volumes, move piece by piece and record each file as it lands, so a
+        // failure part-way still leaves everything already moved restorable
+        if std::fs::symlink_metadata(path)?.is_dir() {
+            let mut moved = 0u64;
+            for entry in std::fs::read_dir(path)?.flatten() {
+                moved += self.quarantine(&entry.path())?;
+            }
+            std::fs::remove_dir(path)?;
+            return Ok(moved);
+        }
+        std::fs::copy(path, &stored)?;
+        if let Err(e) = std::fs::remove_file(path) {
+            let _ = std::fs::remove_file(&stored);
+            return Err(e);
+        }
+        self.record(path, &stored, size);
+        Ok(size)
+    }
+
+    fn record(&mut self, original: &Path, stored: &Path, size: u64) {
+        self.manifest.entries.push(QuarantineEntry {
+            original: original.to_string_lossy().to_string(),
+            stored: stored.to_string_lossy().to_string(),
+            size_bytes: size,
+            registry: false,
+        });
+    }
+
+    /// Export a registry key before the caller deletes it
+    pub fn export_registry_key(&mut self, key: &str) -> Result<(), String> {
+        std::fs::create_dir_all(&self.dir).map_err(|e| e.to_string())?;
+        let index = self.manifest.entries.len();
+        let file = self.dir.join(format!("registry-{}.reg", index));
+        let output = Command::new("reg")
+            .args(["export", key])
+            .arg(&file)
+            .arg("/y")
+            .output()
+            .map_err(|e| e.to_string())?;
+        if !output.status.success() {
+            return Err(format!(
+                "Could not back up {}: {}",
+                key,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        self.manifest.entries.push(QuarantineEntry {
+            original: key.to_string(),
+            stored: file.to_string_lossy().to_string(),
+            size_bytes: 0,
+            registry: true,
+        });
+        Ok(())
+    }
+
+    /// Write the manifest; an empty session leaves nothing behind
+    pub fn finish(self) -> Result<String, String> {
+        if self.manifest.entries.is_empty() {
+            // Only unrecorded, empty folders can be here; never delete anything recursively
+            remove_empty_dirs(&self.dir);
+            return Ok(self.manifest.session_id);
+        }
+        std::fs::create_dir_all(&self.dir).map_err(|e| e.to_string())?;
+        let json = serde_json::to_string_pretty(&self.manifest).map_err(|e| e.to_string())?;
+        std::fs::write(self.dir.join("manifest.json"), json)
+            .map_err(|e| format!("Could not write quarantine manifest: {}", e))?;
+        Ok(self.manifest.session_id)
+    }
+}
+
+/// Remove `dir` and any empty folders under it, leaving anything that holds a file
+fn remove_empty_dirs(dir: &Path) {
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                remove_empty_dirs(&entry.path());
+            }
+        }
+    }
+    let _ = std::fs::remove_dir(dir);
+}
+
+fn load_manifest(dir: &Path) -> Option<QuarantineManifest> {
+    let data = std::fs::read_to_string(dir.join("manifest.json")).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+/// Quarantined cleanup sessions, newest first
+pub fn list_sessions() -> Vec<QuarantineSessionInfo> {
+    let mut sessions: Vec<QuarantineSessionInfo> = std::fs::read_dir(quarantine_root())
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| load_manifest(&e.path()))
+                .map(|m| QuarantineSessionInfo {
+                    items: m.entries.len(),
+                    size_bytes: m.entries.iter().map(|e| e.size_bytes).sum(),
+                    session_id: m.session_id,
+                    label: m.label,
+                    created_at: m.created_at,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    sessions.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    sessions
+}
+
+/// Put every file of a session back where it came from and re-import registry exports.
+///
+/// Files whose original path has been recreated meanwhile (a browser rebuilding
+/// its cache) are left in quarantine rather than overwritten.
+pub fn undo_cleanup(session_id: &str) -> Result<String, String> {
+    let dir = session_dir(session_id)?;
+    let manifest =
+        load_manifest(&dir).ok_or_else(|| format!("No quarantine session {}", session_id))?;
+
+    let mut restored = 0usize;
+    let mut failures = Vec::new();
+    for entry in &manifest.entries {
+        let stored = Path::new(&entry.stored);
+        if entry.registry {
+            match Command::new("reg").args(["import", &entry.stored]).output() {
+                Ok(o) if o.status.success() => restored += 1,
+                Ok(o) => failures.push(format!(
+                    "{}: {}",
+                    entry.original,
+                    String::from_utf8_lossy(&o.stderr).trim()
+                )),
+                Err(e) => failures.push(format!("{}: {}", entry.original, e)),
+            }
+            continue;
+        }
+        let original = Path::new(&entry.original);
+        if original.exists() {
+            failures.push(format!("{}: already exists", entry.original));
+            continue;
+        }
+        match move_path(stored, original) {
+            Ok(()) => restored += 1,
+            Err(e) => failures.push(format!("{}: {}", entry.original, e)),
+        }
+    }
+
+    if failures.is_empty() {
+        let _ = std::fs::remove_dir_all(&dir);
+        Ok(format!(
+            "Restored {} items from \"{}\"",
+            restored, manifest.label
+        ))
+    } else {
+        Err(format!(
+            "Restored {} of {} items; failed: {}",
+            restored,
+            manifest.entries.len(),
+            failures.join("; ")
+        ))
+    }
+}
+
+/// Permanently delete sessions older than `older_than_days` (0 purges everything)
+pub fn purge_quarantine(older_than_days: u64) -> Result<String, String> {
+    let cutoff = now_secs().saturating_sub(older_than_days * 86_400);
+    let mut purged = 0usize;
+    let mut freed = 0u64;
+    for session in list_sessions() {
+        if session.created_at > cutoff {
+            continue;
+        }
+        let dir = session_dir(&session.session_id)?;
+        if std::fs::remove_dir_all(&dir).is_ok() {
+            purged += 1;
+            freed += session.size_bytes;
+        }
+    }
+    Ok(format!(
+        "Purged {} quarantine sessions, freed {:.1} MB",
+        purged,
+        freed as f64 / 1_048_576.0
+    ))
+}
@@ -2,6 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::process::Command;
+use std::sync::Mutex;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegistryIssue {
@@ -13,6 +14,13 @@ pub struct RegistryIssue {
     pub safe_to_fix: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryFixRequest {
+    pub key_path: String,
+    pub value_name: String,
+    pub issue_type: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegistryScanResult {
     pub issues: Vec<RegistryIssue>,
@@ -24,22 +32,44 @@ pub struct RegistryScanResult {
 /// Scan registry for common issues
 pub fn scan_registry() -> RegistryScanResult {
     let start = std::time::Instant::now();
-    let mut issues = Vec::new();
 
-    // 1. Orphaned software entries — programs listed in Uninstall that don't exist
-    scan_orphaned_uninstall(&mut issues);
+    let scans: [fn(&mut Vec<RegistryIssue>); 7] = [
+        // 1. Orphaned software entries — programs listed in Uninstall that don't exist
+        scan_orphaned_uninstall,
+        // 2. Broken file associations
+        scan_broken_associations,
+        // 3. Invalid SharedDLLs paths
+        scan_shared_dlls,
+        // 4. Broken App Paths
+        scan_app_paths,
+        // 5. MUI Cache orphans
+        scan_mui_cache,
+        // 6. COM classes whose server binary is gone
+        scan_obsolete_clsid,
+        // 7. Empty keys left behind by uninstallers
+        scan_empty_keys,
+    ];
 
-    // 2. Broken file associations
-    scan_broken_associations(&mut issues);
-
-    // 3. Invalid SharedDLLs paths
-    scan_shared_dlls(&mut issues);
-
-    // 4. Broken App Paths
-    scan_app_paths(&mut issues);
-
-    // 5. MUI Cache orphans
-    scan_mui_cache(&mut issues);
+    // Each sub-scan waits on its own PowerShell, so run them side by side and
+    // collect the results in the order above
+    let mut issues = Vec::new();
+    std::thread::scope(|s| {
+        let workers: Vec<_> = scans
+            .iter()
+            .map(|scan| {
+                s.spawn(move || {
+                    let mut found = Vec::new();
+                    scan(&mut found);
+                    found
+                })
+            })
+            .collect();
+        for worker in workers {
+            if let Ok(found) = worker.join() {
+                issues.extend(found);
+            }
+        }
+    });
 
     // Tally by type
     let mut type_counts: std::collections::HashMap<String, usize> =
@@ -124,26 +154,113 @@ fn scan_broken_associations(issues: &mut Vec<RegistryIssue>) {
 }
 
 fn scan_shared_dlls(issues: &mut Vec<RegistryIssue>) {
+    #[cfg(windows)]
+    {
+        const SHARED_DLLS: &str = "HKLM\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\SharedDLLs";
+        let Some(key) = open_key(SHARED_DLLS) else {
+            return;
+        };
+        // The value names are the DLL paths
+        let missing = key
+            .enum_values()
+            .flatten()
+            .map(|(name, _)| name)
+            .filter(|name| !name.is_empty() && !std::path::Path::new(name).exists())
+            .take(50);
+        for path in missing {
+            issues.push(RegistryIssue {
+                key_path: SHARED_DLLS.into(),
+                description: format!("SharedDLL entry points to missing file: {}", path),
+                value_name: path,
+                issue_type: "invalid_path".into(),
+                severity: "low".into(),
+                safe_to_fix: true,
+            });
+        }
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = issues;
+    }
+}
+
+fn scan_app_paths(issues: &mut Vec<RegistryIssue>) {
+    #[cfg(windows)]
+    {
+        const APP_PATHS: &str = "HKLM\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\App Paths";
+        let Some(app_paths) = open_key(APP_PATHS) else {
+            return;
+        };
+        let mut found = 0;
+        for name in app_paths.enum_keys().flatten() {
+            let Ok(target) = app_paths
+                .open_subkey(&name)
+                .and_then(|k| k.get_value::<String, _>(""))
+            else {
+                continue;
+            };
+            // Some installers quote the path; the file itself is what matters
+            let target = crate::startup::expand_env_vars(target.trim().trim_matches('"'));
+            if target.is_empty() || std::path::Path::new(&target).exists() {
+                continue;
+            }
+            issues.push(RegistryIssue {
+                key_path: APP_PATHS.into(),
+                description: format!("App path for '{}' points to missing: {}", name, target),
+                value_name: name,
+                issue_type: "orphaned_software".into(),
+                severity: "medium".into(),
+                safe_to_fix: true,
+            });
+            found += 1;
+            if found == 50 {
+                break;
+            }
+        }
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = issues;
+    }
+}
+
+/// Open `HKLM\...` or `HKCU\...` for reading
+#[cfg(windows)]
+pub fn open_key(path: &str) -> Option<winreg::RegKey> {
+    use winreg::enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+
+    let (hive, subkey) = path.split_once('\\')?;
+    let root = match hive.to_uppercase().as_str() {
+        "HKLM" | "HKEY_LOCAL_MACHINE" => HKEY_LOCAL_MACHINE,
+        "HKCU" | "HKEY_CURRENT_USER" => HKEY_CURRENT_USER,
+        _ => return None,
+    };
+    winreg::RegKey::predef(root).open_subkey(subkey).ok()
+}
+
+fn scan_mui_cache(issues: &mut Vec<RegistryIssue>) {
     if let Ok(output) = Command::new("powershell")
         .args(["-Command", r#"
-            $key = Get-Item 'HKLM:\SOFTWARE\Microsoft\Windows\CurrentVersion\SharedDLLs' -ErrorAction SilentlyContinue
-            if($key) {
-                $key.GetValueNames() | ForEach-Object {
-                    if($_ -and !(Test-Path $_ -ErrorAction SilentlyContinue)) { $_ }
-                } | Select-Object -First 50
+            $path = "HKCU:\SOFTWARE\Classes\Local Settings\Software\Microsoft\Windows\Shell\MuiCache"
+            if(Test-Path $path) {
+                $key = Get-Item $path
+                $key.GetValueNames() | Where-Object { $_ -match '^[A-Z]:\\' } | ForEach-Object {
+                    $file = ($_ -split '\\.')[0..($_.Split('.').Count-2)] -join '.'
+                    if(!(Test-Path $file -ErrorAction SilentlyContinue)) { $_ }
+                } | Select-Object -First 30
             }
         "#])
         .output()
     {
         let stdout = String::from_utf8_lossy(&output.stdout);
         for line in stdout.lines() {
-            let path = line.trim();
-            if !path.is_empty() {
+            let entry = line.trim();
+            if !entry.is_empty() {
                 issues.push(RegistryIssue {
-                    key_path: "HKLM\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\SharedDLLs".into(),
-                    value_name: path.to_string(),
-                    issue_type: "invalid_path".into(),
-                    description: format!("SharedDLL entry points to missing file: {}", path),
+                    key_path: "HKCU\\SOFTWARE\\Classes\\Local Settings\\MuiCache".into(),
+                    value_name: entry.to_string(),
+                    issue_type: "orphaned_software".into(),
+                    description: format!("MUI cache entry for removed application: {}", entry.split('\\').last().unwrap_or(entry)),
                     severity: "low".into(),
                     safe_to_fix: true,
                 });
@@ -152,84 +269,299 @@ fn scan_shared_dlls(issues: &mut Vec<RegistryIssue>) {
     }
 }
 
-fn scan_app_paths(issues: &mut Vec<RegistryIssue>) {
+fn scan_obsolete_clsid(issues: &mut Vec<RegistryIssue>) {
+    // Only rooted paths are checked — bare names like "ole32.dll" resolve via the search path.
+    // A class is flagged only when every server it registers is gone: the fix removes the
+    // whole CLSID key, which would take a working sibling server with it.
     if let Ok(output) = Command::new("powershell")
         .args(["-Command", r#"
-            Get-ChildItem 'HKLM:\SOFTWARE\Microsoft\Windows\CurrentVersion\App Paths' -ErrorAction SilentlyContinue | ForEach-Object {
-                $p = (Get-ItemProperty $_.PSPath -ErrorAction SilentlyContinue).'(default)'
-                $name = $_.PSChildName
-                if($p -and $p -ne '' -and !(Test-Path $p -ErrorAction SilentlyContinue)) {
-                    "$name|$p"
+            Get-ChildItem 'HKLM:\SOFTWARE\Classes\CLSID' -ErrorAction SilentlyContinue | ForEach-Object {
+                $clsid = $_.PSChildName
+                $dead = @(); $files = @(); $live = $false
+                foreach($server in @('InprocServer32','LocalServer32')) {
+                    $raw = (Get-ItemProperty "$($_.PSPath)\$server" -ErrorAction SilentlyContinue).'(default)'
+                    if(!$raw) { continue }
+                    $exp = [Environment]::ExpandEnvironmentVariables($raw).Trim()
+                    if($exp.StartsWith('"')) { $candidates = @($exp.Split('"')[1]) }
+                    else {
+                        # Unquoted: like CreateProcess, try the path ending at each space in turn
+                        $words = $exp -split ' '
+                        $candidates = @(for($i = 0; $i -lt $words.Count; $i++) { $words[0..$i] -join ' ' })
+                    }
+                    if($candidates[0] -notmatch '^[A-Za-z]:\\' -or $exp -match '%') { $live = $true; continue }
+                    $found = $candidates | Where-Object {
+                        (Test-Path -LiteralPath $_ -PathType Leaf -ErrorAction SilentlyContinue) -or
+                        (Test-Path -LiteralPath "$_.exe" -PathType Leaf -ErrorAction SilentlyContinue)
+                    }
+                    if($found) { $live = $true } else { $dead += $server; $files += $candidates[-1] }
                 }
+                if(!$live -and $dead.Count -gt 0) { "$clsid|$($dead -join ', ')|$($files -join '; ')" }
             } | Select-Object -First 50
         "#])
         .output()
     {
+        let windir = std::env::var("SystemRoot")
+            .unwrap_or_else(|_| "C:\\Windows".into())
+            .to_lowercase();
         let stdout = String::from_utf8_lossy(&output.stdout);
         for line in stdout.lines() {
             let parts: Vec<&str> = line.split('|').collect();
-            if parts.len() >= 2 {
+            if parts.len() >= 3 {
+                let file = parts[2].trim();
+                // Classes registered by Windows itself are left for the user to judge
+                let system = file
+                    .split("; ")
+                    .any(|f| f.to_lowercase().starts_with(&windir));
                 issues.push(RegistryIssue {
-                    key_path: "HKLM\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\App Paths".into(),
+                    key_path: format!("HKLM\\SOFTWARE\\Classes\\CLSID\\{}", parts[0].trim()),
                     value_name: parts[0].trim().to_string(),
-                    issue_type: "orphaned_software".into(),
-                    description: format!("App path for '{}' points to missing: {}", parts[0].trim(), parts[1].trim()),
-                    severity: "medium".into(),
-                    safe_to_fix: true,
+                    issue_type: "obsolete_clsid".into(),
+                    description: format!("COM class {} missing: {}", parts[1].trim(), file),
+                    severity: if system { "medium".into() } else { "low".into() },
+                    safe_to_fix: !system,
                 });
             }
         }
     }
 }
 
-fn scan_mui_cache(issues: &mut Vec<RegistryIssue>) {
+fn scan_empty_keys(issues: &mut Vec<RegistryIssue>) {
+    // Vendor keys under Software plus per-app Uninstall/App Paths entries; the shared
+    // top-level keys Windows relies on even when empty are skipped
     if let Ok(output) = Command::new("powershell")
         .args(["-Command", r#"
-            $path = "HKCU:\SOFTWARE\Classes\Local Settings\Software\Microsoft\Windows\Shell\MuiCache"
-            if(Test-Path $path) {
-                $key = Get-Item $path
-                $key.GetValueNames() | Where-Object { $_ -match '^[A-Z]:\\' } | ForEach-Object {
-                    $file = ($_ -split '\\.')[0..($_.Split('.').Count-2)] -join '.'
-                    if(!(Test-Path $file -ErrorAction SilentlyContinue)) { $_ }
-                } | Select-Object -First 30
+            $skip = @('Classes','Clients','Microsoft','Policies','RegisteredApplications','Wow6432Node')
+            $roots = @('HKCU:\Software','HKLM:\SOFTWARE','HKLM:\SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall','HKLM:\SOFTWARE\Microsoft\Windows\CurrentVersion\App Paths')
+            foreach($root in $roots) {
+                Get-ChildItem $root -ErrorAction SilentlyContinue | Where-Object {
+                    $skip -notcontains $_.PSChildName -and $_.SubKeyCount -eq 0 -and $_.ValueCount -eq 0
+                } | ForEach-Object { $_.Name }
             }
         "#])
         .output()
     {
         let stdout = String::from_utf8_lossy(&output.stdout);
-        for line in stdout.lines() {
-            let entry = line.trim();
-            if !entry.is_empty() {
-                issues.push(RegistryIssue {
-                    key_path: "HKCU\\SOFTWARE\\Classes\\Local Settings\\MuiCache".into(),
-                    value_name: entry.to_string(),
-                    issue_type: "orphaned_software".into(),
-                    description: format!("MUI cache entry for removed application: {}", entry.split('\\').last().unwrap_or(entry)),
-                    severity: "low".into(),
-                    safe_to_fix: true,
-                });
+        for line in stdout.lines().take(50) {
+            let key = line
+                .trim()
+                .replace("HKEY_LOCAL_MACHINE\\", "HKLM\\")
+                .replace("HKEY_CURRENT_USER\\", "HKCU\\");
+            if key.is_empty() {
+                continue;
             }
+            let leaf = key.rsplit('\\').next().unwrap_or(&key).to_string();
+            issues.push(RegistryIssue {
+                description: format!("Empty key left behind: {}", key),
+                key_path: key,
+                value_name: leaf,
+                issue_type: "empty_key".into(),
+                severity: "low".into(),
+                safe_to_fix: true,
+            });
         }
     }
 }
 
-/// Fix a specific registry issue (delete orphaned key/value)
+/// When the last restore point for registry fixes was taken (or found to exist)
+static LAST_RESTORE_POINT: Mutex<Option<std::time::Instant>> = Mutex::new(None);
+
+/// Fixes within this window of a restore point are treated as one batch
+const RESTORE_POINT_BATCH_SECS: u64 = 30 * 60;
+
+/// Create a System Restore point via `Checkpoint-Computer`
+pub fn create_restore_point(description: &str) -> Result<(), String> {
+    crate::tweaks::create_restore_point(description).map(|_| ())
+}
+
+/// Create at most one restore point per batch of registry fixes
+fn ensure_batch_restore_point() -> Result<(), String> {
+    let mut last = LAST_RESTORE_POINT.lock().unwrap_or_else(|e| e.into_inner());
+    if last.is_some_and(|t| t.elapsed().as_secs() < RESTORE_POINT_BATCH_SECS) {
+        return Ok(());
+    }
+    match create_restore_point("VegaOptimizer registry fixes") {
+        Ok(()) => {}
+        // Windows refuses a second point within 24 hours — the recent one covers us
+        Err(e) if e.contains("24 hours") => log::info!("Using existing restore point: {}", e),
+        Err(e) => return Err(e),
+    }
+    *last = Some(std::time::Instant::now());
+    Ok(())
+}
+
+fn backups_dir() -> std::path::PathBuf {
+    let base = std::env::var("LOCALAPPDATA").unwrap_or_else(|_| {
+        let profile = std::env::var("USERPROFILE").unwrap_or_else(|_| ".".into());
+        format!("{}\\AppData\\Local", profile)
+    });
+    std::path::PathBuf::from(base)
+        .join("VegaOptimizer")
+        .join("backups")
+}
+
+/// Export `key_path` to a timestamped `.reg` file under the backups folder
+fn export_key_backup(key_path: &str) -> Result<String, String> {
+    let dir = backups_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Cannot create backup folder: {}", e))?;
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    let leaf: String = key_path
+        .rsplit('\\')
+        .next()
+        .unwrap_or("key")
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(48)
+        .collect();
+    // Fixes under a shared parent key export the same leaf; nanos and a counter keep each
+    // backup separate so an earlier undo is never overwritten
+    let stamp = format!("{}-{:09}", now.as_secs(), now.subsec_nanos());
+    let mut file = dir.join(format!("registry-{}-{}.reg", stamp, leaf));
+    let mut n = 1;
+    while file.exists() {
+        file = dir.join(format!("registry-{}-{}-{}.reg", stamp, leaf, n));
+        n += 1;
+    }
+    let file_str = file.to_string_lossy().to_string();
+
+    match Command::new("reg")
+        .args(["export", key_path, &file_str, "/y"])
+        .output()
+    {
+        Ok(o) if o.status.success() => Ok(file_str),
+        Ok(o) => Err(format!(
+            "reg export failed: {}",
+            String::from_utf8_lossy(&o.stderr).trim()
+        )),
+        Err(e) => Err(format!("reg export failed: {}", e)),
+    }
+}
+
+/// Whether `apply_registry_fix` will change anything for this issue (mirrors its branches)
+fn fix_modifies_registry(key_path: &str, issue_type: &str) -> bool {
+    key_path.contains("SharedDLLs")
+        || key_path.contains("MuiCache")
+        || (issue_type == "broken_shortcut" && key_path.contains("Classes"))
+        || (issue_type == "orphaned_software"
+            && (key_path.contains("Uninstall") || key_path.contains("App Paths")))
+        || issue_type == "invalid_path"
+        || issue_type == "obsolete_clsid"
+        || issue_type == "empty_key"
+}
+
+/// Import a `.reg` backup written by `fix_registry_issue`
+pub fn restore_from_backup(reg_file: &str) -> Result<String, String> {
+    let path = std::path::Path::new(reg_file);
+    let is_reg = path
+        .extension()
+        .map(|e| e.eq_ignore_ascii_case("reg"))
+        .unwrap_or(false);
+    if !is_reg {
+        return Err("Not a .reg file".into());
+    }
+    // Only import our own backups — an arbitrary .reg file can rewrite anything
+    let canonical = path
+        .canonicalize()
+        .map_err(|e| format!("Backup not found: {}", e))?;
+    let dir = backups_dir()
+        .canonicalize()
+        .unwrap_or_else(|_| backups_dir());
+    if !canonical.starts_with(&dir) {
+        return Err(format!(
+            "Only backups in {} can be restored",
+            backups_dir().display()
+        ));
+    }
+
+    match Command::new("reg").args(["import", reg_file]).output() {
+        Ok(o) if o.status.success() => Ok(format!("Restored registry backup {}", reg_file)),
+        Ok(o) => Err(format!(
+            "reg import failed: {}",
+            String::from_utf8_lossy(&o.stderr).trim()
+        )),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Fix a specific registry issue (delete orphaned key/value).
+///
+/// The affected key is exported to a `.reg` file first and its path is included in
+/// the success message. With `restore_point`, a System Restore point is created
+/// before the first fix of a batch; if either safeguard fails nothing is changed.
 pub fn fix_registry_issue(
     key_path: &str,
     value_name: &str,
     issue_type: &str,
+    restore_point: bool,
 ) -> Result<String, String> {
     // Only fix known safe types
     match issue_type {
-        "orphaned_software" | "broken_shortcut" | "invalid_path" => {}
+        "orphaned_software" | "broken_shortcut" | "invalid_path" | "obsolete_clsid"
+        | "empty_key" => {}
         _ => return Err("This issue type cannot be auto-fixed".into()),
     }
 
+    if restore_point {
+        ensure_batch_restore_point()
+            .map_err(|e| format!("No changes made — restore point failed: {}", e))?;
+    }
+
+    let backup = if fix_modifies_registry(key_path, issue_type) {
+        Some(
+            export_key_backup(key_path)
+                .map_err(|e| format!("No changes made — backup failed: {}", e))?,
+        )
+    } else {
+        None
+    };
+    let msg = apply_registry_fix(key_path, value_name, issue_type)?;
+    Ok(match backup {
+        Some(path) => format!("{} (backup: {})", msg, path),
+        None => msg,
+    })
+}
+
+/// Perform the deletion for an already-validated issue type
+fn apply_registry_fix(
+    key_path: &str,
+    value_name: &str,
+    issue_type: &str,
+) -> Result<String, String> {
     // Convert backslash-based paths to PowerShell PSProvider paths
     let ps_path = key_path
         .replace("HKLM\\", "HKLM:\\")
         .replace("HKCU\\", "HKCU:\\");
 
+    // Orphaned COM class — remove the whole CLSID key
+    if issue_type == "obsolete_clsid" {
+        return run_ps_fix(
+            &format!(
+                "Remove-Item -LiteralPath '{}' -Recurse -Force -ErrorAction Stop",
+                ps_path.replace('\'', "''")
+            ),
+            &format!("Removed orphaned COM class {}", value_name),
+        );
+    }
+
+    // Empty key — re-checked so anything written since the scan is never lost
+    if issue_type == "empty_key" {
+        return run_ps_fix(
+            &format!(
+                "$k = Get-Item -LiteralPath '{0}' -ErrorAction Stop; if($k.SubKeyCount -eq 0 -and $k.ValueCount -eq 0) {{ Remove-Item -LiteralPath '{0}' -Force -ErrorAction Stop }} else {{ throw 'Key is no longer empty' }}",
+                ps_path.replace('\'', "''")
+            ),
+            &format!("Removed empty key {}", value_name),
+        );
+    }
+
     // SharedDLLs entries — remove the value (the value name IS the file path)
     if key_path.contains("SharedDLLs") {
         return run_ps_fix(
@@ -292,6 +624,135 @@ pub fn fix_registry_issue(
     Ok(format!("Marked for review: {} — {}", key_path, value_name))
 }
 
+/// SharedDLLs removals per PowerShell spawn — keeps the command line under Windows' 32K limit
+const SHARED_DLL_BATCH: usize = 40;
+
+/// Fix many registry issues at once, returning `(value_name, success, message)` in input order.
+///
+/// SharedDLLs values (usually the bulk of a scan) are removed in a few PowerShell
+/// sessions instead of one per value, after a single `.reg` backup per key. Everything
+/// else goes through `fix_registry_issue`. A restore point, if requested, is taken once.
+pub fn fix_registry_issues(
+    issues: Vec<RegistryFixRequest>,
+    restore_point: bool,
+) -> Vec<(String, bool, String)> {
+    if restore_point {
+        if let Err(e) = ensure_batch_restore_point() {
+            let msg = format!("No changes made — restore point failed: {}", e);
+            return issues
+                .into_iter()
+                .map(|i| (i.value_name, false, msg.clone()))
+                .collect();
+        }
+    }
+
+    let mut results: Vec<Option<(String, bool, String)>> = vec![None; issues.len()];
+    let mut shared: std::collections::BTreeMap<String, Vec<(usize, String)>> =
+        std::collections::BTreeMap::new();
+
+    for (idx, issue) in issues.iter().enumerate() {
+        let fixable = matches!(
+            issue.issue_type.as_str(),
+            "orphaned_software" | "broken_shortcut" | "invalid_path"
+        );
+        if fixable && issue.key_path.contains("SharedDLLs") {
+            shared
+                .entry(issue.key_path.clone())
+                .or_default()
+                .push((idx, issue.value_name.clone()));
+        } else {
+            let outcome =
+                fix_registry_issue(&issue.key_path, &issue.value_name, &issue.issue_type, false);
+            results[idx] = Some(match outcome {
+                Ok(msg) => (issue.value_name.clone(), true, msg),
+                Err(e) => (issue.value_name.clone(), false, e.trim().to_string()),
+            });
+        }
+    }
+
+    for (key_path, values) in shared {
+        let backup = match export_key_backup(&key_path) {
+            Ok(path) => path,
+            Err(e) => {
+                for (idx, name) in values {
+                    results[idx] = Some((
+                        name,
+                        false,
+                        format!("No changes made — backup failed: {}", e),
+                    ));
+                }
+                continue;
+            }
+        };
+        let ps_path = key_path
+            .replace("HKLM\\", "HKLM:\\")
+            .replace("HKCU\\", "HKCU:\\");
+
+        for chunk in values.chunks(SHARED_DLL_BATCH) {
+            let script: String = chunk
+                .iter()
+                .map(|(idx, name)| {
+                    format!(
+                        "try {{ Remove-ItemProperty -Path '{}' -Name '{}' -ErrorAction Stop; 'OK|{}' }} catch {{ \"ERR|{}|$($_.Exception.Message)\" }}\n",
+                        ps_path.replace('\'', "''"),
+                        name.replace('\'', "''"),
+                        idx,
+                        idx
+                    )
+                })
+                .collect();
+
+            let mut outcomes: std::collections::HashMap<usize, Result<(), String>> =
+                std::collections::HashMap::new();
+            match Command::new("powershell")
+                .args(["-Command", &script])
+                .output()
+            {
+                Ok(o) => {
+                    let stdout = String::from_utf8_lossy(&o.stdout);
+                    for line in stdout.lines() {
+                        let mut parts = line.trim().splitn(3, '|');
+                        let status = parts.next().unwrap_or("");
+                        let Some(idx) = parts.next().and_then(|i| i.parse::<usize>().ok()) else {
+                            continue;
+                        };
+                        match status {
+                            "OK" => outcomes.insert(idx, Ok(())),
+                            "ERR" => {
+                                outcomes.insert(idx, Err(parts.next().unwrap_or("").to_string()))
+                            }
+                            _ => None,
+                        };
+                    }
+                }
+                Err(e) => {
+                    for (idx, _) in chunk {
+                        outcomes.insert(*idx, Err(e.to_string()));
+                    }
+                }
+            }
+
+            for (idx, name) in chunk {
+                results[*idx] = Some(match outcomes.remove(idx) {
+                    Some(Ok(())) => (
+                        name.clone(),
+                        true,
+                        format!("Removed SharedDLL entry: {} (backup: {})", name, backup),
+                    ),
+                    Some(Err(e)) => (name.clone(), false, e),
+                    None => (name.clone(), false, "No result from PowerShell".into()),
+                });
+            }
+        }
+    }
+
+    results
+        .into_iter()
+        .zip(issues)
+        .map(|(r, issue)| r.unwrap_or((issue.value_name, false, "Not processed".into())))
+        .collect()
+}
+
 /// Helper: execute a PowerShell fix command and return a result
 fn run_ps_fix(command: &str, success_msg: &str) -> Result<String, String> {
     match Command::new("powershell")
@@ -311,3 +772,198 @@ fn run_ps_fix(command: &str, success_msg: &str) -> Result<String, String> {
         Err(e) => Err(e.to_string()),
     }
 }
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Hive Compaction — rewrite user hives into fresh, defragmented files
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HiveCompaction {
+    pub name: String,      // "NTUSER.DAT", "UsrClass.dat"
+    pub root_key: String,  // key passed to `reg save`
+    pub hive_path: String, // on-disk hive file
+    pub before_bytes: u64,
+    pub after_bytes: u64,
+    pub saved_bytes: u64,
+    pub scheduled: bool, // offline compaction queued for next startup
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryCompactionResult {
+    pub hives: Vec<HiveCompaction>,
+    pub total_before_bytes: u64,
+    pub total_after_bytes: u64,
+    pub reboot_required: bool,
+    pub message: String,
+}
+
+/// Compact the current user's hives.
+///
+/// `reg save` writes a hive into a brand-new file with no free cells, which is the
+/// supported way to get a compacted copy. Measuring uses the live hive, but a copy
+/// taken now and swapped in later would roll back every change made in between, so
+/// with `apply` the compaction itself is left to a one-shot startup task (SYSTEM,
+/// requires admin). At boot, before anyone signs in, the task loads each hive
+/// offline, saves a compacted copy and swaps it in, keeping the original next to it
+/// as `.vega-backup`. Without `apply` this only measures the savings.
+pub fn compact_registry(apply: bool) -> Result<RegistryCompactionResult, String> {
+    let profile = std::env::var("USERPROFILE").map_err(|_| "USERPROFILE not set")?;
+    let local =
+        std::env::var("LOCALAPPDATA").unwrap_or_else(|_| format!("{}\\AppData\\Local", profile));
+
+    let targets = [
+        (
+            "NTUSER.DAT",
+            "HKCU".to_string(),
+            format!("{}\\NTUSER.DAT", profile),
+        ),
+        (
+            "UsrClass.dat",
+            "HKCU\\Software\\Classes".to_string(),
+            format!("{}\\Microsoft\\Windows\\UsrClass.dat", local),
+        ),
+    ];
+
+    let mut hives = Vec::new();
+    for (name, root_key, hive_path) in targets {
+        hives.push(measure_hive(name, &root_key, &hive_path));
+    }
+
+    let ok: Vec<&HiveCompaction> = hives.iter().filter(|h| h.error.is_none()).collect();
+    if ok.is_empty() {
+        let first = hives
+            .iter()
+            .find_map(|h| h.error.clone())
+            .unwrap_or_default();
+        return Err(format!("Could not compact any registry hive: {}", first));
+    }
+
+    let total_before_bytes: u64 = ok.iter().map(|h| h.before_bytes).sum();
+    let total_after_bytes: u64 = ok.iter().map(|h| h.after_bytes).sum();
+    let saved_mb = total_before_bytes.saturating_sub(total_after_bytes) as f64 / 1_048_576.0;
+
+    if apply {
+        let worth: Vec<String> = hives
+            .iter()
+            .filter(|h| h.error.is_none() && h.saved_bytes > 0)
+            .map(|h| h.hive_path.clone())
+            .collect();
+        if !worth.is_empty() {
+            schedule_boot_compaction(&worth)?;
+            for hive in hives.iter_mut() {
+                hive.scheduled = worth.contains(&hive.hive_path);
+            }
+        }
+    }
+
+    let reboot_required = hives.iter().any(|h| h.scheduled);
+    let message = if reboot_required {
+        format!(
+            "Compaction scheduled — about {:.1} MB will be reclaimed at the next startup",
+            saved_mb
+        )
+    } else {
+        format!("Compaction would reclaim {:.1} MB", saved_mb)
+    };
+
+    Ok(RegistryCompactionResult {
+        hives,
+        total_before_bytes,
+        total_after_bytes,
+        reboot_required,
+        message,
+    })
+}
+
+/// Measure a hive by saving a compacted copy of it; the copy is discarded
+fn measure_hive(name: &str, root_key: &str, hive_path: &str) -> HiveCompaction {
+    let mut result = HiveCompaction {
+        name: name.to_string(),
+        root_key: root_key.to_string(),
+        hive_path: hive_path.to_string(),
+        before_bytes: std::fs::metadata(hive_path).map(|m| m.len()).unwrap_or(0),
+        after_bytes: 0,
+        saved_bytes: 0,
+        scheduled: false,
+        error: None,
+    };
+
+    if result.before_bytes == 0 {
+        result.error = Some("Hive file not found".into());
+        return result;
+    }
+
+    let staged = format!("{}.vega-compact", hive_path);
+    let _ = std::fs::remove_file(&staged);
+    if let Err(e) = reg_save(root_key, &staged) {
+        result.error = Some(e);
+        return result;
+    }
+    result.after_bytes = std::fs::metadata(&staged).map(|m| m.len()).unwrap_or(0);
+    result.saved_bytes = result.before_bytes.saturating_sub(result.after_bytes);
+    let _ = std::fs::remove_file(&staged);
+    result
+}
+
+/// `reg save <key> <file> /y` — writes a fresh, compacted copy of a loaded hive
+fn reg_save(root_key: &str, file: &str) -> Result<(), String> {
+    let output = Command::new("reg")
+        .args(["save", root_key, file, "/y"])
+        .output()
+        .map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Err(if stderr.is_empty() { stdout } else { stderr })
+    }
+}
+
+/// Register a one-shot SYSTEM task that compacts `hives` offline at the next startup.
+///
+/// The hives aren't loaded before sign-in, so each one is loaded under a temporary key,
+/// saved compacted and swapped in. A hive still in use (an automatic sign-in won the
+/// race) fails to load and is left as it is. The task removes itself once it has run.
+fn schedule_boot_compaction(hives: &[String]) -> Result<(), String> {
+    let list = hives
+        .iter()
+        .map(|h| format!("'{}'", h.replace('\'', "''")))
+        .collect::<Vec<_>>()
+        .join(",");
+    let script = format!(
+        r#"
+$boot = @'
+foreach($hive in @({})) {{
+    $staged = "$hive.vega-compact"
+    Remove-Item -LiteralPath $staged -Force -ErrorAction SilentlyContinue
+    & reg.exe load 'HKU\VegaCompact' $hive | Out-Null
+    if($LASTEXITCODE -ne 0) {{ continue }}
+    & reg.exe save 'HKU\VegaCompact' $staged /y | Out-Null
+    $saved = $LASTEXITCODE
+    & reg.exe unload 'HKU\VegaCompact' | Out-Null
+    if($saved -ne 0 -or $LASTEXITCODE -ne 0 -or !(Test-Path -LiteralPath $staged) -or (Get-Item -LiteralPath $staged).Length -eq 0) {{
+        Remove-Item -LiteralPath $staged -Force -ErrorAction SilentlyContinue
+        continue
+    }}
+    Copy-Item -LiteralPath $hive -Destination "$hive.vega-backup" -Force
+    Move-Item -LiteralPath $staged -Destination $hive -Force
+}}
+Unregister-ScheduledTask -TaskName 'CompactHives' -TaskPath '\VegaOptimizer\' -Confirm:$false
+'@
+$enc = [Convert]::ToBase64String([Text.Encoding]::Unicode.GetBytes($boot))
+$action = New-ScheduledTaskAction -Execute 'powershell.exe' -Argument "-NoProfile -NonInteractive -EncodedCommand $enc"
+$trigger = New-ScheduledTaskTrigger -AtStartup
+$principal = New-ScheduledTaskPrincipal -UserId 'SYSTEM' -LogonType ServiceAccount -RunLevel Highest
+Register-ScheduledTask -TaskName 'CompactHives' -TaskPath '\VegaOptimizer\' -Action $action -Trigger $trigger -Principal $principal -Force -ErrorAction Stop | Out-Null
+"#,
+        list
+    );
+    run_ps_fix(&script, "").map(|_| ()).map_err(|e| {
+        format!(
+            "Could not schedule hive compaction (run as administrator): {}",
+            e.trim()
+        )
+    })
+}
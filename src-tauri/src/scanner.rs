@@ -2,6 +2,34 @@
 
 use serde::{Deserialize, Serialize};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use crate::quarantine::QuarantineSession;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Scan cancellation — shared by the large-file and duplicate scans
+// ═══════════════════════════════════════════════════════════════════════════════
+
+static SCAN_CANCEL: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+
+fn scan_cancel_token() -> Arc<AtomicBool> {
+    SCAN_CANCEL
+        .get_or_init(|| Arc::new(AtomicBool::new(false)))
+        .clone()
+}
+
+/// Clear any earlier cancellation and hand out the token a new scan should poll
+pub fn begin_scan() -> Arc<AtomicBool> {
+    let token = scan_cancel_token();
+    token.store(false, Ordering::SeqCst);
+    token
+}
+
+/// Ask every running scan to stop and return what it has gathered so far
+pub fn cancel_scan() {
+    scan_cancel_token().store(true, Ordering::SeqCst);
+}
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // Large File Scanner
@@ -15,98 +43,266 @@ pub struct LargeFile {
     pub category: String,
     pub modified: String,
     pub ai_tooltip: Option<String>,
+    pub token: String, // size/mtime identity, re-verified before delete
 }
 
-pub fn scan_large_files(min_size_mb: u64, max_results: usize) -> Vec<LargeFile> {
-    let mut files: Vec<LargeFile> = Vec::new();
-    let min_bytes = min_size_mb * 1_048_576;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LargeFileScanResult {
+    pub files: Vec<LargeFile>,
+    pub cancelled: bool, // stopped early — `files` holds partial results
+}
 
-    let skip_dirs = [
-        "Windows",
-        "Program Files",
-        "Program Files (x86)",
-        "$Recycle.Bin",
-        "System Volume Information",
-        ".git",
-        "node_modules",
-        "target",
-        "AppData",
-    ];
+/// Directories never descended into by the large-file scan
+const LARGE_FILE_SKIP_DIRS: [&str; 9] = [
+    "Windows",
+    "Program Files",
+    "Program Files (x86)",
+    "$Recycle.Bin",
+    "System Volume Information",
+    ".git",
+    "node_modules",
+    "target",
+    "AppData",
+];
+
+/// Max recursion depth below each scan root
+const LARGE_FILE_MAX_DEPTH: u32 = 8;
+
+/// Shared state of the large-file worker pool: pending directories plus the number of
+/// workers still reading one (new work can only appear while someone is busy)
+struct DirQueue {
+    pending: Vec<(String, u32)>,
+    busy: usize,
+}
+
+pub fn scan_large_files(min_size_mb: u64, max_results: usize) -> LargeFileScanResult {
+    let min_bytes = min_size_mb * 1_048_576;
+    let cancel = begin_scan();
 
     let sys_drive = format!("{}\\" , std::env::var("SystemDrive").unwrap_or_else(|_| "C:".into()));
 
-    let mut stack = vec![
-        (std::env::var("USERPROFILE").unwrap_or_default(), 0),
-        (sys_drive, 0),
-    ];
+    let queue = std::sync::Arc::new((
+        std::sync::Mutex::new(DirQueue {
+            pending: vec![
+                (std::env::var("USERPROFILE").unwrap_or_default(), 0),
+                (sys_drive, 0),
+            ],
+            busy: 0,
+        }),
+        std::sync::Condvar::new(),
+    ));
+    let results = std::sync::Arc::new(std::sync::Mutex::new(Vec::<LargeFile>::new()));
+
+    // Directory reads are I/O bound; a few extra threads keep a slow disk's queue full
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+        .clamp(2, 16);
+
+    let handles: Vec<_> = (0..workers)
+        .map(|_| {
+            let queue = queue.clone();
+            let results = results.clone();
+            let cancel = cancel.clone();
+            std::thread::spawn(move || large_file_worker(&queue, &results, min_bytes, &cancel))
+        })
+        .collect();
+    for h in handles {
+        let _ = h.join();
+    }
 
-    while let Some((dir, depth)) = stack.pop() {
-        if dir.is_empty() || depth > 8 {
-            // Max depth 8
-            continue;
-        }
+    let mut files = std::mem::take(&mut *results.lock().unwrap_or_else(|e| e.into_inner()));
 
-        if let Ok(entries) = std::fs::read_dir(&dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                let name = entry.file_name().to_string_lossy().to_string();
+    // The profile also lives under the system drive — keep one entry per path
+    let mut seen = std::collections::HashSet::new();
+    files.retain(|f| seen.insert(f.path.to_lowercase()));
+
+    files.sort_by(|a, b| {
+        b.size_mb
+            .partial_cmp(&a.size_mb)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    files.truncate(max_results);
+    LargeFileScanResult {
+        files,
+        cancelled: cancel.load(Ordering::SeqCst),
+    }
+}
 
-                if let Ok(meta) = entry.metadata() {
-                    if meta.is_dir() {
-                        if !skip_dirs.iter().any(|s| name.eq_ignore_ascii_case(s)) {
-                            stack.push((path.to_string_lossy().to_string(), depth + 1));
+/// Pull directories off the shared queue until it is empty and no worker is busy
+fn large_file_worker(
+    queue: &(std::sync::Mutex<DirQueue>, std::sync::Condvar),
+    results: &std::sync::Mutex<Vec<LargeFile>>,
+    min_bytes: u64,
+    cancel: &AtomicBool,
+) {
+    let (lock, cvar) = queue;
+    loop {
+        let (dir, depth) = {
+            let mut q = lock.lock().unwrap_or_else(|e| e.into_inner());
+            loop {
+                if cancel.load(Ordering::Relaxed) {
+                    // Drop the remaining work so idle workers wake up and exit too
+                    q.pending.clear();
+                }
+                if let Some(item) = q.pending.pop() {
+                    q.busy += 1;
+                    break item;
+                }
+                if q.busy == 0 {
+                    cvar.notify_all();
+                    return;
+                }
+                q = cvar.wait(q).unwrap_or_else(|e| e.into_inner());
+            }
+        };
+
+        let mut subdirs = Vec::new();
+        let mut found = Vec::new();
+        if !dir.is_empty() && depth <= LARGE_FILE_MAX_DEPTH {
+            if let Ok(entries) = std::fs::read_dir(&dir) {
+                for entry in entries.flatten() {
+                    if cancel.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if let Ok(meta) = entry.metadata() {
+                        if meta.is_dir() {
+                            if !LARGE_FILE_SKIP_DIRS
+                                .iter()
+                                .any(|s| name.eq_ignore_ascii_case(s))
+                            {
+                                subdirs
+                                    .push((entry.path().to_string_lossy().to_string(), depth + 1));
+                            }
+                        } else if meta.is_file() && meta.len() >= min_bytes {
+                            found.push(large_file_entry(&entry.path(), &meta));
                         }
-                    } else if meta.is_file() && meta.len() >= min_bytes {
-                        let ext = path
-                            .extension()
-                            .map(|e| e.to_string_lossy().to_lowercase())
-                            .unwrap_or_default();
-                        let modified = meta
-                            .modified()
-                            .ok()
-                            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-                            .map(|d| {
-                                let secs = d.as_secs();
-                                let days_ago = (std::time::SystemTime::now()
-                                    .duration_since(std::time::UNIX_EPOCH)
-                                    .unwrap_or_default()
-                                    .as_secs()
-                                    - secs)
-                                    / 86400;
-                                if days_ago == 0 {
-                                    "Today".into()
-                                } else if days_ago == 1 {
-                                    "Yesterday".into()
-                                } else {
-                                    format!("{} days ago", days_ago)
-                                }
-                            })
-                            .unwrap_or_else(|| "Unknown".into());
-
-                        files.push(LargeFile {
-                            path: path.to_string_lossy().to_string(),
-                            size_mb: meta.len() as f64 / 1_048_576.0,
-                            extension: ext.clone(),
-                            category: categorize_extension(&ext),
-                            modified,
-                            ai_tooltip: None,
-                        });
                     }
                 }
             }
         }
+
+        if !found.is_empty() {
+            results
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .extend(found);
+        }
+
+        let mut q = lock.lock().unwrap_or_else(|e| e.into_inner());
+        q.pending.extend(subdirs);
+        q.busy -= 1;
+        cvar.notify_all();
     }
+}
 
-    files.sort_by(|a, b| {
-        b.size_mb
-            .partial_cmp(&a.size_mb)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
-    files.truncate(max_results);
-    files
+fn large_file_entry(path: &std::path::Path, meta: &std::fs::Metadata) -> LargeFile {
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let modified = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| {
+            let secs = d.as_secs();
+            let days_ago = (std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs()
+                - secs)
+                / 86400;
+            if days_ago == 0 {
+                "Today".into()
+            } else if days_ago == 1 {
+                "Yesterday".into()
+            } else {
+                format!("{} days ago", days_ago)
+            }
+        })
+        .unwrap_or_else(|| "Unknown".into());
+
+    LargeFile {
+        path: path.to_string_lossy().to_string(),
+        size_mb: meta.len() as f64 / 1_048_576.0,
+        extension: ext.clone(),
+        category: categorize_extension(&ext),
+        modified,
+        ai_tooltip: None,
+        token: file_token(path).unwrap_or_default(),
+    }
 }
 
-fn categorize_extension(ext: &str) -> String {
+/// Identity token for a file as seen at scan time: "<size>:<mtime in ns since epoch>"
+pub fn file_token(path: &std::path::Path) -> Option<String> {
+    let meta = std::fs::metadata(path).ok()?;
+    let mtime = meta
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_nanos();
+    Some(format!("{}:{}", meta.len(), mtime))
+}
+
+/// Refuse to act on a path whose size/mtime no longer match what the scan recorded,
+/// so a delete can't hit a different file that has since taken the same path
+pub fn verify_file_token(path: &std::path::Path, token: &str) -> Result<(), String> {
+    match file_token(path) {
+        Some(current) if current == token => Ok(()),
+        Some(_) => Err(format!(
+            "{} changed since it was scanned — rescan before deleting",
+            path.display()
+        )),
+        None => Err(format!("Cannot read {} to verify it", path.display())),
+    }
+}
+
+/// Send a file to the Recycle Bin instead of unlinking it, so the user can undo
+pub fn move_to_recycle_bin(path: &std::path::Path) -> Result<(), String> {
+    #[cfg(windows)]
+    {
+        use winapi::um::shellapi::{
+            SHFileOperationW, FOF_ALLOWUNDO, FOF_NOCONFIRMATION, FOF_NOERRORUI, FOF_SILENT,
+            FO_DELETE, SHFILEOPSTRUCTW,
+        };
+
+        // SHFileOperation wants an absolute, double-null-terminated list
+        let absolute = std::fs::canonicalize(path).map_err(|e| e.to_string())?;
+        let absolute = absolute.to_string_lossy();
+        let absolute = absolute.strip_prefix("\\\\?\\").unwrap_or(&absolute);
+        let from: Vec<u16> = absolute
+            .encode_utf16()
+            .chain(std::iter::once(0))
+            .chain(std::iter::once(0))
+            .collect();
+
+        let mut op: SHFILEOPSTRUCTW = unsafe { std::mem::zeroed() };
+        op.wFunc = FO_DELETE as u32;
+        op.pFrom = from.as_ptr();
+        op.fFlags = FOF_ALLOWUNDO | FOF_NOCONFIRMATION | FOF_NOERRORUI | FOF_SILENT;
+
+        let code = unsafe { SHFileOperationW(&mut op) };
+        if code != 0 {
+            return Err(format!("Failed to recycle (error {:#x})", code));
+        }
+        if op.fAnyOperationsAborted != 0 {
+            return Err("Recycling was cancelled".into());
+        }
+        return Ok(());
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = path;
+        Err("Recycle Bin is only available on Windows".into())
+    }
+}
+
+/// Map a lowercase file extension to a display category ("Video", "Image", ...)
+pub fn categorize_extension(ext: &str) -> String {
     match ext {
         // ── Video ────────────────────────────────────────────────────
         | "mp4" | "m4v" | "avi" | "mkv" | "mov" | "wmv" | "flv" | "webm"
@@ -246,127 +442,477 @@ pub struct BrowserInfo {
     pub installed: bool,
 }
 
-pub fn detect_browsers() -> Vec<BrowserInfo> {
-    let local = std::env::var("LOCALAPPDATA").unwrap_or_default();
-    let appdata = std::env::var("APPDATA").unwrap_or_default();
+/// `cache2` of every Firefox profile. The cache normally lives under the local profile,
+/// but older installs keep it next to the roaming one, so both roots are checked.
+fn firefox_cache_dirs() -> Vec<String> {
+    let mut dirs = Vec::new();
+    for var in ["LOCALAPPDATA", "APPDATA"] {
+        let profiles_dir = format!(
+            "{}\\Mozilla\\Firefox\\Profiles",
+            std::env::var(var).unwrap_or_default()
+        );
+        if let Ok(entries) = std::fs::read_dir(&profiles_dir) {
+            for entry in entries.flatten() {
+                let cache = entry.path().join("cache2");
+                if cache.is_dir() {
+                    dirs.push(cache.to_string_lossy().to_string());
+                }
+            }
+        }
+    }
+    dirs
+}
 
-    let browsers = vec![
-        (
-            "Google Chrome",
-            format!("{}\\Google\\Chrome\\User Data\\Default\\Cache", local),
-        ),
-        (
-            "Microsoft Edge",
-            format!("{}\\Microsoft\\Edge\\User Data\\Default\\Cache", local),
-        ),
-        (
-            "Mozilla Firefox",
-            format!("{}\\Mozilla\\Firefox\\Profiles", appdata),
-        ),
-        (
-            "Brave",
-            format!(
-                "{}\\BraveSoftware\\Brave-Browser\\User Data\\Default\\Cache",
-                local
-            ),
-        ),
-        (
-            "Opera",
-            format!("{}\\Opera Software\\Opera Stable\\Cache", appdata),
-        ),
-        (
-            "Vivaldi",
-            format!("{}\\Vivaldi\\User Data\\Default\\Cache", local),
-        ),
-    ];
+/// Browsers shown in the cleaner, in display order
+const KNOWN_BROWSERS: &[&str] = &[
+    "Google Chrome",
+    "Microsoft Edge",
+    "Mozilla Firefox",
+    "Brave",
+    "Opera",
+    "Vivaldi",
+];
+
+/// Curated browsers plus any Chromium forks found on disk
+pub fn detect_browsers() -> Vec<BrowserInfo> {
+    let mut browsers: Vec<(String, String)> = KNOWN_BROWSERS
+        .iter()
+        .filter_map(|&name| Some((name.to_string(), browser_data_dir(name)?)))
+        .collect();
+    browsers.extend(discover_chromium_browsers());
 
     browsers
         .into_iter()
-        .map(|(name, cache_path)| {
-            let exists = std::path::Path::new(&cache_path).exists();
-            let size = if exists { dir_size(&cache_path) } else { 0 };
+        .map(|(name, data_dir)| {
+            let installed = std::path::Path::new(&data_dir).exists();
+            // Only the caches cleaning frees, summed over every profile
+            let size: u64 = if installed {
+                browser_profile_dirs(&name)
+                    .map(|profiles| {
+                        browser_cache_dirs(&name, &profiles)
+                            .iter()
+                            .map(|d| dir_size(d))
+                            .sum()
+                    })
+                    .unwrap_or(0)
+            } else {
+                0
+            };
             BrowserInfo {
-                name: name.to_string(),
+                name,
                 cache_size_mb: size as f64 / 1_048_576.0,
-                cache_path,
-                installed: exists,
+                cache_path: data_dir,
+                installed,
             }
         })
         .collect()
 }
 
-pub fn clean_browser_cache(browser_name: &str) -> Result<String, String> {
+/// What `clean_browser_data` should remove; anything beyond the cache needs the browser closed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BrowserCleanOptions {
+    pub cache: bool,
+    pub cookies: bool,
+    pub history: bool,
+    pub downloads: bool,
+    pub sessions: bool, // saved tabs and windows restored on the next launch
+}
+
+/// Chromium browsers: display name, `User Data` folder under %LOCALAPPDATA%, process image
+const CHROMIUM_BROWSERS: &[(&str, &str, &str)] = &[
+    ("Google Chrome", "Google\\Chrome\\User Data", "chrome.exe"),
+    ("Microsoft Edge", "Microsoft\\Edge\\User Data", "msedge.exe"),
+    (
+        "Brave",
+        "BraveSoftware\\Brave-Browser\\User Data",
+        "brave.exe",
+    ),
+    ("Vivaldi", "Vivaldi\\User Data", "vivaldi.exe"),
+];
+
+// Profile-relative stores. Cookies and history are SQLite databases the browser keeps
+// open, so they can only be moved while it is closed.
+const CHROMIUM_COOKIES: &[&str] = &[
+    "Network\\Cookies",
+    "Network\\Cookies-journal",
+    "Cookies",
+    "Cookies-journal",
+];
+const CHROMIUM_HISTORY: &[&str] = &[
+    "History",
+    "History-journal",
+    "Visited Links",
+    "Top Sites",
+    "Top Sites-journal",
+    "Shortcuts",
+    "Shortcuts-journal",
+];
+const CHROMIUM_SESSIONS: &[&str] = &[
+    "Sessions",
+    "Current Session",
+    "Current Tabs",
+    "Last Session",
+    "Last Tabs",
+];
+const FIREFOX_COOKIES: &[&str] = &["cookies.sqlite", "cookies.sqlite-wal", "cookies.sqlite-shm"];
+const FIREFOX_HISTORY: &[&str] = &[
+    "places.sqlite",
+    "places.sqlite-wal",
+    "places.sqlite-shm",
+    "formhistory.sqlite",
+];
+const FIREFOX_SESSIONS: &[&str] = &["sessionstore.jsonlz4", "sessionstore-backups"];
+
+/// Folder holding all of a browser's profiles (`User Data` for Chromium browsers)
+fn browser_data_dir(browser_name: &str) -> Option<String> {
     let local = std::env::var("LOCALAPPDATA").unwrap_or_default();
     let appdata = std::env::var("APPDATA").unwrap_or_default();
 
-    let cache_paths: Vec<String> = match browser_name {
-        "Google Chrome" => vec![
-            format!("{}\\Google\\Chrome\\User Data\\Default\\Cache", local),
-            format!("{}\\Google\\Chrome\\User Data\\Default\\Code Cache", local),
-            format!("{}\\Google\\Chrome\\User Data\\Default\\GPUCache", local),
-        ],
-        "Microsoft Edge" => vec![
-            format!("{}\\Microsoft\\Edge\\User Data\\Default\\Cache", local),
-            format!("{}\\Microsoft\\Edge\\User Data\\Default\\Code Cache", local),
-        ],
-        "Brave" => vec![format!(
-            "{}\\BraveSoftware\\Brave-Browser\\User Data\\Default\\Cache",
-            local
-        )],
+    if let Some((_, user_data, _)) = CHROMIUM_BROWSERS.iter().find(|b| b.0 == browser_name) {
+        return Some(format!("{}\\{}", local, user_data));
+    }
+    match browser_name {
+        "Opera" => Some(format!("{}\\Opera Software\\Opera Stable", appdata)),
+        "Mozilla Firefox" => Some(format!("{}\\Mozilla\\Firefox\\Profiles", appdata)),
+        _ => discover_chromium_browsers()
+            .into_iter()
+            .find(|(name, _)| name == browser_name)
+            .map(|(_, user_data)| user_data),
+    }
+}
+
+/// Chromium forks not in `CHROMIUM_BROWSERS`, as (name, `User Data` folder).
+///
+/// Looks for `<vendor>\User Data` or `<vendor>\<product>\User Data` under %LOCALAPPDATA% and
+/// %APPDATA% with at least one profile cache, and names the browser after the folder
+/// holding `User Data` (e.g. `Thorium`, `YandexBrowser`).
+fn discover_chromium_browsers() -> Vec<(String, String)> {
+    let known: Vec<String> = CHROMIUM_BROWSERS
+        .iter()
+        .filter_map(|b| browser_data_dir(b.0))
+        .map(|d| d.to_lowercase())
+        .collect();
+    let mut found: Vec<(String, String)> = Vec::new();
+
+    for var in ["LOCALAPPDATA", "APPDATA"] {
+        let Ok(root) = std::env::var(var) else {
+            continue;
+        };
+        let Ok(vendors) = std::fs::read_dir(&root) else {
+            continue;
+        };
+        for vendor in vendors.flatten() {
+            let vendor_name = vendor.file_name().to_string_lossy().to_lowercase();
+            // Thousands of app containers and temp dirs, none of them browsers
+            if vendor_name == "packages" || vendor_name == "temp" {
+                continue;
+            }
+            let vendor = vendor.path();
+            let mut candidates = vec![vendor.join("User Data")];
+            if let Ok(products) = std::fs::read_dir(&vendor) {
+                candidates.extend(products.flatten().map(|p| p.path().join("User Data")));
+            }
+            for user_data in candidates {
+                let user_data_str = user_data.to_string_lossy().to_string();
+                if known.contains(&user_data_str.to_lowercase()) {
+                    continue;
+                }
+                let has_cache = chromium_profiles(&user_data_str)
+                    .iter()
+                    .any(|p| std::path::Path::new(p).join("Cache").is_dir());
+                if !has_cache {
+                    continue;
+                }
+                let Some(name) = user_data
+                    .parent()
+                    .and_then(|p| p.file_name())
+                    .map(|n| n.to_string_lossy().to_string())
+                else {
+                    continue;
+                };
+                if KNOWN_BROWSERS.contains(&name.as_str()) || found.iter().any(|f| f.0 == name) {
+                    continue;
+                }
+                found.push((name, user_data_str));
+            }
+        }
+    }
+    found.sort();
+    found
+}
+
+/// `Default` and `Profile N` folders of a Chromium `User Data` directory; guest and
+/// system profiles hold nothing worth cleaning
+fn chromium_profiles(user_data: &str) -> Vec<String> {
+    let mut profiles = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(user_data) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if (name == "Default" || name.starts_with("Profile ")) && entry.path().is_dir() {
+                profiles.push(entry.path().to_string_lossy().to_string());
+            }
+        }
+    }
+    profiles.sort();
+    profiles
+}
+
+/// Profile folders holding a browser's cookies, history and sessions
+fn browser_profile_dirs(browser_name: &str) -> Result<Vec<String>, String> {
+    let data_dir = browser_data_dir(browser_name)
+        .ok_or_else(|| format!("Unknown browser: {}", browser_name))?;
+    match browser_name {
+        // Opera keeps a single profile directly in its data folder
+        "Opera" => Ok(vec![data_dir]),
         "Mozilla Firefox" => {
             // Firefox profiles have random names
-            let profiles_dir = format!("{}\\Mozilla\\Firefox\\Profiles", appdata);
             let mut paths = Vec::new();
-            if let Ok(entries) = std::fs::read_dir(&profiles_dir) {
+            if let Ok(entries) = std::fs::read_dir(&data_dir) {
                 for entry in entries.flatten() {
                     if entry.metadata().map(|m| m.is_dir()).unwrap_or(false) {
-                        paths.push(format!("{}\\cache2", entry.path().to_string_lossy()));
+                        paths.push(entry.path().to_string_lossy().to_string());
                     }
                 }
             }
-            paths
+            Ok(paths)
         }
-        _ => return Err(format!("Unknown browser: {}", browser_name)),
-    };
+        _ => Ok(chromium_profiles(&data_dir)),
+    }
+}
+
+/// Cache folders of every profile, as sized by `detect_browsers`
+fn browser_cache_dirs(browser_name: &str, profiles: &[String]) -> Vec<String> {
+    if browser_name == "Mozilla Firefox" {
+        return firefox_cache_dirs();
+    }
+    profiles
+        .iter()
+        .flat_map(|p| {
+            ["Cache", "Code Cache", "GPUCache"]
+                .iter()
+                .map(move |d| format!("{}\\{}", p, d))
+        })
+        .collect()
+}
 
+/// Folders whose contents cache cleaning quarantines
+fn browser_cache_paths(browser_name: &str, profiles: &[String]) -> Vec<String> {
     // Chrome and Edge now bury cache in Cache/Cache_Data
     let mut actual_paths = Vec::new();
-    for p in &cache_paths {
+    for p in &browser_cache_dirs(browser_name, profiles) {
         actual_paths.push(p.clone());
         actual_paths.push(format!("{}\\Cache_Data", p));
         actual_paths.push(format!("{}\\js", p));
     }
+    actual_paths
+}
 
-    let mut total_freed: u64 = 0;
-    let mut files_deleted: u32 = 0;
+fn browser_process(browser_name: &str) -> Option<&'static str> {
+    if let Some((_, _, image)) = CHROMIUM_BROWSERS.iter().find(|b| b.0 == browser_name) {
+        return Some(image);
+    }
+    match browser_name {
+        "Opera" => Some("opera.exe"),
+        "Mozilla Firefox" => Some("firefox.exe"),
+        _ => None,
+    }
+}
 
-    for path in &actual_paths {
-        if let Ok(entries) = std::fs::read_dir(path) {
-            for entry in entries.flatten() {
-                if let Ok(meta) = entry.metadata() {
-                    let size = meta.len();
-                    let entry_path = entry.path();
-                    if meta.is_dir() {
-                        if std::fs::remove_dir_all(&entry_path).is_ok() {
-                            total_freed += size; // Size of dir itself might be small, but it's something.
-                        }
-                    } else if meta.is_file() {
-                        if std::fs::remove_file(&entry_path).is_ok() {
-                            total_freed += size;
-                            files_deleted += 1;
-                        }
-                    }
+fn is_browser_running(browser_name: &str) -> bool {
+    let image = browser_process(browser_name);
+    // Discovered forks have no known image; look for anything running from their install
+    // folder, which holds both `User Data` and the `Application` binaries
+    let install_dir = match image {
+        Some(_) => None,
+        None => browser_data_dir(browser_name)
+            .and_then(|d| std::path::Path::new(&d).parent().map(|p| p.to_path_buf())),
+    };
+    if image.is_none() && install_dir.is_none() {
+        return false;
+    }
+    let mut sys = sysinfo::System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    sys.processes()
+        .values()
+        .any(|p| match (image, &install_dir) {
+            (Some(image), _) => p.name().to_string_lossy().eq_ignore_ascii_case(image),
+            (None, Some(dir)) => p.exe().is_some_and(|exe| exe.starts_with(dir)),
+            (None, None) => false,
+        })
+}
+
+/// Quarantine one file or folder, adding to the running totals; reparse points are left alone
+fn quarantine_browser_item(
+    session: &mut QuarantineSession,
+    path: &std::path::Path,
+    total_moved: &mut u64,
+    files_moved: &mut u32,
+) {
+    if crate::disk_cleanup::is_reparse_protected(path) {
+        log::warn!("Cleanup skipped reparse point: {}", path.display());
+        return;
+    }
+    if let Ok(meta) = std::fs::metadata(path) {
+        if meta.is_dir() || meta.is_file() {
+            if let Ok(size) = session.quarantine(path) {
+                *total_moved += size;
+                if meta.is_file() {
+                    *files_moved += 1;
                 }
             }
         }
     }
+}
 
-    Ok(format!(
-        "Cleaned {} — deleted {} files, freed {:.1} MB",
+pub fn clean_browser_cache(browser_name: &str) -> Result<String, String> {
+    clean_browser_data(
         browser_name,
-        files_deleted,
-        total_freed as f64 / 1_048_576.0
-    ))
+        &BrowserCleanOptions {
+            cache: true,
+            ..Default::default()
+        },
+    )
+}
+
+/// Quarantine the selected kinds of browser data across the browser's profiles.
+///
+/// The cache is cleaned even while the browser runs; cookies, history and sessions are
+/// skipped with a warning until it is closed.
+pub fn clean_browser_data(
+    browser_name: &str,
+    opts: &BrowserCleanOptions,
+) -> Result<String, String> {
+    let profiles = browser_profile_dirs(browser_name)?;
+    let firefox = browser_name == "Mozilla Firefox";
+
+    let mut kinds: Vec<&str> = Vec::new();
+    let mut stores: Vec<&[&str]> = Vec::new();
+    if opts.cache {
+        kinds.push("cache");
+    }
+    if opts.cookies {
+        kinds.push("cookies");
+        stores.push(if firefox {
+            FIREFOX_COOKIES
+        } else {
+            CHROMIUM_COOKIES
+        });
+    }
+    // Both engines keep the download list inside the history database (on Firefox that is
+    // places.sqlite, which holds bookmarks too), so downloads can't be cleared on their own
+    if opts.downloads && !opts.history {
+        return Err(
+            "The download list is stored with history — select History to clear downloads".into(),
+        );
+    }
+    if opts.history {
+        kinds.push(if opts.downloads {
+            "history, downloads"
+        } else {
+            "history"
+        });
+        stores.push(if firefox {
+            FIREFOX_HISTORY
+        } else {
+            CHROMIUM_HISTORY
+        });
+    }
+    if opts.sessions {
+        kinds.push("sessions");
+        stores.push(if firefox {
+            FIREFOX_SESSIONS
+        } else {
+            CHROMIUM_SESSIONS
+        });
+    }
+    if kinds.is_empty() {
+        return Err("Select at least one kind of browser data to clean".into());
+    }
+
+    let mut warnings: Vec<String> = Vec::new();
+    if !stores.is_empty() && is_browser_running(browser_name) {
+        let warning = format!(
+            "{} is running — close it to clear cookies, history and sessions",
+            browser_name
+        );
+        if !opts.cache {
+            return Err(warning);
+        }
+        warnings.push(warning);
+        stores.clear();
+    }
+
+    let mut total_moved: u64 = 0;
+    let mut files_moved: u32 = 0;
+    let mut session = QuarantineSession::new(&format!("{} {}", browser_name, kinds.join(", ")));
+
+    if opts.cache {
+        for path in &browser_cache_paths(browser_name, &profiles) {
+            if let Ok(entries) = std::fs::read_dir(path) {
+                for entry in entries.flatten() {
+                    quarantine_browser_item(
+                        &mut session,
+                        &entry.path(),
+                        &mut total_moved,
+                        &mut files_moved,
+                    );
+                }
+            }
+        }
+    }
+
+    for profile in &profiles {
+        let profile = std::path::Path::new(profile);
+        // Firefox rebuilds bookmarks from its newest backup when places.sqlite goes missing;
+        // without one, clearing history would lose them
+        let has_bookmark_backup = std::fs::read_dir(profile.join("bookmarkbackups"))
+            .map(|mut e| e.next().is_some())
+            .unwrap_or(false);
+        for store in &stores {
+            for name in store.iter() {
+                if firefox && name.starts_with("places.sqlite") && !has_bookmark_backup {
+                    continue;
+                }
+                let path = profile.join(name);
+                if path.exists() {
+                    quarantine_browser_item(
+                        &mut session,
+                        &path,
+                        &mut total_moved,
+                        &mut files_moved,
+                    );
+                }
+            }
+        }
+        if firefox && !has_bookmark_backup && opts.history && !stores.is_empty() {
+            warnings.push(format!(
+                "kept history in {} — it has no bookmark backup",
+                profile.file_name().unwrap_or_default().to_string_lossy()
+            ));
+        }
+    }
+
+    let session_id = session.finish()?;
+    let scope = if profiles.len() > 1 {
+        format!(" across {} profiles", profiles.len())
+    } else {
+        String::new()
+    };
+    let mut message = format!(
+        "Cleaned {} ({}){} — quarantined {} files ({:.1} MB, freed after purge); undo with session {}",
+        browser_name,
+        kinds.join(", "),
+        scope,
+        files_moved,
+        total_moved as f64 / 1_048_576.0,
+        session_id
+    );
+    for warning in warnings {
+        message.push_str("; ");
+        message.push_str(&warning);
+    }
+    Ok(message)
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -439,37 +985,56 @@ pub fn clean_privacy_item(id: &str) -> Result<String, String> {
     match id {
         "recent_files" => {
             let path = format!("{}\\Microsoft\\Windows\\Recent", appdata);
-            let count = clean_dir_files(&path);
-            Ok(format!("Cleared {} recent file entries", count))
+            let (count, session_id) = quarantine_dir_files(&path, "Recent files")?;
+            Ok(format!(
+                "Cleared {} recent file entries (undo: {})",
+                count, session_id
+            ))
         }
         "clipboard" => {
             let _ = Command::new("cmd").args(["/C", "echo off | clip"]).output();
             Ok("Clipboard cleared".into())
         }
         "explorer_history" => {
-            let _ = Command::new("reg")
-                .args([
-                    "delete",
-                    "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\TypedPaths",
-                    "/f",
-                ])
-                .output();
-            Ok("Explorer history cleared".into())
+            let key = "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\TypedPaths";
+            // Explorer only creates the key once a path is typed; nothing to clear without it
+            let exists = Command::new("reg")
+                .args(["query", key])
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false);
+            if !exists {
+                return Ok("Explorer history is already empty".into());
+            }
+            let mut session = QuarantineSession::new("Explorer history");
+            session.export_registry_key(key)?;
+            let _ = Command::new("reg").args(["delete", key, "/f"]).output();
+            let session_id = session.finish()?;
+            Ok(format!("Explorer history cleared (undo: {})", session_id))
         }
         "notification_cache" => {
             let path = format!("{}\\Microsoft\\Windows\\Notifications", local);
-            let count = clean_dir_files(&path);
-            Ok(format!("Cleared {} notification entries", count))
+            let (count, session_id) = quarantine_dir_files(&path, "Notification cache")?;
+            Ok(format!(
+                "Cleared {} notification entries (undo: {})",
+                count, session_id
+            ))
         }
         "activity_history" => {
             let path = format!("{}\\ConnectedDevicesPlatform", local);
-            let count = clean_dir_files(&path);
-            Ok(format!("Cleared activity history ({} items)", count))
+            let (count, session_id) = quarantine_dir_files(&path, "Activity history")?;
+            Ok(format!(
+                "Cleared activity history ({} items, undo: {})",
+                count, session_id
+            ))
         }
         "prefetch" => {
             let prefetch = format!("{}\\Prefetch", std::env::var("SystemRoot").unwrap_or_else(|_| "C:\\Windows".into()));
-            let count = clean_dir_files(&prefetch);
-            Ok(format!("Cleared {} prefetch files", count))
+            let (count, session_id) = quarantine_dir_files(&prefetch, "Prefetch data")?;
+            Ok(format!(
+                "Cleared {} prefetch files (undo: {})",
+                count, session_id
+            ))
         }
         _ => Err(format!("Unknown privacy item: {}", id)),
     }
@@ -490,7 +1055,95 @@ pub struct DriverInfo {
     pub status: String, // "OK", "Outdated", "Problem"
 }
 
-pub fn list_drivers() -> Vec<DriverInfo> {
+/// Drivers dated further back than this are reported as outdated
+const DRIVER_MAX_AGE_MONTHS: u32 = 36;
+
+/// PnP devices read when the caller gives no limit
+const DEFAULT_PNP_LIMIT: usize = 50;
+
+/// First version of the driver branch each GPU vendor still updates, as (provider, device
+/// name fragment, version), matched case-insensitively. Older versions are outdated
+/// whatever their date says, since vendors re-sign legacy branches with fresh dates.
+const DRIVER_BASELINES: &[(&str, &str, &str)] = &[
+    ("nvidia", "geforce", "31.0.15.0"),
+    ("nvidia", "rtx", "31.0.15.0"),
+    ("nvidia", "quadro", "31.0.15.0"),
+    ("advanced micro devices", "radeon", "31.0.0.0"),
+    ("intel", "graphics", "31.0.101.0"),
+];
+
+/// Current local (year, month); UTC off Windows
+fn current_year_month() -> (u32, u32) {
+    #[cfg(windows)]
+    {
+        let mut st: winapi::um::minwinbase::SYSTEMTIME = unsafe { std::mem::zeroed() };
+        unsafe { winapi::um::sysinfoapi::GetLocalTime(&mut st) };
+        return (st.wYear as u32, st.wMonth as u32);
+    }
+    #[cfg(not(windows))]
+    {
+        let days = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+            / 86_400;
+        let year_days = days * 400 / 146_097;
+        let month = (days - year_days * 146_097 / 400) * 12 / 366 + 1;
+        (1970 + year_days as u32, month as u32)
+    }
+}
+
+/// (year, month) of a driver date: WMI's `yyyyMMdd...` or a locale date from driverquery,
+/// where only the year can be trusted and the month is taken as December
+fn driver_year_month(date: &str) -> Option<(u32, u32)> {
+    let digits: String = date.chars().take(8).collect();
+    if digits.len() == 8 && digits.chars().all(|c| c.is_ascii_digit()) {
+        return Some((digits[..4].parse().ok()?, digits[4..6].parse().ok()?));
+    }
+    date.split(|c: char| !c.is_ascii_digit())
+        .find(|part| part.len() == 4)
+        .and_then(|year| year.parse().ok())
+        .map(|year| (year, 12))
+}
+
+fn parse_version(version: &str) -> Vec<u32> {
+    version
+        .split('.')
+        .map(|part| part.trim().parse().unwrap_or(0))
+        .collect()
+}
+
+/// Whether a driver is past the age limit or below its vendor's baseline version
+fn is_driver_outdated(provider: &str, name: &str, version: &str, date: &str) -> bool {
+    let provider = provider.to_lowercase();
+    let name = name.to_lowercase();
+
+    if !version.is_empty() {
+        let below_baseline = DRIVER_BASELINES
+            .iter()
+            .filter(|(vendor, fragment, _)| provider.contains(vendor) && name.contains(fragment))
+            .any(|(_, _, baseline)| parse_version(version) < parse_version(baseline));
+        if below_baseline {
+            return true;
+        }
+    }
+
+    // Windows' generic in-box drivers all carry the same 2006 date; that isn't age
+    if provider.starts_with("microsoft") || provider.starts_with("(standard") {
+        return false;
+    }
+    let Some((year, month)) = driver_year_month(date) else {
+        return false;
+    };
+    let (now_year, now_month) = current_year_month();
+    let age_months = (now_year * 12 + now_month).saturating_sub(year * 12 + month);
+    age_months > DRIVER_MAX_AGE_MONTHS
+}
+
+/// One entry per device from `driverquery` and the PnP signed-driver list combined.
+/// `pnp_limit` caps how many PnP devices are read; `None` reads `DEFAULT_PNP_LIMIT`.
+/// `Some(0)` reads them all.
+pub fn list_drivers(pnp_limit: Option<usize>) -> Vec<DriverInfo> {
     // Use driverquery for a comprehensive list
     let output = Command::new("driverquery")
         .args(["/v", "/fo", "csv"])
@@ -506,6 +1159,10 @@ pub fn list_drivers() -> Vec<DriverInfo> {
                     continue;
                 } // Skip header
 
+                // Columns of `driverquery /v`: Module Name, Display Name, Description,
+                // Driver Type, Start Mode, State, Status, Accept Stop, Accept Pause,
+                // Paged Pool, Code, BSS, Link Date, Path, Init. Headers are localized, so
+                // fields are read by position.
                 let fields: Vec<&str> = line.split("\",\"").collect();
                 if fields.len() >= 6 {
                     let name = fields[0].trim_matches('"').to_string();
@@ -521,17 +1178,14 @@ pub fn list_drivers() -> Vec<DriverInfo> {
                     } else {
                         "Unknown".into()
                     };
-                    let state = if fields.len() > 4 {
-                        fields[4].trim_matches('"').to_string()
+                    let state = fields[5].trim_matches('"').to_string();
+                    let link_date = if fields.len() > 12 {
+                        fields[12].trim_matches('"').to_string()
                     } else {
                         "Unknown".into()
                     };
-                    let link_date = if fields.len() > 10 {
-                        fields[10].trim_matches('"').to_string()
-                    } else {
-                        "Unknown".into()
-                    };
-
+                    // driverquery has no provider, and link dates of in-box drivers are often
+                    // deterministic build stamps; only PnP rows below are checked for age
                     drivers.push(DriverInfo {
                         name: display_name,
                         provider: name,
@@ -539,23 +1193,33 @@ pub fn list_drivers() -> Vec<DriverInfo> {
                         date: link_date,
                         device_class: driver_type,
                         signed: true,
-                        status: if state.contains("Running") {
-                            "OK".into()
-                        } else {
+                        status: if !state.contains("Running") {
                             "Stopped".into()
+                        } else {
+                            "OK".into()
                         },
                     });
                 }
             }
 
-            // Also get PnP driver info for versions
+            // Also get PnP driver info for versions, stopping the query itself at the limit
+            let limit = match pnp_limit.unwrap_or(DEFAULT_PNP_LIMIT) {
+                0 => usize::MAX,
+                limit => limit,
+            };
+            let first = if limit == usize::MAX {
+                String::new()
+            } else {
+                format!(" | Select-Object -First {}", limit)
+            };
+            let query = format!("Get-WmiObject Win32_PnPSignedDriver{} | Select-Object DeviceName,DriverVersion,Manufacturer,DriverDate,IsSigned | ConvertTo-Csv -NoTypeInformation", first);
             if let Ok(pnp) = Command::new("powershell")
-                .args(["-Command", "Get-WmiObject Win32_PnPSignedDriver | Select-Object DeviceName,DriverVersion,Manufacturer,DriverDate,IsSigned | ConvertTo-Csv -NoTypeInformation | Select-Object -First 50"])
+                .args(["-Command", &query])
                 .output()
             {
                 let pnp_out = String::from_utf8_lossy(&pnp.stdout);
-                for (i, line) in pnp_out.lines().enumerate() {
-                    if i == 0 { continue; }
+                // Skip header
+                for line in pnp_out.lines().skip(1).take(limit) {
                     let fields: Vec<&str> = line.split("\",\"").collect();
                     if fields.len() >= 5 {
                         let dev_name = fields[0].trim_matches('"').to_string();
@@ -565,6 +1229,22 @@ pub fn list_drivers() -> Vec<DriverInfo> {
                         let signed = fields[4].trim_matches('"').contains("True");
 
                         if dev_name.is_empty() { continue; }
+                        let outdated = is_driver_outdated(&mfr, &dev_name, &version, &date);
+
+                        if let Some(existing) = drivers.iter_mut().find(|d| d.name.eq_ignore_ascii_case(&dev_name)) {
+                            // Identical devices (e.g. several USB hubs) share one driver entry
+                            if existing.device_class == "PnP Device" { continue; }
+                            // Same device from driverquery: PnP knows version and signature,
+                            // driverquery knows whether it is running
+                            existing.provider = mfr;
+                            existing.version = version;
+                            existing.date = date;
+                            existing.signed = signed;
+                            if existing.status != "Stopped" {
+                                existing.status = if outdated { "Outdated".into() } else { "OK".into() };
+                            }
+                            continue;
+                        }
 
                         drivers.push(DriverInfo {
                             name: dev_name,
@@ -573,7 +1253,7 @@ pub fn list_drivers() -> Vec<DriverInfo> {
                             date,
                             device_class: "PnP Device".into(),
                             signed,
-                            status: "OK".into(),
+                            status: if outdated { "Outdated".into() } else { "OK".into() },
                         });
                     }
                 }
@@ -585,6 +1265,68 @@ pub fn list_drivers() -> Vec<DriverInfo> {
     }
 }
 
+/// Back up every third-party driver package to `dest` with `pnputil /export-driver`.
+/// Needs admin rights; returns how many packages were exported.
+pub fn export_drivers(dest: &str) -> Result<String, String> {
+    if dest.trim().is_empty() {
+        return Err("Choose a folder to export drivers to".into());
+    }
+    std::fs::create_dir_all(dest).map_err(|e| format!("Could not create {}: {}", dest, e))?;
+    let before = count_driver_packages(dest);
+
+    let output = Command::new("pnputil")
+        .args(["/export-driver", "*", dest])
+        .output()
+        .map_err(|e| format!("Failed to run pnputil: {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // pnputil's messages are localized, so count what actually landed in `dest`
+    let after = count_driver_packages(dest);
+    let exported = after.saturating_sub(before);
+    if !output.status.success() && exported == 0 {
+        let detail = stdout
+            .lines()
+            .map(str::trim)
+            .rfind(|l| !l.is_empty())
+            .unwrap_or("pnputil failed — run as administrator");
+        return Err(format!("Driver export failed: {}", detail));
+    }
+    if before > 0 {
+        // Re-exporting into the same folder overwrites packages instead of adding them
+        return Ok(format!(
+            "Exported driver packages to {} ({} new, {} in the folder)",
+            dest, exported, after
+        ));
+    }
+    Ok(format!("Exported {} driver packages to {}", exported, dest))
+}
+
+/// Exported driver packages under `dest`: pnputil writes each into its own folder holding
+/// the package's .inf; loose .inf files count too
+fn count_driver_packages(dest: &str) -> usize {
+    let is_inf = |p: &std::path::Path| {
+        p.extension()
+            .map(|e| e.eq_ignore_ascii_case("inf"))
+            .unwrap_or(false)
+    };
+    let Ok(entries) = std::fs::read_dir(dest) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter(|entry| {
+            let path = entry.path();
+            if path.is_dir() {
+                std::fs::read_dir(&path)
+                    .map(|files| files.flatten().any(|f| is_inf(&f.path())))
+                    .unwrap_or(false)
+            } else {
+                is_inf(&path)
+            }
+        })
+        .count()
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Windows Update Cleanup
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -599,6 +1341,10 @@ pub fn clean_windows_update() -> Result<String, String> {
     let dl_path = format!("{}\\SoftwareDistribution\\Download", sys_root);
     if let Ok(entries) = std::fs::read_dir(dl_path) {
         for entry in entries.flatten() {
+            if crate::disk_cleanup::is_reparse_protected(&entry.path()) {
+                log::warn!("Cleanup skipped reparse point: {}", entry.path().display());
+                continue;
+            }
             if let Ok(meta) = entry.metadata() {
                 if meta.is_file() {
                     freed += meta.len();
@@ -622,6 +1368,422 @@ pub fn clean_windows_update() -> Result<String, String> {
     ))
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// Leftover Program Folders
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A folder under Program Files, ProgramData or Local AppData that no installed program claims
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeftoverFolder {
+    pub path: String,
+    pub name: String,
+    pub root: String,
+    pub size_mb: f64,
+    pub empty: bool,
+    /// Empty, or under Program Files where only installers write; anything else is removed
+    /// only after an explicit confirmation
+    pub removable: bool,
+}
+
+const UNINSTALL_KEYS: &[&str] = &[
+    r"HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+    r"HKLM\SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall",
+    r"HKCU\Software\Microsoft\Windows\CurrentVersion\Uninstall",
+];
+
+/// Run keys whose commands can point into an otherwise unclaimed folder
+const LEFTOVER_RUN_KEYS: &[&str] = &[
+    r"HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\Run",
+    r"HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\RunOnce",
+    r"HKLM\SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Run",
+    r"HKLM\SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\RunOnce",
+    r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run",
+    r"HKCU\Software\Microsoft\Windows\CurrentVersion\RunOnce",
+];
+
+/// Folders modified within this many days may belong to a portable tool still in use
+const LEFTOVER_RECENT_DAYS: u64 = 30;
+
+/// Folders owned by Windows or shared between programs. Driver vendors are listed too:
+/// drivers delivered through Windows Update leave folders without an Uninstall entry.
+const LEFTOVER_SKIP: &[&str] = &[
+    "Application Data",
+    "chocolatey",
+    "Comms",
+    "Common Files",
+    "ConnectedDevicesPlatform",
+    "CrashDumps",
+    "D3DSCache",
+    "Desktop",
+    "Documents",
+    "dotnet",
+    "History",
+    "Internet Explorer",
+    "ModifiableWindowsApps",
+    "MSBuild",
+    "Microsoft",
+    "Microsoft.NET",
+    "Package Cache",
+    "PackageManagement",
+    "Packages",
+    "PlaceholderTileLogoFolder",
+    "Programs",
+    "Publishers",
+    "Reference Assemblies",
+    "ssh",
+    "Start Menu",
+    "Temp",
+    "Templates",
+    "Temporary Internet Files",
+    "Uninstall Information",
+    "USOPrivate",
+    "USOShared",
+    "VirtualStore",
+    "VegaOptimizer",
+    "Windows Kits",
+    "WindowsApps",
+    "WindowsPowerShell",
+    "AMD",
+    "Intel",
+    "NVIDIA",
+    "NVIDIA Corporation",
+    "Realtek",
+];
+
+/// Names and install directories gathered from the Uninstall keys, plus the lowercased
+/// commands of services, Run keys and scheduled tasks and the PATH entries
+#[derive(Default)]
+struct InstalledIndex {
+    names: Vec<String>,
+    locations: Vec<String>,
+    references: Vec<String>,
+}
+
+fn installed_index() -> InstalledIndex {
+    #[cfg(windows)]
+    {
+        let mut index = InstalledIndex::default();
+        for root in UNINSTALL_KEYS {
+            let Some(key) = crate::registry::open_key(root) else {
+                continue;
+            };
+            for sub in key.enum_keys().flatten() {
+                let Ok(app) = key.open_subkey(&sub) else {
+                    continue;
+                };
+                let value = |name: &str| app.get_value::<String, _>(name).unwrap_or_default();
+                for name in [value("DisplayName"), value("Publisher"), sub.clone()] {
+                    let name = normalize_app_name(&name);
+                    if name.len() >= 3 {
+                        index.names.push(name);
+                    }
+                }
+                for raw in [
+                    value("InstallLocation"),
+                    value("DisplayIcon"),
+                    value("UninstallString"),
+                ] {
+                    if let Some(dir) = registry_path_dir(&raw) {
+                        index.locations.push(dir);
+                    }
+                }
+            }
+        }
+        index.references = leftover_references();
+        return index;
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = (UNINSTALL_KEYS, LEFTOVER_RUN_KEYS);
+        InstalledIndex::default()
+    }
+}
+
+/// Commands that keep a folder in use without an Uninstall entry: service ImagePaths,
+/// Run and RunOnce values, scheduled task actions and PATH entries, expanded and lowercased
+#[cfg(windows)]
+fn leftover_references() -> Vec<String> {
+    use winreg::types::FromRegValue;
+
+    let mut refs: Vec<String> = Vec::new();
+    let mut push = |raw: &str| {
+        let raw = raw.trim();
+        if !raw.is_empty() {
+            refs.push(crate::startup::expand_env_vars(raw).to_lowercase());
+        }
+    };
+    if let Some(services) = crate::registry::open_key(r"HKLM\SYSTEM\CurrentControlSet\Services") {
+        for sub in services.enum_keys().flatten() {
+            if let Ok(image) = services
+                .open_subkey(&sub)
+                .and_then(|svc| svc.get_value::<String, _>("ImagePath"))
+            {
+                push(&image);
+            }
+        }
+    }
+    for key in LEFTOVER_RUN_KEYS {
+        let Some(run) = crate::registry::open_key(key) else {
+            continue;
+        };
+        for (_, value) in run.enum_values().flatten() {
+            if let Ok(command) = String::from_reg_value(&value) {
+                push(&command);
+            }
+        }
+    }
+    if let Ok(out) = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            "Get-ScheduledTask | ForEach-Object { $_.Actions } | ForEach-Object { $_.Execute; $_.Arguments; $_.WorkingDirectory }",
+        ])
+        .output()
+    {
+        for line in String::from_utf8_lossy(&out.stdout).lines() {
+            push(line);
+        }
+    }
+    if let Ok(path) = std::env::var("PATH") {
+        for dir in path.split(';') {
+            push(dir.trim_end_matches('\\'));
+        }
+    }
+    refs
+}
+
+/// True when the folder or anything directly inside it changed in the last
+/// `LEFTOVER_RECENT_DAYS` days
+fn modified_recently(path: &std::path::Path) -> bool {
+    let Some(cutoff) = std::time::SystemTime::now().checked_sub(std::time::Duration::from_secs(
+        LEFTOVER_RECENT_DAYS * 86_400,
+    )) else {
+        return false;
+    };
+    let recent = |p: &std::path::Path| {
+        std::fs::metadata(p)
+            .and_then(|m| m.modified())
+            .is_ok_and(|t| t >= cutoff)
+    };
+    recent(path)
+        || std::fs::read_dir(path)
+            .map(|mut entries| entries.any(|e| e.is_ok_and(|e| recent(&e.path()))))
+            .unwrap_or(false)
+}
+
+/// Only installers write under Program Files, so an unclaimed folder there is a safer
+/// guess than one in ProgramData or Local AppData
+fn under_program_files(path: &std::path::Path) -> bool {
+    let parent = path
+        .parent()
+        .map(|p| p.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    ["ProgramFiles", "ProgramFiles(x86)"].iter().any(|var| {
+        std::env::var(var).is_ok_and(|dir| {
+            !dir.is_empty()
+                && dir.to_lowercase().trim_end_matches('\\') == parent.trim_end_matches('\\')
+        })
+    })
+}
+
+/// Lowercase letters and digits only, so "Foo-Bar Inc." and "FooBar" compare equal
+fn normalize_app_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// Lowercased directory of an InstallLocation, DisplayIcon or UninstallString value;
+/// `None` for commands such as `MsiExec.exe /X{...}` that name no absolute path
+#[cfg(windows)]
+fn registry_path_dir(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    let path = if let Some(rest) = raw.strip_prefix('"') {
+        rest.split('"').next().unwrap_or("")
+    } else {
+        // Unquoted: cut after the executable, or drop an icon index like ",0"
+        match raw.to_ascii_lowercase().find(".exe") {
+            Some(i) => &raw[..i + 4],
+            None => raw.split(',').next().unwrap_or(""),
+        }
+    };
+    let path = path.trim().trim_end_matches('\\');
+    if path.len() < 3 || path.as_bytes()[1] != b':' {
+        return None;
+    }
+    let p = std::path::Path::new(path);
+    let dir = if p.extension().is_some() && !p.is_dir() {
+        p.parent()?
+    } else {
+        p
+    };
+    Some(dir.to_string_lossy().to_lowercase())
+}
+
+fn leftover_roots() -> Vec<std::path::PathBuf> {
+    let mut roots: Vec<std::path::PathBuf> = Vec::new();
+    for var in [
+        "ProgramFiles",
+        "ProgramFiles(x86)",
+        "ProgramData",
+        "LOCALAPPDATA",
+    ] {
+        let Ok(dir) = std::env::var(var) else {
+            continue;
+        };
+        let dir = std::path::PathBuf::from(dir);
+        if !dir.as_os_str().is_empty() && !roots.contains(&dir) {
+            roots.push(dir);
+        }
+    }
+    roots
+}
+
+/// True when nothing claims the folder: no install location, service, Run value, scheduled
+/// task or PATH entry inside it, no installed name or publisher that matches its name, and
+/// no change in the last `LEFTOVER_RECENT_DAYS` days
+fn is_leftover(path: &std::path::Path, name: &str, index: &InstalledIndex) -> bool {
+    // Without the uninstall list every folder would look orphaned
+    if index.names.is_empty() || name.starts_with('{') || name.starts_with('.') {
+        return false;
+    }
+    if LEFTOVER_SKIP.iter().any(|s| s.eq_ignore_ascii_case(name)) {
+        return false;
+    }
+    let lower = path.to_string_lossy().to_lowercase();
+    let prefix = format!("{}\\", lower);
+    if let Some(exe_dir) = std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(|d| d.to_string_lossy().to_lowercase()))
+    {
+        if exe_dir == lower || exe_dir.starts_with(&prefix) {
+            return false;
+        }
+    }
+    if index
+        .locations
+        .iter()
+        .any(|loc| *loc == lower || loc.starts_with(&prefix))
+    {
+        return false;
+    }
+    // Commands name the folder as a prefix of a longer path, or end right after it
+    let quoted = format!("{}\"", lower);
+    if index.references.iter().any(|r| {
+        *r == lower || r.contains(&prefix) || r.contains(&quoted) || r.ends_with(lower.as_str())
+    }) {
+        return false;
+    }
+    let key = normalize_app_name(name);
+    // Too short to compare by name; leave it alone
+    if key.len() < 3 {
+        return false;
+    }
+    if index
+        .names
+        .iter()
+        .any(|n| n.contains(&key) || (n.len() >= 4 && key.contains(n.as_str())))
+    {
+        return false;
+    }
+    !modified_recently(path)
+}
+
+/// Folders directly under Program Files, ProgramData and Local AppData that no entry in
+/// the Uninstall keys accounts for, largest first
+pub fn scan_leftover_folders() -> Vec<LeftoverFolder> {
+    let index = installed_index();
+    let mut found = Vec::new();
+    for root in leftover_roots() {
+        let Ok(entries) = std::fs::read_dir(&root) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let is_dir = entry.metadata().map(|m| m.is_dir()).unwrap_or(false);
+            if !is_dir
+                || crate::disk_cleanup::is_reparse_protected(&path)
+                || !is_leftover(&path, &name, &index)
+            {
+                continue;
+            }
+            let empty = std::fs::read_dir(&path)
+                .map(|mut e| e.next().is_none())
+                .unwrap_or(false);
+            found.push(LeftoverFolder {
+                path: path.to_string_lossy().to_string(),
+                name,
+                root: root.to_string_lossy().to_string(),
+                size_mb: dir_size_mb(&path.to_string_lossy()),
+                empty,
+                removable: empty || under_program_files(&path),
+            });
+        }
+    }
+    found.sort_by(|a, b| {
+        b.size_mb
+            .partial_cmp(&a.size_mb)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    found
+}
+
+/// Quarantine a folder from `scan_leftover_folders`, re-checking first that it sits directly
+/// under a scanned root and that nothing claims it. Folders that aren't `removable` need
+/// `confirmed`, since a portable tool or cache in ProgramData looks the same as a leftover.
+pub fn delete_leftover_folder(path: &str, confirmed: bool) -> Result<String, String> {
+    let target = std::path::Path::new(path);
+    let name = target
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| format!("{} is not a leftover folder", path))?;
+    let parent = target
+        .parent()
+        .map(|p| p.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let under_root = leftover_roots().iter().any(|root| {
+        root.to_string_lossy().to_lowercase().trim_end_matches('\\')
+            == parent.trim_end_matches('\\')
+    });
+    if !under_root {
+        return Err(format!(
+            "{} is not directly under Program Files, ProgramData or Local AppData",
+            path
+        ));
+    }
+    let is_dir = std::fs::symlink_metadata(target)
+        .map(|m| m.is_dir())
+        .map_err(|e| format!("{}: {}", path, e))?;
+    if !is_dir || crate::disk_cleanup::is_reparse_protected(target) {
+        return Err(format!("{} is not a regular folder", path));
+    }
+    if !is_leftover(target, &name, &installed_index()) {
+        return Err(format!("{} belongs to an installed program", name));
+    }
+    let empty = std::fs::read_dir(target)
+        .map(|mut e| e.next().is_none())
+        .unwrap_or(false);
+    if !confirmed && !empty && !under_program_files(target) {
+        return Err(format!(
+            "{} may belong to a portable tool or cache; confirm to remove it anyway",
+            name
+        ));
+    }
+    let mut session = QuarantineSession::new(&format!("Leftover folder {}", name));
+    let size = session
+        .quarantine(target)
+        .map_err(|e| format!("Could not move {}: {}", path, e))?;
+    let session_id = session.finish()?;
+    Ok(format!(
+        "Quarantined {} ({:.1} MB, freed after purge); undo with session {}",
+        name,
+        size as f64 / 1_048_576.0,
+        session_id
+    ))
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Helpers
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -646,22 +1808,22 @@ fn dir_size_result(path: &str) -> Result<u64, std::io::Error> {
     Ok(total)
 }
 
-fn dir_size_mb(path: &str) -> f64 {
+pub fn dir_size_mb(path: &str) -> f64 {
     dir_size(path) as f64 / 1_048_576.0
 }
 
-fn clean_dir_files(path: &str) -> u32 {
+/// Move the top-level files of `path` into a new quarantine session; returns (count, session id)
+fn quarantine_dir_files(path: &str, label: &str) -> Result<(u32, String), String> {
+    let mut session = QuarantineSession::new(label);
     let mut count = 0u32;
     if let Ok(entries) = std::fs::read_dir(path) {
         for entry in entries.flatten() {
             if let Ok(meta) = entry.metadata() {
-                if meta.is_file() {
-                    if std::fs::remove_file(entry.path()).is_ok() {
-                        count += 1;
-                    }
+                if meta.is_file() && session.quarantine(&entry.path()).is_ok() {
+                    count += 1;
                 }
             }
         }
     }
-    count
+    Ok((count, session.finish()?))
 }
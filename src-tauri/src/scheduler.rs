@@ -0,0 +1,214 @@
+//! Scheduler — run a chosen set of optimizations on a daily, memory-pressure or idle trigger
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Trigger {
+    Daily { hour: u8 }, // local hour, 0-23
+    OnMemoryPressure { threshold_pct: u32 },
+    OnIdle,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Schedule {
+    pub id: String,
+    pub optimization_ids: Vec<String>,
+    pub trigger: Trigger,
+    pub last_run: Option<u64>, // unix seconds
+    #[serde(default)]
+    pub min_idle_secs: u64, // also require this much user inactivity; 0 = no gate
+}
+
+/// Emitted to the frontend after a scheduled run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleRun {
+    pub schedule_id: String,
+    pub items_succeeded: usize,
+    pub items_failed: usize,
+    pub memory_freed_mb: f64,
+}
+
+/// How often the runner thread evaluates triggers
+const TICK_SECS: u64 = 30;
+
+/// Minimum gap between two memory-pressure runs of the same schedule
+const PRESSURE_COOLDOWN_SECS: u64 = 15 * 60;
+
+/// No keyboard/mouse input for this long counts as idle
+const IDLE_AFTER_SECS: u64 = 10 * 60;
+
+/// Serializes load-modify-save of the schedule file between commands and the runner
+static SCHEDULES_LOCK: Mutex<()> = Mutex::new(());
+
+fn schedules_path() -> std::path::PathBuf {
+    let base = std::env::var("LOCALAPPDATA").unwrap_or_else(|_| {
+        let profile = std::env::var("USERPROFILE").unwrap_or_else(|_| ".".into());
+        format!("{}\\AppData\\Local", profile)
+    });
+    std::path::PathBuf::from(base)
+        .join("VegaOptimizer")
+        .join("schedules.json")
+}
+
+fn load_schedules() -> Vec<Schedule> {
+    std::fs::read_to_string(schedules_path())
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn save_schedules(schedules: &[Schedule]) -> Result<(), String> {
+    let path = schedules_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(schedules).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Could not save schedules: {}", e))
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Current local hour (UTC off Windows)
+fn local_hour() -> u8 {
+    #[cfg(windows)]
+    {
+        let mut st: winapi::um::minwinbase::SYSTEMTIME = unsafe { std::mem::zeroed() };
+        unsafe { winapi::um::sysinfoapi::GetLocalTime(&mut st) };
+        return st.wHour as u8;
+    }
+    #[cfg(not(windows))]
+    {
+        ((now_secs() / 3600) % 24) as u8
+    }
+}
+
+fn memory_used_pct() -> u32 {
+    let mut sys = sysinfo::System::new();
+    sys.refresh_memory();
+    if sys.total_memory() == 0 {
+        return 0;
+    }
+    (sys.used_memory() as f64 / sys.total_memory() as f64 * 100.0) as u32
+}
+
+/// Whether a schedule should fire now
+fn is_due(schedule: &Schedule, now: u64) -> bool {
+    // Never run counts as infinitely long ago
+    let since_last = schedule
+        .last_run
+        .map_or(u64::MAX, |t| now.saturating_sub(t));
+    if schedule.min_idle_secs > 0 && crate::monitor::get_idle_seconds() < schedule.min_idle_secs {
+        return false;
+    }
+    match schedule.trigger {
+        // Once per day: the hour matches and the last run was in an earlier day's window
+        Trigger::Daily { hour } => local_hour() == hour && since_last > 20 * 3600,
+        Trigger::OnMemoryPressure { threshold_pct } => {
+            since_last > PRESSURE_COOLDOWN_SECS && memory_used_pct() >= threshold_pct
+        }
+        // Once per idle stretch: the last run happened before the current idle period began
+        Trigger::OnIdle => {
+            let idle = crate::monitor::get_idle_seconds();
+            idle >= IDLE_AFTER_SECS && since_last > idle
+        }
+    }
+}
+
+pub fn list_schedules() -> Vec<Schedule> {
+    let _guard = SCHEDULES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    load_schedules()
+}
+
+/// Add a schedule running `ids` (catalog item ids) whenever `trigger` fires and the user
+/// has been idle for at least `min_idle_secs`
+pub fn register_schedule(
+    ids: Vec<String>,
+    trigger: Trigger,
+    min_idle_secs: u64,
+) -> Result<Schedule, String> {
+    if ids.is_empty() {
+        return Err("Select at least one optimization to schedule".into());
+    }
+    match trigger {
+        Trigger::Daily { hour } if hour > 23 => return Err("Hour must be 0-23".into()),
+        Trigger::OnMemoryPressure { threshold_pct } if !(50..=99).contains(&threshold_pct) => {
+            return Err("Memory threshold must be between 50% and 99%".into())
+        }
+        _ => {}
+    }
+
+    let _guard = SCHEDULES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut schedules = load_schedules();
+    let schedule = Schedule {
+        id: format!("sched-{}", now_secs()),
+        optimization_ids: ids,
+        trigger,
+        last_run: None,
+        min_idle_secs,
+    };
+    if schedules.iter().any(|s| s.id == schedule.id) {
+        return Err("A schedule was just added — try again in a second".into());
+    }
+    schedules.push(schedule.clone());
+    save_schedules(&schedules)?;
+    Ok(schedule)
+}
+
+pub fn remove_schedule(id: &str) -> Result<String, String> {
+    let _guard = SCHEDULES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut schedules = load_schedules();
+    let before = schedules.len();
+    schedules.retain(|s| s.id != id);
+    if schedules.len() == before {
+        return Err(format!("No schedule with id {}", id));
+    }
+    save_schedules(&schedules)?;
+    Ok("Schedule removed".into())
+}
+
+/// Start the in-process runner thread; `on_run` is called after every scheduled run.
+///
+/// Schedules only fire while the app is running. The file is re-read every tick,
+/// so commands never need to signal the runner.
+pub fn start(on_run: impl Fn(ScheduleRun) + Send + 'static) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(std::time::Duration::from_secs(TICK_SECS));
+
+        let now = now_secs();
+        let due: Vec<Schedule> = {
+            let _guard = SCHEDULES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            load_schedules()
+                .into_iter()
+                .filter(|s| is_due(s, now))
+                .collect()
+        };
+
+        for schedule in due {
+            log::info!("Running schedule {} ({:?})", schedule.id, schedule.trigger);
+            let report = crate::optimizer::run_optimization(schedule.optimization_ids.clone());
+
+            {
+                let _guard = SCHEDULES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+                let mut schedules = load_schedules();
+                if let Some(s) = schedules.iter_mut().find(|s| s.id == schedule.id) {
+                    s.last_run = Some(now_secs());
+                }
+                let _ = save_schedules(&schedules);
+            }
+
+            on_run(ScheduleRun {
+                schedule_id: schedule.id,
+                items_succeeded: report.items_succeeded,
+                items_failed: report.items_failed,
+                memory_freed_mb: report.total_memory_freed_mb,
+            });
+        }
+    });
+}
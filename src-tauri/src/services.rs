@@ -9,12 +9,43 @@ pub struct ServiceInfo {
     pub display_name: String,
     pub status: String,     // Running, Stopped, Paused
     pub start_type: String, // Automatic, Manual, Disabled
-    pub memory_mb: f64,
+    pub memory_mb: f64,     // this service's share of its host process
     pub pid: u32,
     pub description: String,
     pub category: String, // "essential", "optional", "telemetry", "gaming", "media", "unknown"
     pub safe_to_disable: bool,
     pub recommendation: String,
+    pub shared_host: bool,   // other services run in the same svchost.exe
+    pub host_memory_mb: f64, // whole host process, shared by every service in it
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceSuggestion {
+    pub name: String,
+    pub display_name: String,
+    pub category: String,
+    pub pid: u32,
+    pub estimated_savings_mb: f64,
+    pub shared_host: bool, // savings are this service's share of a svchost
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceGroup {
+    pub pid: u32,
+    pub service_names: Vec<String>,
+    pub total_memory_mb: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EssentialServiceIssue {
+    pub name: String,
+    pub display_name: String,
+    pub status: String,     // Running, Stopped, ... or "Missing"
+    pub start_type: String, // Auto, Manual, Disabled (as reported by WMI)
+    pub expected_start_type: String,
+    pub problem: String, // "disabled", "stopped"
+    pub recommendation: String,
 }
 
 /// Known service classifications
@@ -126,6 +157,86 @@ const SERVICE_CLASSIFICATIONS: &[(&str, &str, &str, bool, &str)] = &[
     ),
     // Fax
     ("Fax", "optional", "Fax", true, "Legacy fax support"),
+    // GPU / driver vendor — NVIDIA
+    (
+        "NvTelemetryContainer",
+        "telemetry",
+        "NVIDIA Telemetry Container",
+        true,
+        "Sends driver usage data to NVIDIA — no effect on graphics",
+    ),
+    (
+        "NvContainerNetworkService",
+        "telemetry",
+        "NVIDIA NetworkService Container",
+        true,
+        "GeForce Experience online features — safe to disable",
+    ),
+    (
+        "NvContainerLocalSystem",
+        "optional",
+        "NVIDIA LocalSystem Container",
+        true,
+        "Only needed for GeForce Experience overlay/ShadowPlay",
+    ),
+    (
+        "NVDisplay.ContainerLocalSystem",
+        "optional",
+        "NVIDIA Display Container LS",
+        false,
+        "Required by NVIDIA Control Panel — disabling breaks it",
+    ),
+    // GPU / driver vendor — AMD
+    (
+        "AUEPLauncher",
+        "telemetry",
+        "AMD User Experience Program Launcher",
+        true,
+        "AMD usage analytics — safe to disable",
+    ),
+    (
+        "AMD External Events Utility",
+        "optional",
+        "AMD External Events Utility",
+        false,
+        "Needed for FreeSync and Radeon hotkeys — keep enabled",
+    ),
+    // GPU / driver vendor — Intel
+    (
+        "ESRV_SVC_QUEENCREEK",
+        "telemetry",
+        "Intel Computing Improvement Program",
+        true,
+        "Intel usage analytics — safe to disable",
+    ),
+    (
+        "SystemUsageReportSvc_QUEENCREEK",
+        "telemetry",
+        "Intel System Usage Report",
+        true,
+        "Intel usage analytics — safe to disable",
+    ),
+    (
+        "DSAService",
+        "optional",
+        "Intel Driver & Support Assistant",
+        true,
+        "Only checks for Intel driver updates",
+    ),
+    (
+        "igccservice",
+        "optional",
+        "Intel Graphics Command Center Service",
+        false,
+        "Required by Intel Graphics Command Center — disabling breaks it",
+    ),
+    (
+        "cplspcon",
+        "optional",
+        "Intel Content Protection HDCP Service",
+        false,
+        "Needed for protected video playback (HDCP)",
+    ),
     // Essential — NEVER disable
     (
         "Winmgmt",
@@ -242,6 +353,57 @@ const SERVICE_CLASSIFICATIONS: &[(&str, &str, &str, bool, &str)] = &[
     ),
 ];
 
+/// Documented default start types, used when no snapshot of the original configuration exists
+const DEFAULT_START_TYPES: &[(&str, &str, &str)] = &[
+    // (service_name, windows_10_default, windows_11_default)
+    // Telemetry
+    ("DiagTrack", "Automatic", "Automatic"),
+    ("dmwappushservice", "Manual", "Manual"),
+    (
+        "diagnosticshub.standardcollector.service",
+        "Manual",
+        "Manual",
+    ),
+    ("WerSvc", "Manual", "Manual"),
+    // Gaming
+    ("XblAuthManager", "Manual", "Manual"),
+    ("XblGameSave", "Manual", "Manual"),
+    ("XboxNetApiSvc", "Manual", "Manual"),
+    ("XboxGipSvc", "Manual", "Manual"),
+    // Search, indexing & delivery
+    ("WSearch", "Delayed", "Delayed"),
+    ("SysMain", "Automatic", "Automatic"),
+    ("DoSvc", "Delayed", "Manual"),
+    ("MapsBroker", "Delayed", "Delayed"),
+    // Print & fax
+    ("Spooler", "Automatic", "Automatic"),
+    ("Fax", "Manual", "Manual"),
+    // Remote
+    ("RemoteRegistry", "Disabled", "Disabled"),
+    ("RemoteAccess", "Disabled", "Disabled"),
+    ("TermService", "Manual", "Manual"),
+    // Media
+    ("WMPNetworkSvc", "Manual", "Manual"),
+    // Essential
+    ("Winmgmt", "Automatic", "Automatic"),
+    ("RpcSs", "Automatic", "Automatic"),
+    ("DcomLaunch", "Automatic", "Automatic"),
+    ("LSM", "Automatic", "Automatic"),
+    ("EventLog", "Automatic", "Automatic"),
+    ("Dhcp", "Automatic", "Automatic"),
+    ("Dnscache", "Automatic", "Automatic"),
+    ("BFE", "Automatic", "Automatic"),
+    ("mpssvc", "Automatic", "Automatic"),
+    ("WinDefend", "Automatic", "Automatic"),
+    ("Schedule", "Automatic", "Automatic"),
+    ("Themes", "Automatic", "Automatic"),
+    ("AudioSrv", "Automatic", "Automatic"),
+    ("AudioEndpointBuilder", "Automatic", "Automatic"),
+    ("wuauserv", "Manual", "Manual"),
+    ("BITS", "Manual", "Manual"),
+    ("CryptSvc", "Automatic", "Automatic"),
+];
+
 /// List all Windows services with classifications
 pub fn list_services() -> Vec<ServiceInfo> {
     let mut services = Vec::new();
@@ -297,10 +459,25 @@ pub fn list_services() -> Vec<ServiceInfo> {
                 category,
                 safe_to_disable: safe,
                 recommendation: rec,
+                shared_host: false,
+                host_memory_mb: mem,
             });
         }
     }
 
+    // Services sharing one svchost.exe would otherwise each report the whole host's memory
+    let mut per_pid: std::collections::HashMap<u32, usize> = std::collections::HashMap::new();
+    for s in services.iter().filter(|s| s.pid != 0) {
+        *per_pid.entry(s.pid).or_default() += 1;
+    }
+    for s in services.iter_mut().filter(|s| s.pid != 0) {
+        let count = per_pid.get(&s.pid).copied().unwrap_or(1);
+        if count > 1 {
+            s.shared_host = true;
+            s.memory_mb = s.host_memory_mb / count as f64;
+        }
+    }
+
     // Sort: running first, then by memory
     services.sort_by(|a, b| {
         let a_running = if a.status == "Running" { 0 } else { 1 };
@@ -315,6 +492,55 @@ pub fn list_services() -> Vec<ServiceInfo> {
     services
 }
 
+/// Running, auto-start services classified telemetry/gaming/media, biggest savings first
+pub fn get_optimization_suggestions() -> Vec<ServiceSuggestion> {
+    let mut suggestions: Vec<ServiceSuggestion> = list_services()
+        .into_iter()
+        .filter(|s| matches!(s.category.as_str(), "telemetry" | "gaming" | "media"))
+        .filter(|s| s.safe_to_disable)
+        .filter(|s| s.status == "Running" && s.start_type.starts_with("Auto"))
+        .map(|s| ServiceSuggestion {
+            reason: format!(
+                "{} service starting automatically — {}",
+                s.category, s.recommendation
+            ),
+            estimated_savings_mb: s.memory_mb,
+            name: s.name,
+            display_name: s.display_name,
+            category: s.category,
+            pid: s.pid,
+            shared_host: s.shared_host,
+        })
+        .collect();
+    suggestions.sort_by(|a, b| {
+        b.estimated_savings_mb
+            .partial_cmp(&a.estimated_savings_mb)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    suggestions
+}
+
+/// Running services grouped by host process, largest host first
+pub fn get_service_groups() -> Vec<ServiceGroup> {
+    let mut groups: Vec<ServiceGroup> = Vec::new();
+    for s in list_services().into_iter().filter(|s| s.pid != 0) {
+        match groups.iter_mut().find(|g| g.pid == s.pid) {
+            Some(group) => group.service_names.push(s.name),
+            None => groups.push(ServiceGroup {
+                pid: s.pid,
+                service_names: vec![s.name],
+                total_memory_mb: s.host_memory_mb,
+            }),
+        }
+    }
+    groups.sort_by(|a, b| {
+        b.total_memory_mb
+            .partial_cmp(&a.total_memory_mb)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    groups
+}
+
 /// Start a service
 pub fn start_service(name: &str) -> Result<String, String> {
     match Command::new("sc").args(["start", name]).output() {
@@ -343,6 +569,7 @@ pub fn stop_service(name: &str) -> Result<String, String> {
 pub fn set_service_startup(name: &str, startup: &str) -> Result<String, String> {
     let sc_type = match startup {
         "Automatic" | "Auto" => "auto",
+        "Delayed" | "Automatic (Delayed Start)" => "delayed-auto",
         "Manual" => "demand",
         "Disabled" => "disabled",
         _ => return Err("Invalid startup type".into()),
@@ -356,3 +583,331 @@ pub fn set_service_startup(name: &str, startup: &str) -> Result<String, String>
         Err(e) => Err(e.to_string()),
     }
 }
+
+/// Whether the running OS is Windows 11 (build 22000 or later)
+fn is_windows_11() -> bool {
+    sysinfo::System::kernel_version()
+        .and_then(|v| {
+            v.split('.')
+                .find_map(|p| p.parse::<u32>().ok().filter(|b| *b >= 10000))
+        })
+        .map(|build| build >= 22000)
+        .unwrap_or(false)
+}
+
+/// Documented default start type for a service on the running OS, if known
+pub fn default_start_type(name: &str) -> Option<&'static str> {
+    let win11 = is_windows_11();
+    DEFAULT_START_TYPES
+        .iter()
+        .find(|(svc, _, _)| svc.eq_ignore_ascii_case(name))
+        .map(|(_, win10_default, win11_default)| {
+            if win11 {
+                *win11_default
+            } else {
+                *win10_default
+            }
+        })
+}
+
+/// Restore a service's startup type to the documented Windows default
+pub fn reset_service_to_default(name: &str) -> Result<String, String> {
+    let default = default_start_type(name)
+        .ok_or_else(|| format!("No documented default start type for {}", name))?;
+    set_service_startup(name, default)
+        .map(|_| format!("Reset {} to its default startup type ({})", name, default))
+}
+
+/// Start type an essential service should have: the documented default, else Automatic
+fn expected_start_type(name: &str) -> &'static str {
+    default_start_type(name).unwrap_or("Automatic")
+}
+
+/// Find essential services that were disabled, or stopped when they should be running.
+///
+/// Uses the classification table in reverse: every `essential` entry is checked against
+/// its current WMI start mode and state. Demand-start services (Windows Update, BITS)
+/// are only flagged when disabled, since being stopped is their normal idle state.
+pub fn check_essential_services() -> Vec<EssentialServiceIssue> {
+    let essentials: Vec<(&str, &str, &str)> = SERVICE_CLASSIFICATIONS
+        .iter()
+        .filter(|(_, cat, _, _, _)| *cat == "essential")
+        .map(|(name, _, display, _, rec)| (*name, *display, *rec))
+        .collect();
+
+    let filter = essentials
+        .iter()
+        .map(|(name, _, _)| format!("Name='{}'", name))
+        .collect::<Vec<_>>()
+        .join(" OR ");
+    let script = format!(
+        r#"Get-CimInstance Win32_Service -Filter "{}" | ForEach-Object {{ "$($_.Name)|$($_.State)|$($_.StartMode)" }}"#,
+        filter
+    );
+
+    let mut current: std::collections::HashMap<String, (String, String)> =
+        std::collections::HashMap::new();
+    if let Ok(output) = Command::new("powershell")
+        .args(["-Command", &script])
+        .output()
+    {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for line in stdout.lines() {
+            let parts: Vec<&str> = line.split('|').collect();
+            if parts.len() >= 3 {
+                current.insert(
+                    parts[0].trim().to_lowercase(),
+                    (parts[1].trim().to_string(), parts[2].trim().to_string()),
+                );
+            }
+        }
+    }
+    if current.is_empty() {
+        // Query failed entirely — don't report every service as broken
+        return Vec::new();
+    }
+
+    let mut issues = Vec::new();
+    for (name, display, rec) in essentials {
+        // Absent services (e.g. WinDefend with third-party AV) aren't something we can fix
+        let Some((state, start_mode)) = current.get(&name.to_lowercase()) else {
+            continue;
+        };
+        let expected = expected_start_type(name);
+        let auto_start = matches!(expected, "Automatic" | "Delayed");
+
+        let problem = if start_mode.eq_ignore_ascii_case("Disabled") {
+            "disabled"
+        } else if auto_start && !state.eq_ignore_ascii_case("Running") {
+            "stopped"
+        } else {
+            continue;
+        };
+
+        issues.push(EssentialServiceIssue {
+            name: name.to_string(),
+            display_name: display.to_string(),
+            status: state.clone(),
+            start_type: start_mode.clone(),
+            expected_start_type: expected.to_string(),
+            problem: problem.to_string(),
+            recommendation: rec.to_string(),
+        });
+    }
+
+    issues
+}
+
+/// Repair an essential service: restore its expected start type and start it if it auto-starts
+pub fn fix_essential_service(name: &str) -> Result<String, String> {
+    let is_essential = SERVICE_CLASSIFICATIONS
+        .iter()
+        .any(|(svc, cat, _, _, _)| *cat == "essential" && svc.eq_ignore_ascii_case(name));
+    if !is_essential {
+        return Err(format!("{} is not an essential service", name));
+    }
+
+    let expected = expected_start_type(name);
+    set_service_startup(name, expected)?;
+    if matches!(expected, "Automatic" | "Delayed") {
+        // sc start fails with 1056 if it is already running — that's fine
+        if let Err(e) = start_service(name) {
+            if !e.contains("1056") {
+                return Err(format!(
+                    "Set {} to {} but could not start it: {}",
+                    name, expected, e
+                ));
+            }
+        }
+        return Ok(format!("Restored {} ({}) and started it", name, expected));
+    }
+    Ok(format!("Restored {} to {}", name, expected))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Bulk "apply recommended" — safe services to Manual, with a snapshot to undo
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceSnapshotEntry {
+    pub name: String,
+    pub start_type: String,
+    pub was_running: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceChangeResult {
+    pub name: String,
+    pub display_name: String,
+    pub previous_start_type: String,
+    pub previous_status: String,
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecommendedServicesReport {
+    pub restore_point: String, // outcome of the system restore point attempt
+    pub snapshot_path: String,
+    pub changes: Vec<ServiceChangeResult>,
+    pub changed: usize,
+    pub failed: usize,
+}
+
+fn snapshot_path() -> std::path::PathBuf {
+    let base = std::env::var("LOCALAPPDATA").unwrap_or_else(|_| {
+        let profile = std::env::var("USERPROFILE").unwrap_or_else(|_| ".".into());
+        format!("{}\\AppData\\Local", profile)
+    });
+    std::path::PathBuf::from(base)
+        .join("VegaOptimizer")
+        .join("services_snapshot.json")
+}
+
+/// Set every running or auto-start service classified `safe_to_disable` to Manual and stop it.
+///
+/// Manual rather than Disabled keeps on-demand start working (an Xbox game can still
+/// start XblAuth). A restore point is attempted first, and the original start types
+/// are always added to a snapshot that `restore_service_snapshot` can replay; a service
+/// already in it keeps its first recorded start type.
+pub fn apply_recommended_service_changes() -> Result<RecommendedServicesReport, String> {
+    let targets: Vec<ServiceInfo> = list_services()
+        .into_iter()
+        .filter(|s| s.safe_to_disable)
+        .filter(|s| !s.start_type.eq_ignore_ascii_case("Disabled"))
+        .filter(|s| s.status == "Running" || s.start_type.starts_with("Auto"))
+        .collect();
+
+    if targets.is_empty() {
+        return Ok(RecommendedServicesReport {
+            restore_point: "Not needed — nothing to change".into(),
+            snapshot_path: String::new(),
+            changes: Vec::new(),
+            changed: 0,
+            failed: 0,
+        });
+    }
+
+    // Windows allows one restore point per 24h, so a failure here is reported, not fatal
+    let restore_point = match crate::tweaks::create_restore_point("VegaOptimizer services") {
+        Ok(msg) => msg,
+        Err(e) => format!("Skipped: {}", e),
+    };
+
+    // Merge into an earlier snapshot: services changed by a previous run are Manual now,
+    // so only the first recorded start type can restore them
+    let path = snapshot_path();
+    let mut snapshot: Vec<ServiceSnapshotEntry> = match std::fs::read_to_string(&path) {
+        Ok(data) => serde_json::from_str(&data)
+            .map_err(|e| format!("Corrupt service snapshot, not overwriting it: {}", e))?,
+        Err(_) => Vec::new(),
+    };
+    let fresh: Vec<ServiceSnapshotEntry> = targets
+        .iter()
+        .filter(|s| {
+            !snapshot
+                .iter()
+                .any(|e| e.name.eq_ignore_ascii_case(&s.name))
+        })
+        .map(|s| ServiceSnapshotEntry {
+            name: s.name.clone(),
+            start_type: if s.start_type.starts_with("Auto") && is_delayed_autostart(&s.name) {
+                "Delayed".into()
+            } else {
+                s.start_type.clone()
+            },
+            was_running: s.status == "Running",
+        })
+        .collect();
+    snapshot.extend(fresh);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(&snapshot).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Could not save service snapshot: {}", e))?;
+
+    let mut changes = Vec::new();
+    for svc in targets {
+        let outcome = set_service_startup(&svc.name, "Manual").and_then(|_| {
+            if svc.status == "Running" {
+                stop_service(&svc.name)
+                    .map(|_| "Set to Manual and stopped".to_string())
+                    .map_err(|e| format!("Set to Manual but could not stop: {}", e.trim()))
+            } else {
+                Ok("Set to Manual".to_string())
+            }
+        });
+        let (success, message) = match outcome {
+            Ok(m) => (true, m),
+            Err(e) => (false, e.trim().to_string()),
+        };
+        changes.push(ServiceChangeResult {
+            name: svc.name,
+            display_name: svc.display_name,
+            previous_start_type: svc.start_type,
+            previous_status: svc.status,
+            success,
+            message,
+        });
+    }
+
+    let changed = changes.iter().filter(|c| c.success).count();
+    Ok(RecommendedServicesReport {
+        restore_point,
+        snapshot_path: path.to_string_lossy().to_string(),
+        failed: changes.len() - changed,
+        changed,
+        changes,
+    })
+}
+
+/// WMI reports delayed-start services as plain "Auto"; the flag lives in the service's key
+fn is_delayed_autostart(name: &str) -> bool {
+    #[cfg(windows)]
+    {
+        return crate::registry::open_key(&format!(
+            r"HKLM\SYSTEM\CurrentControlSet\Services\{}",
+            name
+        ))
+        .and_then(|key| key.get_value::<u32, _>("DelayedAutostart").ok())
+        .map(|flag| flag == 1)
+        .unwrap_or(false);
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = name;
+        false
+    }
+}
+
+/// Put services back the way `apply_recommended_service_changes` found them
+pub fn restore_service_snapshot() -> Result<String, String> {
+    let data = std::fs::read_to_string(snapshot_path())
+        .map_err(|_| "No service snapshot to restore".to_string())?;
+    let snapshot: Vec<ServiceSnapshotEntry> =
+        serde_json::from_str(&data).map_err(|e| format!("Corrupt service snapshot: {}", e))?;
+
+    let mut failures = Vec::new();
+    for entry in &snapshot {
+        // "Auto" from WMI or "Delayed" from the registry flag; set_service_startup takes both
+        if let Err(e) = set_service_startup(&entry.name, &entry.start_type) {
+            failures.push(format!("{}: {}", entry.name, e.trim()));
+            continue;
+        }
+        if entry.was_running {
+            let _ = start_service(&entry.name);
+        }
+    }
+
+    if failures.is_empty() {
+        let _ = std::fs::remove_file(snapshot_path());
+        Ok(format!("Restored {} services", snapshot.len()))
+    } else {
+        Err(format!(
+            "Restored {} of {} services; failed: {}",
+            snapshot.len() - failures.len(),
+            snapshot.len(),
+            failures.join("; ")
+        ))
+    }
+}
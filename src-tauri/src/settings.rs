@@ -0,0 +1,94 @@
+//! Settings — user preferences persisted across launches
+
+use serde::{Deserialize, Serialize};
+
+use crate::tweaks::AutoPurgeConfig;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub enabled_ids: Vec<String>, // catalog items the user keeps selected
+    pub scan_dirs: Vec<String>,   // custom folders for the duplicate finder
+    pub auto_trim: AutoPurgeConfig,
+    pub recycle_by_default: bool,
+    pub process_whitelist: Vec<String>, // lowercase process names never suggested or trimmed
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            enabled_ids: Vec::new(),
+            scan_dirs: Vec::new(),
+            auto_trim: AutoPurgeConfig::default(),
+            recycle_by_default: true,
+            process_whitelist: Vec::new(),
+        }
+    }
+}
+
+/// `%APPDATA%\VegaOptimizer\settings.json` — roaming, unlike the local caches and logs
+fn settings_path() -> std::path::PathBuf {
+    let base = std::env::var("APPDATA").unwrap_or_else(|_| {
+        let profile = std::env::var("USERPROFILE").unwrap_or_else(|_| ".".into());
+        format!("{}\\AppData\\Roaming", profile)
+    });
+    std::path::PathBuf::from(base)
+        .join("VegaOptimizer")
+        .join("settings.json")
+}
+
+/// Saved settings, or None if the user never saved any (or the file is unreadable)
+pub fn saved_settings() -> Option<Settings> {
+    let data = std::fs::read_to_string(settings_path()).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+pub fn load_settings() -> Settings {
+    saved_settings().unwrap_or_default()
+}
+
+pub fn save_settings(settings: &Settings) -> Result<String, String> {
+    let path = settings_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Could not save settings: {}", e))?;
+    Ok("Settings saved".into())
+}
+
+/// Lowercased so lookups can compare against `name().to_lowercase()`
+pub fn add_process_whitelist(name: &str) -> Result<String, String> {
+    let name = name.trim().to_lowercase();
+    if name.is_empty() {
+        return Err("Process name is empty".into());
+    }
+    update_settings(|s| {
+        if !s.process_whitelist.contains(&name) {
+            s.process_whitelist.push(name.clone());
+        }
+    })?;
+    Ok(format!("{} will no longer be suggested or trimmed", name))
+}
+
+pub fn remove_process_whitelist(name: &str) -> Result<String, String> {
+    let name = name.trim().to_lowercase();
+    let mut removed = false;
+    update_settings(|s| {
+        let before = s.process_whitelist.len();
+        s.process_whitelist.retain(|n| *n != name);
+        removed = s.process_whitelist.len() != before;
+    })?;
+    if removed {
+        Ok(format!("{} removed from the whitelist", name))
+    } else {
+        Err(format!("{} is not whitelisted", name))
+    }
+}
+
+/// Load, modify and save in one step (used by commands that own a single field)
+pub fn update_settings(f: impl FnOnce(&mut Settings)) -> Result<String, String> {
+    let mut settings = load_settings();
+    f(&mut settings);
+    save_settings(&settings)
+}
@@ -1,96 +1,312 @@
 //! Startup program management — list, enable, disable startup entries
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::OnceLock;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StartupEntry {
     pub name: String,
     pub command: String,
-    pub location: String,      // "Registry" or "StartupFolder"
-    pub registry_path: String, // Full registry key if applicable
+    pub location: String, // "User (RunOnce)", "All Users Startup Folder", "Scheduled Task (Logon)"...
+    /// Registry key, startup folder, or task folder for logon tasks
+    pub registry_path: String,
     pub enabled: bool,
     pub publisher: String,
-    pub impact: String, // "High", "Medium", "Low", "Unknown"
+    pub impact: String,               // "High", "Medium", "Low", "Unknown"
+    pub startup_time_ms: Option<u64>, // measured by Windows at boot, when recorded
 }
 
-pub fn list_startup_programs() -> Vec<StartupEntry> {
-    let mut entries: Vec<StartupEntry> = Vec::new();
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartupFootprint {
+    pub name: String,
+    pub executable: Option<String>,
+    pub scope: String, // "machine" (all users) or "user"
+    pub exe_size_mb: f64,
+    pub dependencies: Vec<DependencyInfo>,
+    pub app_dependencies_mb: f64, // DLLs shipped alongside the app
+    pub total_mb: f64,            // exe + app-local DLLs (shared system DLLs excluded)
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyInfo {
+    pub name: String,
+    pub path: Option<String>,
+    pub size_mb: f64,
+    pub system: bool, // resolved from System32 — shared, not part of the app's footprint
+}
 
-    // HKCU\SOFTWARE\Microsoft\Windows\CurrentVersion\Run
-    add_registry_entries(
-        &mut entries,
+/// Registry keys Windows runs at logon: (key, location label)
+const RUN_KEYS: &[(&str, &str)] = &[
+    (
         "HKCU\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Run",
         "User (Run)",
-    );
-
-    // HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\Run
-    add_registry_entries(
-        &mut entries,
+    ),
+    (
+        "HKCU\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\RunOnce",
+        "User (RunOnce)",
+    ),
+    (
         "HKLM\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Run",
         "System (Run)",
-    );
+    ),
+    (
+        "HKLM\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\RunOnce",
+        "System (RunOnce)",
+    ),
+    // 32-bit programs on 64-bit Windows register here
+    (
+        "HKLM\\SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Run",
+        "System 32-bit (Run)",
+    ),
+    (
+        "HKLM\\SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\RunOnce",
+        "System 32-bit (RunOnce)",
+    ),
+];
+
+pub fn list_startup_programs() -> Vec<StartupEntry> {
+    let mut entries = run_key_and_folder_entries();
+    add_logon_task_entries(&mut entries);
+
+    apply_measured_impact(&mut entries);
+    entries
+}
 
-    // Startup folder
+/// Enabled Run-key and Startup-folder entries. Skips the logon-task and boot-timing
+/// queries, so health scores and snapshots can count startup programs cheaply.
+pub fn count_enabled_startup_entries() -> usize {
+    run_key_and_folder_entries()
+        .iter()
+        .filter(|e| e.enabled)
+        .count()
+}
+
+/// Entries from the Run keys and the Startup folders: registry and directory reads only
+fn run_key_and_folder_entries() -> Vec<StartupEntry> {
+    let mut entries: Vec<StartupEntry> = Vec::new();
+
+    for (key, location) in RUN_KEYS {
+        add_registry_entries(&mut entries, key, location);
+    }
+
+    // Per-user and all-users Startup folders
     if let Ok(appdata) = std::env::var("APPDATA") {
-        let startup_path = format!(
-            "{}\\Microsoft\\Windows\\Start Menu\\Programs\\Startup",
-            appdata
+        add_folder_entries(
+            &mut entries,
+            &format!(
+                "{}\\Microsoft\\Windows\\Start Menu\\Programs\\Startup",
+                appdata
+            ),
+            "User Startup Folder",
         );
-        if let Ok(files) = std::fs::read_dir(&startup_path) {
-            for entry in files.flatten() {
-                let name = entry.file_name().to_string_lossy().to_string();
-                if name.starts_with("desktop.ini") {
-                    continue;
-                }
-                entries.push(StartupEntry {
-                    name: name.replace(".lnk", "").replace(".url", ""),
-                    command: entry.path().to_string_lossy().to_string(),
-                    location: "Startup Folder".into(),
-                    registry_path: startup_path.clone(),
-                    enabled: true,
-                    publisher: "Unknown".into(),
-                    impact: estimate_impact(&name),
-                });
+    }
+    if let Ok(program_data) = std::env::var("ProgramData") {
+        add_folder_entries(
+            &mut entries,
+            &format!(
+                "{}\\Microsoft\\Windows\\Start Menu\\Programs\\Startup",
+                program_data
+            ),
+            "All Users Startup Folder",
+        );
+    }
+    entries
+}
+
+/// Explorer's StartupApproved key shadowing a Run key; RunOnce has none
+fn startup_approved_key(run_key: &str) -> Option<String> {
+    let (hive, rest) = run_key.split_once('\\')?;
+    let rest = rest.to_lowercase();
+    if !rest.ends_with("\\currentversion\\run") {
+        return None;
+    }
+    let view = if rest.contains("wow6432node") {
+        "Run32"
+    } else {
+        "Run"
+    };
+    Some(format!(
+        "{}\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Explorer\\StartupApproved\\{}",
+        hive, view
+    ))
+}
+
+/// Value names flagged disabled in a StartupApproved key. The first byte of each
+/// REG_BINARY is even (02/06) when enabled and odd (03/07) when disabled.
+#[cfg(windows)]
+fn disabled_approved_values(approved_key: &str) -> std::collections::HashSet<String> {
+    let mut disabled = std::collections::HashSet::new();
+    if let Some(key) = crate::registry::open_key(approved_key) {
+        for (name, value) in key.enum_values().flatten() {
+            if value.vtype == winreg::enums::REG_BINARY
+                && value.bytes.first().is_some_and(|flag| flag & 1 == 1)
+            {
+                disabled.insert(name);
             }
         }
     }
+    disabled
+}
 
-    entries
+fn add_folder_entries(entries: &mut Vec<StartupEntry>, startup_path: &str, location: &str) {
+    if let Ok(files) = std::fs::read_dir(startup_path) {
+        for entry in files.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with("desktop.ini") {
+                continue;
+            }
+            entries.push(StartupEntry {
+                name: name.replace(".lnk", "").replace(".url", ""),
+                command: entry.path().to_string_lossy().to_string(),
+                location: location.into(),
+                registry_path: startup_path.to_string(),
+                enabled: true,
+                publisher: "Unknown".into(),
+                impact: estimate_impact(&name),
+                startup_time_ms: None,
+            });
+        }
+    }
+}
+
+/// Third-party scheduled tasks with a logon trigger. Tasks under \Microsoft\ belong to
+/// Windows itself and are left to the Scheduled Tasks tab.
+fn add_logon_task_entries(entries: &mut Vec<StartupEntry>) {
+    let script = r#"Get-ScheduledTask -ErrorAction SilentlyContinue | Where-Object { $_.TaskPath -notlike '\Microsoft\*' -and ($_.Triggers | Where-Object { $_.CimClass.CimClassName -eq 'MSFT_TaskLogonTrigger' }) } | ForEach-Object { $a = $_.Actions | Select-Object -First 1; "$($_.TaskPath)|$($_.TaskName)|$($_.State)|$($_.Author)|$($a.Execute) $($a.Arguments)" }"#;
+    let Ok(output) = Command::new("powershell")
+        .args(["-NoProfile", "-Command", script])
+        .output()
+    else {
+        return;
+    };
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let parts: Vec<&str> = line.trim().splitn(5, '|').collect();
+        if parts.len() < 5 || parts[1].is_empty() {
+            continue;
+        }
+        let name = parts[1].to_string();
+        let author = parts[3].trim();
+        entries.push(StartupEntry {
+            impact: estimate_impact(&name),
+            name,
+            command: parts[4].trim().to_string(),
+            location: "Scheduled Task (Logon)".into(),
+            registry_path: parts[0].to_string(),
+            enabled: parts[2] != "Disabled",
+            publisher: if author.is_empty() {
+                "Unknown".into()
+            } else {
+                author.to_string()
+            },
+            startup_time_ms: None,
+        });
+    }
 }
 
 fn add_registry_entries(entries: &mut Vec<StartupEntry>, key: &str, location: &str) {
-    if let Ok(output) = Command::new("reg").args(["query", key]).output() {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        for line in stdout.lines() {
-            let line = line.trim();
-            if line.is_empty() || line.starts_with("HKEY") {
+    #[cfg(windows)]
+    {
+        use winreg::types::FromRegValue;
+
+        let Some(run_key) = crate::registry::open_key(key) else {
+            return;
+        };
+        let disabled = startup_approved_key(key)
+            .map(|approved| disabled_approved_values(&approved))
+            .unwrap_or_default();
+        for (name, value) in run_key.enum_values().flatten() {
+            // The unnamed default value isn't an entry; non-string values aren't commands
+            if name.is_empty() {
                 continue;
             }
+            let Ok(command) = String::from_reg_value(&value) else {
+                continue;
+            };
 
-            // Format: "    ValueName    REG_SZ    CommandLine"
-            let parts: Vec<&str> = line.splitn(3, "    ").collect();
-            if parts.len() >= 3 {
-                let name = parts[0].trim().to_string();
-                let command = parts[2].trim().to_string();
-                if name.is_empty() || name == "(Default)" {
-                    continue;
-                }
+            entries.push(StartupEntry {
+                name: name.clone(),
+                command: command.trim().to_string(),
+                location: location.to_string(),
+                registry_path: key.to_string(),
+                enabled: !disabled.contains(&name),
+                publisher: "Unknown".into(),
+                impact: estimate_impact(&name),
+                startup_time_ms: None,
+            });
+        }
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = (entries, key, location);
+    }
+}
 
-                entries.push(StartupEntry {
-                    name: name.clone(),
-                    command,
-                    location: location.to_string(),
-                    registry_path: key.to_string(),
-                    enabled: true,
-                    publisher: "Unknown".into(),
-                    impact: estimate_impact(&name),
-                });
-            }
+/// Replace the keyword estimate with boot timing where Windows recorded one
+fn apply_measured_impact(entries: &mut [StartupEntry]) {
+    let times = measured_startup_times();
+    if times.is_empty() {
+        return;
+    }
+    for entry in entries.iter_mut() {
+        let exe = Path::new(&command_target(&entry.command))
+            .file_name()
+            .map(|f| f.to_string_lossy().to_lowercase())
+            .filter(|f| f.ends_with(".exe"))
+            .unwrap_or_else(|| format!("{}.exe", entry.name.to_lowercase()));
+        if let Some(&ms) = times.get(&exe) {
+            entry.startup_time_ms = Some(ms);
+            entry.impact = impact_from_ms(ms).into();
         }
     }
 }
 
+fn impact_from_ms(ms: u64) -> &'static str {
+    if ms >= 3000 {
+        "High"
+    } else if ms >= 1000 {
+        "Medium"
+    } else {
+        "Low"
+    }
+}
+
+/// Most recent per-application start time (ms) from boot degradation records, keyed by
+/// lowercase exe name.
+///
+/// Event 101 in Diagnostics-Performance/Operational is logged when an application slowed
+/// down boot. StartupApproved only holds enable flags, so this log is the only place with
+/// real timing. Reading it needs admin rights; an empty map means fall back to keywords.
+/// Boot records don't change while we run, so the query happens once.
+fn measured_startup_times() -> &'static HashMap<String, u64> {
+    static TIMES: OnceLock<HashMap<String, u64>> = OnceLock::new();
+    TIMES.get_or_init(|| {
+        let mut times = HashMap::new();
+        let script = r#"Get-WinEvent -LogName 'Microsoft-Windows-Diagnostics-Performance/Operational' -FilterXPath '*[System[EventID=101]]' -MaxEvents 500 -ErrorAction SilentlyContinue | ForEach-Object { $d=@{}; ([xml]$_.ToXml()).Event.EventData.Data | ForEach-Object { $d[$_.Name]=$_.'#text' }; "$($d['Name'])|$($d['TotalTime'])" }"#;
+        let Ok(output) = Command::new("powershell")
+            .args(["-NoProfile", "-Command", script])
+            .output()
+        else {
+            return times;
+        };
+        // Newest events come first; keep the latest boot's figure for each app
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let Some((name, ms)) = line.trim().split_once('|') else {
+                continue;
+            };
+            if let Ok(ms) = ms.trim().parse::<u64>() {
+                times.entry(name.trim().to_lowercase()).or_insert(ms);
+            }
+        }
+        times
+    })
+}
+
+/// Keyword guess, used when Windows has no measured start time for the entry
 fn estimate_impact(name: &str) -> String {
     let n = name.to_lowercase();
     // Known high-impact startup programs
@@ -120,6 +336,28 @@ fn estimate_impact(name: &str) -> String {
 }
 
 pub fn toggle_startup(name: &str, registry_path: &str, enable: bool) -> Result<String, String> {
+    if registry_path.starts_with('\\') {
+        // Logon scheduled task: registry_path is its task folder
+        let task = format!("{}{}", registry_path, name);
+        let action = if enable { "/enable" } else { "/disable" };
+        let output = Command::new("schtasks")
+            .args(["/change", "/tn", &task, action])
+            .output()
+            .map_err(|e| e.to_string())?;
+        return if output.status.success() {
+            Ok(format!(
+                "{} scheduled task: {}",
+                if enable { "Enabled" } else { "Disabled" },
+                name
+            ))
+        } else {
+            Err(format!(
+                "Failed to toggle scheduled task {}: {}",
+                name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        };
+    }
     if registry_path.contains("Startup")
         && !registry_path.contains("HKCU")
         && !registry_path.contains("HKLM")
@@ -141,12 +379,48 @@ pub fn toggle_startup(name: &str, registry_path: &str, enable: bool) -> Result<S
         return Err("Failed to toggle startup folder entry".into());
     }
 
-    // Registry-based entry
+    // Run keys: flip Explorer's StartupApproved flag, the same switch Task Manager uses.
+    // The original value and its command stay untouched, so enabling restores it exactly.
+    if let Some(approved) = startup_approved_key(registry_path) {
+        // 12 bytes: state flag, then a FILETIME Windows only uses for display
+        let flag = if enable {
+            "020000000000000000000000"
+        } else {
+            "030000000000000000000000"
+        };
+        let output = Command::new("reg")
+            .args([
+                "add",
+                &approved,
+                "/v",
+                name,
+                "/t",
+                "REG_BINARY",
+                "/d",
+                flag,
+                "/f",
+            ])
+            .output()
+            .map_err(|e| e.to_string())?;
+        return if output.status.success() {
+            Ok(format!(
+                "{} startup entry: {}",
+                if enable { "Enabled" } else { "Disabled" },
+                name
+            ))
+        } else {
+            Err(format!(
+                "Failed to toggle {}: {}",
+                name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        };
+    }
+
+    // RunOnce entries have no StartupApproved flag and Windows deletes them after one run
     if enable {
-        // The original value was deleted on disable and cannot be automatically restored.
-        // The user must re-run the application's installer or manually recreate the entry.
         return Err(format!(
-            "Cannot re-enable '{}' automatically — the registry entry was removed. \
+            "Cannot re-enable '{}' automatically — RunOnce entries are removed when disabled. \
              Re-run the application's installer or manually add it back to: {}",
             name, registry_path
         ));
@@ -161,3 +435,271 @@ pub fn toggle_startup(name: &str, registry_path: &str, enable: bool) -> Result<S
         }
     }
 }
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Disk footprint — target executable + immediate DLL imports
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Footprint of every startup entry
+pub fn get_startup_footprints() -> Vec<StartupFootprint> {
+    let mut footprints: Vec<StartupFootprint> = list_startup_programs()
+        .iter()
+        .map(get_startup_footprint)
+        .collect();
+    footprints.sort_by(|a, b| {
+        b.total_mb
+            .partial_cmp(&a.total_mb)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    footprints
+}
+
+/// Resolve an entry's executable and measure it plus its immediate DLL dependencies
+pub fn get_startup_footprint(entry: &StartupEntry) -> StartupFootprint {
+    let scope = if entry.registry_path.starts_with("HKLM")
+        || entry.location == "All Users Startup Folder"
+    {
+        "machine"
+    } else {
+        "user"
+    };
+    let mut fp = StartupFootprint {
+        name: entry.name.clone(),
+        executable: None,
+        scope: scope.into(),
+        exe_size_mb: 0.0,
+        dependencies: Vec::new(),
+        app_dependencies_mb: 0.0,
+        total_mb: 0.0,
+        error: None,
+    };
+
+    let exe = match resolve_executable(&entry.command) {
+        Some(p) => p,
+        None => {
+            fp.error = Some("Could not resolve target executable".into());
+            return fp;
+        }
+    };
+    fp.executable = Some(exe.to_string_lossy().to_string());
+    fp.exe_size_mb = std::fs::metadata(&exe).map(|m| m.len()).unwrap_or(0) as f64 / 1_048_576.0;
+
+    let sys32 = PathBuf::from(std::env::var("SystemRoot").unwrap_or_else(|_| "C:\\Windows".into()))
+        .join("System32");
+    let exe_dir = exe.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+
+    match pe_imports(&exe) {
+        Some(imports) => {
+            for dll in imports {
+                let lower = dll.to_lowercase();
+                // API-set contracts are virtual and never exist on disk
+                if lower.starts_with("api-ms-") || lower.starts_with("ext-ms-") {
+                    continue;
+                }
+                let local = exe_dir.join(&dll);
+                let system = sys32.join(&dll);
+                let (path, is_system) = if local.is_file() {
+                    (Some(local), false)
+                } else if system.is_file() {
+                    (Some(system), true)
+                } else {
+                    (None, false)
+                };
+                let size = path
+                    .as_ref()
+                    .and_then(|p| std::fs::metadata(p).ok())
+                    .map(|m| m.len())
+                    .unwrap_or(0);
+                fp.dependencies.push(DependencyInfo {
+                    name: dll,
+                    path: path.map(|p| p.to_string_lossy().to_string()),
+                    size_mb: size as f64 / 1_048_576.0,
+                    system: is_system,
+                });
+            }
+        }
+        None => fp.error = Some("Not a readable PE executable".into()),
+    }
+
+    fp.app_dependencies_mb = fp
+        .dependencies
+        .iter()
+        .filter(|d| !d.system)
+        .map(|d| d.size_mb)
+        .sum();
+    fp.total_mb = fp.exe_size_mb + fp.app_dependencies_mb;
+    fp
+}
+
+/// The program part of a command line, with environment variables expanded
+fn command_target(command: &str) -> String {
+    let command = expand_env_vars(command.trim());
+    if let Some(rest) = command.strip_prefix('"') {
+        rest.split('"').next().unwrap_or("").to_string()
+    } else if let Some(idx) = command.to_ascii_lowercase().find(".exe") {
+        command[..idx + 4].to_string()
+    } else {
+        command.split_whitespace().next().unwrap_or("").to_string()
+    }
+}
+
+/// Extract the executable path from a startup command line (or .lnk shortcut)
+fn resolve_executable(command: &str) -> Option<PathBuf> {
+    let candidate = command_target(command);
+
+    let path = PathBuf::from(&candidate);
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if ext == "lnk" {
+        return resolve_shortcut(&path);
+    }
+    if path.is_file() {
+        return Some(path);
+    }
+    // Bare names like "rundll32.exe" live in System32
+    let sys32 = PathBuf::from(std::env::var("SystemRoot").unwrap_or_else(|_| "C:\\Windows".into()))
+        .join("System32")
+        .join(&candidate);
+    if sys32.is_file() {
+        Some(sys32)
+    } else {
+        None
+    }
+}
+
+/// Resolve a .lnk shortcut's target via WScript.Shell
+fn resolve_shortcut(lnk: &Path) -> Option<PathBuf> {
+    let script = format!(
+        "(New-Object -ComObject WScript.Shell).CreateShortcut('{}').TargetPath",
+        lnk.to_string_lossy().replace('\'', "''")
+    );
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-Command", &script])
+        .output()
+        .ok()?;
+    let target = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let path = PathBuf::from(target);
+    if path.is_file() {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+/// Expand %VAR% references in a command line or REG_EXPAND_SZ value; unknown variables
+/// are left as written
+pub(crate) fn expand_env_vars(input: &str) -> String {
+    let mut out = String::new();
+    let mut rest = input;
+    while let Some(start) = rest.find('%') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('%') {
+            Some(end) => {
+                let var = &after[..end];
+                match std::env::var(var) {
+                    Ok(val) => out.push_str(&val),
+                    Err(_) => {
+                        out.push('%');
+                        out.push_str(var);
+                        out.push('%');
+                    }
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// DLL names from a PE file's import directory
+fn pe_imports(path: &Path) -> Option<Vec<String>> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let read_at = |file: &mut std::fs::File, offset: u64, len: usize| -> Option<Vec<u8>> {
+        let mut buf = vec![0u8; len];
+        file.seek(SeekFrom::Start(offset)).ok()?;
+        file.read_exact(&mut buf).ok()?;
+        Some(buf)
+    };
+    let u16_at = |b: &[u8], o: usize| -> Option<u16> {
+        Some(u16::from_le_bytes(b.get(o..o + 2)?.try_into().ok()?))
+    };
+    let u32_at = |b: &[u8], o: usize| -> Option<u32> {
+        Some(u32::from_le_bytes(b.get(o..o + 4)?.try_into().ok()?))
+    };
+
+    let dos = read_at(&mut file, 0, 64)?;
+    if u16_at(&dos, 0)? != 0x5A4D {
+        return None; // "MZ"
+    }
+    let pe_offset = u32_at(&dos, 0x3C)? as u64;
+    let headers = read_at(&mut file, pe_offset, 24 + 240)?;
+    if u32_at(&headers, 0)? != 0x0000_4550 {
+        return None; // "PE\0\0"
+    }
+    let section_count = u16_at(&headers, 6)? as usize;
+    let optional_size = u16_at(&headers, 20)? as usize;
+    let optional = 24;
+    // Data directory 1 (imports) sits at 0x68 in PE32 and 0x78 in PE32+
+    let import_dir = match u16_at(&headers, optional)? {
+        0x10B => optional + 0x68,
+        0x20B => optional + 0x78,
+        _ => return None,
+    };
+    let import_rva = u32_at(&headers, import_dir)?;
+    if import_rva == 0 {
+        return Some(Vec::new());
+    }
+
+    // Section table maps RVAs to file offsets
+    let sections = read_at(
+        &mut file,
+        pe_offset + (optional + optional_size) as u64,
+        section_count * 40,
+    )?;
+    let rva_to_offset = |rva: u32| -> Option<u64> {
+        (0..section_count).find_map(|i| {
+            let s = i * 40;
+            let virt_size = u32_at(&sections, s + 8)?;
+            let virt_addr = u32_at(&sections, s + 12)?;
+            let raw_size = u32_at(&sections, s + 16)?;
+            let raw_ptr = u32_at(&sections, s + 20)?;
+            // Header fields are untrusted: a section that overflows maps nothing
+            let end = virt_addr.checked_add(virt_size.max(raw_size))?;
+            if rva >= virt_addr && rva < end {
+                (rva - virt_addr).checked_add(raw_ptr).map(u64::from)
+            } else {
+                None
+            }
+        })
+    };
+
+    let mut dlls = Vec::new();
+    let mut desc_offset = rva_to_offset(import_rva)?;
+    // IMAGE_IMPORT_DESCRIPTOR is 20 bytes; the table ends with an all-zero entry
+    for _ in 0..512 {
+        let desc = read_at(&mut file, desc_offset, 20)?;
+        let name_rva = u32_at(&desc, 12)?;
+        if name_rva == 0 {
+            break;
+        }
+        if let Some(name_offset) = rva_to_offset(name_rva) {
+            if let Some(raw) = read_at(&mut file, name_offset, 256) {
+                let end = raw.iter().position(|&c| c == 0).unwrap_or(raw.len());
+                let name = String::from_utf8_lossy(&raw[..end]).to_string();
+                if !name.is_empty() && !dlls.contains(&name) {
+                    dlls.push(name);
+                }
+            }
+        }
+        desc_offset += 20;
+    }
+    Some(dlls)
+}
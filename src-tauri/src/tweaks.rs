@@ -2,6 +2,9 @@
 
 use serde::{Deserialize, Serialize};
 use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // Windows Theme (Dark/Light Mode)
@@ -150,8 +153,9 @@ pub fn is_restore_enabled() -> bool {
 // Auto Memory Purge Settings
 // ═══════════════════════════════════════════════════════════════════════════════
 
-#[allow(dead_code)] // Config struct ready for background auto-purge timer
+#[allow(dead_code)] // purge_* flags aren't wired up yet — auto-trim only trims working sets
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AutoPurgeConfig {
     pub enabled: bool,
     pub threshold_percent: u32, // Purge when RAM usage exceeds this %
@@ -159,6 +163,7 @@ pub struct AutoPurgeConfig {
     pub purge_standby: bool,
     pub purge_modified: bool,
     pub purge_cache: bool,
+    pub min_idle_secs: u64, // only trim after this much user inactivity; 0 = no gate
 }
 
 impl Default for AutoPurgeConfig {
@@ -170,6 +175,102 @@ impl Default for AutoPurgeConfig {
             purge_standby: true,
             purge_modified: false,
             purge_cache: false,
+            min_idle_secs: 0,
         }
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoTrimEvent {
+    pub memory_percent: f64, // usage that triggered the trim
+    pub freed_mb: f64,
+    pub message: String,
+}
+
+/// Minimum gap between two automatic trims, so usage hovering at the threshold doesn't thrash
+const AUTO_TRIM_COOLDOWN_SECS: u64 = 300;
+
+/// Active auto-trim settings; None while disabled
+static AUTO_TRIM: Mutex<Option<AutoPurgeConfig>> = Mutex::new(None);
+
+/// Bumped on every enable/disable so a superseded polling task knows to exit
+static AUTO_TRIM_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+static LAST_AUTO_TRIM: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Turn on auto-trim; returns the generation the caller's polling task must check
+pub fn enable_auto_trim(
+    threshold_pct: u32,
+    interval_secs: u32,
+    min_idle_secs: u64,
+) -> Result<u64, String> {
+    if !(50..=99).contains(&threshold_pct) {
+        return Err("Threshold must be between 50% and 99%".into());
+    }
+    if !(5..=3600).contains(&interval_secs) {
+        return Err("Interval must be between 5 and 3600 seconds".into());
+    }
+    *AUTO_TRIM.lock().unwrap_or_else(|e| e.into_inner()) = Some(AutoPurgeConfig {
+        enabled: true,
+        threshold_percent: threshold_pct,
+        interval_seconds: interval_secs,
+        min_idle_secs,
+        ..AutoPurgeConfig::default()
+    });
+    Ok(AUTO_TRIM_GENERATION.fetch_add(1, Ordering::SeqCst) + 1)
+}
+
+pub fn disable_auto_trim() -> String {
+    *AUTO_TRIM.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    AUTO_TRIM_GENERATION.fetch_add(1, Ordering::SeqCst);
+    "Auto-trim disabled".into()
+}
+
+/// Current auto-trim settings (`enabled: false` with defaults when off)
+pub fn get_auto_trim_config() -> AutoPurgeConfig {
+    AUTO_TRIM
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+/// Whether the polling task started for `generation` is still the current one
+pub fn auto_trim_active(generation: u64) -> bool {
+    AUTO_TRIM_GENERATION.load(Ordering::SeqCst) == generation
+}
+
+/// One poll: trim working sets if memory is over the threshold and the cooldown has passed
+pub fn auto_trim_tick() -> Option<AutoTrimEvent> {
+    let config = AUTO_TRIM
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()?;
+    // Memory only: the full live metrics would sample GPU, temperatures and processes
+    let mut sys = sysinfo::System::new();
+    sys.refresh_memory();
+    if sys.total_memory() == 0 {
+        return None;
+    }
+    let memory_percent = sys.used_memory() as f64 / sys.total_memory() as f64 * 100.0;
+    if memory_percent < config.threshold_percent as f64 {
+        return None;
+    }
+    if config.min_idle_secs > 0 && crate::monitor::get_idle_seconds() < config.min_idle_secs {
+        return None;
+    }
+
+    let mut last = LAST_AUTO_TRIM.lock().unwrap_or_else(|e| e.into_inner());
+    if last.is_some_and(|t| t.elapsed().as_secs() < AUTO_TRIM_COOLDOWN_SECS) {
+        return None;
+    }
+    *last = Some(Instant::now());
+    drop(last);
+
+    let result = crate::optimizer::optimize_selective_trim();
+    Some(AutoTrimEvent {
+        memory_percent,
+        freed_mb: result.memory_freed_mb.unwrap_or(0.0),
+        message: result.message,
+    })
+}
@@ -1,6 +1,7 @@
 import { useState, useEffect, useCallback, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { open } from "@tauri-apps/plugin-dialog";
+import { listen } from "@tauri-apps/api/event";
+import { open, save } from "@tauri-apps/plugin-dialog";
 import "./index.css";
 
 // ═══════════════════════════════════════════════════════════════════
@@ -8,19 +9,26 @@ import "./index.css";
 // ═══════════════════════════════════════════════════════════════════
 interface SystemInfo { os_name: string; os_version: string; hostname: string; cpu_name: string; cpu_cores: number; total_memory_mb: number; used_memory_mb: number; available_memory_mb: number; memory_usage_percent: number; total_swap_mb: number; used_swap_mb: number; uptime_seconds: number; }
 interface ProcessInfo { pid: number; name: string; memory_mb: number; cpu_percent: number; status: string; parent_pid: number | null; }
-interface OptimizationItem { id: string; category: string; name: string; description: string; tooltip: string; risk: string; enabled_by_default: boolean; available: boolean; estimated_savings: string | null; }
+interface OptimizationItem { id: string; category: string; name: string; description: string; tooltip: string; risk: string; enabled_by_default: boolean; available: boolean; requires_admin: boolean; estimated_savings: string | null; }
 interface OptimizationResult { id: string; name: string; success: boolean; message: string; duration_ms: number; memory_freed_mb: number | null; }
-interface OptimizationReport { total_duration_ms: number; total_memory_freed_mb: number; items_attempted: number; items_succeeded: number; items_failed: number; results: OptimizationResult[]; memory_before_mb: number; memory_after_mb: number; }
-interface LiveMetrics { cpu_usage: number; cpu_per_core: number[]; memory_used_mb: number; memory_total_mb: number; memory_percent: number; swap_used_mb: number; swap_total_mb: number; disk_read_bytes: number; disk_write_bytes: number; net_rx_bytes: number; net_tx_bytes: number; process_count: number; uptime_seconds: number; temperatures: TempReading[]; }
-interface TempReading { label: string; temp_c: number; critical: number | null; }
-interface HealthScore { overall: number; memory_score: number; cpu_score: number; disk_score: number; startup_score: number; uptime_score: number; details: HealthDetail[]; }
+interface OptimizationReport { total_duration_ms: number; total_memory_freed_mb: number; items_attempted: number; items_succeeded: number; items_failed: number; results: OptimizationResult[]; memory_before_mb: number; memory_after_mb: number; slowest?: [string, number][]; slow_steps?: string[]; reboot_pending?: boolean; }
+interface LiveMetrics { cpu_usage: number; cpu_per_core: number[]; memory_used_mb: number; memory_total_mb: number; memory_percent: number; swap_used_mb: number; swap_total_mb: number; disk_read_bytes: number; disk_write_bytes: number; disk_read_bps: number; disk_write_bps: number; net_rx_bytes: number; net_tx_bytes: number; net_rx_bps: number; net_tx_bps: number; process_count: number; thread_count: number; uptime_seconds: number; temperatures: TempReading[]; gpu_usage_percent: number; vram_used_mb: number; vram_total_mb: number; }
+interface TempReading { label: string; temp_c: number; critical: number | null; source: string; }
+interface DashboardSummary { os_name: string; os_version: string; hostname: string; cpu_name: string; cpu_cores: number; cpu_usage: number; memory_used_mb: number; memory_total_mb: number; memory_percent: number; swap_used_mb: number; swap_total_mb: number; process_count: number; uptime_seconds: number; health: HealthScore; disks: DiskInfo[]; battery_present: boolean; battery_percent: number | null; on_ac_power: boolean | null; }
+interface HealthScore { overall: number; memory_score: number; cpu_score: number; disk_score: number; startup_score: number; uptime_score: number; details: HealthDetail[]; alerts: HealthAlert[]; }
+interface HealthAlert { category: string; previous: number; current: number; suggestion: string; }
 interface HealthDetail { category: string; score: number; label: string; suggestion: string; }
 interface HardwareInfo { cpu_name: string; cpu_arch: string; cpu_cores_physical: number; cpu_cores_logical: number; cpu_frequency_mhz: number; ram_total_gb: number; ram_type: string; os_name: string; os_version: string; os_build: string; hostname: string; disks: DiskInfo[]; gpus: string[]; network_adapters: string[]; }
 interface DiskInfo { name: string; mount_point: string; fs_type: string; total_gb: number; used_gb: number; free_gb: number; usage_percent: number; is_removable: boolean; }
-interface StartupEntry { name: string; command: string; location: string; registry_path: string; enabled: boolean; publisher: string; impact: string; }
-interface LargeFile { path: string; size_mb: number; extension: string; category: string; modified: string; ai_tooltip?: string; }
+interface StartupEntry { name: string; command: string; location: string; registry_path: string; enabled: boolean; publisher: string; impact: string; startup_time_ms: number | null; }
+interface LargeFile { path: string; size_mb: number; extension: string; category: string; modified: string; ai_tooltip?: string; token?: string; }
 interface BrowserInfo { name: string; cache_size_mb: number; cache_path: string; installed: boolean; }
+interface BrowserCleanOptions { cache: boolean; cookies: boolean; history: boolean; downloads: boolean; sessions: boolean; }
 interface PrivacyItem { id: string; name: string; description: string; category: string; data_size_mb: number; }
+interface SystemSnapshot { id: number; timestamp: number; memory_used_mb: number; process_count: number; standby_mb: number; disk_free_gb: number; startup_count: number; }
+interface SnapshotDiff { before: SystemSnapshot; after: SystemSnapshot; elapsed_secs: number; memory_freed_mb: number; processes_removed: number; standby_freed_mb: number; disk_freed_gb: number; startups_removed: number; summary: string; }
+interface TimerResolution { current_ms: number; finest_ms: number; default_ms: number; raised: boolean; }
+interface QuarantineSessionInfo { session_id: string; label: string; created_at: number; items: number; size_bytes: number; }
 interface DriverInfo { name: string; provider: string; version: string; date: string; device_class: string; signed: boolean; status: string; }
 interface ScheduledTask { name: string; status: string; }
 interface DnsProvider { id: string; name: string; primary: string; secondary: string; description: string; icon: string; }
@@ -66,6 +74,20 @@ function ProgressBar({ value, color = "var(--accent)", size = "" }: { value: num
   );
 }
 
+interface Settings { enabled_ids: string[]; scan_dirs: string[]; auto_trim: AutoPurgeConfig; recycle_by_default: boolean; process_whitelist: string[]; }
+
+// Read-modify-write so each page only touches the settings fields it owns
+function updateSettings(patch: Partial<Settings>): Promise<string> {
+  return invoke<Settings>("cmd_load_settings").then(s => invoke<string>("cmd_save_settings", { settings: { ...s, ...patch } }));
+}
+
+// Ask where to save, then have the backend export its latest result of that kind
+async function exportResult(command: string, title: string, defaultPath: string) {
+  const ext = defaultPath.split(".").pop() || "csv";
+  const path = await save({ title, defaultPath, filters: [{ name: ext.toUpperCase(), extensions: [ext] }] });
+  if (path) invoke<string>(command, { path }).then(m => alert(m)).catch(e => alert(String(e)));
+}
+
 function formatBytes(bytes: number): string {
   if (bytes < 1024) return `${bytes} B`;
   if (bytes < 1048576) return `${(bytes / 1024).toFixed(1)} KB`;
@@ -153,6 +175,7 @@ export default function App() {
   const [selected, setSelected] = useState<Set<string>>(new Set());
   const [optimizing, setOptimizing] = useState(false);
   const [report, setReport] = useState<OptimizationReport | null>(null);
+  const [sessionDiff, setSessionDiff] = useState<SnapshotDiff | null>(null);
   const [expandedCats, setExpandedCats] = useState<Set<string>>(new Set());
 
   // Process manager
@@ -178,21 +201,36 @@ export default function App() {
   // Hardware
   const [hardware, setHardware] = useState<HardwareInfo | null>(null);
 
+  // Elevation — null until checked; many optimizations fail with access denied without it
+  const [elevated, setElevated] = useState<boolean | null>(null);
+
+  // System info and health score in one round trip; shares a single sysinfo refresh
+  const loadSummary = () => invoke<DashboardSummary>("cmd_get_dashboard_summary").then(s => {
+    setSysInfo({
+      os_name: s.os_name, os_version: s.os_version, hostname: s.hostname, cpu_name: s.cpu_name, cpu_cores: s.cpu_cores,
+      total_memory_mb: s.memory_total_mb, used_memory_mb: s.memory_used_mb, available_memory_mb: s.memory_total_mb - s.memory_used_mb,
+      memory_usage_percent: s.memory_percent, total_swap_mb: s.swap_total_mb, used_swap_mb: s.swap_used_mb, uptime_seconds: s.uptime_seconds,
+    });
+    setHealth(s.health);
+  }).catch(console.error);
+
   // ── Load data (staggered to avoid overloading) ──
   useEffect(() => {
-    invoke<SystemInfo>("cmd_get_system_info").then(setSysInfo).catch(console.error);
+    loadSummary();
+    invoke<boolean>("cmd_is_elevated").then(setElevated).catch(console.error);
     // Delay heavy calls slightly so they don't all run simultaneously
-    setTimeout(() => invoke<HealthScore>("cmd_get_health_score").then(setHealth).catch(console.error), 500);
     setTimeout(() => invoke<OptimizationItem[]>("cmd_get_catalog").then(c => {
       setCatalog(c);
-      // Restore saved selections from localStorage, or fall back to defaults
-      const saved = localStorage.getItem("vega_optimizer_selected");
-      if (saved) {
+      // enabled_by_default already reflects saved settings; migrate selections older builds kept in localStorage
+      const legacy = localStorage.getItem("vega_optimizer_selected");
+      if (legacy) {
         try {
-          const ids: string[] = JSON.parse(saved);
           const validIds = new Set(c.map(i => i.id));
-          setSelected(new Set(ids.filter(id => validIds.has(id))));
+          const ids = (JSON.parse(legacy) as string[]).filter(id => validIds.has(id));
+          setSelected(new Set(ids));
+          updateSettings({ enabled_ids: ids }).catch(console.error);
         } catch { setSelected(new Set(c.filter(i => i.enabled_by_default).map(i => i.id))); }
+        localStorage.removeItem("vega_optimizer_selected");
       } else {
         setSelected(new Set(c.filter(i => i.enabled_by_default).map(i => i.id)));
       }
@@ -241,23 +279,29 @@ export default function App() {
   const toggleItem = (id: string) => {
     setSelected(prev => {
       const s = new Set(prev); s.has(id) ? s.delete(id) : s.add(id);
-      localStorage.setItem("vega_optimizer_selected", JSON.stringify([...s]));
+      updateSettings({ enabled_ids: [...s] }).catch(console.error);
       return s;
     });
   };
 
   const applyProfile = (ids: string[]) => {
     setSelected(new Set(ids));
-    localStorage.setItem("vega_optimizer_selected", JSON.stringify(ids));
+    updateSettings({ enabled_ids: ids }).catch(console.error);
   };
 
   const runOptimize = async () => {
     setOptimizing(true);
+    setSessionDiff(null);
     try {
+      const before = await invoke<number>("cmd_capture_snapshot").catch(() => null);
       const r = await invoke<OptimizationReport>("cmd_optimize", { ids: [...selected] });
       setReport(r);
-      invoke<HealthScore>("cmd_get_health_score").then(setHealth).catch(console.error);
-      invoke<SystemInfo>("cmd_get_system_info").then(setSysInfo).catch(console.error);
+      if (before !== null) {
+        invoke<number>("cmd_capture_snapshot")
+          .then(after => invoke<SnapshotDiff>("cmd_compare_snapshots", { a: before, b: after }))
+          .then(setSessionDiff).catch(console.error);
+      }
+      loadSummary();
     } catch (e) { console.error(e); }
     setOptimizing(false);
   };
@@ -268,7 +312,7 @@ export default function App() {
 
   const scanLargeFiles = async () => {
     setScanning(true);
-    try { const f = await invoke<LargeFile[]>("cmd_scan_large_files", { minSizeMb: 100 }); setLargeFiles(f); } catch (e) { console.error(e); }
+    try { const r = await invoke<{ files: LargeFile[]; cancelled: boolean }>("cmd_scan_large_files", { minSizeMb: 100 }); setLargeFiles(r.files); } catch (e) { console.error(e); }
     setScanning(false);
   };
 
@@ -335,6 +379,12 @@ export default function App() {
 
       {/* ── Main ── */}
       <main className={`main-content ${focusZone === "main" ? "focus-zone-active" : ""}`} ref={mainRef} tabIndex={-1}>
+        {elevated === false && (
+          <div className="card" style={{ display: "flex", alignItems: "center", justifyContent: "space-between", gap: 12, padding: "10px 16px", marginBottom: 16, borderColor: "var(--warning)" }}>
+            <span style={{ fontSize: 13, color: "var(--warning)" }}>🛡️ Elevation required — running without administrator rights, so service, memory and system-wide changes will be denied.</span>
+            <button className="btn btn-sm btn-primary" onClick={() => invoke("cmd_relaunch_as_admin").catch(e => alert(String(e)))}>Restart as Administrator</button>
+          </div>
+        )}
         {page === "dashboard" && <DashboardPage health={health} metrics={metrics} sysInfo={sysInfo} hardware={hardware} />}
         {page === "optimizer" && (
           <OptimizerPage
@@ -342,7 +392,7 @@ export default function App() {
             expandedCats={expandedCats} setExpandedCats={setExpandedCats}
             toggleItem={toggleItem} applyProfile={applyProfile}
             optimizing={optimizing} runOptimize={runOptimize}
-            report={report} setReport={setReport}
+            report={report} setReport={setReport} elevated={elevated}
           />
         )}
         {page === "processes" && <ProcessPage processes={sortedProcesses} sort={procSort} setSort={setProcSort} search={procSearch} setSearch={setProcSearch} refresh={loadProcesses} kill={killProcess} />}
@@ -364,7 +414,7 @@ export default function App() {
       </main>
 
       {/* ── Report Overlay ── */}
-      {report && <ReportOverlay report={report} onClose={() => setReport(null)} />}
+      {report && <ReportOverlay report={report} diff={sessionDiff} onClose={() => setReport(null)} />}
     </div>
   );
 }
@@ -393,6 +443,10 @@ function NavItem({ icon, label, id, active, onClick, badge }: { icon: string; la
 // Dashboard
 // ═══════════════════════════════════════════════════════════════════
 function DashboardPage({ health, metrics, sysInfo, hardware }: { health: HealthScore | null; metrics: LiveMetrics | null; sysInfo: SystemInfo | null; hardware: HardwareInfo | null }) {
+  const [healthHistory, setHealthHistory] = useState<[string, number][]>([]);
+  // Each health check appends a sample, so re-read the trend whenever a new score arrives
+  useEffect(() => { invoke<[string, number][]>("cmd_get_health_history").then(setHealthHistory).catch(() => {}); }, [health]);
+
   return (
     <div>
       <div className="page-header">
@@ -419,6 +473,18 @@ function DashboardPage({ health, metrics, sysInfo, hardware }: { health: HealthS
               </div>
             ))}
           </div>}
+          {health && health.alerts.length > 0 && <div style={{ width: "100%", marginTop: 8 }}>
+            {health.alerts.map(a => (
+              <div key={a.category} style={{ fontSize: 11, color: "var(--danger)", padding: "2px 0" }} title={a.suggestion}>⚠ {a.category} dropped {a.previous} → {a.current} — {a.suggestion}</div>
+            ))}
+          </div>}
+          {healthHistory.length >= 2 && <div style={{ width: "100%", marginTop: 8 }} title={`${healthHistory[0][0]} — ${healthHistory[healthHistory.length - 1][0]}`}>
+            <svg width="100%" height={32} viewBox="0 0 200 32" preserveAspectRatio="none" style={{ background: "var(--bg-tertiary)", borderRadius: 4 }}>
+              <polyline fill="none" stroke="var(--accent)" strokeWidth={1.5}
+                points={healthHistory.map(([, s], i) => `${(i / (healthHistory.length - 1)) * 200},${32 - (s / 100) * 32}`).join(" ")} />
+            </svg>
+            <div style={{ fontSize: 10, color: "var(--text-muted)" }}>Health trend • {healthHistory.length} checks</div>
+          </div>}
         </div>
 
         <div className="card-grid card-grid-2" style={{ alignContent: "start" }}>
@@ -432,12 +498,20 @@ function DashboardPage({ health, metrics, sysInfo, hardware }: { health: HealthS
           </div>
           <div className="stat-mini">
             <div className="stat-icon" style={{ background: "var(--success-dim)", color: "var(--success)" }}>📡</div>
-            <div><div className="stat-value">{metrics ? formatBytes(metrics.net_rx_bytes) : "..."}</div><div className="stat-label">Network RX</div></div>
+            <div><div className="stat-value">{metrics ? `${formatBytes(metrics.net_rx_bps)}/s` : "..."}</div><div className="stat-label">Network ↓ {metrics ? `• ↑ ${formatBytes(metrics.net_tx_bps)}/s` : ""}</div></div>
           </div>
           <div className="stat-mini">
             <div className="stat-icon" style={{ background: "var(--warning-dim)", color: "var(--warning)" }}>⏱️</div>
             <div><div className="stat-value">{metrics ? formatUptime(metrics.uptime_seconds) : "..."}</div><div className="stat-label">Uptime</div></div>
           </div>
+          <div className="stat-mini">
+            <div className="stat-icon" style={{ background: "var(--accent-dim)", color: "var(--accent)" }}>📋</div>
+            <div><div className="stat-value">{metrics ? metrics.process_count : "..."}</div><div className="stat-label">Processes</div></div>
+          </div>
+          <div className="stat-mini">
+            <div className="stat-icon" style={{ background: "var(--purple-dim)", color: "var(--purple)" }}>🧵</div>
+            <div><div className="stat-value">{metrics ? metrics.thread_count.toLocaleString() : "..."}</div><div className="stat-label">Threads</div></div>
+          </div>
         </div>
       </div>
 
@@ -454,12 +528,17 @@ function DashboardPage({ health, metrics, sysInfo, hardware }: { health: HealthS
               <div className="metric-row"><span className="label">Memory</span><span className="value">{(metrics.memory_used_mb / 1024).toFixed(1)} / {(metrics.memory_total_mb / 1024).toFixed(1)} GB</span></div>
               <ProgressBar value={metrics.memory_percent} color={metrics.memory_percent > 85 ? "var(--danger)" : metrics.memory_percent > 60 ? "var(--warning)" : "var(--purple)"} />
             </div>
+            <div>
+              <div className="metric-row"><span className="label">GPU</span><span className="value">{metrics.gpu_usage_percent.toFixed(1)}%{metrics.vram_total_mb > 0 ? ` • VRAM ${(metrics.vram_used_mb / 1024).toFixed(1)} / ${(metrics.vram_total_mb / 1024).toFixed(1)} GB` : ""}</span></div>
+              <ProgressBar value={metrics.gpu_usage_percent} color={metrics.gpu_usage_percent > 80 ? "var(--danger)" : metrics.gpu_usage_percent > 50 ? "var(--warning)" : "var(--success)"} />
+            </div>
             {hardware && hardware.disks.filter(d => d.mount_point.toLowerCase() === "c:\\" || d.name.toLowerCase().includes("c:") || d.mount_point === "/").slice(0, 1).map((disk, i) => (
               <div key={i}>
                 <div className="metric-row"><span className="label">OS Drive Space ({disk.mount_point})</span><span className="value">{(disk.total_gb - disk.free_gb).toFixed(1)} / {disk.total_gb.toFixed(1)} GB ({disk.usage_percent.toFixed(0)}%)</span></div>
                 <ProgressBar value={disk.usage_percent} color={disk.usage_percent > 90 ? "var(--danger)" : disk.usage_percent > 75 ? "var(--warning)" : "var(--success)"} />
               </div>
             ))}
+            <div className="metric-row"><span className="label">Disk Activity</span><span className="value">R {formatBytes(metrics.disk_read_bps)}/s • W {formatBytes(metrics.disk_write_bps)}/s</span></div>
             {metrics.swap_total_mb > 0 && <div>
               <div className="metric-row"><span className="label">Swap</span><span className="value">{(metrics.swap_used_mb / 1024).toFixed(1)} / {(metrics.swap_total_mb / 1024).toFixed(1)} GB</span></div>
               <ProgressBar value={(metrics.swap_used_mb / metrics.swap_total_mb) * 100} color="var(--orange)" />
@@ -484,10 +563,10 @@ function DashboardPage({ health, metrics, sysInfo, hardware }: { health: HealthS
       {/* Temperatures */}
       {metrics && metrics.temperatures.length > 0 && (
         <div className="card">
-          <div className="card-header"><h3>Temperatures</h3></div>
+          <div className="card-header"><h3>Temperatures</h3>{metrics.temperatures[0].source !== "sysinfo" && <span style={{ fontSize: 11, color: "var(--text-muted)" }}>via {metrics.temperatures[0].source === "acpi" ? "ACPI thermal zones" : "LibreHardwareMonitor"}</span>}</div>
           <div style={{ display: "grid", gridTemplateColumns: "1fr 1fr 1fr", gap: 8 }}>
             {metrics.temperatures.map((t, i) => (
-              <div key={i} className="metric-row">
+              <div key={i} className="metric-row" title={`Source: ${t.source}`}>
                 <span className="label" style={{ fontSize: 11 }}>{t.label}</span>
                 <span className="value" style={{ color: t.temp_c > 80 ? "var(--danger)" : t.temp_c > 60 ? "var(--warning)" : "var(--success)" }}>{t.temp_c.toFixed(0)}°C</span>
               </div>
@@ -502,12 +581,13 @@ function DashboardPage({ health, metrics, sysInfo, hardware }: { health: HealthS
 // ═══════════════════════════════════════════════════════════════════
 // Optimizer
 // ═══════════════════════════════════════════════════════════════════
-function OptimizerPage({ catalog, categories, selected, expandedCats, setExpandedCats, toggleItem, applyProfile, optimizing, runOptimize, report, setReport }: {
+function OptimizerPage({ catalog, categories, selected, expandedCats, setExpandedCats, toggleItem, applyProfile, optimizing, runOptimize, report, setReport, elevated }: {
   catalog: OptimizationItem[]; categories: string[]; selected: Set<string>;
   expandedCats: Set<string>; setExpandedCats: React.Dispatch<React.SetStateAction<Set<string>>>;
   toggleItem: (id: string) => void; applyProfile: (ids: string[]) => void;
   optimizing: boolean; runOptimize: () => void;
   report: OptimizationReport | null; setReport: (r: OptimizationReport | null) => void;
+  elevated: boolean | null;
 }) {
   return (
     <div>
@@ -545,10 +625,10 @@ function OptimizerPage({ catalog, categories, selected, expandedCats, setExpande
               {isExpanded && (
                 <div className="category-items">
                   {items.map((item: OptimizationItem) => (
-                    <div key={item.id} className="checkbox-row" onClick={() => toggleItem(item.id)}>
+                    <div key={item.id} className="checkbox-row" onClick={() => toggleItem(item.id)} style={item.requires_admin && elevated === false ? { opacity: 0.55 } : undefined}>
                       <div className={`checkbox-custom ${selected.has(item.id) ? "checked" : ""}`} />
                       <div className="checkbox-info" style={{ flex: 1 }}>
-                        <h4>{item.name} <span className={`badge badge-${item.risk}`} style={{ marginLeft: 6 }}>{item.risk}</span></h4>
+                        <h4>{item.name} <span className={`badge badge-${item.risk}`} style={{ marginLeft: 6 }}>{item.risk}</span>{item.requires_admin && <span className="badge badge-medium" style={{ marginLeft: 6 }} title={elevated === false ? "Needs administrator rights — will fail until you restart as administrator" : "Needs administrator rights"}>🛡️ admin</span>}</h4>
                         <p>{item.description}</p>
                       </div>
                       {item.estimated_savings && <span style={{ fontSize: 11, color: "var(--success)", fontFamily: "'JetBrains Mono', monospace", whiteSpace: "nowrap" }}>~{item.estimated_savings}</span>}
@@ -566,6 +646,64 @@ function OptimizerPage({ catalog, categories, selected, expandedCats, setExpande
         {optimizing ? <><div className="spinner" style={{ display: "inline-block", marginRight: 8, borderTopColor: "white" }} /> OPTIMIZING...</> : <>⚡ OPTIMIZE NOW</>}
         <div className="sub">{selected.size} items selected</div>
       </button>
+
+      <SchedulePanel selected={selected} />
+    </div>
+  );
+}
+
+type ScheduleTrigger = { type: "Daily"; hour: number } | { type: "OnMemoryPressure"; threshold_pct: number } | { type: "OnIdle" };
+interface Schedule { id: string; optimization_ids: string[]; trigger: ScheduleTrigger; last_run: number | null; min_idle_secs: number; }
+
+function describeTrigger(t: ScheduleTrigger): string {
+  if (t.type === "Daily") return `Daily at ${String(t.hour).padStart(2, "0")}:00`;
+  if (t.type === "OnMemoryPressure") return `When memory ≥ ${t.threshold_pct}%`;
+  return "When the PC is idle";
+}
+
+function SchedulePanel({ selected }: { selected: Set<string> }) {
+  const [schedules, setSchedules] = useState<Schedule[]>([]);
+  const [kind, setKind] = useState<ScheduleTrigger["type"]>("Daily");
+  const [hour, setHour] = useState(3);
+  const [threshold, setThreshold] = useState(85);
+  const [idleMin, setIdleMin] = useState(0);
+
+  const load = useCallback(() => { invoke<Schedule[]>("cmd_list_schedules").then(setSchedules).catch(console.error); }, []);
+  useEffect(() => {
+    load();
+    const unlisten = listen("schedule-ran", () => load());
+    return () => { unlisten.then(f => f()); };
+  }, [load]);
+
+  const add = () => {
+    const trigger: ScheduleTrigger = kind === "Daily" ? { type: "Daily", hour } : kind === "OnMemoryPressure" ? { type: "OnMemoryPressure", threshold_pct: threshold } : { type: "OnIdle" };
+    invoke<Schedule>("cmd_add_schedule", { ids: [...selected], trigger, minIdleSecs: idleMin * 60 }).then(load).catch(e => alert(String(e)));
+  };
+  const remove = (id: string) => { invoke<string>("cmd_remove_schedule", { id }).then(load).catch(e => alert(String(e))); };
+
+  return (
+    <div className="card" style={{ padding: 16, marginTop: 16 }}>
+      <h3 style={{ fontSize: 14, marginBottom: 4 }}>⏰ Scheduled Runs</h3>
+      <div style={{ fontSize: 12, color: "var(--text-muted)", marginBottom: 12 }}>Run the selected optimizations automatically while VegaOptimizer is open</div>
+      <div style={{ display: "flex", gap: 8, alignItems: "center", marginBottom: 12 }}>
+        <select value={kind} onChange={e => setKind(e.target.value as ScheduleTrigger["type"])}>
+          <option value="Daily">Daily</option>
+          <option value="OnMemoryPressure">On memory pressure</option>
+          <option value="OnIdle">When idle</option>
+        </select>
+        {kind === "Daily" && <select value={hour} onChange={e => setHour(Number(e.target.value))}>
+          {Array.from({ length: 24 }, (_, h) => <option key={h} value={h}>{String(h).padStart(2, "0")}:00</option>)}
+        </select>}
+        {kind === "OnMemoryPressure" && <input type="number" min={50} max={99} value={threshold} onChange={e => setThreshold(Number(e.target.value))} style={{ width: 64 }} />}
+        <label style={{ fontSize: 12 }}>Idle ≥ <input type="number" min={0} max={240} value={idleMin} onChange={e => setIdleMin(Number(e.target.value))} style={{ width: 56 }} /> min</label>
+        <button className="btn btn-primary btn-sm" disabled={selected.size === 0} onClick={add}>Add schedule ({selected.size} items)</button>
+      </div>
+      {schedules.map(s => (
+        <div key={s.id} style={{ display: "flex", justifyContent: "space-between", alignItems: "center", fontSize: 12, padding: "6px 0", borderTop: "1px solid var(--border)" }}>
+          <span><strong>{describeTrigger(s.trigger)}</strong> — {s.optimization_ids.length} optimizations{s.min_idle_secs > 0 ? ` • after ${Math.round(s.min_idle_secs / 60)} min idle` : ""}{s.last_run ? ` • last run ${new Date(s.last_run * 1000).toLocaleString()}` : ""}</span>
+          <button className="btn btn-ghost btn-sm" onClick={() => remove(s.id)}>✕</button>
+        </div>
+      ))}
     </div>
   );
 }
@@ -575,6 +713,8 @@ function OptimizerPage({ catalog, categories, selected, expandedCats, setExpande
 // ═══════════════════════════════════════════════════════════════════
 interface ProcessSuggestion { pid: number; name: string; memory_mb: number; cpu_percent: number; estimated_savings_mb: number; reason: string; severity: string; category: string; safe_to_optimize: boolean; }
 interface ProcessOptResult { pid: number; name: string; memory_before_mb: number; memory_after_mb: number; freed_mb: number; success: boolean; message: string; }
+interface ProcessAffinity { pid: number; mask: number; system_mask: number; logical_cores: number; }
+interface SuspendState { pid: number; name: string; suspended: boolean; }
 interface ProcessOptReport { total_freed_mb: number; processes_trimmed: number; results: ProcessOptResult[]; }
 
 const CATEGORY_LABELS: Record<string, { emoji: string; label: string; color: string }> = {
@@ -591,6 +731,19 @@ function ProcessPage({ processes, sort, setSort, search, setSearch, refresh, kil
   const [optimizingProcs, setOptimizingProcs] = useState(false);
   const [procReport, setProcReport] = useState<ProcessOptReport | null>(null);
   const [view, setView] = useState<"suggestions" | "all">("suggestions");
+  const [whitelist, setWhitelist] = useState<string[]>([]);
+  const [suspended, setSuspended] = useState<Set<number>>(new Set());
+  const [affinityFor, setAffinityFor] = useState<ProcessInfo | null>(null);
+
+  useEffect(() => { invoke<SuspendState[]>("cmd_list_suspended_processes").then(list => setSuspended(new Set(list.map(p => p.pid)))).catch(console.error); }, []);
+  const toggleSuspend = (pid: number) => {
+    invoke<SuspendState>(suspended.has(pid) ? "cmd_resume_process" : "cmd_suspend_process", { pid }).then(st => {
+      setSuspended(prev => { const s = new Set(prev); st.suspended ? s.add(pid) : s.delete(pid); return s; });
+    }).catch(e => alert(String(e)));
+  };
+
+  const loadWhitelist = useCallback(() => { invoke<Settings>("cmd_load_settings").then(s => setWhitelist(s.process_whitelist)).catch(console.error); }, []);
+  useEffect(() => { loadWhitelist(); }, [loadWhitelist]);
 
   const loadSuggestions = useCallback(() => {
     setLoadingSuggestions(true);
@@ -608,6 +761,17 @@ function ProcessPage({ processes, sort, setSort, search, setSearch, refresh, kil
     setSelectedPids(prev => { const s = new Set(prev); s.has(pid) ? s.delete(pid) : s.add(pid); return s; });
   };
 
+  const addToWhitelist = (name: string) => {
+    invoke<string>("cmd_add_process_whitelist", { name }).then(() => {
+      loadWhitelist();
+      const lower = name.toLowerCase();
+      setSuggestions(prev => prev.filter(s => s.name.toLowerCase() !== lower));
+    }).catch(e => alert(String(e)));
+  };
+  const removeFromWhitelist = (name: string) => {
+    invoke<string>("cmd_remove_process_whitelist", { name }).then(() => { loadWhitelist(); loadSuggestions(); }).catch(e => alert(String(e)));
+  };
+
   const selectAll = () => setSelectedPids(new Set(suggestions.map(s => s.pid)));
   const selectNone = () => setSelectedPids(new Set());
 
@@ -677,6 +841,15 @@ function ProcessPage({ processes, sort, setSort, search, setSearch, refresh, kil
             </div>
           )}
 
+          {whitelist.length > 0 && (
+            <div style={{ display: "flex", flexWrap: "wrap", gap: 6, alignItems: "center", marginBottom: 12, fontSize: 12, color: "var(--text-muted)" }}>
+              <span>Never suggested:</span>
+              {whitelist.map(n => (
+                <span key={n} className="badge badge-low" style={{ cursor: "pointer" }} title="Remove from whitelist" onClick={() => removeFromWhitelist(n)}>{n} ✕</span>
+              ))}
+            </div>
+          )}
+
           {loadingSuggestions ? (
             <div className="empty-state"><div className="spinner lg" style={{ margin: "0 auto" }} /><p style={{ marginTop: 12 }}>Analyzing processes...</p></div>
           ) : suggestions.length === 0 ? (
@@ -723,6 +896,7 @@ function ProcessPage({ processes, sort, setSort, search, setSearch, refresh, kil
                         ~{s.estimated_savings_mb >= 1024 ? `${(s.estimated_savings_mb / 1024).toFixed(1)} GB` : `${s.estimated_savings_mb.toFixed(0)} MB`}
                       </div>
                       <div style={{ fontSize: 10, color: "var(--text-muted)", textTransform: "uppercase" }}>est. savings</div>
+                      <button className="btn btn-ghost btn-sm" style={{ marginTop: 4 }} title="Never suggest this process again" onClick={e => { e.stopPropagation(); addToWhitelist(s.name); }}>Ignore</button>
                     </div>
                   </div>
                 );
@@ -750,8 +924,12 @@ function ProcessPage({ processes, sort, setSort, search, setSearch, refresh, kil
                   <td className="mono">{p.pid}</td>
                   <td className="mono">{p.memory_mb.toFixed(1)} MB</td>
                   <td className="mono" style={{ color: p.cpu_percent > 50 ? "var(--danger)" : p.cpu_percent > 10 ? "var(--warning)" : "var(--text-secondary)" }}>{p.cpu_percent.toFixed(1)}%</td>
-                  <td><span className={`badge ${p.status === "Run" ? "badge-low" : "badge-medium"}`}>{p.status}</span></td>
-                  <td><button className="btn-icon" onClick={() => kill(p.pid)} title="Kill process">✕</button></td>
+                  <td>{suspended.has(p.pid) ? <span className="badge badge-high">Suspended</span> : <span className={`badge ${p.status === "Run" ? "badge-low" : "badge-medium"}`}>{p.status}</span>}</td>
+                  <td style={{ whiteSpace: "nowrap" }}>
+                    <button className="btn-icon" onClick={() => setAffinityFor(p)} title="Priority & CPU affinity">🧩</button>
+                    <button className="btn-icon" onClick={() => toggleSuspend(p.pid)} title={suspended.has(p.pid) ? "Resume process" : "Suspend process"}>{suspended.has(p.pid) ? "▶" : "⏸"}</button>
+                    <button className="btn-icon" onClick={() => kill(p.pid)} title="Kill process">✕</button>
+                  </td>
                 </tr>
               ))}
             </tbody>
@@ -798,6 +976,70 @@ function ProcessPage({ processes, sort, setSort, search, setSearch, refresh, kil
           </div>
         </div>
       )}
+      {affinityFor && <ProcessControlsDialog proc={affinityFor} onClose={() => setAffinityFor(null)} />}
+    </div>
+  );
+}
+
+function ProcessControlsDialog({ proc, onClose }: { proc: ProcessInfo; onClose: () => void }) {
+  const [affinity, setAffinity] = useState<ProcessAffinity | null>(null);
+  const [cores, setCores] = useState<Set<number>>(new Set());
+  const [error, setError] = useState("");
+  const [priorityMsg, setPriorityMsg] = useState("");
+
+  const setPriority = (priority: string) => {
+    setError("");
+    invoke<string>("cmd_set_process_priority", { pid: proc.pid, priority })
+      .then(applied => setPriorityMsg(applied === priority ? `Priority set to ${applied}` : `Windows applied ${applied} instead of ${priority}`))
+      .catch(e => setError(String(e)));
+  };
+
+  useEffect(() => {
+    invoke<ProcessAffinity>("cmd_get_process_affinity", { pid: proc.pid }).then(a => {
+      setAffinity(a);
+      setCores(new Set(Array.from({ length: a.logical_cores }, (_, i) => i).filter(i => Math.floor(a.mask / 2 ** i) % 2 === 1)));
+    }).catch(e => setError(String(e)));
+  }, [proc.pid]);
+
+  const toggle = (i: number) => setCores(prev => { const s = new Set(prev); s.has(i) ? s.delete(i) : s.add(i); return s; });
+  const apply = () => {
+    const mask = [...cores].reduce((m, i) => m + 2 ** i, 0);
+    invoke<string>("cmd_set_affinity", { pid: proc.pid, mask }).then(onClose).catch(e => setError(String(e)));
+  };
+
+  return (
+    <div className="overlay" onClick={onClose}>
+      <div className="overlay-panel" onClick={e => e.stopPropagation()} style={{ maxWidth: 420 }}>
+        <div style={{ display: "flex", justifyContent: "space-between", alignItems: "center", marginBottom: 12 }}>
+          <h3 style={{ fontSize: 16 }}>🧩 {proc.name} <span style={{ fontSize: 12, color: "var(--text-muted)" }}>PID {proc.pid}</span></h3>
+          <button className="btn-icon" onClick={onClose}>✕</button>
+        </div>
+        {error && <div style={{ fontSize: 12, color: "var(--danger)", marginBottom: 8 }}>{error}</div>}
+        <div className="card-label" style={{ marginBottom: 6 }}>Priority</div>
+        <div style={{ display: "flex", gap: 8, alignItems: "center", marginBottom: 16 }}>
+          <select defaultValue="" onChange={e => e.target.value && setPriority(e.target.value)}>
+            <option value="" disabled>Choose…</option>
+            <option value="idle">Idle</option>
+            <option value="below_normal">Below normal</option>
+            <option value="normal">Normal</option>
+            <option value="above_normal">Above normal</option>
+            <option value="high">High</option>
+            <option value="realtime">Realtime</option>
+          </select>
+          {priorityMsg && <span style={{ fontSize: 12, color: "var(--text-muted)" }}>{priorityMsg}</span>}
+        </div>
+        <div className="card-label" style={{ marginBottom: 6 }}>CPU Affinity</div>
+        {affinity && (
+          <>
+            <div style={{ display: "grid", gridTemplateColumns: "repeat(8, 1fr)", gap: 6, marginBottom: 12 }}>
+              {Array.from({ length: affinity.logical_cores }, (_, i) => (
+                <button key={i} className={`btn btn-sm ${cores.has(i) ? "btn-primary" : "btn-ghost"}`} onClick={() => toggle(i)}>{i}</button>
+              ))}
+            </div>
+            <button className="btn btn-primary btn-sm" disabled={cores.size === 0} onClick={apply}>Apply ({cores.size} of {affinity.logical_cores} cores)</button>
+          </>
+        )}
+      </div>
     </div>
   );
 }
@@ -896,7 +1138,7 @@ function StartupPage({ items }: { items: StartupEntry[] }) {
                 <td style={{ fontWeight: 500, color: "var(--text-primary)" }}>{s.name}</td>
                 <td><span className={`badge ${s.enabled ? "badge-low" : "badge-medium"}`}>{s.enabled ? "Enabled" : "Disabled"}</span></td>
                 <td><span className="badge badge-info">{s.location}</span></td>
-                <td><span className={`badge badge-${s.impact === "High" ? "high" : s.impact === "Medium" ? "medium" : "low"}`}>{s.impact}</span></td>
+                <td><span className={`badge badge-${s.impact === "High" ? "high" : s.impact === "Medium" ? "medium" : "low"}`} title={s.startup_time_ms !== null ? "Measured by Windows at boot" : "Estimated from the program name"}>{s.impact}{s.startup_time_ms !== null ? ` • ${(s.startup_time_ms / 1000).toFixed(1)}s` : ""}</span></td>
                 <td style={{ fontSize: 11, color: "var(--text-muted)", maxWidth: 250, overflow: "hidden", textOverflow: "ellipsis", whiteSpace: "nowrap" }} title={s.command}>{s.command}</td>
                 <td>
                   <button
@@ -952,6 +1194,9 @@ function StartupPage({ items }: { items: StartupEntry[] }) {
 // ═══════════════════════════════════════════════════════════════════
 // Settings & Telemetry
 // ═══════════════════════════════════════════════════════════════════
+interface AutoPurgeConfig { enabled: boolean; threshold_percent: number; interval_seconds: number; purge_standby: boolean; purge_modified: boolean; purge_cache: boolean; min_idle_secs: number; }
+interface AutoTrimEvent { memory_percent: number; freed_mb: number; message: string; }
+
 function SettingsPage() {
   const toggleSetting = async (setting: string, e: any, disable: boolean) => {
     const checked = e.target.checked;
@@ -996,15 +1241,40 @@ function SettingsPage() {
       .finally(() => setCreating(false));
   };
 
+  // Persisted preferences
+  const [recycleDefault, setRecycleDefault] = useState(true);
+  useEffect(() => { invoke<Settings>("cmd_load_settings").then(s => setRecycleDefault(s.recycle_by_default)).catch(console.error); }, []);
+  const toggleRecycleDefault = () => {
+    const next = !recycleDefault;
+    updateSettings({ recycle_by_default: next }).then(() => setRecycleDefault(next)).catch(e => alert(String(e)));
+  };
+
+  // Memory-pressure auto-trim
+  const [autoTrim, setAutoTrim] = useState<AutoPurgeConfig | null>(null);
+  const [lastTrim, setLastTrim] = useState<AutoTrimEvent | null>(null);
+  useEffect(() => {
+    invoke<AutoPurgeConfig>("cmd_get_auto_trim_config").then(setAutoTrim).catch(console.error);
+    const unlisten = listen<AutoTrimEvent>("auto-trim-fired", e => setLastTrim(e.payload));
+    return () => { unlisten.then(f => f()); };
+  }, []);
+
+  const toggleAutoTrim = () => {
+    if (!autoTrim) return;
+    const next = autoTrim.enabled
+      ? invoke<string>("cmd_disable_auto_trim")
+      : invoke<string>("cmd_enable_auto_trim", { thresholdPct: autoTrim.threshold_percent, intervalSecs: autoTrim.interval_seconds, minIdleSecs: autoTrim.min_idle_secs });
+    next.then(() => invoke<AutoPurgeConfig>("cmd_get_auto_trim_config").then(setAutoTrim)).catch(e => alert(String(e)));
+  };
+
   // Profile export/import
-  const exportProfile = () => {
-    const saved = localStorage.getItem("vega_optimizer_selected");
+  const exportProfile = async () => {
+    const settings = await invoke<Settings>("cmd_load_settings");
     const telemetry: Record<string, string> = {};
     ["telemetry", "cortana", "activity_history", "ad_id"].forEach(k => {
       const v = localStorage.getItem(`vega_telemetry_${k}`);
       if (v !== null) telemetry[k] = v;
     });
-    const profile = { version: "3.0.0", timestamp: new Date().toISOString(), selections: saved ? JSON.parse(saved) : [], telemetry };
+    const profile = { version: "3.0.0", timestamp: new Date().toISOString(), selections: settings.enabled_ids, telemetry };
     const blob = new Blob([JSON.stringify(profile, null, 2)], { type: "application/json" });
     const url = URL.createObjectURL(blob);
     const a = document.createElement("a");
@@ -1026,7 +1296,7 @@ function SettingsPage() {
         try {
           const profile = JSON.parse(reader.result as string);
           if (profile.selections && Array.isArray(profile.selections)) {
-            localStorage.setItem("vega_optimizer_selected", JSON.stringify(profile.selections));
+            updateSettings({ enabled_ids: profile.selections }).catch(console.error);
           }
           if (profile.telemetry && typeof profile.telemetry === "object") {
             Object.entries(profile.telemetry).forEach(([k, v]) => {
@@ -1059,8 +1329,53 @@ function SettingsPage() {
             {themeLoading ? "Applying..." : theme?.apps_dark ? "\u2600\ufe0f Switch to Light" : "\ud83c\udf19 Switch to Dark"}
           </button>
         </div>
+        <div style={{ display: "flex", alignItems: "center", justifyContent: "space-between", padding: "0 16px 16px" }}>
+          <div>
+            <div style={{ fontWeight: 600, fontSize: 14 }}>Restart Explorer</div>
+            <div style={{ fontSize: 12, color: "var(--text-muted)", marginTop: 4 }}>Applies taskbar, theme and thumbnail changes that only show up after the Windows shell restarts</div>
+          </div>
+          <button className="btn btn-sm btn-ghost" onClick={() => invoke<string>("cmd_restart_explorer").then(m => alert(m)).catch(e => alert(String(e)))} style={{ minWidth: 120 }}>
+            🔄 Restart
+          </button>
+        </div>
       </div>
 
+      {/* File Deletion Preference */}
+      <div className="card" style={{ marginBottom: 16 }}>
+        <div className="card-header"><h3>File Deletion</h3></div>
+        <div style={{ display: "flex", alignItems: "center", justifyContent: "space-between", padding: "16px" }}>
+          <div>
+            <div style={{ fontWeight: 600, fontSize: 14 }}>Use the Recycle Bin</div>
+            <div style={{ fontSize: 12, color: "var(--text-muted)", marginTop: 4 }}>Duplicate files are moved to the Recycle Bin instead of being deleted permanently</div>
+          </div>
+          <button className={`btn btn-sm ${recycleDefault ? "btn-primary" : "btn-ghost"}`} onClick={toggleRecycleDefault} style={{ minWidth: 100 }}>
+            {recycleDefault ? "Enabled" : "Disabled"}
+          </button>
+        </div>
+      </div>
+
+      {/* Memory-Pressure Auto-Trim */}
+      {autoTrim && <div className="card" style={{ marginBottom: 16 }}>
+        <div className="card-header"><h3>Auto-Trim</h3></div>
+        <div style={{ display: "flex", alignItems: "center", justifyContent: "space-between", padding: "16px", gap: 12 }}>
+          <div style={{ flex: 1 }}>
+            <div style={{ fontWeight: 600, fontSize: 14 }}>Trim memory under pressure</div>
+            <div style={{ fontSize: 12, color: "var(--text-muted)", marginTop: 4 }}>
+              {lastTrim ? `Last trim at ${lastTrim.memory_percent.toFixed(0)}% usage freed ${lastTrim.freed_mb.toFixed(0)} MB` : "Trims idle processes' working sets when RAM usage crosses the threshold (at most every 5 minutes)"}
+            </div>
+          </div>
+          <label style={{ fontSize: 12 }}>Threshold <input type="number" min={50} max={99} value={autoTrim.threshold_percent} disabled={autoTrim.enabled}
+            onChange={e => setAutoTrim({ ...autoTrim, threshold_percent: Number(e.target.value) })} style={{ width: 56 }} />%</label>
+          <label style={{ fontSize: 12 }}>Every <input type="number" min={5} max={3600} value={autoTrim.interval_seconds} disabled={autoTrim.enabled}
+            onChange={e => setAutoTrim({ ...autoTrim, interval_seconds: Number(e.target.value) })} style={{ width: 64 }} />s</label>
+          <label style={{ fontSize: 12 }}>Idle ≥ <input type="number" min={0} max={240} value={Math.round(autoTrim.min_idle_secs / 60)} disabled={autoTrim.enabled}
+            onChange={e => setAutoTrim({ ...autoTrim, min_idle_secs: Number(e.target.value) * 60 })} style={{ width: 56 }} /> min</label>
+          <button className={`btn btn-sm ${autoTrim.enabled ? "btn-primary" : "btn-ghost"}`} onClick={toggleAutoTrim} style={{ minWidth: 100 }}>
+            {autoTrim.enabled ? "Enabled" : "Disabled"}
+          </button>
+        </div>
+      </div>}
+
       {/* System Restore Point */}
       <div className="card" style={{ marginBottom: 16 }}>
         <div className="card-header"><h3>System Protection</h3></div>
@@ -1085,6 +1400,10 @@ function SettingsPage() {
           <div style={{ display: "flex", gap: 8 }}>
             <button className="btn btn-sm btn-ghost" onClick={exportProfile}>Export Profile</button>
             <button className="btn btn-sm btn-ghost" onClick={importProfile}>Import Profile</button>
+            <button className="btn btn-sm btn-ghost" title="Full catalog with current savings estimates, for scripting" onClick={async () => {
+              const path = await save({ title: "Export optimization catalog", defaultPath: "vega_catalog.json", filters: [{ name: "JSON", extensions: ["json"] }] });
+              if (path) invoke<string>("cmd_export_catalog", { path }).then(m => alert(m)).catch(e => alert(String(e)));
+            }}>Export Catalog</button>
           </div>
         </div>
       </div>
@@ -1144,15 +1463,17 @@ function DiskPage({ files, scanning, scan, browsers, cleanBrowser }: any) {
   const [localBrowsers, setLocalBrowsers] = useState<BrowserInfo[]>(browsers);
   const [selectedFiles, setSelectedFiles] = useState<Set<string>>(new Set());
   const [deletingFile, setDeletingFile] = useState<string | null>(null);
+  const [browserExtras, setBrowserExtras] = useState<Omit<BrowserCleanOptions, "cache">>({ cookies: false, history: false, downloads: false, sessions: false });
 
   useEffect(() => { setLocalFiles(files); setSelectedFiles(new Set()); }, [files]);
   useEffect(() => { setLocalBrowsers(browsers); }, [browsers]);
 
   const deleteFile = async (path: string) => {
-    if (!confirm(`Delete this file?\n${path}`)) return;
+    if (!confirm(`Move this file to the Recycle Bin?\n${path}`)) return;
     setDeletingFile(path);
     try {
-      await invoke<string>("cmd_delete_file", { path });
+      const token = localFiles.find(f => f.path === path)?.token;
+      await invoke<string>("cmd_delete_file_to_recycle", { path, token });
       setLocalFiles(prev => prev.filter(f => f.path !== path));
       setSelectedFiles(prev => { const s = new Set(prev); s.delete(path); return s; });
     } catch (e) { alert(String(e)); }
@@ -1161,19 +1482,21 @@ function DiskPage({ files, scanning, scan, browsers, cleanBrowser }: any) {
 
   const deleteSelectedFiles = async () => {
     if (selectedFiles.size === 0) return;
-    if (!confirm(`Delete ${selectedFiles.size} selected files?\n\nThis cannot be undone!`)) return;
+    if (!confirm(`Move ${selectedFiles.size} selected files to the Recycle Bin?`)) return;
     setDeletingFile("batch");
-    let deleted = 0;
+    const recycled = new Set<string>();
+    const failures: string[] = [];
     for (const path of selectedFiles) {
       try {
-        await invoke<string>("cmd_delete_file", { path });
-        deleted++;
-      } catch (e) { console.error(e); }
+        const token = localFiles.find(f => f.path === path)?.token;
+        await invoke<string>("cmd_delete_file_to_recycle", { path, token });
+        recycled.add(path);
+      } catch (e) { failures.push(`${path.split("\\").pop()}: ${String(e)}`); }
     }
-    setLocalFiles(prev => prev.filter(f => !selectedFiles.has(f.path)));
-    setSelectedFiles(new Set());
+    setLocalFiles(prev => prev.filter(f => !recycled.has(f.path)));
+    setSelectedFiles(new Set([...selectedFiles].filter(p => !recycled.has(p))));
     setDeletingFile(null);
-    alert(`Deleted ${deleted} files.`);
+    alert(`Recycled ${recycled.size} files.` + (failures.length ? `\n\nFailed (${failures.length}):\n${failures.join("\n")}` : ""));
   };
 
   const cleanAllCaches = async () => {
@@ -1188,6 +1511,20 @@ function DiskPage({ files, scanning, scan, browsers, cleanBrowser }: any) {
     setCleaningAll(false);
   };
 
+  // Cookies, history and sessions only go when ticked; plain cache cleaning keeps the old path
+  const cleanBrowserData = async (name: string) => {
+    if (!Object.values(browserExtras).some(Boolean)) { cleanBrowser(name); return; }
+    // The download list lives in the history database, so it can't be cleared alone
+    const withHistory = browserExtras.downloads && !browserExtras.history;
+    const historyNote = withHistory ? `\n\nDownloads are stored with history, so browsing history will be cleared too.${name === "Mozilla Firefox" ? " On Firefox this file also holds bookmarks; they are restored from the newest bookmark backup." : ""}` : "";
+    if (!confirm(`Clean ${name}? Selected cookies, history or sessions will be removed (undo from quarantine).${historyNote}`)) return;
+    try {
+      const msg = await invoke<string>("cmd_clean_browser_data", { name, options: { cache: true, ...browserExtras, history: browserExtras.history || browserExtras.downloads } });
+      alert(msg);
+    } catch (e) { alert(String(e)); }
+    try { const updated = await invoke<BrowserInfo[]>("cmd_detect_browsers"); setLocalBrowsers(updated); } catch (e) { console.error(e); }
+  };
+
   const [aiTagging, setAiTagging] = useState(false);
   const autoTagWithAI = async () => {
     if (localFiles.length === 0) return;
@@ -1244,6 +1581,15 @@ function DiskPage({ files, scanning, scan, browsers, cleanBrowser }: any) {
             </button>
           )}
         </div>
+        <div style={{ display: "flex", gap: 16, fontSize: 12, color: "var(--text-muted)", marginBottom: 12 }}>
+          <span>Also clear:</span>
+          {(["cookies", "history", "downloads", "sessions"] as const).map(k => (
+            <label key={k} style={{ display: "flex", alignItems: "center", gap: 4, cursor: "pointer" }}>
+              <input type="checkbox" checked={browserExtras[k]} onChange={e => setBrowserExtras(prev => ({ ...prev, [k]: e.target.checked }))} />
+              {k[0].toUpperCase() + k.slice(1)}
+            </label>
+          ))}
+        </div>
         <div className="card-grid card-grid-3">
           {localBrowsers.filter((b: BrowserInfo) => b.installed).map((b: BrowserInfo) => (
             <div key={b.name} className="stat-mini" style={{ justifyContent: "space-between" }}>
@@ -1251,7 +1597,7 @@ function DiskPage({ files, scanning, scan, browsers, cleanBrowser }: any) {
                 <div style={{ fontWeight: 600, fontSize: 13 }}>{b.name}</div>
                 <div style={{ fontSize: 12, color: "var(--text-muted)", fontFamily: "'JetBrains Mono', monospace" }}>{b.cache_size_mb.toFixed(1)} MB</div>
               </div>
-              <button className="btn btn-ghost btn-sm" onClick={() => cleanBrowser(b.name)}>Clean</button>
+              <button className="btn btn-ghost btn-sm" onClick={() => cleanBrowserData(b.name)}>Clean</button>
             </div>
           ))}
           {localBrowsers.filter((b: BrowserInfo) => b.installed).length === 0 && <div className="empty-state"><p>Detecting browsers...</p></div>}
@@ -1263,6 +1609,9 @@ function DiskPage({ files, scanning, scan, browsers, cleanBrowser }: any) {
         <div className="card-header" style={{ display: "flex", justifyContent: "space-between", alignItems: "center" }}>
           <h3>Large Files ({">"}100 MB) {localFiles.length > 0 && <span style={{ fontWeight: 400, fontSize: 12, color: "var(--text-muted)" }}> — {localFiles.length} files, {localFiles.reduce((a: number, f: LargeFile) => a + f.size_mb, 0).toFixed(0)} MB total</span>}</h3>
           <div style={{ display: "flex", gap: 8 }}>
+            {localFiles.length > 0 && (
+              <button className="btn btn-ghost btn-sm" onClick={() => exportResult("cmd_export_large_files_csv", "Export large files", "vega_large_files.csv")}>💾 Export CSV</button>
+            )}
             {localFiles.length > 0 && (
               <button className="btn btn-ghost btn-sm" onClick={autoTagWithAI} disabled={aiTagging || scanning} style={{ color: "var(--accent)" }}>
                 {aiTagging ? "🧠 Identifying..." : "🧠 Built-In Identification"}
@@ -1270,12 +1619,13 @@ function DiskPage({ files, scanning, scan, browsers, cleanBrowser }: any) {
             )}
             {selectedFiles.size > 0 && (
               <button className="optimize-btn" onClick={deleteSelectedFiles} disabled={deletingFile !== null} style={{ padding: "8px 18px", fontSize: 12 }}>
-                🗑️ Delete Selected ({selectedFiles.size})
+                ♻️ Recycle Selected ({selectedFiles.size})
               </button>
             )}
             <button className="btn btn-accent btn-sm" onClick={scan} disabled={scanning}>
               {scanning ? <><div className="spinner" style={{ width: 14, height: 14 }} /> Scanning...</> : "🔍 Scan Now"}
             </button>
+            {scanning && <button className="btn btn-ghost btn-sm" onClick={() => invoke("cmd_cancel_scan")}>✕ Cancel</button>}
           </div>
         </div>
         {localFiles.length > 0 ? (
@@ -1397,6 +1747,38 @@ function PrivacyPage({ items, clean }: { items: PrivacyItem[]; clean: (id: strin
         );
       })}
       {localItems.length === 0 && <div className="empty-state"><div className="icon">🔒</div><p>Loading privacy items...</p></div>}
+      <QuarantinePanel refreshKey={localItems} />
+    </div>
+  );
+}
+
+function QuarantinePanel({ refreshKey }: { refreshKey: unknown }) {
+  const [sessions, setSessions] = useState<QuarantineSessionInfo[]>([]);
+
+  const load = useCallback(() => { invoke<QuarantineSessionInfo[]>("cmd_list_quarantine").then(setSessions).catch(console.error); }, []);
+  useEffect(() => { load(); }, [load, refreshKey]);
+
+  const undo = (id: string) => { invoke<string>("cmd_undo_cleanup", { sessionId: id }).then(load).catch(e => { alert(String(e)); load(); }); };
+  const purgeAll = () => {
+    if (!confirm("Permanently delete all quarantined files? Cleanups can no longer be undone.")) return;
+    invoke<string>("cmd_purge_quarantine", { olderThanDays: 0 }).then(load).catch(e => alert(String(e)));
+  };
+  const totalMb = sessions.reduce((a, s) => a + s.size_bytes, 0) / 1_048_576;
+
+  return (
+    <div className="card" style={{ padding: 16, marginTop: 16 }}>
+      <div style={{ display: "flex", justifyContent: "space-between", alignItems: "center", marginBottom: 4 }}>
+        <h3 style={{ fontSize: 14 }}>↩️ Undo Cleanups</h3>
+        {sessions.length > 0 && <button className="btn btn-ghost btn-sm" onClick={purgeAll}>Purge now ({totalMb.toFixed(1)} MB)</button>}
+      </div>
+      <div style={{ fontSize: 12, color: "var(--text-muted)", marginBottom: 12 }}>Browser and privacy cleanups are kept in quarantine for 7 days before being deleted</div>
+      {sessions.length === 0 && <div style={{ fontSize: 12, color: "var(--text-muted)" }}>Nothing in quarantine</div>}
+      {sessions.map(s => (
+        <div key={s.session_id} style={{ display: "flex", justifyContent: "space-between", alignItems: "center", fontSize: 12, padding: "6px 0", borderTop: "1px solid var(--border)" }}>
+          <span><strong>{s.label}</strong> — {s.items} items, {(s.size_bytes / 1_048_576).toFixed(1)} MB • {new Date(s.created_at * 1000).toLocaleString()}</span>
+          <button className="btn btn-ghost btn-sm" onClick={() => undo(s.session_id)}>Undo</button>
+        </div>
+      ))}
     </div>
   );
 }
@@ -1426,9 +1808,18 @@ function DriverPage({ drivers, loading, refresh }: { drivers: DriverInfo[]; load
     (classFilter === "all" || (d.device_class || "Other") === classFilter) &&
     (!search || d.name.toLowerCase().includes(search.toLowerCase()) || d.provider.toLowerCase().includes(search.toLowerCase()))
   );
-  const outdatedCount = drivers.filter(d => parseDriverAge(d.date) > 2).length;
+  const outdatedCount = drivers.filter(d => d.status === "Outdated").length;
   const unsignedCount = drivers.filter(d => !d.signed).length;
 
+  const [exporting, setExporting] = useState(false);
+  const exportDrivers = async () => {
+    const dest = await open({ directory: true, multiple: false, title: "Folder to back up drivers to" });
+    if (!dest) return;
+    setExporting(true);
+    try { alert(await invoke<string>("cmd_export_drivers", { dest })); } catch (e) { alert(String(e)); }
+    setExporting(false);
+  };
+
   const scanUpdates = async () => {
     setScanning(true);
     try { await invoke<string>("cmd_scan_driver_updates"); alert("Driver scan complete. Check Device Manager for available updates."); } catch (e) { alert(String(e)); }
@@ -1443,6 +1834,7 @@ function DriverPage({ drivers, loading, refresh }: { drivers: DriverInfo[]; load
         <div style={{ display: "flex", gap: 8 }}>
           <button className="btn btn-ghost btn-sm" onClick={() => invoke<string>("cmd_open_device_manager").catch(console.error)}>🖥️ Device Manager</button>
           <button className="btn btn-ghost btn-sm" onClick={() => invoke<string>("cmd_open_windows_update").catch(console.error)}>🔄 Windows Update</button>
+          <button className="btn btn-ghost btn-sm" onClick={exportDrivers} disabled={exporting}>{exporting ? "⏳ Exporting..." : "💾 Backup Drivers"}</button>
           <button className="btn btn-ghost btn-sm" onClick={scanUpdates} disabled={scanning}>{scanning ? "⏳ Scanning..." : "🔍 Scan Updates"}</button>
           <button className="btn btn-ghost btn-sm" onClick={refresh} disabled={loading}>↻ Rescan</button>
         </div>
@@ -1463,7 +1855,7 @@ function DriverPage({ drivers, loading, refresh }: { drivers: DriverInfo[]; load
       <div className="card-grid card-grid-4" style={{ marginBottom: 12 }}>
         <div className="stat-mini"><div><div className="stat-value">{drivers.length}</div><div className="stat-label">Total Drivers</div></div></div>
         <div className="stat-mini"><div><div className="stat-value" style={{ color: "var(--success)" }}>{drivers.filter(d => d.signed).length}</div><div className="stat-label">Signed</div></div></div>
-        <div className="stat-mini"><div><div className="stat-value" style={{ color: "var(--danger)" }}>{outdatedCount}</div><div className="stat-label">Outdated (&gt;2yr)</div></div></div>
+        <div className="stat-mini"><div><div className="stat-value" style={{ color: "var(--danger)" }}>{outdatedCount}</div><div className="stat-label">Outdated</div></div></div>
         <div className="stat-mini"><div><div className="stat-value" style={{ color: "var(--warning)" }}>{unsignedCount}</div><div className="stat-label">Unsigned</div></div></div>
       </div>
 
@@ -1486,10 +1878,10 @@ function DriverPage({ drivers, loading, refresh }: { drivers: DriverInfo[]; load
                     <td className="mono" style={{ fontSize: 11 }}>{d.version || "—"}</td>
                     <td style={{ fontSize: 11, color: "var(--text-muted)" }}>{d.date || "—"}</td>
                     <td><span style={{ fontSize: 11, fontWeight: 600, color: ageColor(age) }}>{ageLabel(age)}</span></td>
-                    <td><span className={`badge ${d.status === "OK" || d.status === "Running" ? "badge-low" : "badge-medium"}`}>{d.status}</span></td>
+                    <td><span className={`badge ${d.status === "OK" || d.status === "Running" ? "badge-low" : d.status === "Outdated" ? "badge-high" : "badge-medium"}`}>{d.status}</span></td>
                     <td>{d.signed ? <span style={{ color: "var(--success)" }}>✓</span> : <span style={{ color: "var(--danger)" }}>✗</span>}</td>
                     <td>
-                      {age > 2 && <button className="btn btn-ghost btn-sm" onClick={() => invoke<string>("cmd_open_windows_update").catch(console.error)} style={{ color: "var(--accent)", fontSize: 11 }}>Update</button>}
+                      {d.status === "Outdated" && <button className="btn btn-ghost btn-sm" onClick={() => invoke<string>("cmd_open_windows_update").catch(console.error)} style={{ color: "var(--accent)", fontSize: 11 }}>Update</button>}
                     </td>
                   </tr>
                 );
@@ -1506,6 +1898,8 @@ function DriverPage({ drivers, loading, refresh }: { drivers: DriverInfo[]; load
 // Hardware
 // ═══════════════════════════════════════════════════════════════════
 function HardwarePage({ info }: { info: HardwareInfo | null }) {
+  const [timer, setTimer] = useState<TimerResolution | null>(null);
+  useEffect(() => { invoke<TimerResolution | null>("cmd_get_timer_resolution").then(setTimer).catch(console.error); }, []);
   if (!info) return <div className="empty-state"><div className="spinner lg" style={{ margin: "0 auto" }} /><p style={{ marginTop: 12 }}>Detecting hardware...</p></div>;
   return (
     <div>
@@ -1520,6 +1914,7 @@ function HardwarePage({ info }: { info: HardwareInfo | null }) {
             <div><div className="card-label">Frequency</div><div style={{ fontWeight: 600 }}>{info.cpu_frequency_mhz} MHz</div></div>
             <div><div className="card-label">Physical Cores</div><div style={{ fontWeight: 600 }}>{info.cpu_cores_physical}</div></div>
             <div><div className="card-label">Logical Cores</div><div style={{ fontWeight: 600 }}>{info.cpu_cores_logical}</div></div>
+            {timer && <div title={`Finest ${timer.finest_ms.toFixed(2)}ms, default ${timer.default_ms.toFixed(1)}ms`}><div className="card-label">Timer Resolution</div><div style={{ fontWeight: 600, color: timer.raised ? "var(--warning)" : undefined }}>{timer.current_ms.toFixed(2)} ms{timer.raised ? " (held by an app)" : ""}</div></div>}
           </div>
         </div>
 
@@ -1568,7 +1963,7 @@ function HardwarePage({ info }: { info: HardwareInfo | null }) {
 // ═══════════════════════════════════════════════════════════════════
 // Report Overlay
 // ═══════════════════════════════════════════════════════════════════
-function ReportOverlay({ report, onClose }: { report: OptimizationReport; onClose: () => void }) {
+function ReportOverlay({ report, diff, onClose }: { report: OptimizationReport; diff: SnapshotDiff | null; onClose: () => void }) {
   return (
     <div className="overlay" onClick={onClose}>
       <div className="overlay-panel" onClick={e => e.stopPropagation()}>
@@ -1587,6 +1982,8 @@ function ReportOverlay({ report, onClose }: { report: OptimizationReport; onClos
           <span>Memory: {report.memory_before_mb} MB → {report.memory_after_mb} MB</span>
           <span>Duration: {(report.total_duration_ms / 1000).toFixed(1)}s</span>
         </div>
+        {report.reboot_pending && <div className="card" style={{ padding: 12, marginBottom: 12, fontSize: 12, borderColor: "var(--warning)", color: "var(--warning)" }}>🔄 Windows has changes waiting for a restart. Restart your PC to finish applying them.</div>}
+        {diff && <div className="card" style={{ padding: 12, marginBottom: 12, fontSize: 12 }} title={`Standby ${diff.before.standby_mb} → ${diff.after.standby_mb} MB • Startups ${diff.before.startup_count} → ${diff.after.startup_count} • Disk free ${diff.before.disk_free_gb.toFixed(1)} → ${diff.after.disk_free_gb.toFixed(1)} GB`}>📸 Measured: {diff.summary}</div>}
 
         <div style={{ maxHeight: 300, overflow: "auto" }}>
           {report.results.map((r, i) => (
@@ -1594,11 +1991,15 @@ function ReportOverlay({ report, onClose }: { report: OptimizationReport; onClos
               <span style={{ color: r.success ? "var(--success)" : "var(--danger)" }}>{r.success ? "✓" : "✗"}</span>
               <span style={{ flex: 1, fontWeight: 500 }}>{r.name}</span>
               <span style={{ fontSize: 11, color: "var(--text-muted)" }}>{r.message}</span>
+              {report.slow_steps?.includes(r.id) && <span className="badge badge-medium" title="This step took unusually long">🐢 {(r.duration_ms / 1000).toFixed(1)}s</span>}
             </div>
           ))}
         </div>
 
-        <button className="btn btn-accent" onClick={onClose} style={{ width: "100%", marginTop: 16 }}>Close Report</button>
+        <div style={{ display: "flex", gap: 8, marginTop: 16 }}>
+          <button className="btn btn-ghost" onClick={() => exportResult("cmd_export_report_json", "Export optimization report", "vega_report.json")}>💾 Export JSON</button>
+          <button className="btn btn-accent" onClick={onClose} style={{ flex: 1 }}>Close Report</button>
+        </div>
       </div>
     </div>
   );
@@ -1607,27 +2008,53 @@ function ReportOverlay({ report, onClose }: { report: OptimizationReport; onClos
 // ═══════════════════════════════════════════════════════════════════
 // Network Monitor
 // ═══════════════════════════════════════════════════════════════════
-interface NetOverview { total_connections: number; tcp_established: number; tcp_listening: number; udp_active: number; processes_with_network: number; top_talkers: { pid: number; name: string; connections: number; status: string }[]; connections: { protocol: string; local_addr: string; remote_addr: string; state: string; pid: number; process_name: string }[]; }
+interface NetConn { protocol: string; local_addr: string; remote_addr: string; state: string; pid: number; process_name: string; remote_host: string | null; }
+interface NetOverview { total_connections: number; tcp_established: number; tcp_listening: number; udp_active: number; processes_with_network: number; top_talkers: { pid: number; name: string; connections: number; bytes_sent: number; bytes_recv: number; send_bps: number; recv_bps: number; status: string }[]; connections: NetConn[]; sample_ms: number; throughput_measured: boolean; }
+interface PingResult { avg_ms: number; min_ms: number; max_ms: number; packet_loss_pct: number; replies: number[]; }
+interface SpeedTestResult { download_mbps: number; upload_mbps: number; ping_ms: number; server: string; }
+interface NetDelta { added: NetConn[]; removed: NetConn[]; unchanged: number; total_connections: number; full_refresh: boolean; }
 
 function NetworkPage() {
   const [overview, setOverview] = useState<NetOverview | null>(null);
   const [loading, setLoading] = useState(false);
-  const [ping, setPing] = useState<number | null>(null);
+  const [ping, setPing] = useState<PingResult | null>(null);
   const [view, setView] = useState<"talkers" | "connections">("talkers");
   const [autoRefresh, setAutoRefresh] = useState(false);
+  const [filter, setFilter] = useState({ include_loopback: false, include_listening: true });
 
   const load = useCallback(() => {
     setLoading(true);
-    invoke<NetOverview>("cmd_get_network_overview").then(o => { setOverview(o); setLoading(false); }).catch(() => setLoading(false));
-  }, []);
-  useEffect(() => { load(); }, []);
+    invoke<NetOverview>("cmd_get_network_overview", { sampleMs: 1000, filter }).then(o => { setOverview(o); setLoading(false); }).catch(() => setLoading(false));
+  }, [filter]);
+  useEffect(() => { load(); }, [filter]);
+  // Live view: poll only added/removed connections instead of re-enumerating everything
+  const applyDelta = useCallback(() => {
+    invoke<NetDelta>("cmd_get_network_delta", { filter }).then(d => setOverview(prev => {
+      if (!prev || d.full_refresh) { load(); return prev; }
+      const key = (c: NetConn) => `${c.protocol}|${c.local_addr}|${c.remote_addr}|${c.state}|${c.pid}`;
+      const gone = new Set(d.removed.map(key));
+      const connections = [...d.added, ...prev.connections.filter(c => !gone.has(key(c)))];
+      return {
+        ...prev, connections, total_connections: d.total_connections,
+        tcp_established: connections.filter(c => c.state === "Established").length,
+        tcp_listening: connections.filter(c => c.state === "Listen").length,
+        udp_active: connections.filter(c => c.protocol === "UDP").length,
+      };
+    })).catch(console.error);
+  }, [load, filter]);
   useEffect(() => {
     if (!autoRefresh) return;
-    const id = setInterval(load, 5000);
+    const id = setInterval(applyDelta, 1000);
     return () => clearInterval(id);
-  }, [autoRefresh, load]);
-
-  const runPing = () => { invoke<number>("cmd_ping_test", { host: "8.8.8.8" }).then(setPing); };
+  }, [autoRefresh, applyDelta]);
+
+  const runPing = () => { invoke<PingResult>("cmd_ping_test", { host: "8.8.8.8" }).then(setPing); };
+  const [speed, setSpeed] = useState<SpeedTestResult | null>(null);
+  const [speedRunning, setSpeedRunning] = useState(false);
+  const runSpeedTest = () => {
+    setSpeedRunning(true);
+    invoke<SpeedTestResult>("cmd_run_speed_test").then(setSpeed).catch(console.error).finally(() => setSpeedRunning(false));
+  };
 
   const [dnsProviders, setDnsProviders] = useState<DnsProvider[]>([]);
   const [dnsStatus, setDnsStatus] = useState<DnsStatus | null>(null);
@@ -1655,6 +2082,7 @@ function NetworkPage() {
         <div style={{ display: "flex", gap: 8, alignItems: "center" }}>
           <button className={`btn btn-sm ${dnsTab ? "btn-primary" : "btn-ghost"}`} onClick={() => setDnsTab(p => !p)}>🔗 DNS Switch</button>
           <button className="btn btn-ghost btn-sm" onClick={runPing}>🏓 Ping Test</button>
+          <button className="btn btn-ghost btn-sm" onClick={runSpeedTest} disabled={speedRunning}>{speedRunning ? "⏳ Testing..." : "🚀 Speed Test"}</button>
           <button className={`btn btn-sm ${autoRefresh ? "btn-success" : "btn-ghost"}`} onClick={() => setAutoRefresh(p => !p)}>{autoRefresh ? "⏸ Pause" : "▶ Auto-Refresh"}</button>
           <button className="btn btn-ghost btn-sm" onClick={load}>{loading ? "⏳" : "↻"} Refresh</button>
         </div>
@@ -1683,7 +2111,20 @@ function NetworkPage() {
 
       {ping !== null && <div className="card" style={{ padding: "10px 16px", marginBottom: 12, display: "flex", gap: 16, alignItems: "center" }}>
         <span style={{ fontWeight: 600 }}>Ping to 8.8.8.8:</span>
-        <span className="mono" style={{ fontSize: 18, fontWeight: 700, color: ping < 50 ? "var(--success)" : ping < 100 ? "var(--warning)" : "var(--danger)" }}>{ping.toFixed(0)} ms</span>
+        {ping.replies.length === 0 ? <span className="mono" style={{ fontSize: 18, fontWeight: 700, color: "var(--danger)" }}>Unreachable</span> : <>
+          <span className="mono" style={{ fontSize: 18, fontWeight: 700, color: ping.avg_ms < 50 ? "var(--success)" : ping.avg_ms < 100 ? "var(--warning)" : "var(--danger)" }}>{ping.avg_ms.toFixed(0)} ms</span>
+          <span className="mono" style={{ fontSize: 12, color: "var(--text-muted)" }}>min {ping.min_ms.toFixed(0)} / max {ping.max_ms.toFixed(0)} ms</span>
+        </>}
+        <span className="mono" style={{ fontSize: 12, color: ping.packet_loss_pct > 0 ? "var(--danger)" : "var(--success)" }}>{ping.packet_loss_pct.toFixed(0)}% loss</span>
+      </div>}
+
+      {speed && <div className="card" style={{ padding: "10px 16px", marginBottom: 12, display: "flex", gap: 20, alignItems: "center" }}>
+        {speed.server === "unavailable" ? <span style={{ color: "var(--danger)" }}>Speed test failed — no connection to the test server</span> : <>
+          <span style={{ fontWeight: 600 }}>Speed ({speed.server}):</span>
+          <span className="mono">⬇ {speed.download_mbps.toFixed(1)} Mbps</span>
+          <span className="mono">⬆ {speed.upload_mbps.toFixed(1)} Mbps</span>
+          <span className="mono">{speed.ping_ms.toFixed(0)} ms</span>
+        </>}
       </div>}
 
       {overview && (
@@ -1698,14 +2139,23 @@ function NetworkPage() {
       <div className="tab-bar" style={{ marginBottom: 12 }}>
         <button className={`tab-btn ${view === "talkers" ? "active" : ""}`} onClick={() => setView("talkers")}>📊 Top Talkers</button>
         <button className={`tab-btn ${view === "connections" ? "active" : ""}`} onClick={() => setView("connections")}>🔗 All Connections</button>
+        <label style={{ display: "flex", alignItems: "center", gap: 6, fontSize: 12, marginLeft: "auto" }}>
+          <input type="checkbox" checked={filter.include_loopback} onChange={e => setFilter(f => ({ ...f, include_loopback: e.target.checked }))} /> Loopback
+        </label>
+        <label style={{ display: "flex", alignItems: "center", gap: 6, fontSize: 12, marginLeft: 12 }}>
+          <input type="checkbox" checked={filter.include_listening} onChange={e => setFilter(f => ({ ...f, include_listening: e.target.checked }))} /> Listening
+        </label>
       </div>
 
       {loading ? <div className="empty-state"><div className="spinner lg" style={{ margin: "0 auto" }} /><p style={{ marginTop: 12 }}>Scanning network...</p></div> :
         overview && view === "talkers" ? (
           <div className="card" style={{ overflow: "auto", maxHeight: "calc(100vh - 340px)" }}>
-            <table className="data-table"><thead><tr><th>Process</th><th>PID</th><th>Connections</th><th>Status</th></tr></thead>
+            {!overview.throughput_measured && <p style={{ fontSize: 12, color: "var(--text-muted)", padding: "8px 12px" }}>Per-process throughput needs administrator rights (TCP only).</p>}
+            <table className="data-table"><thead><tr><th>Process</th><th>PID</th><th>Connections</th><th>Upload</th><th>Download</th><th>Status</th></tr></thead>
               <tbody>{overview.top_talkers.map((t, i) => (
                 <tr key={i}><td style={{ fontWeight: 500 }}>{t.name}</td><td className="mono">{t.pid}</td><td className="mono">{t.connections}</td>
+                  <td className="mono">{overview.throughput_measured ? `${formatBytes(Math.round(t.send_bps))}/s` : "—"}</td>
+                  <td className="mono">{overview.throughput_measured ? `${formatBytes(Math.round(t.recv_bps))}/s` : "—"}</td>
                   <td><span className={`badge ${t.status === "Heavy" ? "badge-high" : t.status === "Active" ? "badge-medium" : "badge-low"}`}>{t.status}</span></td></tr>
               ))}</tbody></table>
           </div>
@@ -1714,7 +2164,7 @@ function NetworkPage() {
             <table className="data-table"><thead><tr><th>Protocol</th><th>Local</th><th>Remote</th><th>State</th><th>Process</th></tr></thead>
               <tbody>{overview.connections.slice(0, 100).map((c, i) => (
                 <tr key={i}><td><span className={`badge ${c.protocol === "TCP" ? "badge-low" : "badge-medium"}`}>{c.protocol}</span></td>
-                  <td className="mono" style={{ fontSize: 11 }}>{c.local_addr}</td><td className="mono" style={{ fontSize: 11 }}>{c.remote_addr}</td>
+                  <td className="mono" style={{ fontSize: 11 }}>{c.local_addr}</td><td className="mono" style={{ fontSize: 11 }}>{c.remote_host ? <span title={c.remote_addr}>{c.remote_host}</span> : c.remote_addr}</td>
                   <td><span className={`badge ${c.state === "Established" ? "badge-low" : c.state === "Listen" ? "badge-medium" : ""}`}>{c.state}</span></td>
                   <td>{c.process_name}</td></tr>
               ))}</tbody></table>
@@ -1727,12 +2177,25 @@ function NetworkPage() {
 // ═══════════════════════════════════════════════════════════════════
 // Windows Debloater
 // ═══════════════════════════════════════════════════════════════════
-interface AppxPkg { name: string; display_name: string; publisher: string; version: string; size_mb: number; category: string; safe_to_remove: boolean; description: string; }
+interface AppxPkg { name: string; display_name: string; publisher: string; version: string; size_mb: number; category: string; safe_to_remove: boolean; description: string; install_date?: number | null; last_used?: number | null; launch_count?: number; never_used?: boolean; }
+interface BloatwareRemovalReport { results: [string, boolean, string][]; removed_count: number; failed_count: number; total_size_mb: number; }
 
 function DebloaterPage() {
   const [packages, setPackages] = useState<AppxPkg[]>([]);
   const [loading, setLoading] = useState(false);
   const [filter, setFilter] = useState<"all" | "bloatware" | "game" | "media" | "utility">("all");
+  const [unusedFirst, setUnusedFirst] = useState(false);
+  const [search, setSearch] = useState("");
+  const [onlyRemovable, setOnlyRemovable] = useState(false);
+  const [removal, setRemoval] = useState<{ current: number; total: number; display_name: string; success: boolean } | null>(null);
+  const [defaults, setDefaults] = useState<{ name: string; display_name: string; category: string; installed: boolean; in_repository: boolean }[] | null>(null);
+  const loadDefaults = () => invoke<typeof defaults>("cmd_list_removable_defaults").then(setDefaults).catch(() => setDefaults([]));
+  const reinstall = (name: string) => invoke<string>("cmd_reinstall_appx", { name }).then(m => { alert(m); loadDefaults(); load(); }).catch(e => alert(String(e)));
+
+  useEffect(() => {
+    const unlisten = listen<{ current: number; total: number; display_name: string; success: boolean }>("bloatware-removal-progress", e => setRemoval(e.payload));
+    return () => { unlisten.then(f => f()); };
+  }, []);
 
   const load = useCallback(() => {
     setLoading(true);
@@ -1747,10 +2210,26 @@ function DebloaterPage() {
 
   const removeAll = () => {
     if (!confirm("Remove ALL bloatware? This will uninstall all safe-to-remove preinstalled apps.")) return;
-    invoke<[string, boolean, string][]>("cmd_remove_all_bloatware").then(() => load());
+    setRemoval({ current: 0, total: bloatCount, display_name: "", success: true });
+    invoke<BloatwareRemovalReport>("cmd_remove_all_bloatware")
+      .then(report => {
+        const failed = report.results.filter(([, ok]) => !ok);
+        const summary = `Removed ${report.removed_count} of ${report.results.length} apps, freeing ${report.total_size_mb.toFixed(0)} MB.`;
+        alert(failed.length ? `${summary}\n\nFailed:\n${failed.map(([n, , m]) => `${n}: ${m}`).join("\n")}` : summary);
+      })
+      .finally(() => { setRemoval(null); load(); });
   };
 
-  const filtered = filter === "all" ? packages : packages.filter(p => p.category === filter);
+  // Same predicate as list_appx_packages_filtered, applied to the already-loaded list
+  const needle = search.trim().toLowerCase();
+  const byCategory = packages
+    .filter(p => filter === "all" || p.category === filter)
+    .filter(p => !onlyRemovable || p.safe_to_remove)
+    .filter(p => !needle || p.name.toLowerCase().includes(needle) || p.display_name.toLowerCase().includes(needle));
+  const filtered = unusedFirst
+    ? [...byCategory].sort((a, b) => (Number(!!b.never_used) - Number(!!a.never_used)) || ((a.last_used ?? 0) - (b.last_used ?? 0)))
+    : byCategory;
+  const fmtDate = (secs?: number | null) => secs ? new Date(secs * 1000).toLocaleDateString() : "unknown";
   const bloatCount = packages.filter(p => p.safe_to_remove).length;
   const totalSize = packages.filter(p => p.safe_to_remove).reduce((a, p) => a + p.size_mb, 0);
 
@@ -1761,15 +2240,39 @@ function DebloaterPage() {
       <div className="page-header">
         <div><h2>🗑️ Windows Debloater</h2><div className="subtitle">{packages.length} apps • {bloatCount} removable ({totalSize.toFixed(0)} MB)</div></div>
         <div style={{ display: "flex", gap: 8 }}>
+          <button className={`btn btn-sm ${unusedFirst ? "btn-primary" : "btn-ghost"}`} onClick={() => setUnusedFirst(!unusedFirst)}>💤 Unused first</button>
+          <button className="btn btn-ghost btn-sm" onClick={() => defaults ? setDefaults(null) : loadDefaults()}>↺ Reinstall apps</button>
           <button className="btn btn-ghost btn-sm" onClick={load}>↻ Refresh</button>
-          {bloatCount > 0 && <button className="optimize-btn" onClick={removeAll} style={{ padding: "8px 16px" }}>🗑️ Remove All Bloatware ({bloatCount})</button>}
+          {bloatCount > 0 && <button className="optimize-btn" onClick={removeAll} disabled={!!removal} style={{ padding: "8px 16px" }}>{removal ? <>⏳ Removing {removal.current}/{removal.total}{removal.display_name ? ` — ${removal.display_name} ${removal.success ? "✓" : "✗"}` : ""}</> : <>🗑️ Remove All Bloatware ({bloatCount})</>}</button>}
         </div>
       </div>
 
-      <div className="tab-bar" style={{ marginBottom: 12 }}>
-        {["all", "bloatware", "game", "media", "utility"].map(f => (
-          <button key={f} className={`tab-btn ${filter === f ? "active" : ""}`} onClick={() => setFilter(f as any)}>{f === "all" ? `All (${packages.length})` : `${f} (${packages.filter(p => p.category === f).length})`}</button>
-        ))}
+      {defaults && (
+        <div className="card" style={{ padding: 14, marginBottom: 12 }}>
+          <h3 style={{ fontSize: 14, marginBottom: 8 }}>↺ Removed default apps</h3>
+          {defaults.filter(d => !d.installed).length === 0 && <div style={{ fontSize: 12, color: "var(--text-muted)" }}>All default apps are installed.</div>}
+          {defaults.filter(d => !d.installed).map(d => (
+            <div key={d.name} style={{ display: "flex", justifyContent: "space-between", alignItems: "center", fontSize: 12, padding: "4px 0" }}>
+              <span>{d.display_name} <span className="mono" style={{ color: "var(--text-muted)", fontSize: 11 }}>{d.name}</span></span>
+              {d.in_repository
+                ? <button className="btn btn-ghost btn-sm" onClick={() => reinstall(d.name)}>Reinstall</button>
+                : <span style={{ color: "var(--text-muted)" }}>Microsoft Store only</span>}
+            </div>
+          ))}
+        </div>
+      )}
+
+      <div style={{ display: "flex", gap: 12, marginBottom: 12, alignItems: "center" }}>
+        <input type="text" placeholder="Search apps..." value={search} onChange={e => setSearch(e.target.value)}
+          style={{ padding: "8px 12px", background: "var(--bg-input)", border: "1px solid var(--border)", borderRadius: 6, color: "var(--text-primary)", fontSize: 13, width: 220, outline: "none", fontFamily: "inherit" }} />
+        <div className="tab-bar" style={{ flex: 1 }}>
+          {["all", "bloatware", "game", "media", "utility"].map(f => (
+            <button key={f} className={`tab-btn ${filter === f ? "active" : ""}`} onClick={() => setFilter(f as any)}>{f === "all" ? `All (${packages.length})` : `${f} (${packages.filter(p => p.category === f).length})`}</button>
+          ))}
+        </div>
+        <label style={{ fontSize: 12, display: "flex", alignItems: "center", gap: 6, whiteSpace: "nowrap" }}>
+          <input type="checkbox" checked={onlyRemovable} onChange={e => setOnlyRemovable(e.target.checked)} /> Only removable
+        </label>
       </div>
 
       {loading ? <div className="empty-state"><div className="spinner lg" style={{ margin: "0 auto" }} /><p style={{ marginTop: 12 }}>Scanning installed apps...</p></div> : (
@@ -1784,6 +2287,7 @@ function DebloaterPage() {
                 </div>
                 <div style={{ fontSize: 12, color: "var(--text-muted)" }}>{p.description || p.name}</div>
                 <div style={{ fontSize: 11, color: "var(--text-muted)", marginTop: 2 }}>Publisher: {p.publisher} • v{p.version}</div>
+                <div style={{ fontSize: 11, color: "var(--text-muted)", marginTop: 2 }}>Installed {fmtDate(p.install_date)} • {p.never_used ? <span style={{ color: "var(--warning)" }}>Never opened</span> : `Last used ${fmtDate(p.last_used)} (${p.launch_count ?? 0} launches)`}</div>
               </div>
               {p.safe_to_remove && <button className="btn btn-ghost btn-sm" onClick={() => remove(p.name)} style={{ color: "var(--danger)", alignSelf: "center" }}>✕ Remove</button>}
             </div>
@@ -1797,21 +2301,51 @@ function DebloaterPage() {
 // ═══════════════════════════════════════════════════════════════════
 // System Benchmark
 // ═══════════════════════════════════════════════════════════════════
-interface BenchResult { cpu_single_score: number; cpu_multi_score: number; cpu_cores_used: number; ram_read_mbps: number; ram_write_mbps: number; ram_latency_ns: number; disk_seq_read_mbps: number; disk_seq_write_mbps: number; disk_random_iops: number; total_score: number; duration_ms: number; }
+interface BenchResult { cpu_single_score: number; cpu_multi_score: number; cpu_cores_used: number; scaling_efficiency: number; ram_read_mbps: number; ram_write_mbps: number; ram_latency_ns: number; disk_seq_read_mbps: number; disk_seq_write_mbps: number; disk_random_iops: number; gpu_score: number; gpu_note: string; total_score: number; duration_ms: number; passes: number; stddev: Record<string, number>; confidence: number; id: string; timestamp: number; percentiles: [string, number][]; disk_target: string; }
+interface BenchDelta { metric: string; previous: number; current: number; change_pct: number; improved: boolean; }
+interface BenchComparison { previous_id: string; current_id: string; deltas: BenchDelta[]; }
+
+const BENCH_METRIC_LABELS: Record<string, string> = {
+  total_score: "Overall", cpu_single_score: "CPU single", cpu_multi_score: "CPU multi", ram_read_mbps: "RAM read", ram_write_mbps: "RAM write",
+  ram_latency_ns: "RAM latency", disk_seq_read_mbps: "Disk read", disk_seq_write_mbps: "Disk write", disk_random_iops: "Disk random IOPS", gpu_score: "GPU",
+};
 
 function BenchmarkPage() {
   const [result, setResult] = useState<BenchResult | null>(null);
   const [running, setRunning] = useState(false);
+  const [history, setHistory] = useState<BenchResult[]>([]);
+  const [comparison, setComparison] = useState<BenchComparison | null>(null);
+  const [drives, setDrives] = useState<DiskInfo[]>([]);
+  const [drive, setDrive] = useState("");
+
+  const loadHistory = () => invoke<BenchResult[]>("cmd_get_benchmark_history").then(setHistory).catch(() => {});
+  useEffect(() => {
+    loadHistory();
+    invoke<HardwareInfo>("cmd_get_hardware_info").then(h => setDrives(h.disks)).catch(() => {});
+  }, []);
 
   const run = () => {
     setRunning(true);
-    invoke<BenchResult>("cmd_run_benchmark").then(r => { setResult(r); setRunning(false); }).catch(() => setRunning(false));
+    setComparison(null);
+    invoke<BenchResult>("cmd_run_benchmark", { drive: drive || null })
+      .then(r => { setResult(r); setRunning(false); loadHistory(); })
+      .catch(e => { setRunning(false); alert(String(e)); });
   };
 
+  const compare = (previousId: string) => {
+    if (!previousId) { setComparison(null); return; }
+    invoke<BenchComparison>("cmd_compare_benchmark", { previousId }).then(setComparison).catch(() => setComparison(null));
+  };
+  const latestId = history.length ? history[history.length - 1].id : "";
+  const previousRuns = history.filter(h => h.id !== latestId).reverse();
+  const bestPercentile = result?.percentiles.reduce<[string, number] | null>((best, p) => (!best || p[1] > best[1] ? p : best), null);
+
   const scoreColor = (score: number, max: number) => {
     const pct = score / max;
     return pct > 0.7 ? "var(--success)" : pct > 0.4 ? "var(--warning)" : "var(--danger)";
   };
+  const spread = (key: string, scale = 1, digits = 0) =>
+    result && result.passes > 2 ? <span style={{ fontSize: 11, fontWeight: 400, color: "var(--text-muted)", marginLeft: 6 }}>±{((result.stddev[key] ?? 0) / scale).toFixed(digits)}</span> : null;
 
   // Reference data from community benchmark baselines (CrystalDiskMark, Geekbench, AIDA64 averages)
   // Each tier represents: [Budget/Office, Mid-range, High-end, Enthusiast] thresholds
@@ -1824,6 +2358,7 @@ function BenchmarkPage() {
     disk_read:  { tiers: [200, 550, 2000, 5000], labels: ["HDD / SATA", "SATA SSD", "NVMe Gen3", "NVMe Gen4/5"] },
     disk_write: { tiers: [150, 500, 1500, 4000], labels: ["HDD / SATA", "SATA SSD", "NVMe Gen3", "NVMe Gen4/5"] },
     disk_iops:  { tiers: [200, 5000, 30000, 80000], labels: ["HDD (~100)", "SATA SSD", "NVMe Gen3", "NVMe Gen4/5"] },
+    gpu:        { tiers: [200, 1000, 3000, 7000], labels: ["Integrated", "Entry discrete", "Mid-range", "Flagship"] },
   };
 
   // Calculate a rough percentile (0-100) based on where value falls relative to the tier thresholds
@@ -1867,36 +2402,76 @@ function BenchmarkPage() {
     <div>
       <div className="page-header">
         <div><h2>System Benchmark</h2><div className="subtitle">CPU, RAM, Disk performance test with hardware comparison</div></div>
-        <button className="optimize-btn" onClick={run} disabled={running} style={{ padding: "10px 24px" }}>
-          {running ? <><div className="spinner" style={{ display: "inline-block", marginRight: 8, borderTopColor: "white", width: 14, height: 14 }} /> RUNNING...</> : <>RUN BENCHMARK</>}
-        </button>
+        <div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+          <select value={drive} onChange={e => setDrive(e.target.value)} disabled={running} title="Drive for the disk test">
+            <option value="">Disk test: TEMP folder</option>
+            {drives.map(d => <option key={d.mount_point} value={d.mount_point.charAt(0)}>Disk test: {d.mount_point} ({d.free_gb.toFixed(0)} GB free)</option>)}
+          </select>
+          <button className="optimize-btn" onClick={run} disabled={running} style={{ padding: "10px 24px" }}>
+            {running ? <><div className="spinner" style={{ display: "inline-block", marginRight: 8, borderTopColor: "white", width: 14, height: 14 }} /> RUNNING...</> : <>RUN BENCHMARK</>}
+          </button>
+        </div>
       </div>
 
-      {running && <div className="empty-state"><div className="spinner lg" style={{ margin: "0 auto" }} /><p style={{ marginTop: 12 }}>Running benchmark... this takes ~15 seconds</p></div>}
+      {running && <div className="empty-state"><div className="spinner lg" style={{ margin: "0 auto" }} /><p style={{ marginTop: 12 }}>Running benchmark (3 passes)... this takes about a minute</p></div>}
 
       {result && !running && (
         <div>
           <div className="card" style={{ textAlign: "center", padding: 24, marginBottom: 16 }}>
             <div style={{ fontSize: 11, textTransform: "uppercase", letterSpacing: 2, color: "var(--text-muted)" }}>Overall Score</div>
             <div style={{ fontSize: 48, fontWeight: 800, color: scoreColor(result.total_score, 3000), fontFamily: "'JetBrains Mono', monospace" }}>{result.total_score}</div>
-            <div style={{ fontSize: 12, color: "var(--text-muted)", marginBottom: 8 }}>Completed in {(result.duration_ms / 1000).toFixed(1)}s using {result.cpu_cores_used} cores</div>
+            <div style={{ fontSize: 12, color: "var(--text-muted)", marginBottom: 8 }}>Completed in {(result.duration_ms / 1000).toFixed(1)}s using {result.cpu_cores_used} cores • median of {result.passes} passes{result.passes > 2 && <> • {(result.confidence * 100).toFixed(0)}% confidence</>}</div>
             <PercentileBar value={result.total_score} tiers={[200, 600, 1200, 2500]} labels={["Below Average", "Average System", "Above Average", "Top-tier System"]} />
+            {bestPercentile && <div style={{ fontSize: 12, color: "var(--text-secondary)", marginTop: 8 }}>Your {BENCH_METRIC_LABELS[bestPercentile[0]] ?? bestPercentile[0]} score is faster than {bestPercentile[1]}% of similar machines</div>}
           </div>
 
+          {result.id === latestId && previousRuns.length > 0 && (
+            <div className="card" style={{ padding: 16, marginBottom: 16 }}>
+              <div className="card-header">
+                <h3>Compare with Previous Run</h3>
+                <select onChange={e => compare(e.target.value)} defaultValue="" style={{ fontSize: 12 }}>
+                  <option value="">Select a run...</option>
+                  {previousRuns.map(h => <option key={h.id} value={h.id}>{new Date(h.timestamp * 1000).toLocaleString()} — score {h.total_score}</option>)}
+                </select>
+              </div>
+              {comparison && (
+                <table className="data-table">
+                  <thead><tr><th>Metric</th><th>Previous</th><th>Current</th><th>Change</th></tr></thead>
+                  <tbody>
+                    {comparison.deltas.map(d => (
+                      <tr key={d.metric}>
+                        <td>{BENCH_METRIC_LABELS[d.metric] ?? d.metric}</td>
+                        <td className="mono">{d.previous.toFixed(d.metric === "ram_latency_ns" ? 1 : 0)}</td>
+                        <td className="mono">{d.current.toFixed(d.metric === "ram_latency_ns" ? 1 : 0)}</td>
+                        <td className="mono" style={{ fontWeight: 700, color: Math.abs(d.change_pct) < 1 ? "var(--text-muted)" : d.improved ? "var(--success)" : "var(--danger)" }}>{d.change_pct >= 0 ? "+" : ""}{d.change_pct.toFixed(1)}%</td>
+                      </tr>
+                    ))}
+                  </tbody>
+                </table>
+              )}
+            </div>
+          )}
+
           <div className="card-grid card-grid-3" style={{ marginBottom: 16 }}>
             <div className="card" style={{ padding: 16 }}>
               <h3 style={{ fontSize: 14, marginBottom: 12 }}>CPU</h3>
               <div style={{ display: "grid", gap: 10 }}>
                 <div>
                   <div style={{ fontSize: 11, color: "var(--text-muted)" }}>Single Core</div>
-                  <div className="mono" style={{ fontSize: 20, fontWeight: 700, color: scoreColor(result.cpu_single_score, 2000) }}>{result.cpu_single_score.toFixed(0)}</div>
+                  <div className="mono" style={{ fontSize: 20, fontWeight: 700, color: scoreColor(result.cpu_single_score, 2000) }}>{result.cpu_single_score.toFixed(0)}{spread("cpu_single_score")}</div>
                   <PercentileBar value={result.cpu_single_score} tiers={BENCH_REF.cpu_single.tiers} labels={BENCH_REF.cpu_single.labels} />
                 </div>
                 <div>
                   <div style={{ fontSize: 11, color: "var(--text-muted)" }}>Multi Core ({result.cpu_cores_used})</div>
-                  <div className="mono" style={{ fontSize: 20, fontWeight: 700, color: scoreColor(result.cpu_multi_score, 8000) }}>{result.cpu_multi_score.toFixed(0)}</div>
+                  <div className="mono" style={{ fontSize: 20, fontWeight: 700, color: scoreColor(result.cpu_multi_score, 8000) }}>{result.cpu_multi_score.toFixed(0)}{spread("cpu_multi_score")}</div>
                   <PercentileBar value={result.cpu_multi_score} tiers={BENCH_REF.cpu_multi.tiers} labels={BENCH_REF.cpu_multi.labels} />
                 </div>
+                {result.scaling_efficiency > 0 && (
+                  <div title="Multi-core score divided by single-core score × cores. Well below 100% suggests cooling or power limits are throttling all-core loads.">
+                    <div style={{ fontSize: 11, color: "var(--text-muted)" }}>Core Scaling</div>
+                    <div className="mono" style={{ fontSize: 14, color: result.scaling_efficiency >= 0.8 ? "var(--success)" : result.scaling_efficiency >= 0.6 ? "var(--warning)" : "var(--danger)" }}>{(result.scaling_efficiency * 100).toFixed(0)}%</div>
+                  </div>
+                )}
               </div>
             </div>
             <div className="card" style={{ padding: 16 }}>
@@ -1904,12 +2479,12 @@ function BenchmarkPage() {
               <div style={{ display: "grid", gap: 10 }}>
                 <div>
                   <div style={{ fontSize: 11, color: "var(--text-muted)" }}>Read</div>
-                  <div className="mono" style={{ fontSize: 20, fontWeight: 700, color: scoreColor(result.ram_read_mbps, 50000) }}>{(result.ram_read_mbps / 1000).toFixed(1)} GB/s</div>
+                  <div className="mono" style={{ fontSize: 20, fontWeight: 700, color: scoreColor(result.ram_read_mbps, 50000) }}>{(result.ram_read_mbps / 1000).toFixed(1)} GB/s{spread("ram_read_mbps", 1000, 1)}</div>
                   <PercentileBar value={result.ram_read_mbps} tiers={BENCH_REF.ram_read.tiers} labels={BENCH_REF.ram_read.labels} />
                 </div>
                 <div>
                   <div style={{ fontSize: 11, color: "var(--text-muted)" }}>Write</div>
-                  <div className="mono" style={{ fontSize: 20, fontWeight: 700, color: scoreColor(result.ram_write_mbps, 40000) }}>{(result.ram_write_mbps / 1000).toFixed(1)} GB/s</div>
+                  <div className="mono" style={{ fontSize: 20, fontWeight: 700, color: scoreColor(result.ram_write_mbps, 40000) }}>{(result.ram_write_mbps / 1000).toFixed(1)} GB/s{spread("ram_write_mbps", 1000, 1)}</div>
                   <PercentileBar value={result.ram_write_mbps} tiers={BENCH_REF.ram_write.tiers} labels={BENCH_REF.ram_write.labels} />
                 </div>
                 <div>
@@ -1920,16 +2495,16 @@ function BenchmarkPage() {
               </div>
             </div>
             <div className="card" style={{ padding: 16 }}>
-              <h3 style={{ fontSize: 14, marginBottom: 12 }}>Disk</h3>
+              <h3 style={{ fontSize: 14, marginBottom: 12 }}>Disk{result.disk_target && <span style={{ fontSize: 11, fontWeight: 400, color: "var(--text-muted)", marginLeft: 6 }}>{result.disk_target}</span>}</h3>
               <div style={{ display: "grid", gap: 10 }}>
                 <div>
                   <div style={{ fontSize: 11, color: "var(--text-muted)" }}>Seq. Read</div>
-                  <div className="mono" style={{ fontSize: 20, fontWeight: 700, color: scoreColor(result.disk_seq_read_mbps, 3000) }}>{result.disk_seq_read_mbps.toFixed(0)} MB/s</div>
+                  <div className="mono" style={{ fontSize: 20, fontWeight: 700, color: scoreColor(result.disk_seq_read_mbps, 3000) }}>{result.disk_seq_read_mbps.toFixed(0)} MB/s{spread("disk_seq_read_mbps")}</div>
                   <PercentileBar value={result.disk_seq_read_mbps} tiers={BENCH_REF.disk_read.tiers} labels={BENCH_REF.disk_read.labels} />
                 </div>
                 <div>
                   <div style={{ fontSize: 11, color: "var(--text-muted)" }}>Seq. Write</div>
-                  <div className="mono" style={{ fontSize: 20, fontWeight: 700, color: scoreColor(result.disk_seq_write_mbps, 2000) }}>{result.disk_seq_write_mbps.toFixed(0)} MB/s</div>
+                  <div className="mono" style={{ fontSize: 20, fontWeight: 700, color: scoreColor(result.disk_seq_write_mbps, 2000) }}>{result.disk_seq_write_mbps.toFixed(0)} MB/s{spread("disk_seq_write_mbps")}</div>
                   <PercentileBar value={result.disk_seq_write_mbps} tiers={BENCH_REF.disk_write.tiers} labels={BENCH_REF.disk_write.labels} />
                 </div>
                 <div>
@@ -1941,6 +2516,19 @@ function BenchmarkPage() {
             </div>
           </div>
 
+          <div className="card" style={{ padding: 16, marginBottom: 16 }}>
+            <h3 style={{ fontSize: 14, marginBottom: 12 }}>GPU</h3>
+            {result.gpu_score > 0 ? (
+              <div>
+                <div style={{ fontSize: 11, color: "var(--text-muted)" }}>Score (video memory copy throughput)</div>
+                <div className="mono" style={{ fontSize: 20, fontWeight: 700, color: scoreColor(result.gpu_score, 7000) }}>{result.gpu_score.toFixed(0)}{spread("gpu_score")}<span style={{ fontSize: 11, fontWeight: 400, color: "var(--text-muted)", marginLeft: 6 }}>{(result.gpu_score / 10).toFixed(1)} GB/s</span></div>
+                <PercentileBar value={result.gpu_score} tiers={BENCH_REF.gpu.tiers} labels={BENCH_REF.gpu.labels} />
+              </div>
+            ) : (
+              <div style={{ fontSize: 12, color: "var(--text-muted)" }}>{result.gpu_note || "No compatible GPU"}</div>
+            )}
+          </div>
+
           {/* Reference comparison table */}
           <div className="card" style={{ padding: 16 }}>
             <div className="card-header"><h3>Hardware Tier Reference</h3><span style={{ fontSize: 10, color: "var(--text-muted)" }}>Based on community benchmark averages</span></div>
@@ -1968,18 +2556,51 @@ function BenchmarkPage() {
 // ═══════════════════════════════════════════════════════════════════
 // Services Manager
 // ═══════════════════════════════════════════════════════════════════
-interface SvcInfo { name: string; display_name: string; status: string; start_type: string; memory_mb: number; pid: number; description: string; category: string; safe_to_disable: boolean; recommendation: string; }
+interface SvcInfo { name: string; display_name: string; status: string; start_type: string; memory_mb: number; pid: number; description: string; category: string; safe_to_disable: boolean; recommendation: string; shared_host: boolean; host_memory_mb: number; }
+interface ServiceChange { name: string; display_name: string; previous_start_type: string; previous_status: string; success: boolean; message: string; }
+interface RecommendedServicesReport { restore_point: string; snapshot_path: string; changes: ServiceChange[]; changed: number; failed: number; }
+interface EssentialIssue { name: string; display_name: string; status: string; start_type: string; expected_start_type: string; problem: string; recommendation: string; }
+
+interface ServiceSuggestion { name: string; display_name: string; category: string; pid: number; estimated_savings_mb: number; shared_host: boolean; reason: string; }
 
 function ServicesPage() {
   const [svcs, setSvcs] = useState<SvcInfo[]>([]);
   const [loading, setLoading] = useState(false);
   const [filter, setFilter] = useState<string>("all");
   const [search, setSearch] = useState("");
+  const [issues, setIssues] = useState<EssentialIssue[]>([]);
+  const [suggestions, setSuggestions] = useState<ServiceSuggestion[]>([]);
 
   const load = useCallback(() => {
     setLoading(true);
     invoke<SvcInfo[]>("cmd_list_services").then(s => { setSvcs(s); setLoading(false); }).catch(() => setLoading(false));
+    invoke<EssentialIssue[]>("cmd_check_essential_services").then(setIssues).catch(() => {});
+    invoke<ServiceSuggestion[]>("cmd_get_service_suggestions").then(setSuggestions).catch(() => {});
   }, []);
+
+  const fixEssential = async (names: string[]) => {
+    const failures: string[] = [];
+    for (const name of names) {
+      try { await invoke<string>("cmd_fix_essential_service", { name }); }
+      catch (e) { failures.push(`${name}: ${String(e)}`); }
+    }
+    if (failures.length) alert(`Could not repair:\n${failures.join("\n")}`);
+    load();
+  };
+
+  const [applying, setApplying] = useState(false);
+  const applyRecommended = () => {
+    if (!confirm("Set every service marked safe to disable to Manual and stop it? A restore point and a service snapshot are taken first so this can be undone.")) return;
+    setApplying(true);
+    invoke<RecommendedServicesReport>("cmd_apply_recommended_services")
+      .then(r => {
+        const lines = r.changes.map(c => `${c.success ? "✓" : "✗"} ${c.display_name} (${c.previous_start_type}) — ${c.message}`);
+        alert(`Changed ${r.changed} services${r.failed ? `, ${r.failed} failed` : ""}.\nRestore point: ${r.restore_point}\n\n${lines.join("\n")}`);
+      })
+      .catch(e => alert(String(e)))
+      .finally(() => { setApplying(false); load(); });
+  };
+  const undoRecommended = () => invoke<string>("cmd_restore_service_snapshot").then(m => { alert(m); load(); }).catch(e => alert(String(e)));
   useEffect(() => { load(); }, []);
 
   const stopSvc = (name: string) => invoke<string>("cmd_stop_service", { name }).then(() => load()).catch(e => alert(String(e)));
@@ -1996,10 +2617,42 @@ function ServicesPage() {
         <div style={{ display: "flex", gap: 8 }}>
           <input type="text" placeholder="Search..." value={search} onChange={e => setSearch(e.target.value)}
             style={{ padding: "8px 12px", background: "var(--bg-input)", border: "1px solid var(--border)", borderRadius: 6, color: "var(--text-primary)", fontSize: 13, width: 200, outline: "none", fontFamily: "inherit" }} />
+          <button className="btn btn-primary btn-sm" onClick={applyRecommended} disabled={applying}>{applying ? "⏳ Applying..." : "⚡ Apply recommended"}</button>
+          <button className="btn btn-ghost btn-sm" onClick={undoRecommended} title="Restore start types saved before the last bulk change">↶ Undo</button>
           <button className="btn btn-ghost btn-sm" onClick={load}>↻ Refresh</button>
         </div>
       </div>
 
+      {issues.length > 0 && (
+        <div className="card" style={{ padding: 14, marginBottom: 12, borderColor: "var(--danger)" }}>
+          <div style={{ display: "flex", justifyContent: "space-between", alignItems: "center", marginBottom: 8 }}>
+            <h3 style={{ fontSize: 14 }}>⚠️ {issues.length} essential service{issues.length > 1 ? "s" : ""} disabled or stopped</h3>
+            <button className="btn btn-primary btn-sm" onClick={() => fixEssential(issues.map(i => i.name))}>🔧 Repair all</button>
+          </div>
+          {issues.map(i => (
+            <div key={i.name} style={{ display: "flex", justifyContent: "space-between", alignItems: "center", fontSize: 12, padding: "4px 0" }}>
+              <span><strong>{i.display_name}</strong> <span style={{ color: "var(--text-muted)" }}>({i.name}) — {i.problem}, start type {i.start_type}, expected {i.expected_start_type} • {i.recommendation}</span></span>
+              <button className="btn btn-ghost btn-sm" onClick={() => fixEssential([i.name])}>Fix</button>
+            </div>
+          ))}
+        </div>
+      )}
+
+      {suggestions.length > 0 && (
+        <div className="card" style={{ padding: 14, marginBottom: 12 }}>
+          <div style={{ display: "flex", justifyContent: "space-between", alignItems: "center", marginBottom: 8 }}>
+            <h3 style={{ fontSize: 14 }}>🎯 {suggestions.length} service{suggestions.length > 1 ? "s" : ""} needing attention • ~{suggestions.reduce((acc, s) => acc + s.estimated_savings_mb, 0).toFixed(0)} MB</h3>
+            <button className="btn btn-primary btn-sm" onClick={applyRecommended} disabled={applying}>⚡ Disable recommended</button>
+          </div>
+          {suggestions.map(s => (
+            <div key={s.name} style={{ display: "flex", justifyContent: "space-between", fontSize: 12, padding: "4px 0" }}>
+              <span><strong>{s.display_name}</strong> <span style={{ color: "var(--text-muted)" }}>({s.name}) — {s.reason}</span></span>
+              <span className="mono">{s.shared_host ? "~" : ""}{s.estimated_savings_mb.toFixed(1)} MB</span>
+            </div>
+          ))}
+        </div>
+      )}
+
       <div className="tab-bar" style={{ marginBottom: 12 }}>
         {["all", "essential", "optional", "telemetry", "gaming"].map(f => (
           <button key={f} className={`tab-btn ${filter === f ? "active" : ""}`} onClick={() => setFilter(f)}>{f} ({f === "all" ? svcs.length : svcs.filter(s => s.category === f).length})</button>
@@ -2014,7 +2667,10 @@ function ServicesPage() {
                 <td><div style={{ fontWeight: 500, color: "var(--text-primary)" }}>{s.display_name}</div><div style={{ fontSize: 11, color: "var(--text-muted)" }}>{s.name}</div>{s.recommendation && <div style={{ fontSize: 10, color: "var(--text-muted)", fontStyle: "italic" }}>{s.recommendation}</div>}</td>
                 <td><span className={`badge ${s.status === "Running" ? "badge-low" : "badge-medium"}`}>{s.status}</span></td>
                 <td style={{ fontSize: 12 }}>{s.start_type}</td>
-                <td className="mono" style={{ fontSize: 12 }}>{s.memory_mb > 0 ? `${s.memory_mb.toFixed(1)} MB` : "—"}</td>
+                <td className="mono" style={{ fontSize: 12 }} title={s.shared_host ? `Shared svchost (PID ${s.pid}) using ${s.host_memory_mb.toFixed(1)} MB in total` : undefined}>
+                  {s.memory_mb > 0 ? `${s.shared_host ? "~" : ""}${s.memory_mb.toFixed(1)} MB` : "—"}
+                  {s.shared_host && <span className="badge" style={{ marginLeft: 6, fontSize: 10 }}>shared</span>}
+                </td>
                 <td><span style={{ fontSize: 11, fontWeight: 600, color: CAT_COLORS[s.category] || "var(--text-muted)" }}>{s.category}</span></td>
                 <td>
                   {s.status === "Running" && s.safe_to_disable && <button className="btn-icon" onClick={() => stopSvc(s.name)} title="Stop">⏹</button>}
@@ -2045,7 +2701,7 @@ function RegistryPage() {
   };
 
   const fix = (issue: RegIssue) => {
-    invoke<string>("cmd_fix_registry_issue", { keyPath: issue.key_path, valueName: issue.value_name, issueType: issue.issue_type })
+    invoke<string>("cmd_fix_registry_issue", { keyPath: issue.key_path, valueName: issue.value_name, issueType: issue.issue_type, restorePoint: true })
       .then(() => {
         setScan(prev => prev ? { ...prev, issues: prev.issues.filter(i => i !== issue), total_issues: prev.total_issues - 1 } : null);
       })
@@ -2059,12 +2715,14 @@ function RegistryPage() {
     if (!confirm(`Fix all ${safeIssues.length} safe registry issues?`)) return;
     setFixingAll(true);
     let fixed = 0;
-    for (const issue of safeIssues) {
-      try {
-        await invoke<string>("cmd_fix_registry_issue", { keyPath: issue.key_path, valueName: issue.value_name, issueType: issue.issue_type });
-        fixed++;
-      } catch (e) { console.error(e); }
-    }
+    try {
+      const results = await invoke<[string, boolean, string][]>("cmd_fix_registry_issues", {
+        issues: safeIssues.map(i => ({ key_path: i.key_path, value_name: i.value_name, issue_type: i.issue_type })),
+        restorePoint: true,
+      });
+      fixed = results.filter(([, ok]) => ok).length;
+      results.filter(([, ok]) => !ok).forEach(([name, , msg]) => console.error(`${name}: ${msg}`));
+    } catch (e) { console.error(e); }
     // Re-scan to get fresh state
     try {
       const r = await invoke<RegScan>("cmd_scan_registry");
@@ -2074,7 +2732,7 @@ function RegistryPage() {
     alert(`Fixed ${fixed} of ${safeIssues.length} issues.`);
   };
 
-  const TYPE_COLORS: Record<string, string> = { orphaned_software: "var(--warning)", broken_shortcut: "var(--danger)", invalid_path: "var(--accent)", obsolete_clsid: "var(--text-muted)" };
+  const TYPE_COLORS: Record<string, string> = { orphaned_software: "var(--warning)", broken_shortcut: "var(--danger)", invalid_path: "var(--accent)", obsolete_clsid: "var(--text-muted)", empty_key: "var(--text-muted)" };
   const safeCount = scan ? scan.issues.filter(i => i.safe_to_fix).length : 0;
 
   return (
@@ -2090,6 +2748,11 @@ function RegistryPage() {
           <button className={scan && safeCount > 0 ? "btn btn-ghost" : "optimize-btn"} onClick={runScan} disabled={scanning} style={{ padding: "10px 24px" }}>
             {scanning ? <><div className="spinner" style={{ display: "inline-block", marginRight: 8, borderTopColor: "white", width: 14, height: 14 }} /> SCANNING...</> : <>🔍 SCAN REGISTRY</>}
           </button>
+          {scan && <button className="btn btn-ghost btn-sm" onClick={() => exportResult("cmd_export_registry_csv", "Export registry issues", "vega_registry_issues.csv")}>💾 Export CSV</button>}
+          <button className="btn btn-ghost btn-sm" title="Import a .reg backup taken before a fix" onClick={async () => {
+            const f = await open({ multiple: false, title: "Select registry backup", filters: [{ name: "Registry backup", extensions: ["reg"] }] });
+            if (f) invoke<string>("cmd_restore_registry_backup", { regFile: f as string }).then(m => alert(m)).catch(e => alert(String(e)));
+          }}>↶ Restore backup</button>
         </div>
       </div>
 
@@ -2135,10 +2798,15 @@ interface BatteryInfo { present: boolean; status: string; charge_percent: number
 function BatteryPage() {
   const [battery, setBattery] = useState<BatteryInfo | null>(null);
   const [loading, setLoading] = useState(false);
+  const [history, setHistory] = useState<[string, number][]>([]);
+  const [chargeLimit, setChargeLimit] = useState(80);
 
   useEffect(() => {
     setLoading(true);
-    invoke<BatteryInfo>("cmd_get_battery_health").then(b => { setBattery(b); setLoading(false); }).catch(() => setLoading(false));
+    // History is read after the health call, which records today's sample
+    invoke<BatteryInfo>("cmd_get_battery_health").then(b => { setBattery(b); setLoading(false); })
+      .catch(() => setLoading(false))
+      .finally(() => invoke<[string, number][]>("cmd_get_battery_history").then(setHistory).catch(() => {}));
   }, []);
 
   if (loading) return <div className="empty-state"><div className="spinner lg" style={{ margin: "0 auto" }} /></div>;
@@ -2151,6 +2819,7 @@ function BatteryPage() {
     <div>
       <div className="page-header">
         <div><h2>🔋 Battery Health</h2><div className="subtitle">{statusEmoji} {battery.status} — {battery.charge_percent}%</div></div>
+        <button className="btn btn-ghost btn-sm" onClick={() => invoke<string>("cmd_open_battery_report").catch(e => alert(String(e)))}>📄 Battery Report</button>
       </div>
 
       <div className="card" style={{ textAlign: "center", padding: 24, marginBottom: 16 }}>
@@ -2160,6 +2829,20 @@ function BatteryPage() {
         <ProgressBar value={battery.health_pct} color={healthColor} />
       </div>
 
+      {history.length >= 2 && (
+        <div className="card" style={{ padding: 16, marginBottom: 16 }}>
+          <h3 style={{ fontSize: 14, marginBottom: 12 }}>📉 Capacity Over Time</h3>
+          <svg width="100%" height={80} viewBox="0 0 400 80" preserveAspectRatio="none" style={{ background: "var(--bg-tertiary)", borderRadius: 4 }}>
+            <polyline fill="none" stroke={healthColor} strokeWidth={2}
+              points={history.map(([, pct], i) => `${(i / (history.length - 1)) * 400},${80 - (pct / 100) * 80}`).join(" ")} />
+          </svg>
+          <div style={{ display: "flex", justifyContent: "space-between", fontSize: 11, color: "var(--text-muted)", marginTop: 4 }}>
+            <span>{history[0][0]} — {history[0][1]}%</span>
+            <span>{history[history.length - 1][0]} — {history[history.length - 1][1]}%</span>
+          </div>
+        </div>
+      )}
+
       <div className="card-grid card-grid-3" style={{ marginBottom: 16 }}>
         <div className="card" style={{ padding: 16 }}>
           <h3 style={{ fontSize: 14, marginBottom: 12 }}>⚡ Current State</h3>
@@ -2195,6 +2878,17 @@ function BatteryPage() {
           </div>
         </div>
       </div>
+
+      <div className="card" style={{ padding: 16, display: "flex", alignItems: "center", gap: 12 }}>
+        <div style={{ flex: 1 }}>
+          <h3 style={{ fontSize: 14 }}>🛡️ Charge Limit</h3>
+          <div style={{ fontSize: 12, color: "var(--text-muted)" }}>Capping the charge below 100% slows battery aging on laptops that stay plugged in (ASUS, Dell)</div>
+        </div>
+        <select value={chargeLimit} onChange={e => setChargeLimit(Number(e.target.value))}>
+          {[60, 80, 100].map(p => <option key={p} value={p}>{p === 100 ? "No limit" : `${p}%`}</option>)}
+        </select>
+        <button className="btn btn-primary btn-sm" onClick={() => invoke<string>("cmd_set_charge_limit", { percent: chargeLimit }).then(alert).catch(e => alert(String(e)))}>Apply</button>
+      </div>
     </div>
   );
 }
@@ -2202,31 +2896,54 @@ function BatteryPage() {
 // ═══════════════════════════════════════════════════════════════════
 // Duplicate File Finder
 // ═══════════════════════════════════════════════════════════════════
-interface DuplicateGroup { hash: string; size_mb: number; files: string[]; }
-interface DupScanResult { groups: DuplicateGroup[]; total_duplicates: number; total_wasted_mb: number; files_scanned: number; duration_ms: number; }
+interface DuplicateFile { path: string; size_mb: number; modified: string; extension: string; token: string; }
+interface DuplicateGroup { hash: string; file_size_mb: number; count: number; total_wasted_mb: number; files: DuplicateFile[]; verified?: boolean; }
+interface DupCategoryStats { category: string; groups: number; duplicates: number; wasted_mb: number; }
+interface DupScanResult { groups: DuplicateGroup[]; total_duplicates: number; total_wasted_mb: number; by_category?: DupCategoryStats[]; files_scanned: number; enumeration?: string; cancelled?: boolean; warnings?: string[]; duration_ms: number; }
+interface DupScanProgress { files_scanned: number; current_dir: string; phase: "sizing" | "hashing" | "verifying" | "grouping"; hashed: number; candidates: number; }
 
 function DuplicatesPage() {
   const [scan, setScan] = useState<DupScanResult | null>(null);
   const [scanning, setScanning] = useState(false);
+  const [progress, setProgress] = useState<DupScanProgress | null>(null);
   const [minSize, setMinSize] = useState(1);
+  const [drive, setDrive] = useState("");
+  const [customDirs, setCustomDirs] = useState<string[]>([]);
+  const [recycle, setRecycle] = useState(true);
+
+  useEffect(() => {
+    invoke<Settings>("cmd_load_settings").then(s => { setCustomDirs(s.scan_dirs); setRecycle(s.recycle_by_default); }).catch(console.error);
+  }, []);
+
+  useEffect(() => {
+    const unlisten = listen<DupScanProgress>("duplicate-scan-progress", e => setProgress(e.payload));
+    return () => { unlisten.then(f => f()); };
+  }, []);
 
   const runScan = () => {
     setScanning(true);
-    invoke<DupScanResult>("cmd_scan_duplicates", { minSizeMb: minSize })
-      .then(r => { setScan(r); setScanning(false); })
+    setProgress(null);
+    const req = drive === "custom"
+      ? invoke<DupScanResult>("cmd_scan_duplicates_in", { dirs: customDirs, minSizeMb: minSize })
+      : drive
+      ? invoke<DupScanResult>("cmd_scan_drive_duplicates", { drive, minSizeMb: minSize })
+      : invoke<DupScanResult>("cmd_scan_duplicates", { minSizeMb: minSize });
+    req
+      .then(r => { setScan(r); setScanning(false); if (r.warnings?.length) alert(r.warnings.join("\n")); })
       .catch(() => setScanning(false));
   };
 
-  const deleteDup = (path: string) => {
-    if (!confirm(`Delete this file?\n${path}`)) return;
-    invoke<string>("cmd_delete_duplicate", { path })
+  // The scan's token makes the backend refuse a file that changed since it was hashed
+  const deleteDup = ({ path, token }: DuplicateFile) => {
+    if (!confirm(recycle ? `Move this file to the Recycle Bin?\n${path}` : `Permanently delete this file?\n${path}`)) return;
+    invoke<string>("cmd_delete_duplicate", { path, token, recycle })
       .then(() => {
         // Remove from UI
         setScan(prev => {
           if (!prev) return null;
           const groups = prev.groups.map(g => ({
             ...g,
-            files: g.files.filter(f => f !== path),
+            files: g.files.filter(f => f.path !== path),
           })).filter(g => g.files.length > 1);
           return { ...prev, groups, total_duplicates: prev.total_duplicates - 1 };
         });
@@ -2238,28 +2955,34 @@ function DuplicatesPage() {
 
   const deleteAllDuplicates = async () => {
     if (!scan || scan.groups.length === 0) return;
-    const totalDups = scan.groups.reduce((a, g) => a + g.files.length - 1, 0);
-    if (!confirm(`Delete ${totalDups} duplicate files? This keeps the first copy of each group and removes the rest.\n\nThis cannot be undone!`)) return;
+    // Groups a cancelled scan left unverified only matched on a partial hash
+    const verified = scan.groups.filter(g => g.verified);
+    const skipped = scan.groups.length - verified.length;
+    if (verified.length === 0) { alert("No verified duplicate groups to delete. Re-run the scan to completion first."); return; }
+    const totalDups = verified.reduce((a, g) => a + g.files.length - 1, 0);
+    if (!confirm(`${recycle ? "Move" : "Permanently delete"} ${totalDups} duplicate files${recycle ? " to the Recycle Bin" : ""}? This keeps the first copy of each group and removes the rest.` + (skipped ? `\n\n${skipped} unverified group(s) will be skipped.` : ""))) return;
     setDeletingAll(true);
     let deleted = 0;
-    for (const g of scan.groups) {
+    const failures: string[] = [];
+    for (const g of verified) {
       // Skip the first file (keep it)
       for (let fi = 1; fi < g.files.length; fi++) {
+        const { path, token } = g.files[fi];
         try {
-          await invoke<string>("cmd_delete_duplicate", { path: g.files[fi] });
+          await invoke<string>("cmd_delete_duplicate", { path, token, recycle });
           deleted++;
-        } catch (e) { console.error(e); }
+        } catch (e) { failures.push(`${path}: ${String(e)}`); }
       }
     }
     setDeletingAll(false);
     setScan(null);
-    alert(`Deleted ${deleted} duplicate files.`);
+    alert(`${recycle ? "Recycled" : "Deleted"} ${deleted} duplicate files.` + (failures.length ? `\n\nFailed (${failures.length}):\n${failures.join("\n")}` : ""));
   };
 
   return (
     <div>
       <div className="page-header">
-        <div><h2>🔍 Duplicate File Finder</h2><div className="subtitle">{scan ? `${scan.total_duplicates} duplicates in ${scan.groups.length} groups — ${scan.total_wasted_mb.toFixed(0)} MB wasted` : "Find and remove duplicate files"}</div></div>
+        <div><h2>🔍 Duplicate File Finder</h2><div className="subtitle">{scan ? `${scan.total_duplicates} duplicates in ${scan.groups.length} groups — ${scan.total_wasted_mb.toFixed(0)} MB wasted${scan.cancelled ? " (scan cancelled — partial results)" : ""}` : "Find and remove duplicate files"}</div></div>
         <div style={{ display: "flex", gap: 8, alignItems: "center" }}>
           <label style={{ fontSize: 12, color: "var(--text-muted)" }}>Min size:</label>
           <select value={minSize} onChange={e => setMinSize(Number(e.target.value))}
@@ -2269,18 +2992,44 @@ function DuplicatesPage() {
             <option value={10}>10 MB</option>
             <option value={50}>50 MB</option>
           </select>
+          <label style={{ fontSize: 12, color: "var(--text-muted)" }}>Scope:</label>
+          <select value={drive} onChange={e => setDrive(e.target.value)}
+            style={{ padding: "6px 10px", background: "var(--bg-input)", border: "1px solid var(--border)", borderRadius: 6, color: "var(--text-primary)", fontSize: 13, fontFamily: "inherit" }}>
+            <option value="">User folders</option>
+            <option value="custom">Custom folders{customDirs.length ? ` (${customDirs.length})` : ""}</option>
+            {"CDEFGHIJ".split("").map(l => <option key={l} value={l}>Whole drive {l}:</option>)}
+          </select>
+          {drive === "custom" && (
+            <button className="btn btn-ghost btn-sm" title={customDirs.join("\n")} onClick={async () => {
+              const picked = await open({ directory: true, multiple: true, title: "Folders to search for duplicates" });
+              if (!picked) return;
+              const dirs = Array.isArray(picked) ? picked : [picked];
+              setCustomDirs(dirs);
+              updateSettings({ scan_dirs: dirs }).catch(console.error);
+            }}>📂 Choose…</button>
+          )}
+          {scan && scan.groups.length > 0 && (
+            <button className="btn btn-ghost btn-sm" onClick={() => exportResult("cmd_export_duplicates_csv", "Export duplicates", "vega_duplicates.csv")}>💾 Export CSV</button>
+          )}
           {scan && scan.groups.length > 0 && (
             <button className="optimize-btn" onClick={deleteAllDuplicates} disabled={deletingAll || scanning} style={{ padding: "10px 24px" }}>
               {deletingAll ? <>⏳ Deleting...</> : <>🗑️ DELETE ALL DUPLICATES ({scan.total_wasted_mb.toFixed(0)} MB)</>}
             </button>
           )}
-          <button className={scan && scan.groups.length > 0 ? "btn btn-ghost" : "optimize-btn"} onClick={runScan} disabled={scanning || deletingAll} style={{ padding: "10px 24px" }}>
+          <button className={scan && scan.groups.length > 0 ? "btn btn-ghost" : "optimize-btn"} onClick={runScan} disabled={scanning || deletingAll || (drive === "custom" && customDirs.length === 0)} style={{ padding: "10px 24px" }}>
             {scanning ? <><div className="spinner" style={{ display: "inline-block", marginRight: 8, borderTopColor: "white", width: 14, height: 14 }} /> SCANNING...</> : <>🔍 SCAN</>}
           </button>
+          {scanning && <button className="btn btn-ghost btn-sm" onClick={() => invoke("cmd_cancel_scan")}>✕ Cancel</button>}
         </div>
       </div>
 
-      {scanning && <div className="empty-state"><div className="spinner lg" style={{ margin: "0 auto" }} /><p style={{ marginTop: 12 }}>Scanning files... this may take a while</p></div>}
+      {scanning && <div className="empty-state"><div className="spinner lg" style={{ margin: "0 auto" }} /><p style={{ marginTop: 12 }}>{
+        !progress ? "Scanning files... this may take a while"
+          : progress.phase === "sizing" ? `Listing files — ${progress.files_scanned.toLocaleString()} found`
+          : progress.phase === "hashing" ? `Hashing candidates — ${progress.hashed.toLocaleString()} / ${progress.candidates.toLocaleString()}`
+          : progress.phase === "verifying" ? `Verifying full contents — ${progress.hashed.toLocaleString()} / ${progress.candidates.toLocaleString()}`
+          : "Grouping duplicates..."
+      }</p>{progress?.current_dir && <p className="mono" style={{ fontSize: 11, color: "var(--text-muted)", marginTop: 4, overflow: "hidden", textOverflow: "ellipsis", whiteSpace: "nowrap" }}>{progress.current_dir}</p>}</div>}
 
       {scan && !scanning && scan.groups.length > 0 && (
         <div className="card-grid card-grid-3" style={{ marginBottom: 12 }}>
@@ -2290,6 +3039,14 @@ function DuplicatesPage() {
         </div>
       )}
 
+      {scan && !scanning && (scan.by_category?.length ?? 0) > 0 && (
+        <div className="card" style={{ padding: 12, marginBottom: 12, display: "flex", flexWrap: "wrap", gap: 16, fontSize: 12 }}>
+          {scan.by_category!.map(c => (
+            <span key={c.category}><strong>{c.category}:</strong> <span className="mono">{c.wasted_mb >= 1024 ? `${(c.wasted_mb / 1024).toFixed(1)} GB` : `${c.wasted_mb.toFixed(0)} MB`}</span> <span style={{ color: "var(--text-muted)" }}>wasted across {c.groups} group{c.groups > 1 ? "s" : ""}</span></span>
+          ))}
+        </div>
+      )}
+
       {scan && !scanning && (
         <div style={{ display: "grid", gap: 10, maxHeight: "calc(100vh - 340px)", overflow: "auto" }}>
           {scan.groups.map((g, gi) => (
@@ -2297,14 +3054,14 @@ function DuplicatesPage() {
               <div style={{ display: "flex", justifyContent: "space-between", alignItems: "center", marginBottom: 10 }}>
                 <div style={{ display: "flex", gap: 8, alignItems: "center" }}>
                   <span className="badge badge-medium">{g.files.length} copies</span>
-                  <span className="mono" style={{ fontSize: 12, color: "var(--warning)" }}>{g.size_mb.toFixed(1)} MB each</span>
+                  <span className="mono" style={{ fontSize: 12, color: "var(--warning)" }}>{g.file_size_mb.toFixed(1)} MB each</span>
                 </div>
-                <span className="mono" style={{ fontSize: 11, color: "var(--text-muted)" }}>{g.hash.slice(0, 16)}...</span>
+                <span className="mono" style={{ fontSize: 11, color: "var(--text-muted)" }}>{g.verified ? "✓ verified " : "unverified "}{g.hash.slice(0, 16)}...</span>
               </div>
               {g.files.map((f, fi) => (
                 <div key={fi} style={{ display: "flex", justifyContent: "space-between", alignItems: "center", padding: "6px 0", borderTop: fi > 0 ? "1px solid var(--border)" : "none", fontSize: 12 }}>
-                  <span style={{ color: fi === 0 ? "var(--success)" : "var(--text-secondary)", overflow: "hidden", textOverflow: "ellipsis", whiteSpace: "nowrap", maxWidth: "80%" }} title={f}>
-                    {fi === 0 ? "✓ " : ""}{f.split("\\").pop()}
+                  <span style={{ color: fi === 0 ? "var(--success)" : "var(--text-secondary)", overflow: "hidden", textOverflow: "ellipsis", whiteSpace: "nowrap", maxWidth: "80%" }} title={f.path}>
+                    {fi === 0 ? "✓ " : ""}{f.path.split("\\").pop()}
                   </span>
                   {fi > 0 && (
                     <button className="btn btn-ghost btn-sm" onClick={() => deleteDup(f)} style={{ color: "var(--danger)", fontSize: 11 }}>Delete</button>
@@ -2325,17 +3082,45 @@ function DuplicatesPage() {
 // ═══════════════════════════════════════════════════════════════════
 // Disk Health (S.M.A.R.T.)
 // ═══════════════════════════════════════════════════════════════════
-interface DiskHealthInfo { name: string; model: string; serial: string; media_type: string; status: string; size_gb: number; temperature_c: number | null; health_pct: number; smart_attributes: { id: number; name: string; value: number; worst: number; threshold: number; raw_value: string; status: string }[]; }
+interface DiskHealthInfo { name: string; model: string; serial: string; media_type: string; status: string; size_gb: number; temperature_c: number | null; health_pct: number; power_on_hours: number | null; total_reads_gb: number | null; total_writes_gb: number | null; smart_attributes: { id: number; name: string; value: string; worst: string; threshold: string; raw_value: string; status: string }[]; }
+
+interface SsdWearRecommendation { id: string; category: string; severity: string; title: string; current_location: string | null; size_mb: number | null; suggested_location: string | null; action: string; }
+interface SsdWearReport { ssd_drives: string[]; hdd_drives: string[]; total_writes_gb: number | null; min_ssd_health_pct: number | null; recommendations: SsdWearRecommendation[]; }
+
+interface HealthSample { timestamp: number; health_pct: number; temperature_c: number | null; power_on_hours: number | null; total_writes_gb: number | null; reallocated_sectors: number | null; }
+
+function HealthTrend({ serial, model }: { serial: string; model: string }) {
+  const [samples, setSamples] = useState<HealthSample[]>([]);
+  useEffect(() => { invoke<HealthSample[]>("cmd_get_disk_health_history", { serial, model }).then(setSamples).catch(() => {}); }, [serial, model]);
+  if (samples.length < 2) return null;
+
+  const first = samples[0], last = samples[samples.length - 1];
+  const points = samples.map((s, i) => `${(i / (samples.length - 1)) * 200},${40 - (s.health_pct / 100) * 40}`).join(" ");
+  const realloc = first.reallocated_sectors != null && last.reallocated_sectors != null ? last.reallocated_sectors - first.reallocated_sectors : null;
+  return (
+    <div style={{ display: "flex", alignItems: "center", gap: 16, marginBottom: 16, fontSize: 12, color: "var(--text-muted)" }}>
+      <svg width={200} height={40} viewBox="0 0 200 40" style={{ background: "var(--bg-tertiary)", borderRadius: 4 }}>
+        <polyline points={points} fill="none" stroke="var(--accent)" strokeWidth={2} />
+      </svg>
+      <div>
+        <div>{samples.length} readings since {new Date(first.timestamp * 1000).toLocaleDateString()} • health {first.health_pct}% → {last.health_pct}%</div>
+        {realloc != null && <div style={{ color: realloc > 0 ? "var(--danger)" : "var(--success)" }}>Reallocated sectors {realloc > 0 ? `+${realloc}` : "unchanged"} ({last.reallocated_sectors})</div>}
+      </div>
+    </div>
+  );
+}
 
 function DiskHealthPage() {
   const [disks, setDisks] = useState<DiskHealthInfo[]>([]);
   const [loading, setLoading] = useState(false);
+  const [wear, setWear] = useState<SsdWearReport | null>(null);
 
   useEffect(() => {
     setLoading(true);
     invoke<DiskHealthInfo[]>("cmd_get_disk_health")
       .then(d => { setDisks(d); setLoading(false); })
       .catch(() => setLoading(false));
+    invoke<SsdWearReport>("cmd_analyze_ssd_wear").then(setWear).catch(() => {});
   }, []);
 
   if (loading) return <div className="empty-state"><div className="spinner lg" style={{ margin: "0 auto" }} /><p style={{ marginTop: 12 }}>Reading disk health data...</p></div>;
@@ -2350,6 +3135,22 @@ function DiskHealthPage() {
         }}>↻ Refresh</button>
       </div>
 
+      {wear && wear.recommendations.length > 0 && (
+        <div className="card" style={{ padding: 16, marginBottom: 16 }}>
+          <h3 style={{ fontSize: 14, marginBottom: 10 }}>🧭 SSD Wear Advice</h3>
+          <div style={{ display: "grid", gap: 8 }}>
+            {wear.recommendations.map(r => (
+              <div key={r.id} style={{ fontSize: 12 }}>
+                <span className={`badge ${r.severity === "medium" ? "badge-high" : r.severity === "low" ? "badge-medium" : "badge-low"}`} style={{ marginRight: 8 }}>{r.severity}</span>
+                <strong>{r.title}</strong>
+                {r.current_location && <span className="mono" style={{ color: "var(--text-muted)", marginLeft: 6, fontSize: 11 }}>{r.current_location}</span>}
+                <div style={{ color: "var(--text-secondary)", marginTop: 2 }}>{r.action}{r.suggested_location ? ` (→ ${r.suggested_location})` : ""}</div>
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+
       {disks.length === 0 && !loading && <div className="empty-state"><div className="icon">💿</div><p>No S.M.A.R.T. data available</p><p style={{ fontSize: 12, color: "var(--text-muted)" }}>Some drives (USB, virtual) don't support S.M.A.R.T.</p></div>}
 
       <div style={{ display: "grid", gap: 16 }}>
@@ -2360,7 +3161,7 @@ function DiskHealthPage() {
               <div style={{ display: "flex", justifyContent: "space-between", alignItems: "center", marginBottom: 16 }}>
                 <div>
                   <h3 style={{ fontSize: 16, fontWeight: 700, color: "var(--text-primary)" }}>{disk.model || disk.name}</h3>
-                  <div style={{ fontSize: 12, color: "var(--text-muted)", marginTop: 2 }}>{disk.media_type} • {disk.size_gb.toFixed(0)} GB{disk.serial ? ` • S/N: ${disk.serial}` : ""}</div>
+                  <div style={{ fontSize: 12, color: "var(--text-muted)", marginTop: 2 }}>{disk.media_type} • {disk.size_gb.toFixed(0)} GB{disk.serial ? ` • S/N: ${disk.serial}` : ""}{disk.power_on_hours != null ? ` • ${disk.power_on_hours.toLocaleString()} h` : ""}{disk.total_writes_gb != null ? ` • ${(disk.total_writes_gb / 1024).toFixed(1)} TB written` : ""}</div>
                 </div>
                 <div style={{ textAlign: "right" }}>
                   <span className={`badge ${disk.status === "OK" || disk.status === "Healthy" ? "badge-low" : "badge-high"}`} style={{ fontSize: 13, padding: "4px 12px" }}>{disk.status}</span>
@@ -2378,6 +3179,8 @@ function DiskHealthPage() {
                 </div>
               )}
 
+              <HealthTrend serial={disk.serial} model={disk.model} />
+
               {disk.smart_attributes && disk.smart_attributes.length > 0 && (
                 <div style={{ overflow: "auto", maxHeight: 250 }}>
                   <table className="data-table">
@@ -2385,13 +3188,13 @@ function DiskHealthPage() {
                     <tbody>
                       {disk.smart_attributes.map((attr, ai) => (
                         <tr key={ai}>
-                          <td className="mono" style={{ fontSize: 11 }}>{attr.id}</td>
+                          <td className="mono" style={{ fontSize: 11 }}>{attr.id || "—"}</td>
                           <td style={{ fontSize: 12 }}>{attr.name}</td>
                           <td className="mono" style={{ fontSize: 12 }}>{attr.value}</td>
                           <td className="mono" style={{ fontSize: 12, color: "var(--text-muted)" }}>{attr.worst}</td>
                           <td className="mono" style={{ fontSize: 12, color: "var(--text-muted)" }}>{attr.threshold}</td>
                           <td className="mono" style={{ fontSize: 11, color: "var(--text-muted)" }}>{attr.raw_value}</td>
-                          <td><span className={`badge ${attr.status === "OK" || attr.status === "Ok" ? "badge-low" : "badge-high"}`}>{attr.status}</span></td>
+                          <td><span className={`badge ${attr.status === "ok" ? "badge-low" : attr.status === "warning" ? "badge-medium" : "badge-high"}`}>{attr.status}</span></td>
                         </tr>
                       ))}
                     </tbody>
@@ -2414,13 +3217,14 @@ interface CleanRes { category: string; files_deleted: number; space_freed_mb: nu
 interface AppCacheInfo { app_name: string; icon: string; cache_size_mb: number; installed: boolean; description: string; }
 interface StaleFileInfo { path: string; size_mb: number; last_accessed_days: number; extension: string; category: string; }
 interface InstalledProg { name: string; publisher: string; version: string; install_date: string; install_location: string; size_mb: number; uninstall_command: string; category: string; recommendation: string; }
+interface LeftoverFolder { path: string; name: string; root: string; size_mb: number; empty: boolean; removable: boolean; }
 interface ShredRes { path: string; size_mb: number; passes_completed: number; success: boolean; message: string; }
 interface WipeProg { drive: string; passes_completed: number; bytes_written: number; success: boolean; message: string; }
 interface AiSug { path: string; name: string; size_mb: number; suggestion_type: string; confidence: number; reason: string; risk: string; action: string; category: string; }
 interface FolderSizeInfo { path: string; name: string; size_mb: number; file_count: number; percentage: number; }
 interface DeepCleanRes { total_freed_mb: number; total_files: number; categories_cleaned: number; results: CleanRes[]; duration_ms: number; }
 
-type CleanupTab = "overview" | "junk" | "apps" | "stale" | "programs" | "shredder" | "ai";
+type CleanupTab = "overview" | "junk" | "apps" | "stale" | "programs" | "leftovers" | "shredder" | "ai";
 
 function DiskCleanupPage() {
   const [tab, setTab] = useState<CleanupTab>("overview");
@@ -2445,6 +3249,11 @@ function DiskCleanupPage() {
   const [progsLoading, setProgsLoading] = useState(false);
   const [progSearch, setProgSearch] = useState("");
 
+  // Leftover folders
+  const [leftovers, setLeftovers] = useState<LeftoverFolder[]>([]);
+  const [leftoversLoading, setLeftoversLoading] = useState(false);
+  const [leftoversScanned, setLeftoversScanned] = useState(false);
+
   // Shredder
   const [shredPath, setShredPath] = useState("");
   const [shredPasses, setShredPasses] = useState(3);
@@ -2468,6 +3277,7 @@ function DiskCleanupPage() {
     if (tab === "junk" && junkCats.length === 0) { setJunkLoading(true); invoke<JunkCat[]>("cmd_scan_junk").then(c => { setJunkCats(c); setJunkLoading(false); }).catch(() => setJunkLoading(false)); }
     if (tab === "apps" && appCaches.length === 0) { setAppsLoading(true); invoke<AppCacheInfo[]>("cmd_scan_app_caches").then(c => { setAppCaches(c); setAppsLoading(false); }).catch(() => setAppsLoading(false)); }
     if (tab === "programs" && programs.length === 0) { setProgsLoading(true); invoke<InstalledProg[]>("cmd_list_installed_programs").then(p => { setPrograms(p); setProgsLoading(false); }).catch(() => setProgsLoading(false)); }
+    if (tab === "leftovers" && !leftoversScanned) scanLeftovers();
     if (tab === "ai" && aiSugs.length === 0) { setAiLoading(true); invoke<AiSug[]>("cmd_get_ai_suggestions").then(s => { setAiSugs(s); setAiLoading(false); }).catch(() => setAiLoading(false)); }
     if (tab === "overview" && junkCats.length === 0) { invoke<JunkCat[]>("cmd_scan_junk").then(setJunkCats).catch(console.error); }
   }, [tab]);
@@ -2525,6 +3335,17 @@ function DiskCleanupPage() {
     try { await invoke<string>("cmd_delete_file", { path }); setStaleFiles(prev => prev.filter(f => f.path !== path)); } catch (e) { alert(String(e)); }
   };
 
+  const scanLeftovers = () => {
+    setLeftoversLoading(true);
+    invoke<LeftoverFolder[]>("cmd_scan_leftovers").then(f => { setLeftovers(f); setLeftoversScanned(true); setLeftoversLoading(false); }).catch(() => setLeftoversLoading(false));
+  };
+
+  const deleteLeftover = async (f: LeftoverFolder) => {
+    if (!confirm(`Move this folder to quarantine?\n${f.path}\n\nIt can be restored from the quarantine list until purged.`)) return;
+    if (!f.removable && !confirm(`${f.name} is outside Program Files and not empty. Portable tools and caches keep folders here without an uninstall entry.\n\nRemove it anyway?`)) return;
+    try { await invoke<string>("cmd_delete_leftover", { path: f.path, confirm: !f.removable }); setLeftovers(prev => prev.filter(x => x.path !== f.path)); } catch (e) { alert(String(e)); }
+  };
+
   const deleteAiSuggestion = async (path: string) => {
     if (!confirm(`Delete this file?\n${path}`)) return;
     try { await invoke<string>("cmd_delete_file", { path }); setAiSugs(prev => prev.filter(s => s.path !== path)); } catch (e) { alert(String(e)); }
@@ -2542,7 +3363,7 @@ function DiskCleanupPage() {
       </div>
 
       <div className="tab-bar" style={{ marginBottom: 16 }}>
-        {([["overview", "📊 Overview"], ["junk", "🗑️ Junk Files"], ["apps", "📦 App Caches"], ["stale", "🕰️ Stale Files"], ["programs", "💿 Programs"], ["shredder", "🔒 Shredder"], ["ai", "🤖 AI Advisor"]] as [CleanupTab, string][]).map(([id, label]) => (
+        {([["overview", "📊 Overview"], ["junk", "🗑️ Junk Files"], ["apps", "📦 App Caches"], ["stale", "🕰️ Stale Files"], ["programs", "💿 Programs"], ["leftovers", "📁 Leftovers"], ["shredder", "🔒 Shredder"], ["ai", "🤖 AI Advisor"]] as [CleanupTab, string][]).map(([id, label]) => (
           <button key={id} className={`tab-btn ${tab === id ? "active" : ""}`} onClick={() => setTab(id)}>{label}</button>
         ))}
       </div>
@@ -2716,6 +3537,33 @@ function DiskCleanupPage() {
         </div>
       )}
 
+      {/* ═══ Leftovers ═══ */}
+      {tab === "leftovers" && (
+        <div>
+          <div style={{ display: "flex", justifyContent: "space-between", alignItems: "center", marginBottom: 12 }}>
+            <div style={{ fontSize: 13, color: "var(--text-secondary)" }}>{leftovers.length} folders with no matching installed program — <strong style={{ color: "var(--warning)" }}>{leftovers.reduce((a, f) => a + f.size_mb, 0).toFixed(0)} MB</strong></div>
+            <button className="btn btn-ghost btn-sm" onClick={scanLeftovers}>↻ Re-scan</button>
+          </div>
+          {leftoversLoading ? <div className="empty-state"><div className="spinner lg" style={{ margin: "0 auto" }} /><p style={{ marginTop: 12 }}>Checking program folders...</p></div> : leftovers.length === 0 ? (
+            <div className="empty-state"><p>No leftover folders found</p></div>
+          ) : (
+            <div className="card" style={{ overflow: "auto", maxHeight: "calc(100vh - 280px)" }}>
+              <table className="data-table"><thead><tr><th>Folder</th><th>Location</th><th>Size</th><th></th></tr></thead>
+                <tbody>{leftovers.map(f => (
+                  <tr key={f.path}>
+                    <td><div style={{ fontWeight: 500, color: "var(--text-primary)" }}>{f.name}</div>
+                      {f.empty && <div style={{ fontSize: 10, color: "var(--text-muted)", fontStyle: "italic" }}>empty</div>}
+                      {!f.removable && <div style={{ fontSize: 10, color: "var(--warning)" }}>may be a portable tool or cache — check before removing</div>}</td>
+                    <td className="mono" style={{ fontSize: 11, color: "var(--text-muted)" }}>{f.root}</td>
+                    <td className="mono" style={{ fontSize: 12 }}>{f.size_mb >= 1024 ? `${(f.size_mb / 1024).toFixed(1)} GB` : `${f.size_mb.toFixed(1)} MB`}</td>
+                    <td><button className="btn btn-ghost btn-sm" onClick={() => deleteLeftover(f)} style={{ color: "var(--danger)", fontSize: 11 }}>{f.removable ? "Remove" : "Remove…"}</button></td>
+                  </tr>
+                ))}</tbody></table>
+            </div>
+          )}
+        </div>
+      )}
+
       {/* ═══ Shredder ═══ */}
       {tab === "shredder" && (
         <div>